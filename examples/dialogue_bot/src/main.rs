@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use std::{env, sync::Arc};
+use telexide::{
+    framework::{Dialogue, Framework},
+    prelude::*,
+};
+
+#[derive(Serialize, Deserialize)]
+enum Registration {
+    AskName,
+    AskAge { name: String },
+    Confirm { name: String, age: u8 },
+}
+
+fn message_text(message: &Message) -> &str {
+    match &message.content {
+        telexide::model::MessageContent::Text {
+            content, ..
+        } => content,
+        _ => "",
+    }
+}
+
+#[command(description = "starts the registration")]
+async fn register(context: Context, message: Message) -> CommandResult {
+    if let Some(dialogue) = context.dialogue::<Registration>(&message) {
+        dialogue.enter(Registration::AskName).await?;
+        context.reply(&message, "What is your name?").await?;
+    }
+    Ok(())
+}
+
+#[command(description = "cancels the registration")]
+async fn cancel(context: Context, message: Message) -> CommandResult {
+    if let Some(dialogue) = context.dialogue::<Registration>(&message) {
+        dialogue.exit().await?;
+        context.reply(&message, "The registration has been cancelled").await?;
+    }
+    Ok(())
+}
+
+#[prepare_listener]
+async fn ask_name(
+    context: Context,
+    message: Message,
+    dialogue: Dialogue<Registration>,
+    _state: Registration,
+) {
+    let name = message_text(&message).to_owned();
+    if dialogue.update(Registration::AskAge { name }).await.is_ok() {
+        context.reply(&message, "How old are you?").await.ok();
+    }
+}
+
+#[prepare_listener]
+async fn ask_age(
+    context: Context,
+    message: Message,
+    dialogue: Dialogue<Registration>,
+    state: Registration,
+) {
+    let name = match state {
+        Registration::AskAge { name } => name,
+        _ => return,
+    };
+
+    match message_text(&message).parse() {
+        Ok(age) => {
+            let question = format!("Are you {} and {} years old? (yes/no)", name, age);
+            if dialogue.update(Registration::Confirm { name, age }).await.is_ok() {
+                context.reply(&message, &question).await.ok();
+            }
+        },
+        Err(_) => {
+            context.reply(&message, "Please send your age as a number").await.ok();
+        },
+    }
+}
+
+#[prepare_listener]
+async fn confirm(
+    context: Context,
+    message: Message,
+    dialogue: Dialogue<Registration>,
+    state: Registration,
+) {
+    if dialogue.exit().await.is_err() {
+        return;
+    }
+
+    let reply = match (state, message_text(&message)) {
+        (Registration::Confirm { name, .. }, "yes") => format!("Welcome, {}!", name),
+        _ => "Use /register to try again".to_owned(),
+    };
+    context.reply(&message, &reply).await.ok();
+}
+
+#[tokio::main]
+async fn main() -> telexide::Result<()> {
+    let token = env::var("BOT_TOKEN").expect("no token environment variable set");
+    let bot_name = env::var("BOT_NAME").expect("no bot name env variable set");
+
+    let mut framework = Framework::new(&bot_name);
+    framework.add_command(&register_COMMAND);
+    framework.add_command(&cancel_COMMAND);
+    framework
+        .add_dialogue_handler::<Registration>("AskName", ask_name)
+        .add_dialogue_handler::<Registration>("AskAge", ask_age)
+        .add_dialogue_handler::<Registration>("Confirm", confirm);
+
+    ClientBuilder::new()
+        .set_token(&token)
+        .set_framework(Arc::new(framework))
+        .build()
+        .start()
+        .await
+}
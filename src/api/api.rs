@@ -32,10 +32,19 @@ pub trait API: Sync {
         files: Option<Vec<FormDataFile>>,
     ) -> Result<Response>;
 
+    /// Downloads the contents of a file gotten using [`API::get_file`].
+    /// Implementations that don't support downloading files return
+    /// [`TelegramError::InvalidEndpoint`].
+    ///
+    /// [`TelegramError::InvalidEndpoint`]: ../utils/result/enum.TelegramError.html#variant.InvalidEndpoint
+    async fn download_file(&self, _file: &File) -> Result<Vec<u8>> {
+        Err(TelegramError::InvalidEndpoint.into())
+    }
+
     /// A simple method for testing your bot's auth token. Requires no
     /// parameters. Returns basic information about the bot in form of a
-    /// [`User`] object.
-    async fn get_me(&self) -> Result<User> {
+    /// [`BotUser`] object.
+    async fn get_me(&self) -> Result<BotUser> {
         self.get(APIEndpoint::GetMe, None).await?.into()
     }
 
@@ -155,7 +164,7 @@ pub trait API: Sync {
     /// returned.
     async fn send_photo(&self, data: SendPhoto) -> Result<Message> {
         match &data.photo {
-            InputFile::String(_) => self
+            InputFile::Url(_) | InputFile::FileId(_) => self
                 .post(APIEndpoint::SendPhoto, Some(serde_json::to_value(&data)?))
                 .await?
                 .into(),
@@ -187,7 +196,7 @@ pub trait API: Sync {
         }
 
         self.post_file(
-            APIEndpoint::SendDocument,
+            APIEndpoint::SendAudio,
             Some(serde_json::to_value(&data)?),
             Some(files),
         )
@@ -236,7 +245,7 @@ pub trait API: Sync {
         }
 
         self.post_file(
-            APIEndpoint::SendDocument,
+            APIEndpoint::SendVideo,
             Some(serde_json::to_value(&data)?),
             Some(files),
         )
@@ -261,7 +270,7 @@ pub trait API: Sync {
         }
 
         self.post_file(
-            APIEndpoint::SendDocument,
+            APIEndpoint::SendAnimation,
             Some(serde_json::to_value(&data)?),
             Some(files),
         )
@@ -282,7 +291,7 @@ pub trait API: Sync {
         }
 
         self.post_file(
-            APIEndpoint::SendDocument,
+            APIEndpoint::SendVoice,
             Some(serde_json::to_value(&data)?),
             Some(files),
         )
@@ -306,7 +315,7 @@ pub trait API: Sync {
         }
 
         self.post_file(
-            APIEndpoint::SendDocument,
+            APIEndpoint::SendVideoNote,
             Some(serde_json::to_value(&data)?),
             Some(files),
         )
@@ -322,6 +331,9 @@ pub trait API: Sync {
             if let InputFile::File(f) = media.get_media() {
                 files.push(f.clone());
             }
+            if let Some(InputFile::File(f)) = media.get_thumb() {
+                files.push(f.clone());
+            }
         }
 
         files.dedup_by(|f1, f2| f1 == f2);
@@ -423,9 +435,27 @@ pub trait API: Sync {
     /// URL. On success, if the edited message was sent by the bot, the
     /// edited [`Message`] is returned, otherwise True is returned.
     async fn edit_message_media(&self, data: EditMessageMedia) -> Result<TrueOrObject<Message>> {
-        self.post(
+        let mut files = Vec::new();
+        if let InputFile::File(f) = data.media.get_media() {
+            files.push(f.clone());
+        }
+        if let Some(InputFile::File(f)) = data.media.get_thumb() {
+            files.push(f.clone());
+        }
+        if files.is_empty() {
+            return self
+                .post(
+                    APIEndpoint::EditMessageMedia,
+                    Some(serde_json::to_value(data)?),
+                )
+                .await?
+                .into();
+        }
+
+        self.post_file(
             APIEndpoint::EditMessageMedia,
-            Some(serde_json::to_value(data)?),
+            Some(serde_json::to_value(&data)?),
+            Some(files),
         )
         .await?
         .into()
@@ -526,7 +556,7 @@ pub trait API: Sync {
 
     /// Use this method to get basic info about a file and prepare it for
     /// downloading. For the moment, bots can download files of up to 20MB
-    /// in size. On success, a [`File`] object is returned. The file can then be downloaded via the link `https://api.telegram.org/file/bot<token>/<file_path>`, where <file_path> is taken from the response.
+    /// in size. On success, a [`File`] object is returned. The file can then be downloaded using [`API::download_file`], or via the link `https://api.telegram.org/file/bot<token>/<file_path>`, where `<file_path>` is taken from the response.
     /// It is guaranteed that the link will be valid for at least 1 hour. When
     /// the link expires, a new one can be requested by calling
     /// [`API::get_file`] again.
@@ -549,21 +579,30 @@ pub trait API: Sync {
         .into()
     }
 
-    /// Use this method to kick a user from a group, a supergroup or a channel.
+    /// Use this method to ban a user in a group, a supergroup or a channel.
     /// In the case of supergroups and channels, the user will not be able to
     /// return to the group on their own using invite links, etc., unless
     /// unbanned first. The bot must be an administrator in the chat for
     /// this to work and must have the appropriate admin rights. Returns True on
     /// success.
-    async fn kick_chat_member(&self, data: KickChatMember) -> Result<bool> {
+    async fn ban_chat_member(&self, data: BanChatMember) -> Result<bool> {
         self.post(
-            APIEndpoint::KickChatMember,
+            APIEndpoint::BanChatMember,
             Some(serde_json::to_value(data)?),
         )
         .await?
         .into()
     }
 
+    /// The old name of [`ban_chat_member`], from before telegram renamed the
+    /// method
+    ///
+    /// [`ban_chat_member`]: #method.ban_chat_member
+    #[deprecated(note = "telegram renamed the method, use `ban_chat_member` instead")]
+    async fn kick_chat_member(&self, data: BanChatMember) -> Result<bool> {
+        self.ban_chat_member(data).await
+    }
+
     /// Use this method to restrict a user in a supergroup.
     /// The bot must be an administrator in the supergroup for this to work and
     /// must have the appropriate admin rights. Pass True for all
@@ -792,9 +831,9 @@ pub trait API: Sync {
 
     /// Use this method to get up to date information about the chat
     /// (current name of the user for one-on-one conversations, current username
-    /// of a user, group or channel, etc.). Returns a [`Chat`] object on
+    /// of a user, group or channel, etc.). Returns a [`ChatFullInfo`] object on
     /// success.
-    async fn get_chat(&self, data: GetChat) -> Result<Chat> {
+    async fn get_chat(&self, data: GetChat) -> Result<ChatFullInfo> {
         self.get(APIEndpoint::GetChat, Some(serde_json::to_value(data)?))
             .await?
             .into()
@@ -819,15 +858,24 @@ pub trait API: Sync {
 
     /// Use this method to get the number of members in a chat. Returns i64 on
     /// success.
-    async fn get_members_count(&self, data: GetChatMembersCount) -> Result<i64> {
+    async fn get_chat_member_count(&self, data: GetChatMemberCount) -> Result<i64> {
         self.get(
-            APIEndpoint::GetChatMembersCount,
+            APIEndpoint::GetChatMemberCount,
             Some(serde_json::to_value(data)?),
         )
         .await?
         .into()
     }
 
+    /// The old name of [`get_chat_member_count`], from before telegram
+    /// renamed the method
+    ///
+    /// [`get_chat_member_count`]: #method.get_chat_member_count
+    #[deprecated(note = "telegram renamed the method, use `get_chat_member_count` instead")]
+    async fn get_members_count(&self, data: GetChatMemberCount) -> Result<i64> {
+        self.get_chat_member_count(data).await
+    }
+
     /// Use this method to get information about a member of a chat. Returns a
     /// [`ChatMember`] object on success.
     async fn get_chat_member(&self, data: GetChatMember) -> Result<ChatMember> {
@@ -883,7 +931,7 @@ pub trait API: Sync {
     /// success, the sent [Message] is returned.
     async fn send_sticker(&self, data: SendSticker) -> Result<Message> {
         match &data.sticker {
-            InputFile::String(_) => self
+            InputFile::Url(_) | InputFile::FileId(_) => self
                 .post(APIEndpoint::SendSticker, Some(serde_json::to_value(&data)?))
                 .await?
                 .into(),
@@ -909,6 +957,26 @@ pub trait API: Sync {
         .into()
     }
 
+    /// Use this method to get information about custom emoji stickers by
+    /// their identifiers. Returns a Vec of [Sticker] objects on success.
+    async fn get_custom_emoji_stickers(
+        &self,
+        data: GetCustomEmojiStickers,
+    ) -> Result<Vec<Sticker>> {
+        if data.custom_emoji_ids.len() > MAX_CUSTOM_EMOJI_STICKERS {
+            return Err(TelegramError::InvalidArgument(format!(
+                "No more than {MAX_CUSTOM_EMOJI_STICKERS} custom emoji can be requested at once."
+            ))
+            .into());
+        }
+        self.post(
+            APIEndpoint::GetCustomEmojiStickers,
+            Some(serde_json::to_value(data)?),
+        )
+        .await?
+        .into()
+    }
+
     /// Use this method to upload a .PNG file with a sticker for later use in
     /// createNewStickerSet and addStickerToSet methods (can be used
     /// multiple times). Returns the uploaded [File] on success.
@@ -1043,7 +1111,7 @@ pub trait API: Sync {
     /// True on success.
     async fn set_sticker_set_thumb(&self, data: SetStickerSetThumb) -> Result<bool> {
         match &data.thumb {
-            Some(InputFile::String(_)) | None => self
+            Some(InputFile::Url(_) | InputFile::FileId(_)) | None => self
                 .post(
                     APIEndpoint::SetStickerSetThumb,
                     Some(serde_json::to_value(&data)?),
@@ -1063,13 +1131,35 @@ pub trait API: Sync {
 
     /// Use this method to send answers to an inline query. On success, True is
     /// returned. No more than 50 results per query are allowed.
-    async fn answer_inline_query(&self, data: AnswerInlineQuery) -> Result<bool> {
-        if data.results.len() > 50 {
+    ///
+    /// Results without an id are given a random one, and an error is returned
+    /// if multiple results have the same id, as telegram would silently drop
+    /// all but the first of them.
+    async fn answer_inline_query(&self, mut data: AnswerInlineQuery) -> Result<bool> {
+        if data.results.len() > MAX_INLINE_QUERY_RESULTS {
             return Err(TelegramError::InvalidArgument(
                 "No more than 50 results per query are allowed.".to_owned(),
             )
             .into());
         }
+        #[allow(deprecated)]
+        if data.button.is_some() && data.switch_pm_text.is_some() {
+            return Err(TelegramError::InvalidArgument(
+                "button and switch_pm_text can't both be set, as telegram will ignore one of them"
+                    .to_owned(),
+            )
+            .into());
+        }
+        if let Some(button) = data.get_button() {
+            if button.web_app.is_some() == button.start_parameter.is_some() {
+                return Err(TelegramError::InvalidArgument(
+                    "exactly one of web_app and start_parameter must be set on the button"
+                        .to_owned(),
+                )
+                .into());
+            }
+        }
+        data.prepare_results()?;
 
         self.post(
             APIEndpoint::AnswerInlineQuery,
@@ -1087,6 +1177,21 @@ pub trait API: Sync {
             .into()
     }
 
+    /// Use this method to create a link for an invoice, which can be shared
+    /// instead of being sent to a chat. Returns an error without calling
+    /// telegram if the provider token doesn't match the currency, see
+    /// [`CreateInvoiceLink`]. On success, the created invoice link is
+    /// returned.
+    async fn create_invoice_link(&self, data: CreateInvoiceLink) -> Result<String> {
+        data.check_currency()?;
+        self.post(
+            APIEndpoint::CreateInvoiceLink,
+            Some(serde_json::to_value(data)?),
+        )
+        .await?
+        .into()
+    }
+
     /// If you sent an invoice requesting a shipping address and the parameter
     /// is_flexible was specified, the Bot API will send an [Update] with a
     /// shipping_query field to the bot. Use this method to reply to
@@ -1128,7 +1233,12 @@ pub trait API: Sync {
     /// Message, otherwise returns True. Returns an error, if the new score
     /// is not greater than the user's current score in the chat and force is
     /// False.
+    ///
+    /// The game message has to be addressed either by its `chat_id` and
+    /// `message_id` or by its `inline_message_id`, otherwise an error is
+    /// returned without calling telegram.
     async fn set_game_score(&self, data: SetGameScore) -> Result<TrueOrObject<Message>> {
+        data.check_target()?;
         self.post(APIEndpoint::SetGameScore, Some(serde_json::to_value(data)?))
             .await?
             .into()
@@ -1138,6 +1248,7 @@ pub trait API: Sync {
     /// of the specified user and several of his neighbors in a game.
     /// On success, returns a Vec of [GameHighScore] objects.
     async fn get_game_high_scores(&self, data: GetGameHighScores) -> Result<Vec<GameHighScore>> {
+        data.check_target()?;
         self.post(
             APIEndpoint::GetGameHighScores,
             Some(serde_json::to_value(data)?),
@@ -1,16 +1,36 @@
-use super::{api::API, endpoints::APIEndpoint, response::Response};
-use crate::utils::{
-    encode_multipart_form_data,
-    result::Result,
-    AsFormData,
-    FormDataFile,
-    BOUNDARY,
+use super::{
+    api::API,
+    endpoints::APIEndpoint,
+    response::Response,
+    validation::{validate_request, validate_text_lengths},
+};
+use crate::{
+    model::File,
+    utils::{
+        encode_multipart_form_data,
+        result::{Result, TelegramError},
+        AsFormData,
+        FormDataFile,
+        BOUNDARY,
+    },
 };
 use async_trait::async_trait;
-use hyper::{body::HttpBody, client::HttpConnector, Body, Client, Request};
-use std::io::Write;
+use hyper::{
+    body::HttpBody,
+    client::{connect::Connect, HttpConnector},
+    Body,
+    Client,
+    Request,
+    StatusCode,
+};
+use std::{io::Write, path::Path, time::Duration};
 
-static TELEGRAM_API: &str = "https://api.telegram.org/bot";
+/// The url of the official bot api server
+pub(crate) const DEFAULT_BASE_URL: &str = "https://api.telegram.org";
+
+/// How much longer than its long polling timeout a `getUpdates` request may
+/// take, to allow for the latency of the request itself
+const LONG_POLL_MARGIN: Duration = Duration::from_secs(5);
 
 /// A default implementation of the [`API`] trait.
 ///
@@ -33,12 +53,26 @@ static TELEGRAM_API: &str = "https://api.telegram.org/bot";
 /// ```
 ///
 /// In most cases you would want to get updates though and the [`Client`] is
-/// best suited for that, as it allows for easier handling of those updates
+/// best suited for that, as it allows for easier handling of those updates.
+///
+/// When running your own bot api server, for example to upload files larger
+/// than 50 MB, use [`set_base_url`] to send the requests to it instead.
 ///
 /// [`Client`]: ../client/struct.Client.html
-pub struct APIClient {
-    hyper_client: Client<hyper_tls::HttpsConnector<HttpConnector>>,
+/// [`set_base_url`]: #method.set_base_url
+///
+/// To connect to telegram in a different way, for example through a
+/// [`ProxyConnector`], the `APIClient` can use any hyper connector by creating
+/// it using [`with_hyper_client`].
+///
+/// [`ProxyConnector`]: struct.ProxyConnector.html
+/// [`with_hyper_client`]: #method.with_hyper_client
+pub struct APIClient<C = hyper_tls::HttpsConnector<HttpConnector>> {
+    hyper_client: Client<C>,
     token: String,
+    base_url: String,
+    request_timeout: Option<Duration>,
+    text_length_validation: bool,
 }
 
 impl APIClient {
@@ -52,10 +86,16 @@ impl APIClient {
             || Self {
                 hyper_client: hyper::Client::builder().build(hyper_tls::HttpsConnector::new()),
                 token: token.to_string(),
+                base_url: DEFAULT_BASE_URL.to_owned(),
+                request_timeout: None,
+                text_length_validation: true,
             },
             |c| Self {
                 hyper_client: c,
                 token: token.to_string(),
+                base_url: DEFAULT_BASE_URL.to_owned(),
+                request_timeout: None,
+                text_length_validation: true,
             },
         )
     }
@@ -66,11 +106,156 @@ impl APIClient {
         Self {
             hyper_client: hyper::Client::builder().build(hyper_tls::HttpsConnector::new()),
             token: token.to_string(),
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            request_timeout: None,
+            text_length_validation: true,
+        }
+    }
+
+    /// Creates a hyper client like the one used by default, but with the given
+    /// timeout for connecting to the bot api server
+    pub fn hyper_client_with_connect_timeout(
+        timeout: Duration,
+    ) -> Client<hyper_tls::HttpsConnector<HttpConnector>> {
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        http.set_connect_timeout(Some(timeout));
+        hyper::Client::builder().build(hyper_tls::HttpsConnector::new_with_connector(http))
+    }
+}
+
+impl<C> APIClient<C>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    /// Creates a new `APIClient` with the provided token, using the hyper
+    /// client with any connector
+    pub fn with_hyper_client<T: Into<String>>(hyper_client: Client<C>, token: T) -> Self {
+        Self {
+            hyper_client,
+            token: token.into(),
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            request_timeout: None,
+            text_length_validation: true,
+        }
+    }
+
+    /// Sets the url of the bot api server the requests are sent to, which
+    /// defaults to `https://api.telegram.org`. When it's set to a server
+    /// running in local mode, files with an absolute path are read from disk
+    /// by [`API::download_file`] instead of being downloaded.
+    ///
+    /// [`API::download_file`]: trait.API.html#method.download_file
+    pub fn set_base_url(&mut self, base_url: &str) -> &mut Self {
+        base_url.trim_end_matches('/').clone_into(&mut self.base_url);
+        self
+    }
+
+    /// Gets the url of the bot api server the requests are sent to
+    pub fn get_base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Sets how long a request may take before it fails with
+    /// [`TelegramError::Timeout`], by default there is no limit. Long polling
+    /// `getUpdates` requests are allowed to take a few seconds longer than
+    /// their long polling timeout when that is longer.
+    ///
+    /// [`TelegramError::Timeout`]: ../utils/result/enum.TelegramError.html#variant.Timeout
+    pub fn set_request_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Gets how long a request may take, if it is limited
+    pub fn get_request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    /// Sets whether texts and captions are checked to not be longer than
+    /// telegram allows before sending a request, which is enabled by default.
+    /// A request with a text that is too long then fails with
+    /// [`TelegramError::Validation`] naming the field and its limit, instead
+    /// of the generic error telegram returns for it. Texts with a parse mode
+    /// aren't checked, as telegram counts their length without the markup.
+    ///
+    /// [`TelegramError::Validation`]: ../utils/result/enum.TelegramError.html#variant.Validation
+    pub fn set_text_length_validation(&mut self, enabled: bool) -> &mut Self {
+        self.text_length_validation = enabled;
+        self
+    }
+
+    /// Gets whether texts and captions are checked to not be too long
+    pub fn get_text_length_validation(&self) -> bool {
+        self.text_length_validation
+    }
+
+    fn validate(&self, data: &serde_json::Value) -> Result<()> {
+        validate_request(data)?;
+        if self.text_length_validation {
+            validate_text_lengths(data)?;
         }
+        Ok(())
+    }
+
+    /// Gets how long a request to the endpoint may take, taking the long
+    /// polling timeout of `getUpdates` requests into account
+    fn timeout_for(
+        &self,
+        endpoint: &APIEndpoint,
+        data: Option<&serde_json::Value>,
+    ) -> Option<Duration> {
+        let timeout = self.request_timeout?;
+        let long_poll = match endpoint {
+            APIEndpoint::GetUpdates => data
+                .and_then(|d| d.get("timeout"))
+                .and_then(serde_json::Value::as_u64)
+                .map(|secs| Duration::from_secs(secs) + LONG_POLL_MARGIN),
+            _ => None,
+        };
+        Some(long_poll.map_or(timeout, |long_poll| long_poll.max(timeout)))
+    }
+
+    /// Sends the request to the endpoint, failing if it takes longer than the
+    /// timeout
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    async fn send(
+        &self,
+        endpoint: &APIEndpoint,
+        request: Request<Body>,
+        timeout: Option<Duration>,
+    ) -> Result<Response> {
+        let receive = async {
+            let mut response = self.hyper_client.request(request).await?;
+
+            let mut res: Vec<u8> = Vec::new();
+            while let Some(chunk) = response.body_mut().data().await {
+                res.write_all(&chunk?)?;
+            }
+
+            Ok(serde_json::from_slice(&res)?)
+        };
+
+        #[cfg(feature = "tracing")]
+        let receive = tracing::Instrument::instrument(
+            receive,
+            tracing::debug_span!("telegram_request", method = endpoint.as_str()),
+        );
+        match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, receive)
+                .await
+                .map_err(|_| TelegramError::Timeout)?,
+            None => receive.await,
+        }
+    }
+
+    /// Gets the url the file with the given `file_path` can be downloaded from
+    pub fn get_file_url(&self, file_path: &str) -> String {
+        format!("{}/file/bot{}/{file_path}", self.base_url, self.token)
     }
 
     fn parse_endpoint(&self, endpoint: &APIEndpoint) -> String {
-        format!("{}{}/{}", TELEGRAM_API, self.token, endpoint)
+        format!("{}/bot{}/{}", self.base_url, self.token, endpoint)
     }
 
     /// Sends a request to the provided `APIEndpoint` with the data provided
@@ -93,13 +278,16 @@ impl APIClient {
 
     /// gets a reference to the underlying hyper client, for example so you can
     /// make custom api requests
-    pub fn get_hyper(&self) -> &Client<hyper_tls::HttpsConnector<HttpConnector>> {
+    pub fn get_hyper(&self) -> &Client<C> {
         &self.hyper_client
     }
 }
 
 #[async_trait]
-impl API for APIClient {
+impl<C> API for APIClient<C>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
     async fn get(
         &self,
         endpoint: APIEndpoint,
@@ -109,21 +297,16 @@ impl API for APIClient {
             .header("content-type", "application/json")
             .header("accept", "application/json");
 
+        let timeout = self.timeout_for(&endpoint, data.as_ref());
         let request = if let Some(d) = data {
+            self.validate(&d)?;
             req_builder.body(Body::from(serde_json::to_string(&d)?))?
         } else {
             req_builder.body(Body::empty())?
         };
 
         log::debug!("GET request to {}", &endpoint);
-        let mut response = self.hyper_client.request(request).await?;
-
-        let mut res: Vec<u8> = Vec::new();
-        while let Some(chunk) = response.body_mut().data().await {
-            res.write_all(&chunk?)?
-        }
-
-        Ok(serde_json::from_slice(&res)?)
+        self.send(&endpoint, request, timeout).await
     }
 
     async fn post(
@@ -135,21 +318,16 @@ impl API for APIClient {
             .header("content-type", "application/json")
             .header("accept", "application/json");
 
+        let timeout = self.timeout_for(&endpoint, data.as_ref());
         let request = if let Some(d) = data {
+            self.validate(&d)?;
             req_builder.body(Body::from(serde_json::to_string(&d)?))?
         } else {
             req_builder.body(Body::empty())?
         };
 
         log::debug!("POST request to {}", &endpoint);
-        let mut response = self.hyper_client.request(request).await?;
-
-        let mut res: Vec<u8> = Vec::new();
-        while let Some(chunk) = response.body_mut().data().await {
-            res.write_all(&chunk?)?
-        }
-
-        Ok(serde_json::from_slice(&res)?)
+        self.send(&endpoint, request, timeout).await
     }
 
     async fn post_file(
@@ -174,21 +352,49 @@ impl API for APIClient {
             )
             .header("accept", "application/json");
 
-        if data.is_some() {
-            files.append(&mut data.expect("no data").as_form_data()?)
+        if let Some(d) = data {
+            self.validate(&d)?;
+            files.append(&mut d.as_form_data()?);
         }
 
         let bytes = encode_multipart_form_data(&files)?;
         let request = req_builder.body(Body::from(bytes))?;
 
         log::debug!("POST request with files to {}", &endpoint);
+        self.send(&endpoint, request, self.request_timeout).await
+    }
+
+    async fn download_file(&self, file: &File) -> Result<Vec<u8>> {
+        let file_path = file.file_path.as_ref().ok_or_else(|| {
+            TelegramError::InvalidArgument("the file doesn't have a file path".to_owned())
+        })?;
+
+        // a bot api server running in local mode returns the absolute path of
+        // the file on the machine it's running on, telegram's own servers
+        // never do so those paths are always downloaded
+        if self.base_url != DEFAULT_BASE_URL && Path::new(file_path).is_absolute() {
+            log::debug!("reading local file {file_path}");
+            return Ok(tokio::fs::read(file_path).await?);
+        }
+
+        log::debug!("downloading file {file_path}");
+        let request = Request::get(self.get_file_url(file_path)).body(Body::empty())?;
         let mut response = self.hyper_client.request(request).await?;
+        match response.status() {
+            StatusCode::OK => {},
+            StatusCode::NOT_FOUND => return Err(TelegramError::NotFound.into()),
+            status => {
+                return Err(TelegramError::APIResponseError(format!(
+                    "downloading the file failed with status {status}"
+                ))
+                .into())
+            },
+        }
 
         let mut res: Vec<u8> = Vec::new();
         while let Some(chunk) = response.body_mut().data().await {
-            res.write_all(&chunk?)?
+            res.write_all(&chunk?)?;
         }
-
-        Ok(serde_json::from_slice(&res)?)
+        Ok(res)
     }
 }
@@ -30,6 +30,8 @@ pub enum APIEndpoint {
     SendChatAction,
     GetUserProfilePhotos,
     GetFile,
+    BanChatMember,
+    #[deprecated(note = "telegram renamed the method, use `BanChatMember` instead")]
     KickChatMember,
     UnbanChatMember,
     RestrictChatMember,
@@ -50,6 +52,8 @@ pub enum APIEndpoint {
     LeaveChat,
     GetChat,
     GetChatAdministrators,
+    GetChatMemberCount,
+    #[deprecated(note = "telegram renamed the method, use `GetChatMemberCount` instead")]
     GetChatMembersCount,
     GetChatMember,
     SetChatStickerSet,
@@ -63,6 +67,7 @@ pub enum APIEndpoint {
     DeleteMessage,
     SendSticker,
     GetStickerSet,
+    GetCustomEmojiStickers,
     UploadStickerFile,
     CreateNewStickerSet,
     AddStickerToSet,
@@ -71,6 +76,7 @@ pub enum APIEndpoint {
     SetStickerSetThumb,
     AnswerInlineQuery,
     SendInvoice,
+    CreateInvoiceLink,
     AnswerShippingQuery,
     AnswerPreCheckoutQuery,
     SendGame,
@@ -84,6 +90,7 @@ pub enum APIEndpoint {
 }
 
 impl APIEndpoint {
+    #[allow(deprecated)]
     pub fn as_str(&self) -> &str {
         match *self {
             Self::GetUpdates => "getUpdates",
@@ -113,6 +120,7 @@ impl APIEndpoint {
             Self::SendChatAction => "sendChatAction",
             Self::GetUserProfilePhotos => "getUserProfilePhotos",
             Self::GetFile => "getFile",
+            Self::BanChatMember => "banChatMember",
             Self::KickChatMember => "kickChatMember",
             Self::UnbanChatMember => "unbanChatMember",
             Self::RestrictChatMember => "restrictChatMember",
@@ -133,6 +141,7 @@ impl APIEndpoint {
             Self::LeaveChat => "leaveChat",
             Self::GetChat => "getChat",
             Self::GetChatAdministrators => "getChatAdministrators",
+            Self::GetChatMemberCount => "getChatMemberCount",
             Self::GetChatMembersCount => "getChatMembersCount",
             Self::GetChatMember => "getChatMember",
             Self::SetChatStickerSet => "setChatStickerSet",
@@ -146,6 +155,7 @@ impl APIEndpoint {
             Self::DeleteMessage => "deleteMessage",
             Self::SendSticker => "sendSticker",
             Self::GetStickerSet => "getStickerSet",
+            Self::GetCustomEmojiStickers => "getCustomEmojiStickers",
             Self::UploadStickerFile => "uploadStickerFile",
             Self::CreateNewStickerSet => "createNewStickerSet",
             Self::AddStickerToSet => "addStickerToSet",
@@ -157,6 +167,7 @@ impl APIEndpoint {
             Self::SetGameScore => "setGameScore",
             Self::GetGameHighScores => "getGameHighScores",
             Self::SendInvoice => "sendInvoice",
+            Self::CreateInvoiceLink => "createInvoiceLink",
             Self::AnswerShippingQuery => "answerShippingQuery",
             Self::AnswerPreCheckoutQuery => "answerPreCheckoutQuery",
             Self::SetWebhook => "setWebHook",
@@ -7,10 +7,17 @@
 mod api;
 mod api_client;
 mod endpoints;
+mod observer;
+#[cfg(feature = "proxy")]
+mod proxy;
 mod response;
 pub mod types;
+mod validation;
 
 pub use api::API;
 pub use api_client::APIClient;
 pub use endpoints::APIEndpoint;
+pub use observer::{ApiObserver, ObservedAPI};
+#[cfg(feature = "proxy")]
+pub use proxy::{Proxy, ProxyConnector, ProxyKind};
 pub use response::Response;
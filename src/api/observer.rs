@@ -0,0 +1,122 @@
+use super::{api::API, endpoints::APIEndpoint, response::Response};
+use crate::{
+    model::File,
+    utils::{result::Result, FormDataFile},
+};
+use async_trait::async_trait;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// A trait for observing the requests made to the telegram API, for example to
+/// collect metrics about them or to wrap them in tracing spans.
+///
+/// It can be registered on the [`Client`] using
+/// [`ClientBuilder::set_api_observer`], or used directly by wrapping an
+/// [`API`] in an [`ObservedAPI`].
+///
+/// ```rust
+/// use std::{sync::atomic::{AtomicUsize, Ordering}, time::Duration};
+/// use telexide::{api::{ApiObserver, Response}, Result};
+///
+/// #[derive(Default)]
+/// struct RequestCounter {
+///     requests: AtomicUsize,
+/// }
+///
+/// impl ApiObserver for RequestCounter {
+///     fn on_request(&self, method_name: &str) {
+///         self.requests.fetch_add(1, Ordering::Relaxed);
+///     }
+///
+///     fn on_response(&self, method_name: &str, duration: Duration, result: &Result<Response>) {
+///         println!("{} took {}ms", method_name, duration.as_millis());
+///     }
+/// }
+/// ```
+///
+/// [`Client`]: ../client/struct.Client.html
+/// [`ClientBuilder::set_api_observer`]: ../client/struct.ClientBuilder.html#method.set_api_observer
+pub trait ApiObserver: Send + Sync {
+    /// Called right before a request to the telegram method with the given
+    /// name, like `sendMessage`, is made
+    fn on_request(&self, _method_name: &str) {}
+
+    /// Called after a request to the telegram method with the given name has
+    /// finished, with how long it took and its result. Note that an error
+    /// returned by telegram is an `Ok` result with a [`Response`] that isn't
+    /// ok.
+    fn on_response(&self, _method_name: &str, _duration: Duration, _result: &Result<Response>) {}
+}
+
+/// An [`API`] that notifies an [`ApiObserver`] around every request it makes
+/// using the underlying [`API`].
+pub struct ObservedAPI {
+    api: Arc<Box<dyn API + Send>>,
+    observer: Arc<dyn ApiObserver>,
+}
+
+impl ObservedAPI {
+    /// Creates a new `ObservedAPI`, making its requests using the given
+    /// [`API`] and notifying the given observer about them
+    pub fn new(api: Arc<Box<dyn API + Send>>, observer: Arc<dyn ApiObserver>) -> Self {
+        Self {
+            api,
+            observer,
+        }
+    }
+
+    /// Gets the underlying [`API`] the requests are made with
+    pub fn get_api(&self) -> &Arc<Box<dyn API + Send>> {
+        &self.api
+    }
+
+    async fn observe<F>(&self, method_name: String, request: F) -> Result<Response>
+    where
+        F: std::future::Future<Output = Result<Response>>,
+    {
+        self.observer.on_request(&method_name);
+
+        let start = Instant::now();
+        let result = request.await;
+        self.observer
+            .on_response(&method_name, start.elapsed(), &result);
+        result
+    }
+}
+
+#[async_trait]
+impl API for ObservedAPI {
+    async fn get(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.observe(endpoint.to_string(), self.api.get(endpoint, data))
+            .await
+    }
+
+    async fn post(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.observe(endpoint.to_string(), self.api.post(endpoint, data))
+            .await
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+        files: Option<Vec<FormDataFile>>,
+    ) -> Result<Response> {
+        self.observe(endpoint.to_string(), self.api.post_file(endpoint, data, files))
+            .await
+    }
+
+    async fn download_file(&self, file: &File) -> Result<Vec<u8>> {
+        self.api.download_file(file).await
+    }
+}
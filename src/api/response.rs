@@ -1,12 +1,14 @@
 use crate::utils::result::{Result, TelegramError};
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 
 /// The response object that gets returned from the telegram API
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Response {
     pub ok: bool,
     pub description: Option<String>,
-    pub result: Option<serde_json::Value>,
+    /// The JSON of the result, exactly as it was received
+    pub result: Option<Box<RawValue>>,
 }
 
 impl<T> From<Response> for Result<T>
@@ -15,9 +17,10 @@ where
 {
     fn from(resp: Response) -> Result<T> {
         if resp.ok {
-            Ok(serde_json::from_value(resp.result.ok_or_else(|| {
+            let result = resp.result.ok_or_else(|| {
                 TelegramError::Unknown("response had no result".to_owned())
-            })?)?)
+            })?;
+            Ok(serde_json::from_str(result.get())?)
         } else if resp.description.is_some() {
             Err(TelegramError::APIResponseError(
                 resp.description
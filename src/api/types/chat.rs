@@ -2,14 +2,35 @@ use super::InputFile;
 use crate::model::{utils::unix_date_formatting, Chat, ChatPermissions};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The shortest restriction telegram doesn't consider to be forever, with some
+/// margin for the time it takes the request to reach telegram
+const MIN_RESTRICTION: Duration = Duration::from_secs(35);
+
+/// The longest restriction telegram doesn't consider to be forever
+#[allow(clippy::duration_suboptimal_units)]
+const MAX_RESTRICTION: Duration = Duration::from_secs(366 * 24 * 3600);
+
+/// Gets the date a restriction for the given duration ends, if it doesn't last
+/// forever. Telegram considers restrictions shorter than 30 seconds or longer
+/// than 366 days to be forever, so shorter durations are rounded up to a bit
+/// over 30 seconds and longer durations result in `None`.
+pub fn restricted_until(duration: Duration) -> Option<DateTime<Utc>> {
+    if duration > MAX_RESTRICTION {
+        return None;
+    }
+    let duration = chrono::Duration::from_std(duration.max(MIN_RESTRICTION)).ok()?;
+    Some(Utc::now() + duration)
+}
 
 /// struct for holding data needed to call
-/// [`kick_chat_member`]
+/// [`ban_chat_member`]
 ///
-/// [`kick_chat_member`]:
-/// ../../api/trait.API.html#method.kick_chat_member
+/// [`ban_chat_member`]:
+/// ../../api/trait.API.html#method.ban_chat_member
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-pub struct KickChatMember {
+pub struct BanChatMember {
     /// Unique identifier for the target chat
     pub chat_id: i64,
     /// Unique identifier of the target user
@@ -27,6 +48,45 @@ pub struct KickChatMember {
     pub revoke_messages: Option<bool>,
 }
 
+impl BanChatMember {
+    /// function to create a new `BanChatMember` object, banning the user
+    /// forever
+    pub fn new(chat_id: i64, user_id: i64) -> Self {
+        Self {
+            chat_id,
+            user_id,
+            until_date: None,
+            revoke_messages: None,
+        }
+    }
+
+    /// function to create a `BanChatMember` object banning the user for the
+    /// given duration, see [`set_until_after`] for how short and long
+    /// durations are handled
+    ///
+    /// [`set_until_after`]: #method.set_until_after
+    pub fn for_duration(chat_id: i64, user_id: i64, duration: Duration) -> Self {
+        let mut data = Self::new(chat_id, user_id);
+        data.set_until_after(duration);
+        data
+    }
+
+    /// Sets the user to be unbanned after the given duration from now, see
+    /// [`restricted_until`] for how short and long durations are handled
+    ///
+    /// [`restricted_until`]: fn.restricted_until.html
+    pub fn set_until_after(&mut self, duration: Duration) -> &mut Self {
+        self.until_date = restricted_until(duration).map(|date| date.timestamp());
+        self
+    }
+}
+
+/// The old name of [`BanChatMember`], from before telegram renamed the method
+///
+/// [`BanChatMember`]: struct.BanChatMember.html
+#[deprecated(note = "telegram renamed the method, use `BanChatMember` instead")]
+pub type KickChatMember = BanChatMember;
+
 /// struct for holding data needed to call
 /// [`unban_chat_member`]
 ///
@@ -56,6 +116,12 @@ pub struct RestrictChatMember {
     pub user_id: i64,
     /// New user permissions
     pub permissions: ChatPermissions,
+    /// Pass True, if the permissions are set independently. Otherwise
+    /// `can_send_other_messages` and `can_add_web_page_previews` imply the
+    /// permissions to send messages and each kind of media, and
+    /// `can_send_polls` implies the permission to send messages
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub use_independent_chat_permissions: bool,
     /// Date when the user will be unbanned, unix time.
     /// If user is banned for more than 366 days or less than 30 seconds from
     /// the current time they are considered to be banned forever
@@ -64,6 +130,57 @@ pub struct RestrictChatMember {
     pub until_date: Option<DateTime<Utc>>,
 }
 
+impl RestrictChatMember {
+    /// function to create a new `RestrictChatMember` object, restricting the
+    /// user to the permissions forever
+    pub fn new(chat_id: i64, user_id: i64, permissions: ChatPermissions) -> Self {
+        Self {
+            chat_id,
+            user_id,
+            permissions,
+            use_independent_chat_permissions: false,
+            until_date: None,
+        }
+    }
+
+    /// Sets whether the granular media permissions are applied as they are
+    pub fn set_use_independent_chat_permissions(&mut self, independent: bool) -> &mut Self {
+        self.use_independent_chat_permissions = independent;
+        self
+    }
+
+    /// function to create a `RestrictChatMember` object muting the user until
+    /// the given date, using [`ChatPermissions::none`]
+    ///
+    /// [`ChatPermissions::none`]: ../../model/struct.ChatPermissions.html#method.none
+    pub fn mute(chat_id: i64, user_id: i64, until: DateTime<Utc>) -> Self {
+        Self {
+            until_date: Some(until),
+            ..Self::new(chat_id, user_id, ChatPermissions::none())
+        }
+    }
+
+    /// function to create a `RestrictChatMember` object muting the user for
+    /// the given duration, see [`set_until_after`] for how short and long
+    /// durations are handled
+    ///
+    /// [`set_until_after`]: #method.set_until_after
+    pub fn mute_for(chat_id: i64, user_id: i64, duration: Duration) -> Self {
+        let mut data = Self::new(chat_id, user_id, ChatPermissions::none());
+        data.set_until_after(duration);
+        data
+    }
+
+    /// Sets the restriction to be lifted after the given duration from now,
+    /// see [`restricted_until`] for how short and long durations are handled
+    ///
+    /// [`restricted_until`]: fn.restricted_until.html
+    pub fn set_until_after(&mut self, duration: Duration) -> &mut Self {
+        self.until_date = restricted_until(duration);
+        self
+    }
+}
+
 /// struct for holding data needed to call
 /// [`promote_chat_member`]
 ///
@@ -165,6 +282,29 @@ pub struct SetChatPermissions {
     pub chat_id: i64,
     /// New default chat permissions
     pub permissions: ChatPermissions,
+    /// Pass True, if the permissions are set independently. Otherwise
+    /// `can_send_other_messages` and `can_add_web_page_previews` imply the
+    /// permissions to send messages and each kind of media, and
+    /// `can_send_polls` implies the permission to send messages
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub use_independent_chat_permissions: bool,
+}
+
+impl SetChatPermissions {
+    /// function to create a new `SetChatPermissions` object
+    pub fn new(chat_id: i64, permissions: ChatPermissions) -> Self {
+        Self {
+            chat_id,
+            permissions,
+            use_independent_chat_permissions: false,
+        }
+    }
+
+    /// Sets whether the granular media permissions are applied as they are
+    pub fn set_use_independent_chat_permissions(&mut self, independent: bool) -> &mut Self {
+        self.use_independent_chat_permissions = independent;
+        self
+    }
 }
 
 /// struct for holding data needed to call [`export_chat_invite_link`]
@@ -304,16 +444,23 @@ pub struct GetChatAdministrators {
 }
 
 /// struct for holding data needed to call
-/// [`get_chat_members_count`]
+/// [`get_chat_member_count`]
 ///
-/// [`get_chat_members_count`]:
-/// ../../api/trait.API.html#method.get_chat_members_count
+/// [`get_chat_member_count`]:
+/// ../../api/trait.API.html#method.get_chat_member_count
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-pub struct GetChatMembersCount {
+pub struct GetChatMemberCount {
     /// Unique identifier for the target chat
     pub chat_id: i64,
 }
 
+/// The old name of [`GetChatMemberCount`], from before telegram renamed the
+/// method
+///
+/// [`GetChatMemberCount`]: struct.GetChatMemberCount.html
+#[deprecated(note = "telegram renamed the method, use `GetChatMemberCount` instead")]
+pub type GetChatMembersCount = GetChatMemberCount;
+
 /// struct for holding data needed to call
 /// [`get_chat_member`]
 ///
@@ -413,5 +560,5 @@ impl_from_chat!(UnpinAllChatMessages);
 impl_from_chat!(LeaveChat);
 impl_from_chat!(GetChat);
 impl_from_chat!(GetChatAdministrators);
-impl_from_chat!(GetChatMembersCount);
+impl_from_chat!(GetChatMemberCount);
 impl_from_chat!(DeleteChatStickerSet);
@@ -24,6 +24,7 @@ impl From<&Vec<TelegramCommand>> for SetMyCommands {
         Self {
             commands: commands
                 .iter()
+                .filter(|c| !c.options.hidden)
                 .map(TelegramCommand::get_bot_command)
                 .collect(),
         }
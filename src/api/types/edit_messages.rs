@@ -22,7 +22,7 @@ pub struct EditMessageText {
     pub text: String,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in message text, which can be
     /// specified instead of parse_mode
@@ -36,7 +36,7 @@ pub struct EditMessageText {
 }
 
 impl EditMessageText {
-    fn new(chat_id: i64, message_id: i64, new_text: &str) -> Self {
+    pub fn new(chat_id: i64, message_id: i64, new_text: &str) -> Self {
         Self {
             chat_id: Some(chat_id),
             message_id: Some(message_id),
@@ -49,7 +49,7 @@ impl EditMessageText {
         }
     }
 
-    fn from_message(message: &Message, new_text: &str) -> Self {
+    pub fn from_message(message: &Message, new_text: &str) -> Self {
         Self {
             chat_id: Some(message.chat.get_id()),
             message_id: Some(message.message_id),
@@ -84,7 +84,7 @@ pub struct EditMessageCaption {
     pub caption: Option<String>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
@@ -96,7 +96,7 @@ pub struct EditMessageCaption {
 }
 
 impl EditMessageCaption {
-    fn new(chat_id: i64, message_id: i64, new_text: Option<&str>) -> Self {
+    pub fn new(chat_id: i64, message_id: i64, new_text: Option<&str>) -> Self {
         Self {
             chat_id: Some(chat_id),
             message_id: Some(message_id),
@@ -108,7 +108,7 @@ impl EditMessageCaption {
         }
     }
 
-    fn from_message(message: &Message, new_text: Option<&str>) -> Self {
+    pub fn from_message(message: &Message, new_text: Option<&str>) -> Self {
         Self {
             chat_id: Some(message.chat.get_id()),
             message_id: Some(message.message_id),
@@ -145,7 +145,7 @@ pub struct EditMessageMedia {
 }
 
 impl EditMessageMedia {
-    fn new(chat_id: i64, message_id: i64, new_media: &InputMedia) -> Self {
+    pub fn new(chat_id: i64, message_id: i64, new_media: &InputMedia) -> Self {
         Self {
             chat_id: Some(chat_id),
             message_id: Some(message_id),
@@ -155,7 +155,7 @@ impl EditMessageMedia {
         }
     }
 
-    fn from_message(message: &Message, new_media: &InputMedia) -> Self {
+    pub fn from_message(message: &Message, new_media: &InputMedia) -> Self {
         Self {
             chat_id: Some(message.chat.get_id()),
             message_id: Some(message.message_id),
@@ -296,6 +296,11 @@ pub struct EditMessageLiveLocation {
     /// The radius of uncertainty for the location, measured in meters; 0-1500
     #[serde(skip_serializing_if = "Option::is_none")]
     pub horizontal_accuracy: Option<f64>,
+    /// New period in seconds during which the location can be updated,
+    /// starting from the sending date of the message, which can be used to
+    /// extend it. It may not exceed the current period by more than a day.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub live_period: Option<i64>,
     /// Direction in which the user is moving, in degrees. Must be between 1 and
     /// 360 if specified.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -326,3 +331,13 @@ pub struct StopMessageLiveLocation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reply_markup: Option<InlineKeyboardMarkup>,
 }
+
+impl_reply_markup_setter!(InlineKeyboardMarkup:
+    EditMessageText,
+    EditMessageCaption,
+    EditMessageMedia,
+    EditMessageReplyMarkup,
+    StopPoll,
+    EditMessageLiveLocation,
+    StopMessageLiveLocation,
+);
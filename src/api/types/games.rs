@@ -1,4 +1,7 @@
-use crate::model::ReplyMarkup;
+use crate::{
+    model::ReplyMarkup,
+    utils::result::{Result, TelegramError},
+};
 use serde::{Deserialize, Serialize};
 
 /// struct for holding data needed to call
@@ -55,6 +58,57 @@ pub struct SetGameScore {
     pub inline_message_id: Option<String>,
 }
 
+impl SetGameScore {
+    /// Creates a request setting the score in the game message with the given
+    /// id in the chat
+    pub fn new(user_id: i64, score: i64, chat_id: i64, message_id: i64) -> Self {
+        Self {
+            user_id,
+            score,
+            force: false,
+            disable_edit_message: false,
+            chat_id: Some(chat_id),
+            message_id: Some(message_id),
+            inline_message_id: None,
+        }
+    }
+
+    /// Creates a request setting the score in the inline game message with the
+    /// given id
+    pub fn new_inline(user_id: i64, score: i64, inline_message_id: &str) -> Self {
+        Self {
+            user_id,
+            score,
+            force: false,
+            disable_edit_message: false,
+            chat_id: None,
+            message_id: None,
+            inline_message_id: Some(inline_message_id.to_owned()),
+        }
+    }
+
+    /// Sets whether the high score is allowed to decrease
+    pub fn set_force(&mut self, force: bool) -> &mut Self {
+        self.force = force;
+        self
+    }
+
+    /// Sets whether the game message should not be automatically edited to
+    /// include the current scoreboard
+    pub fn set_disable_edit_message(&mut self, disable: bool) -> &mut Self {
+        self.disable_edit_message = disable;
+        self
+    }
+
+    pub(crate) fn check_target(&self) -> Result<()> {
+        check_message_target(
+            self.chat_id,
+            self.message_id,
+            self.inline_message_id.as_deref(),
+        )
+    }
+}
+
 /// struct for holding data needed to call
 /// [`get_game_high_scores`]
 ///
@@ -77,3 +131,53 @@ pub struct GetGameHighScores {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inline_message_id: Option<String>,
 }
+
+impl GetGameHighScores {
+    /// Creates a request getting the high scores of the game message with the
+    /// given id in the chat
+    pub fn new(user_id: i64, chat_id: i64, message_id: i64) -> Self {
+        Self {
+            user_id,
+            chat_id: Some(chat_id),
+            message_id: Some(message_id),
+            inline_message_id: None,
+        }
+    }
+
+    /// Creates a request getting the high scores of the inline game message
+    /// with the given id
+    pub fn new_inline(user_id: i64, inline_message_id: &str) -> Self {
+        Self {
+            user_id,
+            chat_id: None,
+            message_id: None,
+            inline_message_id: Some(inline_message_id.to_owned()),
+        }
+    }
+
+    pub(crate) fn check_target(&self) -> Result<()> {
+        check_message_target(
+            self.chat_id,
+            self.message_id,
+            self.inline_message_id.as_deref(),
+        )
+    }
+}
+
+/// checks that a game message is addressed either by its chat and message id
+/// or by its inline message id, but not both
+fn check_message_target(
+    chat_id: Option<i64>,
+    message_id: Option<i64>,
+    inline_message_id: Option<&str>,
+) -> Result<()> {
+    match (chat_id, message_id, inline_message_id) {
+        (Some(_), Some(_), None) | (None, None, Some(_)) => Ok(()),
+        _ => Err(TelegramError::InvalidArgument(
+            "exactly one of chat_id with message_id and inline_message_id must be set".to_owned(),
+        )
+        .into()),
+    }
+}
+
+impl_reply_markup_setter!(ReplyMarkup: SendGame);
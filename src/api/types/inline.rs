@@ -1,12 +1,21 @@
-use crate::model::{InlineKeyboardMarkup, MessageEntity, ParseMode, LabeledPrice};
-use serde::{Deserialize, Serialize};
+use crate::{
+    model::{InlineKeyboardMarkup, MessageEntity, ParseMode, LabeledPrice, WebAppInfo},
+    utils::result::{Result, TelegramError},
+};
+use serde::{Deserialize, Serialize, Serializer};
+use std::{borrow::Cow, collections::HashSet, fmt::Display};
+use uuid::Uuid;
+
+/// The maximum amount of results telegram accepts in a single answer to an
+/// inline query
+pub const MAX_INLINE_QUERY_RESULTS: usize = 50;
 
 /// struct for holding data needed to call
 /// [`answer_inline_query`]
 ///
 /// [`answer_inline_query`]:
 /// ../../api/trait.API.html#method.answer_inline_query
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct AnswerInlineQuery {
     /// Unique identifier for the answered query
     pub inline_query_id: String,
@@ -26,10 +35,14 @@ pub struct AnswerInlineQuery {
     /// can’t exceed 64 bytes.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_offset: Option<String>,
+    /// A button to be shown above the inline query results
+    #[serde(default)]
+    pub button: Option<InlineQueryResultsButton>,
     /// If passed, clients will display a button with specified text that
     /// switches the user to a private chat with the bot and sends the bot a
     /// start message with the parameter switch_pm_parameter
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deprecated(note = "use `button` with `InlineQueryResultsButton::start_parameter` instead")]
+    #[serde(default)]
     pub switch_pm_text: Option<String>,
     /// [Deep-linking](https://core.telegram.org/bots#deep-linking) parameter for the /start message sent to the bot when user presses the switch button.
     /// 1-64 characters, only A-Z, a-z, 0-9, _ and - are allowed.
@@ -43,10 +56,204 @@ pub struct AnswerInlineQuery {
     /// return an oauth link. Once done, the bot can offer a [switch_inline button](https://core.telegram.org/bots/api#inlinekeyboardmarkup)
     /// so that the user can easily return to the chat where they wanted to use
     /// the bot's inline capabilities.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[deprecated(note = "use `button` with `InlineQueryResultsButton::start_parameter` instead")]
+    #[serde(default)]
     pub switch_pm_parameter: Option<String>,
 }
 
+#[allow(deprecated)]
+impl AnswerInlineQuery {
+    pub fn new(inline_query_id: &str, results: Vec<InlineQueryResult>) -> Self {
+        Self {
+            inline_query_id: inline_query_id.to_owned(),
+            results,
+            cache_time: None,
+            is_personal: false,
+            next_offset: None,
+            button: None,
+            switch_pm_text: None,
+            switch_pm_parameter: None,
+        }
+    }
+
+    /// Answers with the given page of the results, setting the next offset to
+    /// the number of the next page if there are more results.
+    ///
+    /// The page to answer with can be parsed from the incoming query using
+    /// [`InlineQuery::page`].
+    ///
+    /// [`InlineQuery::page`]: ../../model/struct.InlineQuery.html#method.page
+    pub fn new_paginated(
+        inline_query_id: &str,
+        mut results: Vec<InlineQueryResult>,
+        page: usize,
+    ) -> Self {
+        let start = page.saturating_mul(MAX_INLINE_QUERY_RESULTS).min(results.len());
+        let end = start.saturating_add(MAX_INLINE_QUERY_RESULTS).min(results.len());
+        let has_more = end < results.len();
+
+        let mut answer = Self::new(inline_query_id, results.drain(start..end).collect());
+        answer.set_next_page(has_more.then(|| page + 1));
+        answer
+    }
+
+    /// Gets the button shown above the results, which is created from the
+    /// deprecated `switch_pm_text` and `switch_pm_parameter` if `button`
+    /// isn't set
+    pub fn get_button(&self) -> Option<Cow<'_, InlineQueryResultsButton>> {
+        match (&self.button, &self.switch_pm_text) {
+            (Some(button), _) => Some(Cow::Borrowed(button)),
+            (None, Some(text)) => Some(Cow::Owned(InlineQueryResultsButton {
+                text: text.clone(),
+                web_app: None,
+                start_parameter: self.switch_pm_parameter.clone(),
+            })),
+            (None, None) => None,
+        }
+    }
+
+    /// Gives the results without an id a random one, and returns an error if
+    /// multiple results have the same id, as telegram would only show the
+    /// first of them
+    pub(crate) fn prepare_results(&mut self) -> Result<()> {
+        if let Some(offset) = self.next_offset.as_deref().filter(|o| o.len() > 64) {
+            return Err(TelegramError::InvalidArgument(format!(
+                "the next offset {offset:?} is longer than 64 bytes"
+            ))
+            .into());
+        }
+
+        for result in &mut self.results {
+            if result.id().is_empty() {
+                result.set_id(&Uuid::new_v4().to_string());
+            }
+        }
+
+        let mut ids = HashSet::new();
+        match self.results.iter().map(InlineQueryResult::id).find(|id| !ids.insert(*id)) {
+            Some(id) => Err(TelegramError::InvalidArgument(format!(
+                "multiple results have the id {id:?}, but telegram only shows the first of them"
+            ))
+            .into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Sets the button shown above the results
+    pub fn set_button(&mut self, button: InlineQueryResultsButton) -> &mut Self {
+        self.button = Some(button);
+        self
+    }
+
+    /// Sets the maximum amount of time in seconds that the results may be
+    /// cached on the server
+    pub fn set_cache_time(&mut self, cache_time: i64) -> &mut Self {
+        self.cache_time = Some(cache_time);
+        self
+    }
+
+    /// Sets whether the results may only be cached for the user that sent the
+    /// query
+    pub fn set_is_personal(&mut self, is_personal: bool) -> &mut Self {
+        self.is_personal = is_personal;
+        self
+    }
+
+    /// Sets the offset the client should send in the next query to receive
+    /// more results
+    pub fn set_next_offset(&mut self, next_offset: &str) -> &mut Self {
+        self.next_offset = Some(next_offset.to_owned());
+        self
+    }
+
+    /// Sets the cursor the client should send in the next query to receive
+    /// more results, or `None` if there are no more results
+    pub fn set_next_cursor<C: Display>(&mut self, cursor: Option<C>) -> &mut Self {
+        self.next_offset = Some(cursor.map(|c| c.to_string()).unwrap_or_default());
+        self
+    }
+
+    /// Sets the page the client should request in the next query to receive
+    /// more results, or `None` if there are no more results
+    pub fn set_next_page(&mut self, page: Option<usize>) -> &mut Self {
+        self.set_next_cursor(page)
+    }
+}
+
+/// the data actually sent to telegram, in which the deprecated `switch_pm_*`
+/// fields are replaced by the button they describe
+#[derive(Serialize)]
+struct AnswerInlineQueryPayload<'a> {
+    inline_query_id: &'a str,
+    results: &'a [InlineQueryResult],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_time: Option<i64>,
+    is_personal: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_offset: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    button: Option<Cow<'a, InlineQueryResultsButton>>,
+}
+
+impl Serialize for AnswerInlineQuery {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        AnswerInlineQueryPayload {
+            inline_query_id: &self.inline_query_id,
+            results: &self.results,
+            cache_time: self.cache_time,
+            is_personal: self.is_personal,
+            next_offset: self.next_offset.as_deref(),
+            button: self.get_button(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// This object represents a button to be shown above inline query results.
+/// Exactly one of `web_app` and `start_parameter` must be set.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InlineQueryResultsButton {
+    /// Label text on the button
+    pub text: String,
+    /// Description of the [Web App] that will be launched when the user
+    /// presses the button. The Web App will be able to switch back to the
+    /// inline mode using the method `switchInlineQuery` inside the Web App.
+    ///
+    /// [Web App]: https://core.telegram.org/bots/webapps
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_app: Option<WebAppInfo>,
+    /// [Deep-linking](https://core.telegram.org/bots#deep-linking) parameter for the /start message sent to the bot when a user presses the button.
+    /// 1-64 characters, only A-Z, a-z, 0-9, _ and - are allowed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_parameter: Option<String>,
+}
+
+impl InlineQueryResultsButton {
+    /// Creates a button that opens the web app at the url
+    pub fn web_app(text: &str, url: &str) -> Self {
+        Self {
+            text: text.to_owned(),
+            web_app: Some(WebAppInfo {
+                url: url.to_owned(),
+            }),
+            start_parameter: None,
+        }
+    }
+
+    /// Creates a button that switches the user to a private chat with the bot
+    /// and sends it a start message with the parameter
+    pub fn start_parameter(text: &str, start_parameter: &str) -> Self {
+        Self {
+            text: text.to_owned(),
+            web_app: None,
+            start_parameter: Some(start_parameter.to_owned()),
+        }
+    }
+}
+
 /// This object represents one result of an inline query.
 #[allow(clippy::large_enum_variant)]
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -78,6 +285,121 @@ pub enum InlineQueryResult {
     Voice(InlineQueryResultVoice),
 }
 
+impl InlineQueryResult {
+    /// Gets the id of the result. Results with an empty id are given a random
+    /// one when they are sent using [`answer_inline_query`]
+    ///
+    /// [`answer_inline_query`]: ../../api/trait.API.html#method.answer_inline_query
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Article(InlineQueryResultArticle { id, .. })
+            | Self::Audio(InlineQueryResultAudio { id, .. })
+            | Self::Contact(InlineQueryResultContact { id, .. })
+            | Self::Game(InlineQueryResultGame { id, .. })
+            | Self::Document(InlineQueryResultDocument { id, .. })
+            | Self::Gif(InlineQueryResultGif { id, .. })
+            | Self::Location(InlineQueryResultLocation { id, .. })
+            | Self::Mpeg4Gif(InlineQueryResultMpeg4Gif { id, .. })
+            | Self::Photo(InlineQueryResultPhoto { id, .. })
+            | Self::Venue(InlineQueryResultVenue { id, .. })
+            | Self::Video(InlineQueryResultVideo { id, .. })
+            | Self::Voice(InlineQueryResultVoice { id, .. }) => id,
+        }
+    }
+
+    /// Sets the id of the result, which has to be unique among the results
+    /// it is sent with
+    pub fn set_id(&mut self, new_id: &str) -> &mut Self {
+        match self {
+            Self::Article(InlineQueryResultArticle { id, .. })
+            | Self::Audio(InlineQueryResultAudio { id, .. })
+            | Self::Contact(InlineQueryResultContact { id, .. })
+            | Self::Game(InlineQueryResultGame { id, .. })
+            | Self::Document(InlineQueryResultDocument { id, .. })
+            | Self::Gif(InlineQueryResultGif { id, .. })
+            | Self::Location(InlineQueryResultLocation { id, .. })
+            | Self::Mpeg4Gif(InlineQueryResultMpeg4Gif { id, .. })
+            | Self::Photo(InlineQueryResultPhoto { id, .. })
+            | Self::Venue(InlineQueryResultVenue { id, .. })
+            | Self::Video(InlineQueryResultVideo { id, .. })
+            | Self::Voice(InlineQueryResultVoice { id, .. }) => new_id.clone_into(id),
+        }
+        self
+    }
+}
+
+impl From<InlineQueryResultArticle> for InlineQueryResult {
+    fn from(article: InlineQueryResultArticle) -> Self {
+        Self::Article(article)
+    }
+}
+
+impl From<InlineQueryResultPhoto> for InlineQueryResult {
+    fn from(photo: InlineQueryResultPhoto) -> Self {
+        Self::Photo(photo)
+    }
+}
+
+impl From<InlineQueryResultGif> for InlineQueryResult {
+    fn from(gif: InlineQueryResultGif) -> Self {
+        Self::Gif(gif)
+    }
+}
+
+impl From<InlineQueryResultMpeg4Gif> for InlineQueryResult {
+    fn from(gif: InlineQueryResultMpeg4Gif) -> Self {
+        Self::Mpeg4Gif(gif)
+    }
+}
+
+impl From<InlineQueryResultVideo> for InlineQueryResult {
+    fn from(video: InlineQueryResultVideo) -> Self {
+        Self::Video(video)
+    }
+}
+
+impl From<InlineQueryResultAudio> for InlineQueryResult {
+    fn from(audio: InlineQueryResultAudio) -> Self {
+        Self::Audio(audio)
+    }
+}
+
+impl From<InlineQueryResultVoice> for InlineQueryResult {
+    fn from(voice: InlineQueryResultVoice) -> Self {
+        Self::Voice(voice)
+    }
+}
+
+impl From<InlineQueryResultDocument> for InlineQueryResult {
+    fn from(document: InlineQueryResultDocument) -> Self {
+        Self::Document(document)
+    }
+}
+
+impl From<InlineQueryResultLocation> for InlineQueryResult {
+    fn from(location: InlineQueryResultLocation) -> Self {
+        Self::Location(location)
+    }
+}
+
+impl From<InlineQueryResultVenue> for InlineQueryResult {
+    fn from(venue: InlineQueryResultVenue) -> Self {
+        Self::Venue(venue)
+    }
+}
+
+impl From<InlineQueryResultContact> for InlineQueryResult {
+    fn from(contact: InlineQueryResultContact) -> Self {
+        Self::Contact(contact)
+    }
+}
+
+impl From<InlineQueryResultGame> for InlineQueryResult {
+    fn from(game: InlineQueryResultGame) -> Self {
+        Self::Game(game)
+    }
+}
+
 // TODO: add support for the cached types too. Add enum with url and cache
 // variant?
 
@@ -112,6 +434,24 @@ pub struct InlineQueryResultArticle {
     pub thumb_height: Option<i64>,
 }
 
+impl InlineQueryResultArticle {
+    /// Creates an article with a random id, which sends the given content
+    pub fn new(title: &str, input_message_content: InputMessageContent) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            title: title.to_owned(),
+            input_message_content,
+            reply_markup: None,
+            url: None,
+            hide_url: false,
+            description: None,
+            thumb_url: None,
+            thumb_width: None,
+            thumb_height: None,
+        }
+    }
+}
+
 /// Represents a link to a photo. By default, this photo will be sent by the
 /// user with optional caption. Alternatively, you can use
 /// `input_message_content` to send a message with the specified content instead
@@ -149,7 +489,7 @@ pub struct InlineQueryResultPhoto {
     pub reply_markup: Option<InlineKeyboardMarkup>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
@@ -157,6 +497,26 @@ pub struct InlineQueryResultPhoto {
     pub caption_entities: Option<Vec<MessageEntity>>,
 }
 
+impl InlineQueryResultPhoto {
+    /// Creates a photo result with a random id
+    pub fn new(photo_url: &str, thumb_url: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            photo_url: photo_url.to_owned(),
+            thumb_url: thumb_url.to_owned(),
+            photo_width: None,
+            photo_height: None,
+            title: None,
+            description: None,
+            caption: None,
+            input_message_content: None,
+            reply_markup: None,
+            parse_mode: None,
+            caption_entities: None,
+        }
+    }
+}
+
 /// Represents a link to an animated GIF file. By default,
 /// this animated GIF file will be sent by the user with optional caption.
 /// Alternatively, you can use `input_message_content` to send a message with
@@ -197,7 +557,7 @@ pub struct InlineQueryResultGif {
     pub reply_markup: Option<InlineKeyboardMarkup>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
@@ -205,6 +565,27 @@ pub struct InlineQueryResultGif {
     pub caption_entities: Option<Vec<MessageEntity>>,
 }
 
+impl InlineQueryResultGif {
+    /// Creates a gif result with a random id
+    pub fn new(gif_url: &str, thumb_url: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            gif_url: gif_url.to_owned(),
+            thumb_url: thumb_url.to_owned(),
+            gif_width: None,
+            gif_height: None,
+            gif_duration: None,
+            thumb_mime_type: None,
+            title: None,
+            caption: None,
+            input_message_content: None,
+            reply_markup: None,
+            parse_mode: None,
+            caption_entities: None,
+        }
+    }
+}
+
 /// Represents a link to a video animation (H.264/MPEG-4 AVC video without
 /// sound). By default, this animated MPEG-4 file will be sent by the user with
 /// optional caption. Alternatively, you can use `input_message_content` to send
@@ -246,7 +627,7 @@ pub struct InlineQueryResultMpeg4Gif {
     pub reply_markup: Option<InlineKeyboardMarkup>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
@@ -254,6 +635,27 @@ pub struct InlineQueryResultMpeg4Gif {
     pub caption_entities: Option<Vec<MessageEntity>>,
 }
 
+impl InlineQueryResultMpeg4Gif {
+    /// Creates an mpeg4 gif result with a random id
+    pub fn new(mpeg4_url: &str, thumb_url: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            mpeg4_url: mpeg4_url.to_owned(),
+            thumb_url: thumb_url.to_owned(),
+            mpeg4_width: None,
+            mpeg4_height: None,
+            mpeg4_duration: None,
+            thumb_mime_type: None,
+            title: None,
+            caption: None,
+            input_message_content: None,
+            reply_markup: None,
+            parse_mode: None,
+            caption_entities: None,
+        }
+    }
+}
+
 /// Represents a link to a page containing an embedded video player or a video
 /// file. By default, this video file will be sent by the user with an optional
 /// caption. Alternatively, you can use `input_message_content` to send a
@@ -294,7 +696,7 @@ pub struct InlineQueryResultVideo {
     pub reply_markup: Option<InlineKeyboardMarkup>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
@@ -302,6 +704,28 @@ pub struct InlineQueryResultVideo {
     pub caption_entities: Option<Vec<MessageEntity>>,
 }
 
+impl InlineQueryResultVideo {
+    /// Creates a video result with a random id
+    pub fn new(video_url: &str, thumb_url: &str, mime_type: &str, title: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            video_url: video_url.to_owned(),
+            thumb_url: thumb_url.to_owned(),
+            mime_type: mime_type.to_owned(),
+            video_width: None,
+            video_height: None,
+            video_duration: None,
+            description: None,
+            title: title.to_owned(),
+            caption: None,
+            input_message_content: None,
+            reply_markup: None,
+            parse_mode: None,
+            caption_entities: None,
+        }
+    }
+}
+
 /// Represents a link to an MP3 audio file. By default, this audio file will be
 /// sent by the user. Alternatively, you can use `input_message_content` to send
 /// a message with the specified content instead of the audio.
@@ -325,7 +749,7 @@ pub struct InlineQueryResultAudio {
     pub audio_duration: Option<i64>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// Content of the message to be sent instead of the audio
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -339,6 +763,24 @@ pub struct InlineQueryResultAudio {
     pub caption_entities: Option<Vec<MessageEntity>>,
 }
 
+impl InlineQueryResultAudio {
+    /// Creates an audio result with a random id
+    pub fn new(audio_url: &str, title: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            audio_url: audio_url.to_owned(),
+            title: title.to_owned(),
+            caption: None,
+            performer: None,
+            audio_duration: None,
+            parse_mode: None,
+            input_message_content: None,
+            reply_markup: None,
+            caption_entities: None,
+        }
+    }
+}
+
 /// Represents a link to a voice recording in an .OGG container encoded with
 /// OPUS. By default, this voice recording will be sent by the user.
 /// Alternatively, you can use `input_message_content` to send a message with
@@ -360,7 +802,7 @@ pub struct InlineQueryResultVoice {
     pub voice_duration: Option<i64>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// Content of the message to be sent instead of the voice recording
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -374,6 +816,23 @@ pub struct InlineQueryResultVoice {
     pub caption_entities: Option<Vec<MessageEntity>>,
 }
 
+impl InlineQueryResultVoice {
+    /// Creates a voice message result with a random id
+    pub fn new(voice_url: &str, title: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            voice_url: voice_url.to_owned(),
+            title: title.to_owned(),
+            caption: None,
+            voice_duration: None,
+            parse_mode: None,
+            input_message_content: None,
+            reply_markup: None,
+            caption_entities: None,
+        }
+    }
+}
+
 /// Represents a link to a file. By default, this file will be sent by the user
 /// with an optional caption. Alternatively, you can use `input_message_content`
 /// to send a message with the specified content instead of the file. Currently,
@@ -401,7 +860,7 @@ pub struct InlineQueryResultDocument {
     pub voice_duration: Option<i64>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
@@ -424,6 +883,28 @@ pub struct InlineQueryResultDocument {
     pub thumb_height: Option<i64>,
 }
 
+impl InlineQueryResultDocument {
+    /// Creates a document result with a random id
+    pub fn new(document_url: &str, title: &str, mime_type: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            document_url: document_url.to_owned(),
+            title: title.to_owned(),
+            mime_type: mime_type.to_owned(),
+            caption: None,
+            description: None,
+            voice_duration: None,
+            parse_mode: None,
+            caption_entities: None,
+            input_message_content: None,
+            reply_markup: None,
+            thumb_url: None,
+            thumb_width: None,
+            thumb_height: None,
+        }
+    }
+}
+
 /// Represents a location on a map. By default, the location will be sent by the
 /// user. Alternatively, you can use `input_message_content` to send a message
 /// with the specified content instead of the location.
@@ -470,6 +951,27 @@ pub struct InlineQueryResultLocation {
     pub thumb_height: Option<i64>,
 }
 
+impl InlineQueryResultLocation {
+    /// Creates a location result with a random id
+    pub fn new(latitude: f64, longitude: f64, title: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            latitude,
+            longitude,
+            title: title.to_owned(),
+            live_period: None,
+            heading: None,
+            proximity_alert_radius: None,
+            horizontal_accuracy: None,
+            input_message_content: None,
+            reply_markup: None,
+            thumb_url: None,
+            thumb_width: None,
+            thumb_height: None,
+        }
+    }
+}
+
 /// Represents a venue. By default, the venue will be sent by the user.
 /// Alternatively, you can use `input_message_content` to send a message with
 /// the specified content instead of the venue.
@@ -522,6 +1024,29 @@ pub struct InlineQueryResultVenue {
     pub thumb_height: Option<i64>,
 }
 
+impl InlineQueryResultVenue {
+    /// Creates a venue result with a random id
+    pub fn new(latitude: f64, longitude: f64, title: &str, address: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            latitude,
+            longitude,
+            title: title.to_owned(),
+            address: address.to_owned(),
+            foursquare_id: None,
+            foursquare_type: None,
+            google_place_id: None,
+            google_place_type: None,
+            live_period: None,
+            input_message_content: None,
+            reply_markup: None,
+            thumb_url: None,
+            thumb_width: None,
+            thumb_height: None,
+        }
+    }
+}
+
 /// Represents a contact with a phone number. By default, this contact will be
 /// sent by the user. Alternatively, you can use `input_message_content` to send
 /// a message with the specified content instead of the contact.
@@ -556,6 +1081,24 @@ pub struct InlineQueryResultContact {
     pub thumb_height: Option<i64>,
 }
 
+impl InlineQueryResultContact {
+    /// Creates a contact result with a random id
+    pub fn new(phone_number: &str, first_name: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            phone_number: phone_number.to_owned(),
+            first_name: first_name.to_owned(),
+            last_name: None,
+            vcard: None,
+            input_message_content: None,
+            reply_markup: None,
+            thumb_url: None,
+            thumb_width: None,
+            thumb_height: None,
+        }
+    }
+}
+
 /// Represents a Game.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct InlineQueryResultGame {
@@ -568,14 +1111,26 @@ pub struct InlineQueryResultGame {
     pub reply_markup: Option<InlineKeyboardMarkup>,
 }
 
+impl InlineQueryResultGame {
+    /// Creates a game result with a random id
+    pub fn new(game_short_name: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            game_short_name: game_short_name.to_owned(),
+            reply_markup: None,
+        }
+    }
+}
+
 /// This object represents the content of a message to be sent as a result of an
 /// inline query.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum InputMessageContent {
     Text(InputTextMessageContent),
-    Location(InputLocationMessageContent),
+    // venues also have the fields of a location, so they have to be tried first
     Venue(InputVenueMessageContent),
+    Location(InputLocationMessageContent),
     Contact(InputContactMessageContent),
 }
 
@@ -587,8 +1142,12 @@ pub struct InputTextMessageContent {
     pub message_text: String,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
+    /// List of special entities that appear in message text, which can be
+    /// specified instead of `parse_mode`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entities: Option<Vec<MessageEntity>>,
     /// Disables link previews for links in the sent message
     pub disable_web_page_preview: bool,
 }
@@ -1,6 +1,13 @@
-use super::InputFile;
-use crate::model::{MessageEntity, ParseMode};
+use super::{InputFile, SendMediaGroup};
+use crate::{
+    model::{MessageEntity, ParseMode},
+    utils::result::{Result, TelegramError},
+};
 use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, ops::RangeInclusive};
+
+/// The amount of items telegram accepts in a single media group
+pub const MEDIA_GROUP_SIZE: RangeInclusive<usize> = 2..=10;
 
 /// This object represents the content of a media message to be sent
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -31,12 +38,15 @@ pub struct InputMediaPhoto {
     pub caption: Option<String>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in the media caption
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
     #[serde(skip_serializing_if = "Option::is_none")]
     pub caption_entities: Option<Vec<MessageEntity>>,
+    /// Pass True, if the photo needs to be covered with a spoiler animation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_spoiler: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -51,7 +61,7 @@ pub struct InputMediaVideo {
     pub caption: Option<String>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in the media caption
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
@@ -68,6 +78,9 @@ pub struct InputMediaVideo {
     pub height: Option<i64>,
     /// If the uploaded video is suitable for streaming
     pub supports_streaming: bool,
+    /// Pass True, if the video needs to be covered with a spoiler animation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_spoiler: Option<bool>,
 }
 
 /// Represents an animation file (GIF or H.264/MPEG-4 AVC video without sound)
@@ -78,18 +91,28 @@ pub struct InputMediaAnimation {
     /// servers (recommended), pass an HTTP URL for Telegram to get a file
     /// from the Internet
     pub media: InputFile,
+    /// Thumbnail of the file sent; can be ignored if thumbnail generation for
+    /// the file is supported server-side. The thumbnail should be in JPEG
+    /// format and less than 200 kB in size. A thumbnail‘s width and height
+    /// should not exceed 320. Ignored if the file is not uploaded using
+    /// multipart/form-data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumb: Option<InputFile>,
     /// Caption of the animation to be sent, 0-1024 characters after entities
     /// parsing
     #[serde(skip_serializing_if = "Option::is_none")]
     pub caption: Option<String>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in the media caption
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
     #[serde(skip_serializing_if = "Option::is_none")]
     pub caption_entities: Option<Vec<MessageEntity>>,
+    /// Pass True, if the caption must be shown above the animation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_caption_above_media: Option<bool>,
     /// Duration of the animation in seconds
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<i64>,
@@ -99,6 +122,26 @@ pub struct InputMediaAnimation {
     /// Animation height
     #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<i64>,
+    /// Pass True, if the animation needs to be covered with a spoiler animation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_spoiler: Option<bool>,
+}
+
+impl InputMediaAnimation {
+    pub fn new(media: InputFile) -> Self {
+        Self {
+            media,
+            thumb: None,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            show_caption_above_media: None,
+            duration: None,
+            width: None,
+            height: None,
+            has_spoiler: None,
+        }
+    }
 }
 
 /// Represents an audio file to be treated as music to be sent.
@@ -114,7 +157,7 @@ pub struct InputMediaAudio {
     pub caption: Option<String>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in the media caption
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
@@ -144,7 +187,7 @@ pub struct InputMediaDocument {
     pub caption: Option<String>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in the media caption
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the caption, which can be
     /// specified instead of parse_mode
@@ -166,4 +209,216 @@ impl InputMedia {
             InputMedia::Document(m) => &m.media,
         }
     }
+
+    /// Gets the thumbnail of the media, if it has one
+    pub fn get_thumb(&self) -> Option<&InputFile> {
+        match self {
+            InputMedia::Animation(m) => m.thumb.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Gets the caption of the media, if it has one
+    pub fn get_caption(&self) -> Option<&str> {
+        match self {
+            InputMedia::Photo(m) => m.caption.as_deref(),
+            InputMedia::Video(m) => m.caption.as_deref(),
+            InputMedia::Audio(m) => m.caption.as_deref(),
+            InputMedia::Animation(m) => m.caption.as_deref(),
+            InputMedia::Document(m) => m.caption.as_deref(),
+        }
+    }
+
+    fn set_caption(&mut self, caption: String, parse_mode: Option<ParseMode>) {
+        match self {
+            InputMedia::Photo(m) => (m.caption, m.parse_mode) = (Some(caption), parse_mode),
+            InputMedia::Video(m) => (m.caption, m.parse_mode) = (Some(caption), parse_mode),
+            InputMedia::Audio(m) => (m.caption, m.parse_mode) = (Some(caption), parse_mode),
+            InputMedia::Animation(m) => (m.caption, m.parse_mode) = (Some(caption), parse_mode),
+            InputMedia::Document(m) => (m.caption, m.parse_mode) = (Some(caption), parse_mode),
+        }
+    }
+}
+
+/// An album of media checked against telegram's rules for media groups, as
+/// built by the [`MediaGroupBuilder`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaGroup {
+    /// The items of the album, in order
+    pub media: Vec<InputMedia>,
+}
+
+impl MediaGroup {
+    pub fn builder() -> MediaGroupBuilder {
+        MediaGroupBuilder::new()
+    }
+
+    /// Creates the data needed to send the album to the given chat
+    pub fn into_request(self, chat_id: i64) -> SendMediaGroup {
+        SendMediaGroup::new(chat_id, self.media)
+    }
+}
+
+/// A builder for a [`MediaGroup`], checking before anything is sent that
+/// telegram will accept the album: it has to contain 2-10 items, and photos
+/// and videos can't be mixed with audio files or documents, which can't be
+/// mixed with each other either.
+///
+/// ```
+/// use telexide::api::types::{InputFile, MediaGroup};
+///
+/// let album = MediaGroup::builder()
+///     .photo(InputFile::file_id("AgACAgQAAx0"))
+///     .video(InputFile::url("https://example.com/clip.mp4"))
+///     .caption("holiday")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(album.media.len(), 2);
+/// assert_eq!(album.media[0].get_caption(), Some("holiday"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MediaGroupBuilder {
+    media: Vec<InputMedia>,
+    caption: Option<String>,
+    parse_mode: Option<ParseMode>,
+}
+
+impl MediaGroupBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an item to the album, keeping the caption it might have
+    pub fn media(&mut self, media: InputMedia) -> &mut Self {
+        self.media.push(media);
+        self
+    }
+
+    /// Adds a photo to the album
+    pub fn photo(&mut self, file: InputFile) -> &mut Self {
+        self.media(InputMedia::Photo(InputMediaPhoto {
+            media: file,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            has_spoiler: None,
+        }))
+    }
+
+    /// Adds a video to the album
+    pub fn video(&mut self, file: InputFile) -> &mut Self {
+        self.media(InputMedia::Video(InputMediaVideo {
+            media: file,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            duration: None,
+            width: None,
+            height: None,
+            supports_streaming: false,
+            has_spoiler: None,
+        }))
+    }
+
+    /// Adds an audio file to the album
+    pub fn audio(&mut self, file: InputFile) -> &mut Self {
+        self.media(InputMedia::Audio(InputMediaAudio {
+            media: file,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            duration: None,
+            performer: None,
+            title: None,
+        }))
+    }
+
+    /// Adds a document to the album
+    pub fn document(&mut self, file: InputFile) -> &mut Self {
+        self.media(InputMedia::Document(InputMediaDocument {
+            media: file,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            disable_content_type_detection: false,
+        }))
+    }
+
+    /// Sets the caption of the album, which telegram shows below it when it
+    /// is the only caption in the album. It is put on the first item, so
+    /// that item can't have a caption of its own.
+    pub fn caption(&mut self, caption: &str) -> &mut Self {
+        self.caption = Some(caption.to_owned());
+        self
+    }
+
+    /// Sets the parse mode of the caption of the album
+    pub fn parse_mode(&mut self, mode: ParseMode) -> &mut Self {
+        self.parse_mode = Some(mode);
+        self
+    }
+
+    /// Builds the [`MediaGroup`], returning an error if telegram would reject
+    /// it
+    pub fn build(&self) -> Result<MediaGroup> {
+        if !MEDIA_GROUP_SIZE.contains(&self.media.len()) {
+            return Err(TelegramError::InvalidArgument(format!(
+                "a media group must contain 2-10 items, got {}",
+                self.media.len()
+            ))
+            .into());
+        }
+
+        let is_visual = |m: &InputMedia| matches!(m, InputMedia::Photo(_) | InputMedia::Video(_));
+        let mixed = if self.media.iter().any(|m| matches!(m, InputMedia::Animation(_))) {
+            Some("animations can't be sent in a media group")
+        } else if self.media.iter().any(is_visual) && !self.media.iter().all(is_visual) {
+            Some("photos and videos can't be mixed with audio files or documents")
+        } else if self.media.iter().any(|m| matches!(m, InputMedia::Audio(_)))
+            && self.media.iter().any(|m| matches!(m, InputMedia::Document(_)))
+        {
+            Some("audio files and documents can't be mixed")
+        } else {
+            None
+        };
+        if let Some(reason) = mixed {
+            return Err(TelegramError::InvalidArgument(format!(
+                "invalid media group: {reason}"
+            ))
+            .into());
+        }
+
+        let mut media = self.media.clone();
+        if let Some(caption) = &self.caption {
+            if media[0].get_caption().is_some() {
+                return Err(TelegramError::InvalidArgument(
+                    "the first item of the media group already has a caption, it can't also \
+                     get the caption of the album"
+                        .to_owned(),
+                )
+                .into());
+            }
+            media[0].set_caption(caption.clone(), self.parse_mode.clone());
+        }
+
+        // uploaded files are attached by their name, so it has to be unique
+        let mut file_names = HashSet::new();
+        for item in &media {
+            if let InputFile::File(f) = item.get_media() {
+                if !file_names.insert(&f.file_name) {
+                    return Err(TelegramError::InvalidArgument(format!(
+                        "multiple files of the media group are named {}, they need unique \
+                         names to be attached",
+                        f.file_name.as_deref().unwrap_or(&f.name)
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        Ok(MediaGroup {
+            media,
+        })
+    }
 }
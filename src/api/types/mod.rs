@@ -1,6 +1,24 @@
 //! This modules provides all the objects describing the payloads to be send to
 //! the different telegram API endpoints
 
+macro_rules! impl_reply_markup_setter {
+    ($markup:ident: $($name:ident),+ $(,)?) => {
+        $(
+            impl $name {
+                /// Sets the reply markup from anything convertible into a
+                #[doc = concat!("[`", stringify!($markup), "`]")]
+                pub fn set_reply_markup<M: Into<crate::model::$markup>>(
+                    &mut self,
+                    markup: M,
+                ) -> &mut Self {
+                    self.reply_markup = Some(markup.into());
+                    self
+                }
+            }
+        )+
+    };
+}
+
 mod chat;
 mod commands;
 mod edit_messages;
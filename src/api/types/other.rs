@@ -4,6 +4,7 @@ use crate::utils::{
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{fs::File, path::Path};
+use tokio::io::{AsyncRead, AsyncReadExt};
 
 /// struct for holding data needed to call
 /// [`get_user_profile_photos`]
@@ -67,6 +68,31 @@ pub struct AnswerCallbackQuery {
     pub cache_time: Option<i64>,
 }
 
+impl AnswerCallbackQuery {
+    pub fn new(callback_query_id: &str) -> Self {
+        Self {
+            callback_query_id: callback_query_id.to_owned(),
+            text: None,
+            show_alert: false,
+            url: None,
+            cache_time: None,
+        }
+    }
+
+    /// Sets the text of the notification to show to the user
+    pub fn set_text(&mut self, text: &str) -> &mut Self {
+        self.text = Some(text.to_owned());
+        self
+    }
+
+    /// Sets whether the text should be shown as an alert instead of a
+    /// notification
+    pub fn set_show_alert(&mut self, show_alert: bool) -> &mut Self {
+        self.show_alert = show_alert;
+        self
+    }
+}
+
 /// Is either true (the bool), or is object T
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
@@ -78,9 +104,17 @@ pub enum TrueOrObject<T> {
 
 /// This object represents either the `file_id`, http url or the contents of a
 /// file to be uploaded.
+///
+/// Urls and file ids are sent as a plain string in the body of the request,
+/// while files to be uploaded are sent as multipart form data and referred to
+/// using `attach://<file_name>`.
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputFile {
-    String(String),
+    /// An HTTP url for telegram to get the file from
+    Url(String),
+    /// The id of a file that already exists on the telegram servers
+    FileId(String),
+    /// The contents of a file to be uploaded
     File(FormDataFile),
 }
 
@@ -91,8 +125,50 @@ impl InputFile {
         )?))
     }
 
+    /// Creates an [`InputFile`] from either an url or a file id, treating the
+    /// string as an url when it starts with `http://` or `https://`. Use
+    /// [`InputFile::url`] or [`InputFile::file_id`] to be explicit instead.
     pub fn new(string: &str) -> Self {
-        Self::String(string.to_owned())
+        if string.starts_with("http://") || string.starts_with("https://") {
+            Self::url(string)
+        } else {
+            Self::file_id(string)
+        }
+    }
+
+    /// Creates an [`InputFile`] for telegram to get from the given url
+    pub fn url(url: &str) -> Self {
+        Self::Url(url.to_owned())
+    }
+
+    /// Creates an [`InputFile`] referring to a file that already exists on the
+    /// telegram servers
+    pub fn file_id(file_id: &str) -> Self {
+        Self::FileId(file_id.to_owned())
+    }
+
+    /// Whether the file has to be uploaded as multipart form data
+    pub fn is_upload(&self) -> bool {
+        matches!(self, Self::File(_))
+    }
+
+    /// Creates a file to be uploaded from its contents in memory, for example
+    /// an image generated by the bot. The media type is guessed from the
+    /// extension of the given file name, which is sent along with the file.
+    pub fn from_bytes(file_name: &str, bytes: Vec<u8>) -> Result<Self> {
+        Ok(Self::File(FormDataFile::new_from_bytes(bytes, file_name)?))
+    }
+
+    /// Creates a file to be uploaded by reading the given reader to its end.
+    /// The media type is guessed from the extension of the given file name,
+    /// which is sent along with the file.
+    pub async fn from_reader<R>(file_name: &str, mut reader: R) -> Result<Self>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Self::from_bytes(file_name, bytes)
     }
 
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
@@ -112,13 +188,13 @@ impl InputFile {
 
 impl From<String> for InputFile {
     fn from(string: String) -> Self {
-        Self::String(string)
+        Self::new(&string)
     }
 }
 
 impl From<&str> for InputFile {
     fn from(string: &str) -> Self {
-        Self::String(string.to_owned())
+        Self::new(string)
     }
 }
 
@@ -134,7 +210,7 @@ impl Serialize for InputFile {
         S: Serializer,
     {
         match self {
-            Self::String(ref c) => serializer.serialize_str(c),
+            Self::Url(ref c) | Self::FileId(ref c) => serializer.serialize_str(c),
             Self::File(ref c) => serializer.serialize_str(&format!(
                 "attach://{}",
                 &c.file_name
@@ -152,6 +228,7 @@ impl<'de> Deserialize<'de> for InputFile {
     where
         D: Deserializer<'de>,
     {
-        Ok(Self::String(Deserialize::deserialize(deserializer)?))
+        let string: String = Deserialize::deserialize(deserializer)?;
+        Ok(Self::new(&string))
     }
 }
@@ -14,6 +14,16 @@ pub struct SetPassportDataErrors {
     pub errors: Vec<PassportElementError>,
 }
 
+impl SetPassportDataErrors {
+    /// function to create a new `SetPassportDataErrors` object
+    pub fn new(user_id: i64, errors: Vec<PassportElementError>) -> Self {
+        Self {
+            user_id,
+            errors,
+        }
+    }
+}
+
 /// This object represents an error in the Telegram Passport element which was
 /// submitted that should be resolved by the user
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -39,6 +49,248 @@ pub enum PassportElementError {
     Unspecified(PassportElementErrorUnspecified),
 }
 
+impl PassportElementError {
+    /// Creates an error in a data field of the element, which is resolved when
+    /// the value of the field changes
+    pub fn data_field(
+        element: PassportDataElement,
+        field_name: &str,
+        data_hash: &str,
+        message: &str,
+    ) -> Self {
+        Self::DataField(PassportElementErrorDataField {
+            section_type: element.into(),
+            field_name: field_name.to_owned(),
+            data_hash: data_hash.to_owned(),
+            message: message.to_owned(),
+        })
+    }
+
+    /// Creates an error with the front side of the document, which is
+    /// resolved when the file with the front side changes
+    pub fn front_side(document: PassportIdentityDocument, file_hash: &str, message: &str) -> Self {
+        Self::FrontSide(PassportElementErrorFrontSide {
+            section_type: document.into(),
+            file_hash: file_hash.to_owned(),
+            message: message.to_owned(),
+        })
+    }
+
+    /// Creates an error with the reverse side of the document, which is
+    /// resolved when the file with the reverse side changes
+    pub fn reverse_side(
+        document: PassportTwoSidedDocument,
+        file_hash: &str,
+        message: &str,
+    ) -> Self {
+        Self::ReverseSide(PassportElementErrorReverseSide {
+            section_type: document.into(),
+            file_hash: file_hash.to_owned(),
+            message: message.to_owned(),
+        })
+    }
+
+    /// Creates an error with the selfie with the document, which is resolved
+    /// when the file with the selfie changes
+    pub fn selfie(document: PassportIdentityDocument, file_hash: &str, message: &str) -> Self {
+        Self::Selfie(PassportElementErrorSelfie {
+            section_type: document.into(),
+            file_hash: file_hash.to_owned(),
+            message: message.to_owned(),
+        })
+    }
+
+    /// Creates an error with a scan of the document, which is resolved when
+    /// the file with the scan changes
+    pub fn file(document: PassportAddressDocument, file_hash: &str, message: &str) -> Self {
+        Self::File(PassportElementErrorFile {
+            section_type: document.into(),
+            file_hash: file_hash.to_owned(),
+            message: message.to_owned(),
+        })
+    }
+
+    /// Creates an error with the list of scans of the document, which is
+    /// resolved when the list of files changes
+    pub fn files(document: PassportAddressDocument, file_hashes: &[&str], message: &str) -> Self {
+        Self::Files(PassportElementErrorFiles {
+            section_type: document.into(),
+            file_hashes: file_hashes.iter().map(|&h| h.to_owned()).collect(),
+            message: message.to_owned(),
+        })
+    }
+
+    /// Creates an error with one of the files of the translation of the
+    /// document, which is resolved when the file changes
+    pub fn translation_file<D>(document: D, file_hash: &str, message: &str) -> Self
+    where
+        D: Into<PassportTranslatableDocument>,
+    {
+        Self::TranslationFile(PassportElementErrorTranslationFile {
+            section_type: document.into().into(),
+            file_hash: file_hash.to_owned(),
+            message: message.to_owned(),
+        })
+    }
+
+    /// Creates an error with the translation of the document, which is
+    /// resolved when a file of the translation changes
+    pub fn translation_files<D>(document: D, file_hashes: &[&str], message: &str) -> Self
+    where
+        D: Into<PassportTranslatableDocument>,
+    {
+        Self::TranslationFiles(PassportElementErrorTranslationFiles {
+            section_type: document.into().into(),
+            file_hashes: file_hashes.iter().map(|&h| h.to_owned()).collect(),
+            message: message.to_owned(),
+        })
+    }
+
+    /// Creates an error in an unspecified place of the element, which is
+    /// resolved when new data is added
+    pub fn unspecified(
+        element: TelegramPassportElement,
+        element_hash: &str,
+        message: &str,
+    ) -> Self {
+        Self::Unspecified(PassportElementErrorUnspecified {
+            section_type: element,
+            element_hash: element_hash.to_owned(),
+            message: message.to_owned(),
+        })
+    }
+}
+
+/// The passport elements with data fields, used by
+/// [`PassportElementError::data_field`]
+///
+/// [`PassportElementError::data_field`]: enum.PassportElementError.html#method.data_field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassportDataElement {
+    PersonalDetails,
+    Passport,
+    DriverLicense,
+    IdentityCard,
+    InternalPassport,
+    Address,
+}
+
+/// The identity documents, which have a front side and a selfie, used by
+/// [`PassportElementError::front_side`] and [`PassportElementError::selfie`]
+///
+/// [`PassportElementError::front_side`]: enum.PassportElementError.html#method.front_side
+/// [`PassportElementError::selfie`]: enum.PassportElementError.html#method.selfie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassportIdentityDocument {
+    Passport,
+    DriverLicense,
+    IdentityCard,
+    InternalPassport,
+}
+
+/// The identity documents with a reverse side, used by
+/// [`PassportElementError::reverse_side`]
+///
+/// [`PassportElementError::reverse_side`]: enum.PassportElementError.html#method.reverse_side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassportTwoSidedDocument {
+    DriverLicense,
+    IdentityCard,
+}
+
+/// The documents proving an address, which consist of scanned files, used by
+/// [`PassportElementError::file`] and [`PassportElementError::files`]
+///
+/// [`PassportElementError::file`]: enum.PassportElementError.html#method.file
+/// [`PassportElementError::files`]: enum.PassportElementError.html#method.files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassportAddressDocument {
+    UtilityBill,
+    BankStatement,
+    RentalAgreement,
+    PassportRegistration,
+    TemporaryRegistration,
+}
+
+/// The documents that can have a translation, being the identity documents
+/// and the documents proving an address, used by
+/// [`PassportElementError::translation_file`] and
+/// [`PassportElementError::translation_files`]
+///
+/// [`PassportElementError::translation_file`]: enum.PassportElementError.html#method.translation_file
+/// [`PassportElementError::translation_files`]: enum.PassportElementError.html#method.translation_files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassportTranslatableDocument {
+    Identity(PassportIdentityDocument),
+    Address(PassportAddressDocument),
+}
+
+impl From<PassportDataElement> for TelegramPassportElement {
+    fn from(element: PassportDataElement) -> Self {
+        match element {
+            PassportDataElement::PersonalDetails => Self::PersonalDetails,
+            PassportDataElement::Passport => Self::Passport,
+            PassportDataElement::DriverLicense => Self::DriverLicense,
+            PassportDataElement::IdentityCard => Self::IdentityCard,
+            PassportDataElement::InternalPassport => Self::InternalPassport,
+            PassportDataElement::Address => Self::Address,
+        }
+    }
+}
+
+impl From<PassportIdentityDocument> for TelegramPassportElement {
+    fn from(document: PassportIdentityDocument) -> Self {
+        match document {
+            PassportIdentityDocument::Passport => Self::Passport,
+            PassportIdentityDocument::DriverLicense => Self::DriverLicense,
+            PassportIdentityDocument::IdentityCard => Self::IdentityCard,
+            PassportIdentityDocument::InternalPassport => Self::InternalPassport,
+        }
+    }
+}
+
+impl From<PassportTwoSidedDocument> for TelegramPassportElement {
+    fn from(document: PassportTwoSidedDocument) -> Self {
+        match document {
+            PassportTwoSidedDocument::DriverLicense => Self::DriverLicense,
+            PassportTwoSidedDocument::IdentityCard => Self::IdentityCard,
+        }
+    }
+}
+
+impl From<PassportAddressDocument> for TelegramPassportElement {
+    fn from(document: PassportAddressDocument) -> Self {
+        match document {
+            PassportAddressDocument::UtilityBill => Self::UtilityBill,
+            PassportAddressDocument::BankStatement => Self::BankStatement,
+            PassportAddressDocument::RentalAgreement => Self::RentalAgreement,
+            PassportAddressDocument::PassportRegistration => Self::PassportRegistration,
+            PassportAddressDocument::TemporaryRegistration => Self::TemporaryRegistration,
+        }
+    }
+}
+
+impl From<PassportTranslatableDocument> for TelegramPassportElement {
+    fn from(document: PassportTranslatableDocument) -> Self {
+        match document {
+            PassportTranslatableDocument::Identity(d) => d.into(),
+            PassportTranslatableDocument::Address(d) => d.into(),
+        }
+    }
+}
+
+impl From<PassportIdentityDocument> for PassportTranslatableDocument {
+    fn from(document: PassportIdentityDocument) -> Self {
+        Self::Identity(document)
+    }
+}
+
+impl From<PassportAddressDocument> for PassportTranslatableDocument {
+    fn from(document: PassportAddressDocument) -> Self {
+        Self::Address(document)
+    }
+}
+
 /// Represents an issue in one of the data fields that was provided by the user.
 /// The error is considered resolved when the field's value changes.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
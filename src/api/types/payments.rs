@@ -1,6 +1,12 @@
-use crate::model::{LabeledPrice, ReplyMarkup, ShippingOption};
+use crate::{
+    model::{LabeledPrice, ReplyMarkup, ShippingOption},
+    utils::result::{Result, TelegramError},
+};
 use serde::{Deserialize, Serialize};
 
+/// The currency of payments in [Telegram Stars](https://t.me/BotNews/90)
+pub const STARS_CURRENCY: &str = "XTR";
+
 /// struct for holding data needed to call
 /// [`send_invoice`]
 ///
@@ -88,6 +94,170 @@ pub struct SendInvoice {
     pub reply_markup: Option<ReplyMarkup>,
 }
 
+/// struct for holding data needed to call
+/// [`create_invoice_link`]
+///
+/// Invoices paid in Telegram Stars use the currency `XTR`, can't have a
+/// provider token or tips, and have exactly one price. Invoices in other
+/// currencies need a provider token. [`create_invoice_link`] checks this
+/// before calling telegram.
+///
+/// [`create_invoice_link`]:
+/// ../../api/trait.API.html#method.create_invoice_link
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CreateInvoiceLink {
+    /// Product name, 1-32 characters
+    pub title: String,
+    /// Product description, 1-255 characters
+    pub description: String,
+    /// Bot-defined invoice payload, 1-128 bytes.
+    /// This will not be displayed to the user, use for your internal processes.
+    pub payload: String,
+    /// Payments provider token, obtained via [Botfather](https://t.me/botfather).
+    /// Has to be omitted for payments in Telegram Stars
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_token: Option<String>,
+    /// Three-letter ISO 4217 currency code, or `XTR` for payments in Telegram
+    /// Stars
+    pub currency: String,
+    /// Price breakdown, a list of components (e.g. product price, tax,
+    /// discount, delivery cost, delivery tax, bonus, etc.). Must contain
+    /// exactly one item for payments in Telegram Stars
+    pub prices: Vec<LabeledPrice>,
+    /// The maximum accepted amount for tips in the smallest units of the currency (integer, not
+    /// float/double). For example, for a maximum tip of `US$ 1.45` pass `max_tip_amount = 145`. See
+    /// the exp parameter in [currencies.json](https://core.telegram.org/bots/payments/currencies.json),
+    /// it shows the number of digits past the decimal point
+    /// for each currency (2 for the majority of currencies). Defaults to 0.
+    /// Not supported for payments in Telegram Stars
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tip_amount: Option<i64>,
+    /// A vec of suggested amounts of tips in the smallest units of the currency (integer, not
+    /// float/double). At most 4 suggested tip amounts can be specified. The suggested tip amounts
+    /// must be positive, passed in a strictly increased order and must not exceed `max_tip_amount`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_tip_amounts: Option<Vec<i64>>,
+    /// JSON-encoded data about the invoice, which will be shared with the
+    /// payment provider. A detailed description of required fields should
+    /// be provided by the payment provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_data: Option<String>,
+    /// URL of the product photo for the invoice.
+    /// Can be a photo of the goods or a marketing image for a service.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_url: Option<String>,
+    /// Photo size in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_size: Option<i64>,
+    /// Photo width
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_width: Option<i64>,
+    /// Photo height
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_height: Option<i64>,
+    /// Pass True, if you require the user's full name to complete the order
+    #[serde(default)]
+    pub need_name: bool,
+    /// Pass True, if you require the user's phone number to complete the order
+    #[serde(default)]
+    pub need_phone_number: bool,
+    /// Pass True, if you require the user's email address to complete the order
+    #[serde(default)]
+    pub need_email: bool,
+    /// Pass True, if you require the user's shipping address to complete the
+    /// order
+    #[serde(default)]
+    pub need_shipping_address: bool,
+    /// Pass True, if user's phone number should be sent to provider
+    #[serde(default)]
+    pub send_phone_number_to_provider: bool,
+    /// Pass True, if user's email address should be sent to provider
+    #[serde(default)]
+    pub send_email_to_provider: bool,
+    /// Pass True, if the final price depends on the shipping method
+    #[serde(default)]
+    pub is_flexible: bool,
+}
+
+impl CreateInvoiceLink {
+    /// function to create a new `CreateInvoiceLink` object for an invoice
+    /// paid through the payment provider of the token
+    pub fn new(
+        title: &str,
+        description: &str,
+        payload: &str,
+        provider_token: &str,
+        currency: &str,
+        prices: Vec<LabeledPrice>,
+    ) -> Self {
+        Self {
+            title: title.to_owned(),
+            description: description.to_owned(),
+            payload: payload.to_owned(),
+            provider_token: Some(provider_token.to_owned()),
+            currency: currency.to_owned(),
+            prices,
+            max_tip_amount: None,
+            suggested_tip_amounts: None,
+            provider_data: None,
+            photo_url: None,
+            photo_size: None,
+            photo_width: None,
+            photo_height: None,
+            need_name: false,
+            need_phone_number: false,
+            need_email: false,
+            need_shipping_address: false,
+            send_phone_number_to_provider: false,
+            send_email_to_provider: false,
+            is_flexible: false,
+        }
+    }
+
+    /// function to create a new `CreateInvoiceLink` object for an invoice
+    /// of the given amount of Telegram Stars
+    pub fn stars(title: &str, description: &str, payload: &str, amount: i64) -> Self {
+        Self {
+            provider_token: None,
+            ..Self::new(
+                title,
+                description,
+                payload,
+                "",
+                STARS_CURRENCY,
+                vec![LabeledPrice::new(title, amount)],
+            )
+        }
+    }
+
+    /// Checks that an invoice in Telegram Stars has no provider token or
+    /// tips and exactly one price, and that other invoices have a provider
+    /// token
+    pub(crate) fn check_currency(&self) -> Result<()> {
+        let has_token = self.provider_token.as_deref().is_some_and(|t| !t.is_empty());
+        let invalid = |reason: &str| Err(TelegramError::InvalidArgument(reason.to_owned()).into());
+
+        if self.currency != STARS_CURRENCY {
+            return if has_token {
+                Ok(())
+            } else {
+                invalid("a provider token is required for invoices not in telegram stars (XTR)")
+            };
+        }
+
+        if has_token {
+            return invalid("invoices in telegram stars (XTR) can't have a provider token");
+        }
+        if self.prices.len() != 1 {
+            return invalid("invoices in telegram stars (XTR) must have exactly one price");
+        }
+        if self.max_tip_amount.is_some() || self.suggested_tip_amounts.is_some() {
+            return invalid("invoices in telegram stars (XTR) don't support tips");
+        }
+        Ok(())
+    }
+}
+
 /// struct for holding data needed to call
 /// [`answer_shipping_query`]
 ///
@@ -113,6 +283,30 @@ pub struct AnswerShippingQuery {
     pub error_message: Option<String>,
 }
 
+impl AnswerShippingQuery {
+    /// function to create a new `AnswerShippingQuery` object, answering that
+    /// delivery is possible using the given shipping options
+    pub fn ok(shipping_query_id: &str, shipping_options: Vec<ShippingOption>) -> Self {
+        Self {
+            shipping_query_id: shipping_query_id.to_owned(),
+            ok: true,
+            shipping_options: Some(shipping_options),
+            error_message: None,
+        }
+    }
+
+    /// function to create a new `AnswerShippingQuery` object, answering that
+    /// delivery isn't possible for the reason in the error message
+    pub fn error(shipping_query_id: &str, error_message: &str) -> Self {
+        Self {
+            shipping_query_id: shipping_query_id.to_owned(),
+            ok: false,
+            shipping_options: None,
+            error_message: Some(error_message.to_owned()),
+        }
+    }
+}
+
 /// struct for holding data needed to call
 /// [`answer_pre_checkout_query`]
 ///
@@ -135,3 +329,27 @@ pub struct AnswerPreCheckoutQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
 }
+
+impl AnswerPreCheckoutQuery {
+    /// function to create a new `AnswerPreCheckoutQuery` object, confirming
+    /// the order
+    pub fn ok(pre_checkout_query_id: &str) -> Self {
+        Self {
+            pre_checkout_query_id: pre_checkout_query_id.to_owned(),
+            ok: true,
+            error_message: None,
+        }
+    }
+
+    /// function to create a new `AnswerPreCheckoutQuery` object, declining the
+    /// order for the reason in the error message
+    pub fn error(pre_checkout_query_id: &str, error_message: &str) -> Self {
+        Self {
+            pre_checkout_query_id: pre_checkout_query_id.to_owned(),
+            ok: false,
+            error_message: Some(error_message.to_owned()),
+        }
+    }
+}
+
+impl_reply_markup_setter!(ReplyMarkup: SendInvoice);
@@ -2,19 +2,37 @@ use super::{InputFile, InputMedia};
 use crate::{
     model::{
         utils::unix_date_formatting,
+        Animation,
+        Audio,
         ChatAction,
+        DiceEmoji,
+        MessageContent,
         MessageEntity,
         ParseMode,
         PhotoSize,
         PollType,
         ReplyMarkup,
+        VideoNote,
+        Voice,
     },
     prelude::Message,
-    utils::result::Result,
+    utils::{
+        result::{Result, TelegramError},
+        FormattedTextBuilder,
+    },
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::{convert::TryFrom, ops::RangeInclusive, path::Path};
+
+/// The maximum length of the question of a poll, in characters
+const MAX_POLL_QUESTION_LENGTH: usize = 300;
+
+/// The maximum length of an answer option of a poll, in characters
+const MAX_POLL_OPTION_LENGTH: usize = 100;
+
+/// The amount of answer options a poll can have
+const POLL_OPTION_COUNT: RangeInclusive<usize> = 2..=10;
 
 /// struct for holding data needed to call
 /// [`send_message`]
@@ -29,12 +47,12 @@ pub struct SendMessage {
     pub text: String,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// List of special entities that appear in message text, which can be
     /// specified instead of parse_mode
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub enitites: Option<Vec<MessageEntity>>,
+    pub entities: Option<Vec<MessageEntity>>,
     /// Disables link previews for links in this message
     pub disable_web_page_preview: bool,
     /// Sends the message silently. Users will receive a notification with no
@@ -52,12 +70,18 @@ pub struct SendMessage {
 }
 
 impl SendMessage {
+    /// Creates a new [`SendMessageBuilder`] for building a message with the
+    /// given text to send to the chat
+    pub fn builder(chat_id: i64, text: &str) -> SendMessageBuilder {
+        SendMessageBuilder::new(chat_id, text)
+    }
+
     pub fn new(chat_id: i64, text: &str) -> Self {
         Self {
             chat_id,
             text: text.to_owned(),
             parse_mode: None,
-            enitites: None,
+            entities: None,
             disable_notification: false,
             disable_web_page_preview: false,
             reply_to_message_id: None,
@@ -66,11 +90,58 @@ impl SendMessage {
         }
     }
 
+    /// Creates a message with the text of the [`FormattedTextBuilder`], using
+    /// entities for the formatting so no parse mode is needed
+    ///
+    /// [`FormattedTextBuilder`]: ../../utils/struct.FormattedTextBuilder.html
+    pub fn new_formatted(chat_id: i64, text: &FormattedTextBuilder) -> Self {
+        let (text, entities) = text.to_entities();
+        let mut message = Self::new(chat_id, &text);
+        if !entities.is_empty() {
+            message.entities = Some(entities);
+        }
+        message
+    }
+
+    /// Creates a message with the text and entities of the given text
+    /// message, keeping its formatting without having to use a parse mode.
+    /// Returns `None` if the message isn't a text message.
+    pub fn from_text_message(chat_id: i64, message: &Message) -> Option<Self> {
+        if !matches!(message.content, MessageContent::Text { .. }) {
+            return None;
+        }
+        let mut data = Self::new(chat_id, "");
+        data.with_formatting_of(message);
+        Some(data)
+    }
+
+    /// Sets the text and entities of the message to the text or caption of
+    /// the given message, keeping its formatting. The parse mode is removed,
+    /// as telegram doesn't accept it together with entities. Nothing is
+    /// changed if the given message has neither a text nor a caption.
+    pub fn with_formatting_of(&mut self, message: &Message) -> &mut Self {
+        if let Some((text, entities)) = message.formatted_text() {
+            text.clone_into(&mut self.text);
+            self.entities = if entities.is_empty() {
+                None
+            } else {
+                Some(entities.to_vec())
+            };
+            self.parse_mode = None;
+        }
+        self
+    }
+
     pub fn set_parse_mode(&mut self, mode: &ParseMode) -> &mut Self {
         self.parse_mode = Some(mode.to_owned());
         self
     }
 
+    pub fn set_entities(&mut self, entities: Vec<MessageEntity>) -> &mut Self {
+        self.entities = Some(entities);
+        self
+    }
+
     pub fn reply_to_message(&mut self, message: &Message) -> &mut Self {
         self.reply_to_message_id = Some(message.message_id);
         self
@@ -81,11 +152,6 @@ impl SendMessage {
         self
     }
 
-    pub fn set_reply_markup(&mut self, markup: &ReplyMarkup) -> &mut Self {
-        self.reply_markup = Some(markup.to_owned());
-        self
-    }
-
     pub fn toggle_disable_notification(&mut self) -> &mut Self {
         self.disable_notification = !self.disable_notification;
         self
@@ -97,6 +163,116 @@ impl SendMessage {
     }
 }
 
+/// A builder for easily creating a [`SendMessage`] with several of its
+/// optional fields set.
+///
+/// ```rust
+/// use telexide::{
+///     api::types::SendMessage,
+///     model::{InlineKeyboardMarkup, ParseMode},
+/// };
+///
+/// let markup = InlineKeyboardMarkup::builder()
+///     .callback("Yes", "yes")
+///     .callback("No", "no")
+///     .build()
+///     .expect("invalid keyboard");
+///
+/// let message = SendMessage::builder(10, "*are you sure?*")
+///     .parse_mode(ParseMode::MarkdownV2)
+///     .reply_to(42)
+///     .reply_markup(markup)
+///     .disable_web_page_preview(true)
+///     .build();
+///
+/// assert_eq!(message.reply_to_message_id, Some(42));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SendMessageBuilder {
+    message: SendMessage,
+}
+
+impl SendMessageBuilder {
+    /// Creates a new builder for a message with the given text to send to the
+    /// chat
+    pub fn new(chat_id: i64, text: &str) -> Self {
+        Self {
+            message: SendMessage::new(chat_id, text),
+        }
+    }
+
+    /// Sets the text of the message to that of the [`FormattedTextBuilder`],
+    /// using entities for the formatting so no parse mode is needed
+    ///
+    /// [`FormattedTextBuilder`]: ../../utils/struct.FormattedTextBuilder.html
+    pub fn formatted(&mut self, text: &FormattedTextBuilder) -> &mut Self {
+        let (text, entities) = text.to_entities();
+        self.message.text = text;
+        self.message.entities = if entities.is_empty() {
+            None
+        } else {
+            Some(entities)
+        };
+        self
+    }
+
+    /// Sets the mode used for parsing entities in the text
+    pub fn parse_mode(&mut self, mode: ParseMode) -> &mut Self {
+        self.message.parse_mode = Some(mode);
+        self
+    }
+
+    /// Sets the entities in the text, which can be used instead of a parse
+    /// mode
+    pub fn entities(&mut self, entities: Vec<MessageEntity>) -> &mut Self {
+        self.message.entities = Some(entities);
+        self
+    }
+
+    /// Makes the message a reply to the message with the given id
+    pub fn reply_to(&mut self, message_id: i64) -> &mut Self {
+        self.message.reply_to_message_id = Some(message_id);
+        self
+    }
+
+    /// Makes the message a reply to the given message
+    pub fn reply_to_message(&mut self, message: &Message) -> &mut Self {
+        self.reply_to(message.message_id)
+    }
+
+    /// Sets whether the message should be sent even if the replied-to message
+    /// is not found
+    pub fn allow_sending_without_reply(&mut self, allow: bool) -> &mut Self {
+        self.message.allow_sending_without_reply = allow;
+        self
+    }
+
+    /// Sets the reply markup of the message, being an inline keyboard, a
+    /// custom reply keyboard, a keyboard removal or a forced reply
+    pub fn reply_markup<M: Into<ReplyMarkup>>(&mut self, markup: M) -> &mut Self {
+        self.message.reply_markup = Some(markup.into());
+        self
+    }
+
+    /// Sets whether link previews for links in the message are disabled
+    pub fn disable_web_page_preview(&mut self, disable: bool) -> &mut Self {
+        self.message.disable_web_page_preview = disable;
+        self
+    }
+
+    /// Sets whether the message is sent silently, without a notification
+    /// sound
+    pub fn disable_notification(&mut self, disable: bool) -> &mut Self {
+        self.message.disable_notification = disable;
+        self
+    }
+
+    /// Builds the [`SendMessage`]
+    pub fn build(&self) -> SendMessage {
+        self.message.clone()
+    }
+}
+
 /// struct for holding data needed to call
 /// [`forward_message`]
 ///
@@ -160,7 +336,7 @@ pub struct CopyMessage {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub caption_entities: Option<Vec<MessageEntity>>,
     /// Mode for parsing entities in the new caption.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// Sends the message silently. Users will receive a notification with no
     /// sound.
@@ -231,8 +407,11 @@ pub struct SendPhoto {
     pub caption_entities: Option<Vec<MessageEntity>>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
+    /// Pass True, if the photo needs to be covered with a spoiler animation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_spoiler: Option<bool>,
     /// Sends the message silently. Users will receive a notification with no
     /// sound.
     pub disable_notification: bool,
@@ -248,13 +427,14 @@ pub struct SendPhoto {
 }
 
 impl SendPhoto {
-    pub fn new(chat_id: i64, photo: String) -> Self {
+    pub fn new<F: Into<InputFile>>(chat_id: i64, photo: F) -> Self {
         Self {
             chat_id,
-            photo: InputFile::String(photo),
+            photo: photo.into(),
             caption: None,
             caption_entities: None,
             parse_mode: None,
+            has_spoiler: None,
             disable_notification: false,
             reply_to_message_id: None,
             allow_sending_without_reply: false,
@@ -265,10 +445,11 @@ impl SendPhoto {
     pub fn from_photo_size(chat_id: i64, photo: &PhotoSize) -> Self {
         Self {
             chat_id,
-            photo: InputFile::String(photo.file_id.clone()),
+            photo: InputFile::FileId(photo.file_id.clone()),
             caption: None,
             caption_entities: None,
             parse_mode: None,
+            has_spoiler: None,
             disable_notification: false,
             reply_to_message_id: None,
             allow_sending_without_reply: false,
@@ -283,6 +464,7 @@ impl SendPhoto {
             caption: None,
             caption_entities: None,
             parse_mode: None,
+            has_spoiler: None,
             disable_notification: false,
             reply_to_message_id: None,
             allow_sending_without_reply: false,
@@ -330,7 +512,7 @@ pub struct SendAudio {
     pub title: Option<String>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// Sends the message silently. Users will receive a notification with no
     /// sound.
@@ -347,10 +529,10 @@ pub struct SendAudio {
 }
 
 impl SendAudio {
-    pub fn new(chat_id: i64, audio: String) -> Self {
+    pub fn new<F: Into<InputFile>>(chat_id: i64, audio: F) -> Self {
         Self {
             chat_id,
-            audio: InputFile::String(audio),
+            audio: audio.into(),
             thumb: None,
             caption: None,
             caption_entities: None,
@@ -365,6 +547,17 @@ impl SendAudio {
         }
     }
 
+    /// Resends an existing audio file, keeping its duration, performer and
+    /// title
+    pub fn from_audio(chat_id: i64, audio: &Audio) -> Self {
+        let mut data = Self::new(chat_id, String::new());
+        data.audio = InputFile::FileId(audio.file_id.clone());
+        data.duration = i64::try_from(audio.duration).ok();
+        data.performer.clone_from(&audio.performer);
+        data.title.clone_from(&audio.title);
+        data
+    }
+
     pub fn from_file<P: AsRef<Path>>(chat_id: i64, path: P) -> Result<Self> {
         Ok(Self {
             chat_id,
@@ -414,7 +607,7 @@ pub struct SendDocument {
     pub caption_entities: Option<Vec<MessageEntity>>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// Disables automatic server-side content type detection for files uploaded
     /// using multipart/form-data
@@ -434,10 +627,10 @@ pub struct SendDocument {
 }
 
 impl SendDocument {
-    pub fn new(chat_id: i64, document: String) -> Self {
+    pub fn new<F: Into<InputFile>>(chat_id: i64, document: F) -> Self {
         Self {
             chat_id,
-            document: InputFile::String(document),
+            document: document.into(),
             thumb: None,
             caption: None,
             caption_entities: None,
@@ -512,8 +705,11 @@ pub struct SendVideo {
     pub title: Option<String>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
+    /// Pass True, if the video needs to be covered with a spoiler animation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_spoiler: Option<bool>,
     /// Sends the message silently. Users will receive a notification with no
     /// sound.
     pub disable_notification: bool,
@@ -531,10 +727,10 @@ pub struct SendVideo {
 }
 
 impl SendVideo {
-    pub fn new(chat_id: i64, video: String) -> Self {
+    pub fn new<F: Into<InputFile>>(chat_id: i64, video: F) -> Self {
         Self {
             chat_id,
-            video: InputFile::String(video),
+            video: video.into(),
             thumb: None,
             caption: None,
             caption_entities: None,
@@ -545,6 +741,7 @@ impl SendVideo {
             title: None,
             supports_streaming: false,
             parse_mode: None,
+            has_spoiler: None,
             disable_notification: false,
             reply_to_message_id: None,
             allow_sending_without_reply: false,
@@ -566,6 +763,7 @@ impl SendVideo {
             title: None,
             supports_streaming: false,
             parse_mode: None,
+            has_spoiler: None,
             disable_notification: false,
             reply_to_message_id: None,
             allow_sending_without_reply: false,
@@ -605,22 +803,30 @@ pub struct SendAnimation {
     /// Duration of the animation in seconds
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<i64>,
-    /// Video width
+    /// Animation width
     #[serde(skip_serializing_if = "Option::is_none")]
     pub width: Option<i64>,
-    /// Video height
+    /// Animation height
     #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<i64>,
-    /// The performer
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Not used by animations and never sent
+    #[deprecated(note = "animations don't have a performer, the field is ignored")]
+    #[serde(skip)]
     pub performer: Option<String>,
-    /// Track name
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Not used by animations and never sent
+    #[deprecated(note = "animations don't have a title, the field is ignored")]
+    #[serde(skip)]
     pub title: Option<String>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
+    /// Pass True, if the caption must be shown above the animation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_caption_above_media: Option<bool>,
+    /// Pass True, if the animation needs to be covered with a spoiler animation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_spoiler: Option<bool>,
     /// Sends the message silently. Users will receive a notification with no
     /// sound.
     pub disable_notification: bool,
@@ -635,11 +841,12 @@ pub struct SendAnimation {
     pub reply_markup: Option<ReplyMarkup>,
 }
 
+#[allow(deprecated)]
 impl SendAnimation {
-    pub fn new(chat_id: i64, animation: String) -> Self {
+    pub fn new<F: Into<InputFile>>(chat_id: i64, animation: F) -> Self {
         Self {
             chat_id,
-            animation: InputFile::String(animation),
+            animation: animation.into(),
             thumb: None,
             caption: None,
             caption_entities: None,
@@ -649,6 +856,8 @@ impl SendAnimation {
             performer: None,
             title: None,
             parse_mode: None,
+            show_caption_above_media: None,
+            has_spoiler: None,
             disable_notification: false,
             reply_to_message_id: None,
             allow_sending_without_reply: false,
@@ -656,6 +865,23 @@ impl SendAnimation {
         }
     }
 
+    /// Resends an existing animation, keeping its duration and dimensions
+    pub fn from_animation(chat_id: i64, animation: &Animation) -> Self {
+        let mut data = Self::new(chat_id, String::new());
+        data.animation = InputFile::FileId(animation.file_id.clone());
+        data.duration = i64::try_from(animation.duration).ok();
+        data.width = i64::try_from(animation.width).ok();
+        data.height = i64::try_from(animation.height).ok();
+        data
+    }
+
+    /// Sets whether the caption is shown above the animation instead of below
+    /// it
+    pub fn set_show_caption_above_media(&mut self, above: bool) -> &mut Self {
+        self.show_caption_above_media = Some(above);
+        self
+    }
+
     pub fn from_file<P: AsRef<Path>>(chat_id: i64, path: P) -> Result<Self> {
         Ok(Self {
             chat_id,
@@ -669,6 +895,8 @@ impl SendAnimation {
             performer: None,
             title: None,
             parse_mode: None,
+            show_caption_above_media: None,
+            has_spoiler: None,
             disable_notification: false,
             reply_to_message_id: None,
             allow_sending_without_reply: false,
@@ -703,7 +931,7 @@ pub struct SendVoice {
     pub duration: Option<i64>,
     /// Send Markdown or HTML, if you want Telegram apps to show bold, italic,
     /// fixed-width text or inline URLs in your bot's message.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub parse_mode: Option<ParseMode>,
     /// Sends the message silently. Users will receive a notification with no
     /// sound.
@@ -720,10 +948,10 @@ pub struct SendVoice {
 }
 
 impl SendVoice {
-    pub fn new(chat_id: i64, voice: String) -> Self {
+    pub fn new<F: Into<InputFile>>(chat_id: i64, voice: F) -> Self {
         Self {
             chat_id,
-            voice: InputFile::String(voice),
+            voice: voice.into(),
             caption: None,
             caption_entities: None,
             duration: None,
@@ -735,6 +963,14 @@ impl SendVoice {
         }
     }
 
+    /// Resends an existing voice message, keeping its duration
+    pub fn from_voice(chat_id: i64, voice: &Voice) -> Self {
+        let mut data = Self::new(chat_id, String::new());
+        data.voice = InputFile::FileId(voice.file_id.clone());
+        data.duration = i64::try_from(voice.duration).ok();
+        data
+    }
+
     pub fn from_file<P: AsRef<Path>>(chat_id: i64, path: P) -> Result<Self> {
         Ok(Self {
             chat_id,
@@ -792,10 +1028,10 @@ pub struct SendVideoNote {
 }
 
 impl SendVideoNote {
-    pub fn new(chat_id: i64, note: String) -> Self {
+    pub fn new<F: Into<InputFile>>(chat_id: i64, note: F) -> Self {
         Self {
             chat_id,
-            video_note: InputFile::String(note),
+            video_note: note.into(),
             thumb: None,
             duration: None,
             length: None,
@@ -806,6 +1042,15 @@ impl SendVideoNote {
         }
     }
 
+    /// Resends an existing video message, keeping its duration and length
+    pub fn from_video_note(chat_id: i64, note: &VideoNote) -> Self {
+        let mut data = Self::new(chat_id, String::new());
+        data.video_note = InputFile::FileId(note.file_id.clone());
+        data.duration = i64::try_from(note.duration).ok();
+        data.length = i64::try_from(note.length).ok();
+        data
+    }
+
     pub fn from_file<P: AsRef<Path>>(chat_id: i64, path: P) -> Result<Self> {
         Ok(Self {
             chat_id,
@@ -869,6 +1114,9 @@ pub struct SendLocation {
     pub latitude: f64,
     /// Longitude of the location
     pub longitude: f64,
+    /// The radius of uncertainty for the location, measured in meters; 0-1500
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizontal_accuracy: Option<f64>,
     /// Period in seconds for which the location will be updated
     #[serde(skip_serializing_if = "Option::is_none")]
     pub live_period: Option<i64>,
@@ -920,6 +1168,14 @@ pub struct SendVenue {
     /// “arts_entertainment/aquarium” or “food/icecream”.)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub foursquare_type: Option<String>,
+    /// Google Places identifier of the venue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub google_place_id: Option<String>,
+    /// Google Places type of the venue. (See [supported types].)
+    ///
+    /// [supported types]: https://developers.google.com/places/web-service/supported_types
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub google_place_type: Option<String>,
     /// Sends the message silently. Users will receive a notification with no
     /// sound.
     pub disable_notification: bool,
@@ -976,9 +1232,9 @@ pub struct SendContact {
 pub struct SendPoll {
     /// Unique identifier for the target chat
     pub chat_id: i64,
-    /// Poll question, 1-255 characters
+    /// Poll question, 1-300 characters
     pub question: String,
-    /// A JSON-serialized list of answer options, 2-10 strings 1-300 characters
+    /// A JSON-serialized list of answer options, 2-10 strings 1-100 characters
     /// each
     pub options: Vec<String>,
     /// True, if the poll needs to be anonymous, defaults to True
@@ -998,14 +1254,15 @@ pub struct SendPoll {
     /// Text that is shown when a user chooses an incorrect answer or taps on
     /// the lamp icon in a quiz-style poll, 0-200 characters with at most 2 line
     /// feeds after entities parsing
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub explanation: Option<String>,
     /// Mode for parsing entities in the explanation.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "ParseMode::is_unset")]
     pub explanation_parse_mode: Option<ParseMode>,
     /// List of special entities that appear in the poll explanation, which can
     /// be specified instead of parse_mode
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub explanation_enitites: Option<Vec<MessageEntity>>,
+    pub explanation_entities: Option<Vec<MessageEntity>>,
     /// Amount of time in seconds the poll will be active after creation, 5-600.
     /// Can't be used together with close_date.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1013,9 +1270,12 @@ pub struct SendPoll {
     /// Point in time (Unix timestamp) when the poll will be automatically
     /// closed. Must be at least 5 and no more than 600 seconds in the future.
     /// Can't be used together with open_period.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(with = "unix_date_formatting::optional")]
     pub close_date: Option<DateTime<Utc>>,
-    /// Pass True, if the poll needs to be immediately closed.
+    /// Pass True, if the poll needs to be immediately closed, which can be
+    /// used to send a poll that only shows its results.
     pub is_closed: bool,
     /// Sends the message silently. Users will receive a notification with no
     /// sound.
@@ -1031,6 +1291,89 @@ pub struct SendPoll {
     pub reply_markup: Option<ReplyMarkup>,
 }
 
+impl SendPoll {
+    /// Creates a regular poll with the given question and answer options,
+    /// returning an error if the question isn't 1-300 characters or if there
+    /// aren't 2-10 options of 1-100 characters each
+    pub fn new_regular(chat_id: i64, question: &str, options: &[&str]) -> Result<Self> {
+        Self::new(chat_id, question, options, PollType::Regular)
+    }
+
+    /// Creates a quiz with the given question and answer options, of which
+    /// the one at `correct_index` is the correct answer. Besides the limits
+    /// of [`new_regular`], it returns an error if there is no option at
+    /// `correct_index`.
+    ///
+    /// [`new_regular`]: #method.new_regular
+    pub fn new_quiz(
+        chat_id: i64,
+        question: &str,
+        options: &[&str],
+        correct_index: usize,
+    ) -> Result<Self> {
+        let mut data = Self::new(chat_id, question, options, PollType::Quiz)?;
+        if correct_index >= options.len() {
+            return Err(TelegramError::InvalidArgument(format!(
+                "the correct option {correct_index} of the quiz doesn't exist, it only has {} \
+                 options",
+                options.len()
+            ))
+            .into());
+        }
+
+        data.correct_option_id = i64::try_from(correct_index).ok();
+        Ok(data)
+    }
+
+    fn new(chat_id: i64, question: &str, options: &[&str], poll_type: PollType) -> Result<Self> {
+        if !(1..=MAX_POLL_QUESTION_LENGTH).contains(&question.chars().count()) {
+            return Err(TelegramError::InvalidArgument(format!(
+                "the question of a poll must be 1-{MAX_POLL_QUESTION_LENGTH} characters"
+            ))
+            .into());
+        }
+        if !POLL_OPTION_COUNT.contains(&options.len()) {
+            return Err(TelegramError::InvalidArgument(format!(
+                "a poll must have {}-{} options, got {}",
+                POLL_OPTION_COUNT.start(),
+                POLL_OPTION_COUNT.end(),
+                options.len()
+            ))
+            .into());
+        }
+        if let Some(option) = options
+            .iter()
+            .find(|o| !(1..=MAX_POLL_OPTION_LENGTH).contains(&o.chars().count()))
+        {
+            return Err(TelegramError::InvalidArgument(format!(
+                "the options of a poll must be 1-{MAX_POLL_OPTION_LENGTH} characters, got \
+                 {option:?}"
+            ))
+            .into());
+        }
+
+        Ok(Self {
+            chat_id,
+            question: question.to_owned(),
+            options: options.iter().map(|&o| o.to_owned()).collect(),
+            is_anonymous: None,
+            poll_type: Some(poll_type),
+            allows_multiple_answers: false,
+            correct_option_id: None,
+            explanation: None,
+            explanation_parse_mode: None,
+            explanation_entities: None,
+            open_period: None,
+            close_date: None,
+            is_closed: false,
+            disable_notification: false,
+            reply_to_message_id: None,
+            allow_sending_without_reply: false,
+            reply_markup: None,
+        })
+    }
+}
+
 /// struct for holding data needed to call
 /// [`send_dice`]
 ///
@@ -1041,11 +1384,11 @@ pub struct SendDice {
     /// Unique identifier for the target chat
     pub chat_id: i64,
     /// Emoji on which the dice throw animation is based.
-    /// Currently, must be one of “🎲”, “🎯”, “🏀”, “⚽”, “🎳”, or “🎰”.
     /// Dice can have values 1-6 for “🎲”, “🎯” and “🎳”, values 1-5 for “🏀”
     /// and “⚽”, and values 1-64 for “🎰”.
     /// Defauts to “🎲”
-    pub emoji: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<DiceEmoji>,
     /// Sends the message silently. Users will receive a notification with no
     /// sound.
     pub disable_notification: bool,
@@ -1060,6 +1403,21 @@ pub struct SendDice {
     pub reply_markup: Option<ReplyMarkup>,
 }
 
+impl SendDice {
+    /// function to create a new `SendDice` object, throwing a dice with the
+    /// given emoji
+    pub fn new(chat_id: i64, emoji: DiceEmoji) -> Self {
+        Self {
+            chat_id,
+            emoji: Some(emoji),
+            disable_notification: false,
+            reply_to_message_id: None,
+            allow_sending_without_reply: false,
+            reply_markup: None,
+        }
+    }
+}
+
 /// struct for holding data needed to call
 /// [`send_chat_action`]
 ///
@@ -1072,3 +1430,68 @@ pub struct SendChatAction {
     /// Type of action to broadcast.
     pub action: ChatAction,
 }
+
+macro_rules! impl_caption_setters {
+    ($name:ident) => {
+        impl $name {
+            /// Sets the caption
+            pub fn set_caption(&mut self, caption: &str) -> &mut Self {
+                self.caption = Some(caption.to_owned());
+                self
+            }
+
+            /// Sets the entities in the caption, which can be used instead of
+            /// a parse mode
+            pub fn set_caption_entities(&mut self, entities: Vec<MessageEntity>) -> &mut Self {
+                self.caption_entities = Some(entities);
+                self
+            }
+
+            /// Sets the mode used for parsing entities in the caption
+            pub fn set_parse_mode(&mut self, mode: &ParseMode) -> &mut Self {
+                self.parse_mode = Some(mode.to_owned());
+                self
+            }
+        }
+    };
+}
+
+impl_caption_setters!(SendPhoto);
+impl_caption_setters!(SendAudio);
+impl_caption_setters!(SendDocument);
+impl_caption_setters!(SendVideo);
+impl_caption_setters!(SendAnimation);
+impl_caption_setters!(SendVoice);
+
+macro_rules! impl_spoiler_setter {
+    ($name:ident) => {
+        impl $name {
+            /// Sets whether the media is covered with a spoiler animation
+            pub fn set_has_spoiler(&mut self, has_spoiler: bool) -> &mut Self {
+                self.has_spoiler = Some(has_spoiler);
+                self
+            }
+        }
+    };
+}
+
+impl_spoiler_setter!(SendPhoto);
+impl_spoiler_setter!(SendVideo);
+impl_spoiler_setter!(SendAnimation);
+
+impl_reply_markup_setter!(ReplyMarkup:
+    SendMessage,
+    CopyMessage,
+    SendPhoto,
+    SendAudio,
+    SendDocument,
+    SendVideo,
+    SendAnimation,
+    SendVoice,
+    SendVideoNote,
+    SendLocation,
+    SendVenue,
+    SendContact,
+    SendPoll,
+    SendDice,
+);
@@ -1,7 +1,14 @@
 use super::InputFile;
-use crate::model::{MaskPosition, ReplyMarkup};
+use crate::model::{MaskPosition, MessageEntity, ReplyMarkup};
 use serde::{Deserialize, Serialize};
 
+/// The maximum amount of custom emoji telegram accepts in a single call to
+/// [`get_custom_emoji_stickers`]
+///
+/// [`get_custom_emoji_stickers`]:
+/// ../../api/trait.API.html#method.get_custom_emoji_stickers
+pub const MAX_CUSTOM_EMOJI_STICKERS: usize = 200;
+
 /// struct for holding data needed to call
 /// [`send_sticker`]
 ///
@@ -37,6 +44,43 @@ pub struct GetStickerSet {
     pub name: String,
 }
 
+/// struct for holding data needed to call
+/// [`get_custom_emoji_stickers`]
+///
+/// [`get_custom_emoji_stickers`]:
+/// ../../api/trait.API.html#method.get_custom_emoji_stickers
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GetCustomEmojiStickers {
+    /// List of custom emoji identifiers. At most 200 custom emoji identifiers
+    /// can be specified.
+    pub custom_emoji_ids: Vec<String>,
+}
+
+impl GetCustomEmojiStickers {
+    pub fn new(custom_emoji_ids: Vec<String>) -> Self {
+        Self {
+            custom_emoji_ids,
+        }
+    }
+
+    /// Collects the ids of all the custom emoji in the given entities, for
+    /// example those of a message, skipping duplicates
+    pub fn from_entities(entities: &[MessageEntity]) -> Self {
+        let mut custom_emoji_ids: Vec<String> = Vec::new();
+        for entity in entities {
+            if let MessageEntity::CustomEmoji(e) = entity {
+                if !custom_emoji_ids.contains(&e.custom_emoji_id) {
+                    custom_emoji_ids.push(e.custom_emoji_id.clone());
+                }
+            }
+        }
+
+        Self {
+            custom_emoji_ids,
+        }
+    }
+}
+
 /// struct for holding data needed to call
 /// [`upload_sticker_file`]
 ///
@@ -163,3 +207,5 @@ pub struct SetStickerSetThumb {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thumb: Option<InputFile>,
 }
+
+impl_reply_markup_setter!(ReplyMarkup: SendSticker);
@@ -67,7 +67,11 @@ impl std::default::Default for GetUpdates {
 }
 
 /// The type of an update, can be used for specifying which update types you
-/// want to receive
+/// want to receive.
+///
+/// Note that [`UpdateType::ChatMember`] updates are only sent when they are
+/// explicitly requested, while an empty list of allowed updates means that all
+/// other update types will be received.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum UpdateType {
     #[serde(rename = "message")]
@@ -92,4 +96,32 @@ pub enum UpdateType {
     Poll,
     #[serde(rename = "poll_answer")]
     PollAnswer,
+    #[serde(rename = "my_chat_member")]
+    MyChatMember,
+    #[serde(rename = "chat_member")]
+    ChatMember,
+    #[serde(rename = "chat_join_request")]
+    ChatJoinRequest,
+}
+
+impl UpdateType {
+    /// The update types that are received when no allowed updates are given,
+    /// which are all of them except for [`UpdateType::ChatMember`]
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::Message,
+            Self::EditedMessage,
+            Self::ChannelPost,
+            Self::EditedChannelPost,
+            Self::InlineQuery,
+            Self::ChosenInlineResult,
+            Self::CallbackQuery,
+            Self::ShippingQuery,
+            Self::PreCheckoutQuery,
+            Self::Poll,
+            Self::PollAnswer,
+            Self::MyChatMember,
+            Self::ChatJoinRequest,
+        ]
+    }
 }
@@ -0,0 +1,125 @@
+use crate::utils::{
+    result::{Result, TelegramError, ValidationError},
+    MAX_CAPTION_LENGTH,
+    MAX_MESSAGE_LENGTH,
+};
+use serde_json::Value;
+
+/// pairs of a field and the fields that can't be set together with it, like a
+/// parse mode and its entities
+const CONFLICTING_FIELDS: &[(&str, &[&str])] = &[
+    ("parse_mode", &["entities", "caption_entities"]),
+    ("explanation_parse_mode", &["explanation_entities"]),
+    ("open_period", &["close_date"]),
+];
+
+/// fields with the range of values telegram accepts for them, which are
+/// checked wherever they are set
+const RANGE_FIELDS: &[(&str, f64, f64)] = &[
+    ("heading", 1.0, 360.0),
+    ("proximity_alert_radius", 1.0, 100_000.0),
+    ("horizontal_accuracy", 0.0, 1500.0),
+];
+
+/// text fields with the amount of characters telegram accepts for them
+const LENGTH_FIELDS: &[(&str, usize, usize)] = &[("input_field_placeholder", 1, 64)];
+
+/// text fields with the maximum length telegram accepts for them, in UTF-16
+/// code units
+const TEXT_LIMITS: &[(&str, usize)] = &[
+    ("text", MAX_MESSAGE_LENGTH),
+    ("message_text", MAX_MESSAGE_LENGTH),
+    ("caption", MAX_CAPTION_LENGTH),
+];
+
+/// fields holding the messages sent by a request besides its own, like the
+/// media of an album or the results of an inline query, whose texts are
+/// limited the same way
+const NESTED_MESSAGE_FIELDS: &[&str] = &["media", "results", "input_message_content"];
+
+fn is_set(value: Option<&Value>) -> bool {
+    matches!(value, Some(v) if !v.is_null())
+}
+
+/// Validates the data of a request before it is sent to telegram, so that
+/// mistakes telegram would silently ignore are turned into errors
+pub(crate) fn validate_request(data: &Value) -> Result<()> {
+    match data {
+        Value::Object(map) => {
+            for (field, conflicting) in CONFLICTING_FIELDS {
+                if is_set(map.get(*field)) {
+                    if let Some(other) = conflicting.iter().find(|f| is_set(map.get(**f))) {
+                        return Err(TelegramError::InvalidArgument(format!(
+                            "{field} and {other} can't both be set, as telegram will ignore one \
+                             of them"
+                        ))
+                        .into());
+                    }
+                }
+            }
+
+            for (field, min, max) in RANGE_FIELDS {
+                if let Some(value) = map.get(*field).and_then(Value::as_f64) {
+                    if value < *min || value > *max {
+                        return Err(TelegramError::InvalidArgument(format!(
+                            "{field} must be between {min} and {max}, got {value}"
+                        ))
+                        .into());
+                    }
+                }
+            }
+
+            for (field, min, max) in LENGTH_FIELDS {
+                if let Some(value) = map.get(*field).and_then(Value::as_str) {
+                    let length = value.chars().count();
+                    if length < *min || length > *max {
+                        return Err(TelegramError::InvalidArgument(format!(
+                            "{field} must be {min}-{max} characters, got {length}"
+                        ))
+                        .into());
+                    }
+                }
+            }
+
+            map.values().try_for_each(validate_request)
+        },
+        Value::Array(values) => values.iter().try_for_each(validate_request),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that the texts and captions in the request aren't longer than
+/// telegram allows, counting their length in UTF-16 code units like telegram
+/// does. Only the texts of the messages being sent are checked, not those of
+/// for example buttons, and not those with a parse mode as telegram counts
+/// them without their markup.
+pub(crate) fn validate_text_lengths(data: &Value) -> Result<()> {
+    let Value::Object(map) = data else {
+        return Ok(());
+    };
+
+    if !is_set(map.get("parse_mode")) {
+        for (field, limit) in TEXT_LIMITS {
+            if let Some(value) = map.get(*field).and_then(Value::as_str) {
+                let length = value.encode_utf16().count();
+                if length > *limit {
+                    return Err(TelegramError::Validation(ValidationError {
+                        field: (*field).to_owned(),
+                        limit: *limit,
+                        length,
+                    })
+                    .into());
+                }
+            }
+        }
+    }
+
+    for field in NESTED_MESSAGE_FIELDS {
+        match map.get(*field) {
+            Some(Value::Array(values)) => values.iter().try_for_each(validate_text_lengths)?,
+            Some(value) => validate_text_lengths(value)?,
+            None => {},
+        }
+    }
+    Ok(())
+}
@@ -1,25 +1,39 @@
 use super::{
+    scheduler::UpdateScheduler,
+    waiters::Waiters,
     APIConnector,
+    ChatMemberHandlerFunc,
     ClientBuilder,
     Context,
     EventHandlerFunc,
     RawEventHandlerFunc,
+    UnparsedUpdateHandlerFunc,
     UpdatesStream,
     Webhook,
     WebhookOptions,
 };
 use crate::{
     api::{
-        types::{SetWebhook, UpdateType},
+        types::{
+            AnswerCallbackQuery,
+            AnswerPreCheckoutQuery,
+            DeleteWebhook,
+            GetUpdates,
+            SetWebhook,
+            UpdateType,
+        },
         APIClient,
     },
-    framework::Framework,
-    model::Update,
+    framework::{Filter, Framework, MessageHandlerFunc},
+    model::{BotUser, ParseMode, Update, UpdateContent},
+    utils::{self, result::TelegramError},
+    Error,
     Result,
 };
-use futures::StreamExt;
+use futures::{future::join_all, StreamExt};
 use parking_lot::RwLock;
 use std::sync::Arc;
+use tokio::task::JoinHandle;
 use typemap::ShareMap;
 
 /// The Client is the main object to manage your interaction with telegram.
@@ -83,11 +97,41 @@ pub struct Client {
     pub data: Arc<RwLock<ShareMap>>,
     pub(super) event_handlers: Vec<EventHandlerFunc>,
     pub(super) raw_event_handlers: Vec<RawEventHandlerFunc>,
+    pub(super) message_handlers: Vec<(Filter, MessageHandlerFunc)>,
+    pub(super) unparsed_handlers: Vec<UnparsedUpdateHandlerFunc>,
+    pub(super) my_chat_member_handlers: Vec<ChatMemberHandlerFunc>,
+    pub(super) chat_member_handlers: Vec<ChatMemberHandlerFunc>,
     pub(super) framework: Option<Arc<Framework>>,
     pub(super) webhook_opts: Option<WebhookOptions>,
     /// The update types that you want to receive, see the documentation of
     /// [`UpdateType`] for more information
     pub allowed_updates: Vec<UpdateType>,
+    /// Whether callback queries that weren't answered by any of the event
+    /// handlers should be answered automatically once they have finished,
+    /// see [`ClientBuilder::set_auto_answer_callbacks`]
+    pub auto_answer_callbacks: bool,
+    /// The error message pre-checkout queries that weren't answered by any of
+    /// the event handlers are declined with once they have finished, see
+    /// [`ClientBuilder::set_auto_decline_pre_checkout`]
+    pub auto_decline_pre_checkout: Option<String>,
+    /// Whether the commands of the framework are registered with telegram
+    /// when starting the client, see [`ClientBuilder::set_register_commands`]
+    pub register_commands: bool,
+    /// The parse mode the helper methods of the [`Context`] use when sending
+    /// text without one, see [`ClientBuilder::set_default_parse_mode`]
+    pub default_parse_mode: Option<ParseMode>,
+    /// Whether a webhook that is still set is deleted before polling for
+    /// updates, see [`ClientBuilder::set_delete_webhook`]
+    pub delete_webhook: bool,
+    /// Whether the updates that are waiting to be received are dropped when
+    /// starting the client, see [`ClientBuilder::set_drop_pending_updates`]
+    pub drop_pending_updates: bool,
+    /// Whether the JSON telegram sent is kept in the updates, see
+    /// [`ClientBuilder::set_keep_raw_json`]
+    pub keep_raw_json: bool,
+    pub(super) scheduler: Option<Arc<UpdateScheduler>>,
+    pub(super) me: Arc<RwLock<Option<BotUser>>>,
+    pub(super) waiters: Arc<Waiters>,
 }
 
 impl Client {
@@ -97,10 +141,24 @@ impl Client {
             api_client: Arc::new(Box::new(APIClient::new(None, token))),
             event_handlers: Vec::new(),
             raw_event_handlers: Vec::new(),
+            message_handlers: Vec::new(),
+            unparsed_handlers: Vec::new(),
+            my_chat_member_handlers: Vec::new(),
+            chat_member_handlers: Vec::new(),
             data: Arc::new(RwLock::new(ShareMap::custom())),
             framework: None,
             webhook_opts: None,
             allowed_updates: Vec::new(),
+            auto_answer_callbacks: false,
+            auto_decline_pre_checkout: None,
+            register_commands: true,
+            default_parse_mode: None,
+            delete_webhook: true,
+            drop_pending_updates: false,
+            keep_raw_json: false,
+            scheduler: None,
+            me: Arc::new(RwLock::new(None)),
+            waiters: Arc::new(Waiters::default()),
         }
     }
 
@@ -110,10 +168,24 @@ impl Client {
             api_client: Arc::new(Box::new(APIClient::new(None, token))),
             event_handlers: Vec::new(),
             raw_event_handlers: Vec::new(),
+            message_handlers: Vec::new(),
+            unparsed_handlers: Vec::new(),
+            my_chat_member_handlers: Vec::new(),
+            chat_member_handlers: Vec::new(),
             data: Arc::new(RwLock::new(ShareMap::custom())),
             webhook_opts: None,
             framework: Some(fr),
             allowed_updates: Vec::new(),
+            auto_answer_callbacks: false,
+            auto_decline_pre_checkout: None,
+            register_commands: true,
+            default_parse_mode: None,
+            delete_webhook: true,
+            drop_pending_updates: false,
+            keep_raw_json: false,
+            scheduler: None,
+            me: Arc::new(RwLock::new(None)),
+            waiters: Arc::new(Waiters::default()),
         }
     }
 
@@ -126,13 +198,20 @@ impl Client {
     /// stream or the program exits (for example due to a panic).
     /// If using the framework, it will update your commands in telegram.
     /// If using a webhook, it will handle it, else it will use polling using a
-    /// default [`UpdatesStream`] object
+    /// default [`UpdatesStream`] object.
+    ///
+    /// The token of the bot is checked first using [`Client::validate_token`],
+    /// returning [`TelegramError::InvalidToken`] right away if it's invalid.
+    ///
+    /// [`TelegramError::InvalidToken`]: ../utils/result/enum.TelegramError.html#variant.InvalidToken
     pub async fn start(&self) -> Result<()> {
         if let Some(opts) = &self.webhook_opts {
             self.start_with_webhook(opts).await
         } else {
             let mut stream = UpdatesStream::new(self.api_client.clone());
-            stream.set_allowed_updates(self.allowed_updates.clone());
+            stream
+                .set_allowed_updates(self.allowed_updates.clone())
+                .set_keep_raw_json(self.keep_raw_json);
 
             self.start_with_stream(&mut stream).await
         }
@@ -142,11 +221,31 @@ impl Client {
     /// stream or the program exits (for example due to a panic).
     /// If using the framework, it will update your commands in telegram
     /// You have to provide your own [`UpdatesStream`] object
+    ///
+    /// Unless disabled using [`ClientBuilder::set_delete_webhook`], a webhook
+    /// that's still set is deleted first, as telegram doesn't allow polling
+    /// while one is set. If it still is, [`TelegramError::WebhookConflict`] is
+    /// returned.
+    ///
+    /// [`TelegramError::WebhookConflict`]: ../utils/result/enum.TelegramError.html#variant.WebhookConflict
     pub async fn start_with_stream(&self, stream: &mut UpdatesStream) -> Result<()> {
-        if let Some(fr) = self.framework.clone() {
+        self.validate_token().await?;
+        self.prepare_framework().await?;
+
+        if self.delete_webhook {
             self.api_client
-                .set_my_commands(fr.get_commands().into())
+                .delete_webhook(DeleteWebhook {
+                    drop_pending_updates: self.drop_pending_updates.then_some(true),
+                })
                 .await?;
+        } else if self.drop_pending_updates {
+            self.skip_pending_updates(stream)
+                .await
+                .map_err(webhook_conflict)?;
+        }
+
+        if let Some(handler) = self.invalid_update_handler() {
+            stream.set_invalid_update_handler(handler);
         }
 
         log::info!("starting long polling to listen for updates from telegram api");
@@ -155,23 +254,33 @@ impl Client {
                 Ok(update) => {
                     self.fire_handlers(update);
                 },
-                Err(err) => return Err(err),
+                Err(err) => return Err(webhook_conflict(err)),
             }
         }
 
         Ok(())
     }
 
+    /// Drops the updates waiting to be received without deleting the webhook,
+    /// by getting only the latest update and starting the stream after it,
+    /// which makes telegram forget all updates before it
+    async fn skip_pending_updates(&self, stream: &mut UpdatesStream) -> Result<()> {
+        let mut data = GetUpdates::new();
+        data.set_offset(-1).set_limit(1).set_timeout(0);
+        if let Some(last) = self.api_client.get_updates(data).await?.last() {
+            log::info!("dropping pending updates up to update {}", last.update_id);
+            stream.set_offset(last.update_id + 1);
+        }
+        Ok(())
+    }
+
     /// Starts the client and blocks until an error happens in the webhook
     /// handling or the program exits (for example due to a panic).
     /// If using the framework, it will update your commands in telegram
     /// You have to provide your own [`WebhookOptions`] object
     pub async fn start_with_webhook(&self, opts: &WebhookOptions) -> Result<()> {
-        if let Some(fr) = self.framework.clone() {
-            self.api_client
-                .set_my_commands(fr.get_commands().into())
-                .await?;
-        }
+        self.validate_token().await?;
+        self.prepare_framework().await?;
 
         if let Some(webhook_url) = &opts.url {
             self.api_client
@@ -180,14 +289,20 @@ impl Client {
                     certificate: None,
                     max_connections: None,
                     allowed_updates: Some(self.allowed_updates.clone()),
-                    drop_pending_updates: None,
+                    drop_pending_updates: self.drop_pending_updates.then_some(true),
                     ip_address: None, // TODO: add opts for these
                 })
                 .await?;
         }
 
         log::info!("starting to listen on the webhook");
-        let mut receiver = Webhook::new(opts).start();
+        let mut webhook = Webhook::new(opts);
+        webhook.set_keep_raw_json(self.keep_raw_json);
+        if let Some(handler) = self.invalid_update_handler() {
+            webhook.set_invalid_update_handler(handler);
+        }
+
+        let mut receiver = webhook.start();
         while let Some(u) = receiver.recv().await {
             match u {
                 Ok(update) => {
@@ -200,6 +315,79 @@ impl Client {
         Ok(())
     }
 
+    /// Gets the bot itself using [`API::get_me`], only calling the API the
+    /// first time and returning the cached [`BotUser`] afterwards
+    ///
+    /// [`API::get_me`]: ../api/trait.API.html#method.get_me
+    pub async fn get_me(&self) -> Result<BotUser> {
+        if let Some(me) = self.me.read().clone() {
+            return Ok(me);
+        }
+
+        let me = self.api_client.get_me().await?;
+        *self.me.write() = Some(me.clone());
+        Ok(me)
+    }
+
+    /// Checks whether the token of the bot is valid using [`Client::get_me`],
+    /// returning [`TelegramError::InvalidToken`] if telegram rejected it. This
+    /// is done when starting the client, before listening for updates.
+    ///
+    /// [`TelegramError::InvalidToken`]: ../utils/result/enum.TelegramError.html#variant.InvalidToken
+    pub async fn validate_token(&self) -> Result<BotUser> {
+        self.get_me().await.map_err(|err| match err {
+            // telegram responds with "Not Found" when the token isn't
+            // formatted like a token at all
+            Error::Telegram(TelegramError::APIResponseError(description))
+                if description.starts_with("Unauthorized") || description == "Not Found" =>
+            {
+                TelegramError::InvalidToken.into()
+            },
+            err => err,
+        })
+    }
+
+    /// Gets the cached [`BotUser`], if [`Client::get_me`] has been called
+    /// before, which happens when starting the client
+    pub fn me(&self) -> Option<BotUser> {
+        self.me.read().clone()
+    }
+
+    /// Updates the commands in telegram and sets the username of the bot on the
+    /// framework, if one is set
+    async fn prepare_framework(&self) -> Result<()> {
+        if let Some(fr) = self.framework.clone() {
+            fr.set_bot_name(&self.get_me().await?.username);
+
+            if self.register_commands {
+                fr.register_commands_with_telegram(self.api_client.as_ref().as_ref())
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates a handler firing the [`UnparsedUpdateHandlerFunc`]s, if any
+    /// have been subscribed
+    fn invalid_update_handler(&self) -> Option<impl Fn(serde_json::Value) + Send + Sync> {
+        if self.unparsed_handlers.is_empty() {
+            return None;
+        }
+
+        let api = self.api_client.clone();
+        let data = self.data.clone();
+        let handlers = self.unparsed_handlers.clone();
+        let parse_mode = self.default_parse_mode.clone();
+        Some(move |raw: serde_json::Value| {
+            for h in &handlers {
+                let ctx = Context::new(api.clone(), data.clone())
+                    .with_default_parse_mode(parse_mode.clone());
+                utils::spawn(h(ctx, raw.clone()));
+            }
+        })
+    }
+
     /// Subscribes an update event handler function ([`EventHandlerFunc`]) to
     /// the client and will be ran whenever a new update is received
     pub fn subscribe_handler_func(&mut self, handler: EventHandlerFunc) {
@@ -212,27 +400,195 @@ impl Client {
         self.raw_event_handlers.push(handler);
     }
 
+    /// Subscribes a handler function ([`MessageHandlerFunc`]) to the client,
+    /// which will be ran for every new message matching the filter. The
+    /// filter is checked before the handler is spawned.
+    pub fn subscribe_message_handler(&mut self, filter: Filter, handler: MessageHandlerFunc) {
+        self.message_handlers.push((filter, handler));
+    }
+
+    /// Subscribes a handler function ([`ChatMemberHandlerFunc`]) to the
+    /// client, which will be ran whenever the status of the bot itself changes
+    /// in a chat, like when it's added to or removed from a group
+    pub fn subscribe_my_chat_member_handler(&mut self, handler: ChatMemberHandlerFunc) {
+        self.my_chat_member_handlers.push(handler);
+    }
+
+    /// Subscribes a handler function ([`ChatMemberHandlerFunc`]) to the
+    /// client, which will be ran whenever the status of a member of a chat
+    /// changes.
+    ///
+    /// Telegram only sends these updates when [`UpdateType::ChatMember`] is in
+    /// the allowed updates, which [`ClientBuilder::add_chat_member_handler`]
+    /// takes care of.
+    pub fn subscribe_chat_member_handler(&mut self, handler: ChatMemberHandlerFunc) {
+        self.chat_member_handlers.push(handler);
+    }
+
+    /// Subscribes a handler function ([`UnparsedUpdateHandlerFunc`]) to the
+    /// client, which will be ran with the raw JSON of every received update
+    /// that couldn't be parsed
+    pub fn subscribe_unparsed_handler(&mut self, handler: UnparsedUpdateHandlerFunc) {
+        self.unparsed_handlers.push(handler);
+    }
+
     // public only for testing purposes
     #[doc(hidden)]
     pub fn fire_handlers(&self, update: Update) {
+        // updates a handler is waiting for are passed to it before scheduling,
+        // as the handler might be holding up the updates of its chat
+        let Some(update) = self.waiters.consume(update) else {
+            return;
+        };
+
+        if let Some(scheduler) = &self.scheduler {
+            let client = self.clone();
+            scheduler.schedule(update.get_chat_id(), async move {
+                join_all(client.dispatch_update(update)).await;
+            });
+        } else {
+            self.dispatch_update(update);
+        }
+    }
+
+    // public only for testing purposes
+    #[doc(hidden)]
+    pub fn waiting_handlers(&self) -> usize {
+        self.waiters.len()
+    }
+
+    /// Spawns the handlers for the update, returning the handles of the
+    /// spawned tasks
+    fn dispatch_update(&self, update: Update) -> Vec<JoinHandle<()>> {
+        // every handler of the update is spawned in this span
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "update",
+            update_id = update.update_id,
+            chat_id = update.get_chat_id()
+        )
+        .entered();
+
+        let ctx = Context::new(self.api_client.clone(), self.data.clone())
+            .with_waiters(self.waiters.clone())
+            .with_default_parse_mode(self.default_parse_mode.clone());
+        let mut handles = Vec::new();
+
         for h in self.raw_event_handlers.clone() {
-            let ctx = Context::new(self.api_client.clone(), self.data.clone());
+            let ctx = ctx.clone();
             let u = update.clone();
-            tokio::spawn(async move { h(ctx, u.into()).await });
+            handles.push(utils::spawn(async move { h(ctx, u.into()).await }));
         }
 
         for h in self.event_handlers.clone() {
-            let ctx = Context::new(self.api_client.clone(), self.data.clone());
+            let ctx = ctx.clone();
             let u = update.clone();
-            tokio::spawn(async move { h(ctx, u).await });
+            handles.push(utils::spawn(async move { h(ctx, u).await }));
         }
 
-        if self.framework.is_some() {
-            let ctx = Context::new(self.api_client.clone(), self.data.clone());
-            let fr = self.framework.clone();
-            fr.as_ref()
-                .expect("Framework needs to be set before trying to fire commands")
-                .fire_commands(ctx, update);
+        if let UpdateContent::Message(message) = &update.content {
+            for (filter, h) in &self.message_handlers {
+                if filter.matches(message) {
+                    handles.push(utils::spawn(h(ctx.clone(), message.clone())));
+                }
+            }
+        }
+
+        let chat_member_handlers = match &update.content {
+            UpdateContent::MyChatMember(c) => Some((&self.my_chat_member_handlers, c)),
+            UpdateContent::ChatMember(c) => Some((&self.chat_member_handlers, c)),
+            _ => None,
+        };
+        if let Some((chat_member_handlers, c)) = chat_member_handlers {
+            for h in chat_member_handlers {
+                handles.push(utils::spawn(h(ctx.clone(), c.clone())));
+            }
+        }
+
+        let unanswered = match &update.content {
+            UpdateContent::CallbackQuery(query) if self.auto_answer_callbacks => {
+                Some(UnansweredQuery::Callback(query.id.clone()))
+            },
+            UpdateContent::PreCheckoutQuery(query) => {
+                self.auto_decline_pre_checkout.clone().map(|error_message| {
+                    UnansweredQuery::PreCheckout {
+                        id: query.id.clone(),
+                        error_message,
+                    }
+                })
+            },
+            _ => None,
+        };
+
+        if let Some(fr) = &self.framework {
+            handles.append(&mut fr.fire_update(ctx.clone(), update));
+        }
+
+        if let Some(query) = unanswered {
+            return vec![utils::spawn(async move {
+                join_all(handles).await;
+                query.answer(&ctx).await;
+            })];
+        }
+
+        handles
+    }
+}
+
+/// Turns the error telegram returns when polling while a webhook is set into
+/// [`TelegramError::WebhookConflict`]
+fn webhook_conflict(err: Error) -> Error {
+    match err {
+        Error::Telegram(TelegramError::APIResponseError(description))
+            if description.starts_with("Conflict") && description.contains("webhook") =>
+        {
+            TelegramError::WebhookConflict.into()
+        },
+        err => err,
+    }
+}
+
+/// A query that is answered automatically when none of the handlers answered
+/// it
+enum UnansweredQuery {
+    Callback(String),
+    PreCheckout {
+        id: String,
+        error_message: String,
+    },
+}
+
+impl UnansweredQuery {
+    /// Answers the query, unless it was already answered using the context
+    async fn answer(self, ctx: &Context) {
+        match self {
+            UnansweredQuery::Callback(id) => {
+                if ctx.callback_answered() {
+                    return;
+                }
+
+                let res = ctx.api.answer_callback_query(AnswerCallbackQuery::new(&id)).await;
+                if let Err(err) = res {
+                    log::warn!("failed to automatically answer callback query: {err}");
+                }
+            },
+            UnansweredQuery::PreCheckout {
+                id,
+                error_message,
+            } => {
+                if ctx.pre_checkout_answered() {
+                    return;
+                }
+
+                log::debug!("declining pre-checkout query {id}, as no handler answered it");
+                let res = ctx
+                    .api
+                    .answer_pre_checkout_query(AnswerPreCheckoutQuery::error(&id, &error_message))
+                    .await;
+                if let Err(err) = res {
+                    log::warn!("failed to automatically decline pre-checkout query: {err}");
+                }
+            },
         }
     }
 }
@@ -243,10 +599,24 @@ impl From<Box<APIConnector>> for Client {
             api_client: Arc::new(api),
             event_handlers: Vec::new(),
             raw_event_handlers: Vec::new(),
+            message_handlers: Vec::new(),
+            unparsed_handlers: Vec::new(),
+            my_chat_member_handlers: Vec::new(),
+            chat_member_handlers: Vec::new(),
             data: Arc::new(RwLock::new(ShareMap::custom())),
             framework: None,
             webhook_opts: None,
             allowed_updates: Vec::new(),
+            auto_answer_callbacks: false,
+            auto_decline_pre_checkout: None,
+            register_commands: true,
+            default_parse_mode: None,
+            delete_webhook: true,
+            drop_pending_updates: false,
+            keep_raw_json: false,
+            scheduler: None,
+            me: Arc::new(RwLock::new(None)),
+            waiters: Arc::new(Waiters::default()),
         }
     }
 }
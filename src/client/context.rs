@@ -1,11 +1,77 @@
-use super::APIConnector;
+use super::{waiters::Waiters, APIConnector};
+use crate::{
+    api::types::{
+        AnswerCallbackQuery,
+        AnswerInlineQuery,
+        AnswerPreCheckoutQuery,
+        AnswerShippingQuery,
+        EditMessageText,
+        InlineQueryResult,
+        InputFile,
+        SendMessage,
+        SendPhoto,
+        TrueOrObject,
+    },
+    framework::{CommandArguments, Dialogue, DialogueKey, DialogueStorage},
+    model::{
+        CallbackQuery,
+        InlineQuery,
+        Message,
+        ParseMode,
+        PreCheckoutQuery,
+        ShippingOption,
+        ShippingQuery,
+    },
+    utils::{result::TelegramError, split_text, FormattedTextBuilder, MAX_MESSAGE_LENGTH},
+    Result,
+};
 use parking_lot::RwLock;
-use std::sync::Arc;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use typemap::ShareMap;
 
+/// The amount of seconds the results given to [`Context::answer_inline`] may
+/// be cached on the server
+const INLINE_CACHE_TIME: i64 = 10;
+
+/// The kind of update the message a handler was called for came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    /// A new message
+    Message,
+    /// A new version of a message that was edited
+    EditedMessage,
+    /// A new post in a channel
+    ChannelPost,
+    /// A new version of a channel post that was edited
+    EditedChannelPost,
+}
+
+impl MessageKind {
+    /// Whether the message is a new version of an edited message or channel
+    /// post
+    pub fn is_edit(self) -> bool {
+        matches!(self, MessageKind::EditedMessage | MessageKind::EditedChannelPost)
+    }
+}
+
 /// The context object is an utility object that gets passed to all event
 /// handlers, it provides access to the API client and to any custom data you
 /// have set in the data object.
+///
+/// The context derefs to the API client, so all methods of the [`API`] trait
+/// can be called on it directly, like `ctx.send_dice(...)`. Answering callback
+/// and pre-checkout queries this way goes through the context, so that they
+/// aren't answered again automatically.
+///
+/// [`API`]: ../api/trait.API.html
 #[derive(Clone)]
 pub struct Context {
     /// The API client, implementing the [`API`] trait
@@ -16,6 +82,14 @@ pub struct Context {
     ///
     /// [`Client::data`]: struct.Client.html#structfield.data
     pub data: Arc<RwLock<ShareMap>>,
+    callback_answered: Arc<AtomicBool>,
+    pre_checkout_answered: Arc<AtomicBool>,
+    command_arguments: Option<Arc<str>>,
+    command_prefix: Option<Arc<str>>,
+    dialogue_storage: Option<Arc<dyn DialogueStorage>>,
+    message_kind: Option<MessageKind>,
+    waiters: Option<Arc<Waiters>>,
+    default_parse_mode: Option<ParseMode>,
 }
 
 impl Context {
@@ -23,6 +97,376 @@ impl Context {
         Self {
             api,
             data,
+            callback_answered: Arc::new(AtomicBool::new(false)),
+            pre_checkout_answered: Arc::new(AtomicBool::new(false)),
+            command_arguments: None,
+            command_prefix: None,
+            dialogue_storage: None,
+            message_kind: None,
+            waiters: None,
+            default_parse_mode: None,
         }
     }
+
+    /// Gets the arguments of the command being called, being the text after
+    /// the command. Outside of commands, this will always be empty.
+    pub fn args(&self) -> CommandArguments {
+        CommandArguments::new(self.command_arguments.as_deref().unwrap_or_default())
+    }
+
+    /// Gets the prefix the command being called was called with, or the first
+    /// prefix of the framework if it was called without one. Outside of
+    /// commands, this will always be `/`.
+    pub fn command_prefix(&self) -> &str {
+        self.command_prefix.as_deref().unwrap_or("/")
+    }
+
+    /// Gets a handle to the dialogue of the user sending the given message in
+    /// its chat, using the storage of the framework. Outside of handlers called
+    /// by the framework, this will always be `None`.
+    pub fn dialogue<S>(&self, message: &Message) -> Option<Dialogue<S>>
+    where
+        S: Serialize + DeserializeOwned,
+    {
+        self.dialogue_storage
+            .clone()
+            .map(|storage| Dialogue::new(DialogueKey::from_message(message), storage))
+    }
+
+    pub(crate) fn with_dialogue_storage(mut self, storage: Arc<dyn DialogueStorage>) -> Self {
+        self.dialogue_storage = Some(storage);
+        self
+    }
+
+    /// Gets the kind of update the message being handled came from. Outside
+    /// of message handlers called by the framework, this will always be
+    /// `None`.
+    pub fn message_kind(&self) -> Option<MessageKind> {
+        self.message_kind
+    }
+
+    /// Whether the message being handled is a new version of an edited
+    /// message or channel post, which can be used to avoid doing things twice
+    /// for the same message
+    pub fn is_edit(&self) -> bool {
+        self.message_kind.is_some_and(MessageKind::is_edit)
+    }
+
+    pub(crate) fn with_message_kind(mut self, kind: MessageKind) -> Self {
+        self.message_kind = Some(kind);
+        self
+    }
+
+    pub(crate) fn with_default_parse_mode(mut self, mode: Option<ParseMode>) -> Self {
+        self.default_parse_mode = mode;
+        self
+    }
+
+    /// Gets the parse mode the helper methods use when sending text without
+    /// one, set using [`ClientBuilder::set_default_parse_mode`]
+    ///
+    /// [`ClientBuilder::set_default_parse_mode`]: struct.ClientBuilder.html#method.set_default_parse_mode
+    pub fn default_parse_mode(&self) -> Option<&ParseMode> {
+        self.default_parse_mode.as_ref()
+    }
+
+    /// Gets the parse mode to send the text with, being the given one, or the
+    /// default parse mode if no parse mode or entities are given.
+    /// [`ParseMode::None`] results in no parse mode at all.
+    fn parse_mode_for(
+        &self,
+        parse_mode: Option<ParseMode>,
+        has_entities: bool,
+    ) -> Option<ParseMode> {
+        match parse_mode {
+            Some(ParseMode::None) => None,
+            None if has_entities => None,
+            None => self.default_parse_mode.clone(),
+            mode => mode,
+        }
+    }
+
+    pub(crate) fn with_waiters(mut self, waiters: Arc<Waiters>) -> Self {
+        self.waiters = Some(waiters);
+        self
+    }
+
+    /// Waits for the next message the user sends in the chat, returning
+    /// [`TelegramError::Timeout`] if none is sent within the timeout. The
+    /// message is only passed to the first handler waiting for it, and not to
+    /// any of the other handlers of the client or the framework.
+    ///
+    /// This is meant for simple prompts, use a [`Dialogue`] for longer
+    /// conversations. Only works in handlers called by the [`Client`].
+    ///
+    /// [`TelegramError::Timeout`]: ../utils/result/enum.TelegramError.html#variant.Timeout
+    /// [`Dialogue`]: ../framework/struct.Dialogue.html
+    /// [`Client`]: struct.Client.html
+    pub async fn wait_for_message(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        timeout: Duration,
+    ) -> Result<Message> {
+        self.get_waiters()?
+            .wait_for_message(chat_id, user_id, timeout)
+            .await
+    }
+
+    /// Waits for the next callback query from a button on the message with
+    /// the given id in the chat, returning [`TelegramError::Timeout`] if there is none
+    /// within the timeout. Like with [`wait_for_message`], the callback query
+    /// isn't passed to any other handlers, so it has to be answered by the
+    /// handler waiting for it.
+    ///
+    /// [`TelegramError::Timeout`]: ../utils/result/enum.TelegramError.html#variant.Timeout
+    /// [`wait_for_message`]: #method.wait_for_message
+    pub async fn wait_for_callback_query(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        timeout: Duration,
+    ) -> Result<CallbackQuery> {
+        self.get_waiters()?
+            .wait_for_callback_query(chat_id, message_id, timeout)
+            .await
+    }
+
+    fn get_waiters(&self) -> Result<&Waiters> {
+        self.waiters.as_deref().ok_or_else(|| {
+            TelegramError::InvalidArgument(
+                "waiting for updates only works in handlers called by the client".to_owned(),
+            )
+            .into()
+        })
+    }
+
+    pub(crate) fn with_command(mut self, prefix: &str, arguments: &str) -> Self {
+        self.command_prefix = Some(prefix.into());
+        self.command_arguments = Some(arguments.into());
+        self
+    }
+
+    /// Sends a message with the given text to the given chat, returning the
+    /// sent [`Message`]
+    pub async fn send_to(&self, chat_id: i64, text: &str) -> Result<Message> {
+        self.send_message(SendMessage::new(chat_id, text)).await
+    }
+
+    /// Sends the given text to the given chat in as many messages as needed
+    /// to not exceed telegram's limit on the length of a message, returning
+    /// the sent messages in order. The text is split on newlines or
+    /// whitespace when possible, and never inside the formatting of the
+    /// default parse mode, see [`split_text`].
+    ///
+    /// [`split_text`]: ../utils/fn.split_text.html
+    pub async fn send_message_split(&self, chat_id: i64, text: &str) -> Result<Vec<Message>> {
+        let parse_mode = self.parse_mode_for(None, false);
+        let parts = split_text(text, parse_mode.as_ref(), MAX_MESSAGE_LENGTH)?;
+
+        let mut messages = Vec::with_capacity(parts.len());
+        for part in parts {
+            messages.push(self.send_to(chat_id, part).await?);
+        }
+        Ok(messages)
+    }
+
+    /// Sends the message, using the default parse mode if it has no parse
+    /// mode or entities, returning the sent [`Message`]. Set its parse mode to
+    /// [`ParseMode::None`] to send its text as is.
+    ///
+    /// [`ParseMode::None`]: ../model/enum.ParseMode.html#variant.None
+    pub async fn send_message(&self, mut data: SendMessage) -> Result<Message> {
+        data.parse_mode = self.parse_mode_for(data.parse_mode, data.entities.is_some());
+        self.api.send_message(data).await
+    }
+
+    /// Sends a message with the given text to the chat of the given message,
+    /// without replying to it, returning the sent [`Message`]
+    pub async fn answer(&self, message: &Message, text: &str) -> Result<Message> {
+        self.send_to(message.chat.get_id(), text).await
+    }
+
+    /// Replies to the given message with the given text, returning the sent
+    /// [`Message`]. The reply will also be sent if the given message has been
+    /// deleted in the meantime
+    pub async fn reply(&self, message: &Message, text: &str) -> Result<Message> {
+        self.send_message(reply_message(message, text)).await
+    }
+
+    /// Replies to the given message with the given text formatted using
+    /// `MarkdownV2`, returning the sent [`Message`]
+    pub async fn reply_markdown(&self, message: &Message, text: &str) -> Result<Message> {
+        let mut data = reply_message(message, text);
+        data.set_parse_mode(&ParseMode::MarkdownV2);
+        self.send_message(data).await
+    }
+
+    /// Replies to the given message with the given text formatted using
+    /// `HTML`, returning the sent [`Message`]
+    pub async fn reply_html(&self, message: &Message, text: &str) -> Result<Message> {
+        let mut data = reply_message(message, text);
+        data.set_parse_mode(&ParseMode::HTML);
+        self.send_message(data).await
+    }
+
+    /// Replies to the given message with the text of the given
+    /// [`FormattedTextBuilder`], sending its formatting as entities so that
+    /// nothing has to be escaped, returning the sent [`Message`]
+    ///
+    /// [`FormattedTextBuilder`]: ../utils/struct.FormattedTextBuilder.html
+    pub async fn reply_formatted(
+        &self,
+        message: &Message,
+        text: &FormattedTextBuilder,
+    ) -> Result<Message> {
+        let (text, entities) = text.to_entities();
+        let mut data = reply_message(message, &text);
+        if entities.is_empty() {
+            data.set_parse_mode(&ParseMode::None);
+        } else {
+            data.set_entities(entities);
+        }
+        self.send_message(data).await
+    }
+
+    /// Replies to the given message with the given photo, returning the sent
+    /// [`Message`]
+    pub async fn reply_photo(&self, message: &Message, photo: InputFile) -> Result<Message> {
+        let mut data = SendPhoto::new(message.chat.get_id(), photo);
+        data.reply_to_message_id = Some(message.message_id);
+        data.allow_sending_without_reply = true;
+        self.api.send_photo(data).await
+    }
+
+    /// Edits the text of the given message, which has to be sent by the bot.
+    /// Returns the edited [`Message`] wrapped in a [`TrueOrObject`], which is
+    /// only `True` for inline messages. The new text uses the default parse
+    /// mode, if one is set
+    ///
+    /// [`TrueOrObject`]: ../api/types/enum.TrueOrObject.html
+    pub async fn edit_text(&self, message: &Message, text: &str) -> Result<TrueOrObject<Message>> {
+        let mut data = EditMessageText::from_message(message, text);
+        data.parse_mode = self.parse_mode_for(None, false);
+        self.api.edit_message_text(data).await
+    }
+
+    /// Answers the given callback query without showing anything to the user
+    pub async fn answer_callback(&self, query: &CallbackQuery) -> Result<bool> {
+        self.answer_callback_query(AnswerCallbackQuery::new(&query.id))
+            .await
+    }
+
+    /// Answers the given callback query, showing the given text as a
+    /// notification at the top of the chat screen
+    pub async fn answer_callback_text(&self, query: &CallbackQuery, text: &str) -> Result<bool> {
+        let mut data = AnswerCallbackQuery::new(&query.id);
+        data.set_text(text);
+        self.answer_callback_query(data).await
+    }
+
+    /// Answers the given callback query, showing the given text as an alert
+    /// the user has to dismiss
+    pub async fn answer_callback_alert(&self, query: &CallbackQuery, text: &str) -> Result<bool> {
+        let mut data = AnswerCallbackQuery::new(&query.id);
+        data.set_text(text).set_show_alert(true);
+        self.answer_callback_query(data).await
+    }
+
+    /// Answers the given inline query with the results. The results are only
+    /// cached for 10 seconds, and only for the user that sent the query, use
+    /// [`API::answer_inline_query`] directly for more control.
+    ///
+    /// [`API::answer_inline_query`]: ../api/trait.API.html#method.answer_inline_query
+    pub async fn answer_inline(
+        &self,
+        query: &InlineQuery,
+        results: Vec<InlineQueryResult>,
+    ) -> Result<bool> {
+        let mut data = AnswerInlineQuery::new(&query.id, results);
+        data.set_cache_time(INLINE_CACHE_TIME).set_is_personal(true);
+        self.api.answer_inline_query(data).await
+    }
+
+    /// Answers the given shipping query, with the available shipping options
+    /// when delivery to the address is possible, or with an error message
+    /// explaining why it isn't, which is shown to the user
+    pub async fn answer_shipping(
+        &self,
+        query: &ShippingQuery,
+        options_or_error: std::result::Result<Vec<ShippingOption>, &str>,
+    ) -> Result<bool> {
+        let data = match options_or_error {
+            Ok(options) => AnswerShippingQuery::ok(&query.id, options),
+            Err(error_message) => AnswerShippingQuery::error(&query.id, error_message),
+        };
+        self.api.answer_shipping_query(data).await
+    }
+
+    /// Answers the given pre-checkout query, confirming the order when
+    /// `error_message` is `None`, otherwise declining it with the message,
+    /// which is shown to the user. Telegram has to receive the answer within
+    /// 10 seconds of sending the query.
+    pub async fn answer_pre_checkout(
+        &self,
+        query: &PreCheckoutQuery,
+        error_message: Option<&str>,
+    ) -> Result<bool> {
+        let data = match error_message {
+            None => AnswerPreCheckoutQuery::ok(&query.id),
+            Some(error_message) => AnswerPreCheckoutQuery::error(&query.id, error_message),
+        };
+        self.answer_pre_checkout_query(data).await
+    }
+
+    /// Answers a callback query like [`API::answer_callback_query`], but
+    /// remembers that it has been answered, so that it isn't answered again
+    /// when using [`ClientBuilder::set_auto_answer_callbacks`]
+    ///
+    /// [`API::answer_callback_query`]: ../api/trait.API.html#method.answer_callback_query
+    /// [`ClientBuilder::set_auto_answer_callbacks`]: struct.ClientBuilder.html#method.set_auto_answer_callbacks
+    pub async fn answer_callback_query(&self, data: AnswerCallbackQuery) -> Result<bool> {
+        let res = self.api.answer_callback_query(data).await?;
+        self.callback_answered.store(true, Ordering::Release);
+        Ok(res)
+    }
+
+    /// Answers a pre-checkout query like [`API::answer_pre_checkout_query`],
+    /// but remembers that it has been answered, so that it isn't declined
+    /// when using [`ClientBuilder::set_auto_decline_pre_checkout`]
+    ///
+    /// [`API::answer_pre_checkout_query`]: ../api/trait.API.html#method.answer_pre_checkout_query
+    /// [`ClientBuilder::set_auto_decline_pre_checkout`]: struct.ClientBuilder.html#method.set_auto_decline_pre_checkout
+    pub async fn answer_pre_checkout_query(&self, data: AnswerPreCheckoutQuery) -> Result<bool> {
+        let res = self.api.answer_pre_checkout_query(data).await?;
+        self.pre_checkout_answered.store(true, Ordering::Release);
+        Ok(res)
+    }
+
+    /// Whether a callback query has been answered using this context, or a
+    /// context it has been cloned from
+    pub(crate) fn callback_answered(&self) -> bool {
+        self.callback_answered.load(Ordering::Acquire)
+    }
+
+    /// Whether a pre-checkout query has been answered using this context, or
+    /// a context it has been cloned from
+    pub(crate) fn pre_checkout_answered(&self) -> bool {
+        self.pre_checkout_answered.load(Ordering::Acquire)
+    }
+}
+
+impl Deref for Context {
+    type Target = APIConnector;
+
+    fn deref(&self) -> &APIConnector {
+        &**self.api
+    }
+}
+
+fn reply_message(message: &Message, text: &str) -> SendMessage {
+    let mut data = SendMessage::new(message.chat.get_id(), text);
+    data.reply_to_message(message);
+    data.allow_sending_without_reply = true;
+    data
 }
@@ -1,5 +1,5 @@
 use super::{Context, FutureOutcome};
-use crate::model::{raw::RawUpdate, Update};
+use crate::model::{raw::RawUpdate, ChatMemberUpdated, Update};
 
 /// A function that handles a new update, it receives a [`Context`] and
 /// [`Update`] and returns a pinned future. Wrap an async function with
@@ -10,3 +10,14 @@ pub type EventHandlerFunc = fn(Context, Update) -> FutureOutcome;
 /// [`RawUpdate`] and returns a pinned future. Wrap an async function with
 /// `#[prepare_listener]` for easier development.
 pub type RawEventHandlerFunc = fn(Context, RawUpdate) -> FutureOutcome;
+
+/// A function that handles an update that couldn't be parsed, it receives a
+/// [`Context`] and the raw JSON of the update and returns a pinned future.
+/// Wrap an async function with `#[prepare_listener]` for easier development.
+pub type UnparsedUpdateHandlerFunc = fn(Context, serde_json::Value) -> FutureOutcome;
+
+/// A function that handles a change of the status of a chat member, it
+/// receives a [`Context`] and the [`ChatMemberUpdated`] and returns a pinned
+/// future. Wrap an async function with `#[prepare_listener]` for easier
+/// development.
+pub type ChatMemberHandlerFunc = fn(Context, ChatMemberUpdated) -> FutureOutcome;
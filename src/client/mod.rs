@@ -20,19 +20,34 @@ mod builder;
 mod client;
 mod context;
 mod event_handlers;
+mod scheduler;
 mod stream;
+mod waiters;
 mod webhook_handling;
 
 use crate::api::API;
 use core::future::Future;
-use std::pin::Pin;
+use std::{pin::Pin, sync::Arc};
 
 pub use builder::ClientBuilder;
 pub use client::Client;
-pub use context::Context;
-pub use event_handlers::{EventHandlerFunc, RawEventHandlerFunc};
+pub use context::{Context, MessageKind};
+pub use event_handlers::{
+    ChatMemberHandlerFunc,
+    EventHandlerFunc,
+    RawEventHandlerFunc,
+    UnparsedUpdateHandlerFunc,
+};
+pub use scheduler::DispatchMode;
 pub use stream::UpdatesStream;
 pub use webhook_handling::{Webhook, WebhookOptions};
 
+/// The type map used for the shared data of [`Client::data`], and the trait
+/// for the keys of the values in it
+///
+/// [`Client::data`]: struct.Client.html#structfield.data
+pub use typemap::{Key as TypeMapKey, ShareMap};
+
 type APIConnector = dyn API + Send;
 pub(crate) type FutureOutcome = Pin<Box<dyn Future<Output = ()> + Send>>;
+type InvalidUpdateHandler = Arc<dyn Fn(serde_json::Value) + Send + Sync>;
@@ -0,0 +1,100 @@
+use futures::Future;
+use parking_lot::Mutex;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::{oneshot, Semaphore};
+
+/// How the [`Client`] dispatches updates to its handlers
+///
+/// [`Client`]: struct.Client.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DispatchMode {
+    /// Every update is handled as soon as it is received, so a later update
+    /// can finish before an earlier one. This is the default.
+    #[default]
+    Concurrent,
+    /// The updates of a chat are queued and handled in the order they were
+    /// received, waiting for the handling of the previous update of the chat
+    /// to finish. Updates of different chats, and updates without a chat, are
+    /// still handled concurrently.
+    ///
+    /// There is no limit on how many updates of a chat can be queued, so a
+    /// chat with a handler that never finishes keeps queueing its updates.
+    OrderedPerChat,
+}
+
+/// Schedules the handling of updates, limiting how many updates are handled
+/// concurrently and optionally handling the updates of a chat in order.
+/// Every update is spawned right away and waits for its turn in its task, so
+/// only the handling is limited and not the amount of waiting updates.
+pub(crate) struct UpdateScheduler {
+    semaphore: Option<Arc<Semaphore>>,
+    mode: DispatchMode,
+    chats: Mutex<ChatQueues>,
+}
+
+#[derive(Default)]
+struct ChatQueues {
+    next_seq: u64,
+    // the sequence number and completion of the last update scheduled per chat
+    last: HashMap<i64, (u64, oneshot::Receiver<()>)>,
+}
+
+impl UpdateScheduler {
+    pub(crate) fn new(max_concurrent_updates: Option<usize>, mode: DispatchMode) -> Self {
+        Self {
+            semaphore: max_concurrent_updates.map(|max| Arc::new(Semaphore::new(max))),
+            mode,
+            chats: Mutex::new(ChatQueues::default()),
+        }
+    }
+
+    /// Spawns the handling of an update, which will wait for the previous
+    /// update in the same chat if ordered and for a free slot if limited
+    pub(crate) fn schedule<F>(self: &Arc<Self>, chat_id: Option<i64>, handling: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let mut queued = match chat_id {
+            Some(id) if self.mode == DispatchMode::OrderedPerChat => {
+                let (tx, rx) = oneshot::channel();
+                let mut chats = self.chats.lock();
+                let seq = chats.next_seq;
+                chats.next_seq += 1;
+                let previous = chats.last.insert(id, (seq, rx)).map(|(_, prev)| prev);
+                Some((id, seq, tx, previous))
+            },
+            _ => None,
+        };
+
+        let scheduler = self.clone();
+        tokio::spawn(async move {
+            if let Some((_, _, _, Some(previous))) = &mut queued {
+                // an error means the previous update has finished as well
+                previous.await.ok();
+            }
+
+            let permit = match &scheduler.semaphore {
+                Some(semaphore) => Some(
+                    semaphore
+                        .clone()
+                        .acquire_owned()
+                        .await
+                        .expect("the update semaphore is never closed"),
+                ),
+                None => None,
+            };
+
+            handling.await;
+            drop(permit);
+
+            if let Some((id, seq, tx, _)) = queued {
+                tx.send(()).ok();
+
+                let mut chats = scheduler.chats.lock();
+                if matches!(chats.last.get(&id), Some((last, _)) if *last == seq) {
+                    chats.last.remove(&id);
+                }
+            }
+        });
+    }
+}
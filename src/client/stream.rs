@@ -7,18 +7,29 @@ use std::{
     task::{Context, Poll},
 };
 
-use super::APIConnector;
+use super::{APIConnector, InvalidUpdateHandler};
 use crate::{
-    api::types::{GetUpdates, UpdateType},
+    api::{
+        types::{GetUpdates, UpdateType},
+        APIEndpoint,
+    },
     model::Update,
     Result,
 };
+use serde::Deserialize;
+use serde_json::{value::RawValue, Value};
 
-type FutureUpdate = Pin<Box<dyn Future<Output = Result<Vec<Update>>>>>;
+type FutureUpdate = Pin<Box<dyn Future<Output = Result<Vec<Box<RawValue>>>>>>;
+type OffsetHandler = Box<dyn Fn(i64) + Send + Sync>;
 
 /// The stream of incoming updates, created by long polling the telegram API
 /// using their getUpdates endpoint.
 ///
+/// Updates that can't be parsed, for example because telegram added a new
+/// field that isn't supported yet, are logged and skipped so that they don't
+/// stall the stream. Use [`UpdatesStream::set_invalid_update_handler`] to
+/// handle their raw JSON yourself.
+///
 /// In most use-cases, this will be handled for you by the [`Client`]
 /// and the new updates then dispatched to your eventhandlers.
 ///
@@ -58,9 +69,13 @@ type FutureUpdate = Pin<Box<dyn Future<Output = Result<Vec<Update>>>>>;
 #[must_use = "streams do nothing unless polled"]
 pub struct UpdatesStream {
     api: Arc<Box<APIConnector>>,
-    buffer: VecDeque<Update>,
+    buffer: VecDeque<Box<RawValue>>,
     allowed_updates: Vec<UpdateType>,
     offset: i64,
+    confirmed_offset: i64,
+    offset_handler: Option<OffsetHandler>,
+    invalid_update_handler: Option<InvalidUpdateHandler>,
+    keep_raw_json: bool,
     limit: usize,
     timeout: usize,
     current_request: Option<FutureUpdate>,
@@ -72,8 +87,20 @@ impl Stream for UpdatesStream {
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         let ref_mut = self.get_mut();
 
-        if let Some(u) = ref_mut.buffer.pop_front() {
-            return Poll::Ready(Some(Ok(u)));
+        while let Some(raw) = ref_mut.buffer.pop_front() {
+            if let Some(update_id) = get_update_id(&raw) {
+                ref_mut.confirm_offset(update_id + 1);
+            }
+
+            match serde_json::from_str::<Update>(raw.get()) {
+                Ok(mut u) => {
+                    if ref_mut.keep_raw_json {
+                        u.raw_json = Some(raw.get().into());
+                    }
+                    return Poll::Ready(Some(Ok(u)));
+                },
+                Err(err) => ref_mut.handle_invalid_update(&raw, &err),
+            }
         }
 
         if let Some(ref mut request) = ref_mut.current_request {
@@ -84,9 +111,11 @@ impl Stream for UpdatesStream {
                     return Pin::new(ref_mut).poll_next(cx);
                 },
                 Poll::Ready(Ok(res)) => {
-                    for u in res {
-                        ref_mut.offset = max(u.update_id, ref_mut.offset);
-                        ref_mut.buffer.push_back(u);
+                    for raw in res {
+                        if let Some(update_id) = get_update_id(&raw) {
+                            ref_mut.offset = max(update_id, ref_mut.offset);
+                        }
+                        ref_mut.buffer.push_back(raw);
                     }
                 },
                 Poll::Ready(Err(err)) => {
@@ -113,7 +142,31 @@ impl UpdatesStream {
             .set_timeout(self.timeout);
 
         let api = self.api.clone();
-        self.current_request = Some(Box::pin(async move { api.get_updates(data).await }));
+        self.current_request = Some(Box::pin(async move {
+            api.get(APIEndpoint::GetUpdates, Some(serde_json::to_value(data)?))
+                .await?
+                .into()
+        }));
+    }
+
+    fn handle_invalid_update(&mut self, raw: &RawValue, err: &serde_json::Error) {
+        log::warn!("skipping update that couldn't be parsed ({err}): {raw}");
+        if let Some(handler) = &self.invalid_update_handler {
+            if let Ok(raw) = serde_json::from_str(raw.get()) {
+                handler(raw);
+            }
+        }
+    }
+
+    fn confirm_offset(&mut self, offset: i64) {
+        if offset <= self.confirmed_offset {
+            return;
+        }
+
+        self.confirmed_offset = offset;
+        if let Some(handler) = &self.offset_handler {
+            handler(offset);
+        }
     }
 
     /// creates a new update stream using the provided [`API`]
@@ -125,12 +178,67 @@ impl UpdatesStream {
             buffer: VecDeque::new(),
             allowed_updates: Vec::new(),
             offset: 0,
+            confirmed_offset: 0,
+            offset_handler: None,
+            invalid_update_handler: None,
+            keep_raw_json: false,
             limit: 100,
             timeout: 5,
             current_request: None,
         }
     }
 
+    /// Returns the offset of the next update to be received, all updates
+    /// with a lower `update_id` have already been returned by the stream.
+    ///
+    /// This can be persisted and passed to [`UpdatesStream::set_offset`] when
+    /// restarting your bot, to continue where it left off.
+    pub fn current_offset(&self) -> i64 {
+        self.confirmed_offset
+    }
+
+    /// Sets the offset to start receiving updates from, usually a value
+    /// previously retrieved using [`UpdatesStream::current_offset`]. Any
+    /// updates with a lower `update_id` will be confirmed to telegram and thus
+    /// not be received anymore.
+    pub fn set_offset(&mut self, offset: i64) -> &mut Self {
+        self.offset = offset - 1;
+        self.confirmed_offset = offset;
+        self.buffer.clear();
+        self.current_request = None;
+        self
+    }
+
+    /// Sets a handler that is called with the new offset every time it
+    /// advances, for example to persist it
+    pub fn set_offset_handler<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: Fn(i64) + Send + Sync + 'static,
+    {
+        self.offset_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Sets a handler that is called with the raw JSON of every update that
+    /// couldn't be parsed, before it is skipped
+    pub fn set_invalid_update_handler<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: Fn(Value) + Send + Sync + 'static,
+    {
+        self.invalid_update_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Sets whether the JSON of every update is kept in [`Update::raw_json`],
+    /// exactly as it was received in the getUpdates response. Defaults to
+    /// false.
+    ///
+    /// [`Update::raw_json`]: ../model/struct.Update.html#structfield.raw_json
+    pub fn set_keep_raw_json(&mut self, keep: bool) -> &mut Self {
+        self.keep_raw_json = keep;
+        self
+    }
+
     /// Sets the maximum amount of updates retrieved in one API call
     pub fn set_limit(&mut self, limit: usize) -> &mut Self {
         self.limit = limit;
@@ -163,3 +271,14 @@ impl UpdatesStream {
         self
     }
 }
+
+fn get_update_id(raw: &RawValue) -> Option<i64> {
+    #[derive(Deserialize)]
+    struct UpdateId {
+        update_id: i64,
+    }
+
+    serde_json::from_str::<UpdateId>(raw.get())
+        .ok()
+        .map(|u| u.update_id)
+}
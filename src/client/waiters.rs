@@ -0,0 +1,140 @@
+use crate::{
+    model::{CallbackQuery, Message, Update, UpdateContent},
+    utils::result::{Result, TelegramError},
+};
+use parking_lot::Mutex;
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use tokio::sync::oneshot;
+
+/// A handler waiting for the next update matching its filter
+struct Waiter<T> {
+    id: u64,
+    matches: Box<dyn Fn(&T) -> bool + Send + Sync>,
+    sender: oneshot::Sender<T>,
+}
+
+/// Removes the waiter from its list when dropped, so that waiters that timed
+/// out or whose future was dropped don't stay registered
+struct Registration<'a, T> {
+    waiters: &'a Mutex<Vec<Waiter<T>>>,
+    id: u64,
+}
+
+impl<T> Drop for Registration<'_, T> {
+    fn drop(&mut self) {
+        self.waiters.lock().retain(|w| w.id != self.id);
+    }
+}
+
+/// The handlers waiting for the next message or callback query matching their
+/// filter, used by [`Context::wait_for_message`] and
+/// [`Context::wait_for_callback_query`]
+///
+/// [`Context::wait_for_message`]: struct.Context.html#method.wait_for_message
+/// [`Context::wait_for_callback_query`]: struct.Context.html#method.wait_for_callback_query
+#[derive(Default)]
+pub(crate) struct Waiters {
+    next_id: AtomicU64,
+    messages: Mutex<Vec<Waiter<Message>>>,
+    callback_queries: Mutex<Vec<Waiter<CallbackQuery>>>,
+}
+
+impl Waiters {
+    /// Passes the update to the first waiter it matches, returning the update
+    /// if no waiter consumed it
+    pub(crate) fn consume(&self, update: Update) -> Option<Update> {
+        let Update {
+            update_id,
+            content,
+            raw_json,
+        } = update;
+
+        let content = match content {
+            UpdateContent::Message(message) => {
+                UpdateContent::Message(Self::pass(&self.messages, message)?)
+            },
+            UpdateContent::CallbackQuery(query) => {
+                UpdateContent::CallbackQuery(Self::pass(&self.callback_queries, query)?)
+            },
+            content => content,
+        };
+
+        Some(Update {
+            update_id,
+            content,
+            raw_json,
+        })
+    }
+
+    /// Sends the value to the first waiter it matches, returning it if there
+    /// is none that is still waiting
+    fn pass<T>(waiters: &Mutex<Vec<Waiter<T>>>, mut value: T) -> Option<T> {
+        let mut waiters = waiters.lock();
+        while let Some(i) = waiters.iter().position(|w| (w.matches)(&value)) {
+            match waiters.remove(i).sender.send(value) {
+                Ok(()) => return None,
+                Err(returned) => value = returned,
+            }
+        }
+        Some(value)
+    }
+
+    async fn wait<T>(
+        &self,
+        waiters: &Mutex<Vec<Waiter<T>>>,
+        matches: Box<dyn Fn(&T) -> bool + Send + Sync>,
+        timeout: Duration,
+    ) -> Result<T> {
+        let (sender, receiver) = oneshot::channel();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        waiters.lock().push(Waiter {
+            id,
+            matches,
+            sender,
+        });
+        let _registration = Registration {
+            waiters,
+            id,
+        };
+
+        match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(value)) => Ok(value),
+            _ => Err(TelegramError::Timeout.into()),
+        }
+    }
+
+    /// Waits for the next message sent by the user in the chat
+    pub(crate) async fn wait_for_message(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        timeout: Duration,
+    ) -> Result<Message> {
+        let matches = move |m: &Message| {
+            m.chat.get_id() == chat_id && m.from.as_ref().is_some_and(|u| u.id == user_id)
+        };
+        self.wait(&self.messages, Box::new(matches), timeout).await
+    }
+
+    /// Waits for the next callback query from a button on the message in the
+    /// chat
+    pub(crate) async fn wait_for_callback_query(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        timeout: Duration,
+    ) -> Result<CallbackQuery> {
+        let matches = move |q: &CallbackQuery| {
+            q.chat_id() == Some(chat_id) && q.message_id() == Some(message_id)
+        };
+        self.wait(&self.callback_queries, Box::new(matches), timeout).await
+    }
+
+    /// Gets the amount of waiters that are registered
+    pub(crate) fn len(&self) -> usize {
+        self.messages.lock().len() + self.callback_queries.lock().len()
+    }
+}
@@ -1,9 +1,12 @@
 use std::{
     convert::Infallible,
+    fmt,
     io::Write,
     net::{IpAddr, SocketAddr},
+    sync::Arc,
 };
 
+use super::InvalidUpdateHandler;
 use crate::{
     model::Update,
     utils::result::{Result as TelegramResult, TelegramError},
@@ -19,13 +22,27 @@ use hyper::{
     StatusCode,
     Uri,
 };
+use serde::Deserialize;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
 
 /// Handles listening to the telegram webhook and will provide you with the
-/// incoming updates
-#[derive(Debug)]
+/// incoming updates.
+///
+/// Updates that can't be parsed are logged and skipped, so that telegram
+/// doesn't keep retrying them. Use [`Webhook::set_invalid_update_handler`] to
+/// handle their raw JSON yourself.
 pub struct Webhook {
     opts: WebhookOptions,
+    invalid_update_handler: Option<InvalidUpdateHandler>,
+    keep_raw_json: bool,
+}
+
+impl fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Webhook")
+            .field("opts", &self.opts)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Webhook {
@@ -33,15 +50,41 @@ impl Webhook {
     pub fn new(opts: &WebhookOptions) -> Self {
         Self {
             opts: opts.clone(),
+            invalid_update_handler: None,
+            keep_raw_json: false,
         }
     }
 
+    /// Sets whether the body of every request telegram sent is kept in
+    /// [`Update::raw_json`], exactly as it was received. Defaults to false.
+    ///
+    /// [`Update::raw_json`]: ../model/struct.Update.html#structfield.raw_json
+    pub fn set_keep_raw_json(&mut self, keep: bool) -> &mut Self {
+        self.keep_raw_json = keep;
+        self
+    }
+
+    /// Sets a handler that is called with the raw JSON of every update that
+    /// couldn't be parsed, before it is skipped
+    pub fn set_invalid_update_handler<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: Fn(serde_json::Value) + Send + Sync + 'static,
+    {
+        self.invalid_update_handler = Some(Arc::new(handler));
+        self
+    }
+
     /// starts the webhandling and returns a [`Receiver`], which will allow you
     /// to receive the incoming updates
     pub fn start(self) -> Receiver<TelegramResult<Update>> {
         let (tx, rx) = channel(1000);
 
-        tokio::spawn(start_ws(self.opts, tx));
+        tokio::spawn(start_ws(
+            self.opts,
+            self.invalid_update_handler,
+            self.keep_raw_json,
+            tx,
+        ));
         rx
     }
 }
@@ -58,7 +101,24 @@ async fn handle_update(
         body.write_all(&chunk?)?
     }
 
-    let update: Update = serde_json::from_slice(&body)?;
+    let raw: serde_json::Value = serde_json::from_slice(&body)?;
+    let mut update = match Update::deserialize(&raw) {
+        Ok(update) => update,
+        Err(err) => {
+            log::warn!("skipping update that couldn't be parsed ({err}): {raw}");
+            if let Some(handler) = &payload.invalid_update_handler {
+                handler(raw);
+            }
+
+            *response.status_mut() = StatusCode::OK;
+            return Ok(response);
+        },
+    };
+
+    if payload.keep_raw_json {
+        update.raw_json = Some(String::from_utf8_lossy(&body).into());
+    }
+
     let send_res = payload.chan.send(Ok(update)).await;
     if send_res.is_err() {
         return Err(TelegramError::WebhookError.into());
@@ -94,11 +154,13 @@ async fn handle_req(
 
 async fn start_ws(
     opts: WebhookOptions,
+    invalid_update_handler: Option<InvalidUpdateHandler>,
+    keep_raw_json: bool,
     chan: Sender<TelegramResult<Update>>,
 ) -> TelegramResult<()> {
     let addr = SocketAddr::from((opts.ip, opts.port));
 
-    let payload = HandlingPayload::new(&opts, chan.clone());
+    let payload = HandlingPayload::new(&opts, invalid_update_handler, keep_raw_json, chan.clone());
     let make_svc = make_service_fn(move |_conn| {
         let inner_payload = payload.clone();
         async move {
@@ -188,16 +250,25 @@ impl Default for WebhookOptions {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 struct HandlingPayload {
     path: String,
+    invalid_update_handler: Option<InvalidUpdateHandler>,
+    keep_raw_json: bool,
     chan: Sender<TelegramResult<Update>>,
 }
 
 impl HandlingPayload {
-    fn new(opts: &WebhookOptions, sender: Sender<TelegramResult<Update>>) -> Self {
+    fn new(
+        opts: &WebhookOptions,
+        invalid_update_handler: Option<InvalidUpdateHandler>,
+        keep_raw_json: bool,
+        sender: Sender<TelegramResult<Update>>,
+    ) -> Self {
         Self {
             path: opts.get_path().to_owned(),
+            invalid_update_handler,
+            keep_raw_json,
             chan: sender,
         }
     }
@@ -0,0 +1,212 @@
+use crate::{
+    client::Context,
+    framework::types::CommandResult,
+    model::Message,
+};
+use std::{fmt, str::FromStr};
+
+/// The arguments given to a command, being the text after the command itself.
+///
+/// Arguments are separated by whitespace, unless they are surrounded by
+/// double quotes, in which case they can contain whitespace. A quote inside a
+/// quoted argument can be escaped using a backslash.
+///
+/// The arguments of the command being called can be retrieved using
+/// [`Context::args`].
+///
+/// # Example
+/// ```rust
+/// use telexide::framework::CommandArguments;
+///
+/// let mut args = CommandArguments::new("12 \"two words\"");
+///
+/// assert_eq!(args.next_arg::<i64>().unwrap(), 12);
+/// assert_eq!(args.next_arg::<String>().unwrap(), "two words");
+/// assert_eq!(args.optional_arg::<String>().unwrap(), None);
+/// ```
+///
+/// [`Context::args`]: ../client/struct.Context.html#method.args
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandArguments {
+    raw: String,
+    position: usize,
+}
+
+impl CommandArguments {
+    /// Creates new arguments from the text after the command
+    pub fn new(raw: &str) -> Self {
+        Self {
+            raw: raw.trim().to_owned(),
+            position: 0,
+        }
+    }
+
+    /// Gets all of the arguments as they were given
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Gets the arguments that haven't been parsed yet
+    pub fn rest(&self) -> &str {
+        self.raw[self.position..].trim_start()
+    }
+
+    /// Whether all arguments have been parsed
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// Parses the next argument, returning an error if there are no arguments
+    /// left or if it can't be parsed
+    pub fn next_arg<T>(&mut self) -> Result<T, ArgumentError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.optional_arg()?.ok_or(ArgumentError::Missing(None))
+    }
+
+    /// Parses the next argument if there are any arguments left, returning an
+    /// error if it can't be parsed
+    pub fn optional_arg<T>(&mut self) -> Result<Option<T>, ArgumentError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.next()
+            .map(|arg| {
+                arg.parse().map_err(|e: T::Err| ArgumentError::Invalid {
+                    name: None,
+                    reason: e.to_string(),
+                    value: arg,
+                })
+            })
+            .transpose()
+    }
+}
+
+impl Iterator for CommandArguments {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest();
+        let start = self.raw.len() - rest.len();
+        let mut chars = rest.char_indices();
+
+        let (res, consumed) = if chars.next()?.1 == '"' {
+            let mut res = String::new();
+            let mut consumed = rest.len();
+            let mut escaped = false;
+            for (i, c) in chars {
+                match c {
+                    _ if escaped => {
+                        res.push(c);
+                        escaped = false;
+                    },
+                    '\\' => escaped = true,
+                    '"' => {
+                        consumed = i + 1;
+                        break;
+                    },
+                    _ => res.push(c),
+                }
+            }
+            (res, consumed)
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            (rest[..end].to_owned(), end)
+        };
+
+        self.position = start + consumed;
+        Some(res)
+    }
+}
+
+/// Types that can be parsed from the [`CommandArguments`] of a command.
+///
+/// Use `#[derive(FromCommandArguments)]` to implement this for a struct with
+/// named fields, parsing each field as an argument in order. Fields with an
+/// `Option` type are optional and should come last.
+///
+/// When a command function takes a third parameter implementing this trait,
+/// the `#[command]` macro will parse it before calling the command, replying
+/// with the error and usage of the command when the arguments are invalid.
+pub trait FromCommandArguments: Sized {
+    /// Parses the type from the given arguments
+    fn from_arguments(args: &mut CommandArguments) -> Result<Self, ArgumentError>;
+
+    /// Describes the expected arguments, for example `<user> [reason]`
+    fn usage() -> String;
+}
+
+/// An error that occurred while parsing [`CommandArguments`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgumentError {
+    /// An argument, optionally with the given name, was missing
+    Missing(Option<String>),
+    /// An argument couldn't be parsed
+    Invalid {
+        /// The name of the argument, if known
+        name: Option<String>,
+        /// The value given for the argument
+        value: String,
+        /// Why the argument couldn't be parsed
+        reason: String,
+    },
+}
+
+impl ArgumentError {
+    /// Sets the name of the argument the error occurred for
+    #[must_use]
+    pub fn named(self, arg_name: &str) -> Self {
+        match self {
+            ArgumentError::Missing(_) => ArgumentError::Missing(Some(arg_name.to_owned())),
+            ArgumentError::Invalid {
+                value,
+                reason,
+                ..
+            } => ArgumentError::Invalid {
+                name: Some(arg_name.to_owned()),
+                value,
+                reason,
+            },
+        }
+    }
+}
+
+impl fmt::Display for ArgumentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgumentError::Missing(Some(name)) => write!(f, "missing argument {name}"),
+            ArgumentError::Missing(None) => write!(f, "missing an argument"),
+            ArgumentError::Invalid {
+                name: Some(name),
+                value,
+                reason,
+            } => write!(f, "invalid value \"{value}\" for argument {name}: {reason}"),
+            ArgumentError::Invalid {
+                name: None,
+                value,
+                reason,
+            } => write!(f, "invalid argument \"{value}\": {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ArgumentError {}
+
+// used by the command macro to reply when the arguments of a command are
+// invalid
+#[doc(hidden)]
+pub async fn reply_argument_error(
+    ctx: &Context,
+    message: &Message,
+    command: &str,
+    usage: &str,
+    err: &ArgumentError,
+) -> CommandResult {
+    let prefix = ctx.command_prefix();
+    ctx.reply(message, &format!("{err}\nusage: {prefix}{command} {usage}"))
+        .await?;
+    Ok(())
+}
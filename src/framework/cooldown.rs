@@ -0,0 +1,235 @@
+use crate::model::Message;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// How often the [`InMemoryCooldownStorage`] removes buckets that are no
+/// longer on cooldown
+#[allow(clippy::duration_suboptimal_units)]
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What a [`Cooldown`] applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CooldownScope {
+    /// The cooldown is tracked separately for every user
+    User,
+    /// The cooldown is tracked separately for every chat
+    Chat,
+    /// The cooldown is shared by everyone
+    Global,
+}
+
+/// A cooldown on a command, set using the `#[cooldown(...)]` attribute on the
+/// command. The command can be used `uses` times within every `duration`,
+/// after which it is on cooldown until the oldest of those uses is `duration`
+/// ago.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cooldown {
+    /// How many times the command can be used within the duration
+    pub uses: u32,
+    /// The duration of the window the uses are counted in
+    pub duration: Duration,
+    /// What the cooldown applies to
+    pub scope: CooldownScope,
+}
+
+/// The key a cooldown is tracked by in a [`CooldownStorage`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CooldownKey {
+    /// The name of the command
+    pub command: &'static str,
+    /// The scope of the cooldown
+    pub scope: CooldownScope,
+    /// The id of the user or chat the cooldown applies to, or 0 for a global
+    /// cooldown
+    pub id: i64,
+}
+
+impl CooldownKey {
+    /// Gets the key of the cooldown with the given scope that applies to the
+    /// command being called by the message
+    pub fn from_message(command: &'static str, scope: CooldownScope, message: &Message) -> Self {
+        let id = match scope {
+            CooldownScope::User => message
+                .from
+                .as_ref()
+                .map_or_else(|| message.chat.get_id(), |u| u.id),
+            CooldownScope::Chat => message.chat.get_id(),
+            CooldownScope::Global => 0,
+        };
+
+        Self {
+            command,
+            scope,
+            id,
+        }
+    }
+}
+
+/// A storage for keeping track of the cooldowns of commands.
+///
+/// By default an [`InMemoryCooldownStorage`] is used, implement this trait
+/// yourself to for example share cooldowns between multiple instances of your
+/// bot
+#[async_trait]
+pub trait CooldownStorage: Send + Sync {
+    /// Checks if any of the keys is still on its cooldown, returning the
+    /// longest remaining duration if one is. If none are, the use is recorded
+    /// for all of the keys.
+    async fn check_cooldowns(&self, cooldowns: &[(CooldownKey, Cooldown)]) -> Option<Duration>;
+}
+
+/// The source of the current time for an [`InMemoryCooldownStorage`] or an
+/// [`ExpiringDialogueStorage`], which can be replaced to control the time in
+/// tests
+///
+/// [`ExpiringDialogueStorage`]: struct.ExpiringDialogueStorage.html
+pub trait Clock: Send + Sync {
+    /// Gets the current time
+    fn now(&self) -> Instant;
+}
+
+/// The default [`Clock`], using the time of the system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// The uses of a command within the window of a cooldown
+#[derive(Debug)]
+struct Bucket {
+    duration: Duration,
+    uses: VecDeque<Instant>,
+}
+
+impl Bucket {
+    /// Forgets the uses that are outside of the window
+    fn expire(&mut self, now: Instant) {
+        while let Some(used) = self.uses.front() {
+            if now.duration_since(*used) < self.duration {
+                break;
+            }
+            self.uses.pop_front();
+        }
+    }
+}
+
+/// The default [`CooldownStorage`], keeping track of cooldowns in a `HashMap`.
+///
+/// Buckets that are no longer on cooldown are removed every minute while the
+/// storage is being used, or when calling [`cleanup`].
+///
+/// [`cleanup`]: #method.cleanup
+pub struct InMemoryCooldownStorage {
+    clock: Arc<dyn Clock>,
+    buckets: Mutex<HashMap<CooldownKey, Bucket>>,
+    last_cleanup: Mutex<Instant>,
+}
+
+impl InMemoryCooldownStorage {
+    /// Creates a new, empty, storage
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Creates a new, empty, storage getting the current time from the given
+    /// clock
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        let now = clock.now();
+        Self {
+            clock,
+            buckets: Mutex::new(HashMap::new()),
+            last_cleanup: Mutex::new(now),
+        }
+    }
+
+    /// Removes the buckets that are no longer on cooldown
+    pub fn cleanup(&self) {
+        let now = self.clock.now();
+        *self.last_cleanup.lock() = now;
+        self.buckets.lock().retain(|_, bucket| {
+            bucket.expire(now);
+            !bucket.uses.is_empty()
+        });
+    }
+
+    /// Gets the amount of buckets currently being kept track of
+    pub fn len(&self) -> usize {
+        self.buckets.lock().len()
+    }
+
+    /// Whether no buckets are currently being kept track of
+    pub fn is_empty(&self) -> bool {
+        self.buckets.lock().is_empty()
+    }
+}
+
+impl Default for InMemoryCooldownStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for InMemoryCooldownStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemoryCooldownStorage")
+            .field("buckets", &self.buckets)
+            .field("last_cleanup", &self.last_cleanup)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl CooldownStorage for InMemoryCooldownStorage {
+    async fn check_cooldowns(&self, cooldowns: &[(CooldownKey, Cooldown)]) -> Option<Duration> {
+        let now = self.clock.now();
+        if now.duration_since(*self.last_cleanup.lock()) >= CLEANUP_INTERVAL {
+            self.cleanup();
+        }
+
+        let mut buckets = self.buckets.lock();
+        let mut remaining: Option<Duration> = None;
+        for (key, cooldown) in cooldowns {
+            let Some(bucket) = buckets.get_mut(key) else {
+                continue;
+            };
+            bucket.duration = cooldown.duration;
+            bucket.expire(now);
+
+            if bucket.uses.len() >= cooldown.uses as usize {
+                let wait = bucket
+                    .uses
+                    .front()
+                    .map_or(Duration::ZERO, |used| {
+                        cooldown.duration.saturating_sub(now.duration_since(*used))
+                    });
+                remaining = Some(remaining.map_or(wait, |r| r.max(wait)));
+            }
+        }
+
+        if remaining.is_some() {
+            return remaining;
+        }
+
+        for (key, cooldown) in cooldowns {
+            let bucket = buckets.entry(key.clone()).or_insert_with(|| Bucket {
+                duration: cooldown.duration,
+                uses: VecDeque::new(),
+            });
+            bucket.uses.push_back(now);
+            while bucket.uses.len() > cooldown.uses as usize {
+                bucket.uses.pop_front();
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,355 @@
+use super::cooldown::{Clock, SystemClock};
+use crate::{
+    model::Message,
+    utils::result::{Result, TelegramError},
+};
+use async_trait::async_trait;
+use log::debug;
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The key a dialogue is tracked by in a [`DialogueStorage`], every user has
+/// their own dialogue in every chat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DialogueKey {
+    /// The id of the chat the dialogue takes place in
+    pub chat_id: i64,
+    /// The id of the user having the dialogue
+    pub user_id: i64,
+}
+
+impl DialogueKey {
+    /// Gets the key of the dialogue the given message is part of. Messages
+    /// sent on behalf of a chat use the id of that chat as the user id.
+    pub fn from_message(message: &Message) -> Self {
+        let chat_id = message.chat.get_id();
+        let user_id = match (&message.from, &message.sender_chat) {
+            (_, Some(sender)) => sender.get_id(),
+            (Some(user), None) => user.id,
+            (None, None) => chat_id,
+        };
+
+        Self {
+            chat_id,
+            user_id,
+        }
+    }
+}
+
+/// A storage for keeping track of the states of dialogues.
+///
+/// The states are stored as JSON, so that the storage doesn't need to know
+/// the type of the state. By default an [`InMemoryDialogueStorage`] is used,
+/// use a [`JsonFileDialogueStorage`] or implement this trait yourself to keep
+/// the dialogues when restarting the bot.
+#[async_trait]
+pub trait DialogueStorage: Send + Sync {
+    /// Gets the state of the dialogue, if it is active
+    async fn get_state(&self, key: DialogueKey) -> Result<Option<Value>>;
+
+    /// Sets the state of the dialogue, starting it if it isn't active
+    async fn set_state(&self, key: DialogueKey, state: Value) -> Result<()>;
+
+    /// Removes the state of the dialogue, ending it
+    async fn remove_state(&self, key: DialogueKey) -> Result<()>;
+}
+
+#[async_trait]
+impl<S: DialogueStorage + ?Sized> DialogueStorage for Arc<S> {
+    async fn get_state(&self, key: DialogueKey) -> Result<Option<Value>> {
+        (**self).get_state(key).await
+    }
+
+    async fn set_state(&self, key: DialogueKey, state: Value) -> Result<()> {
+        (**self).set_state(key, state).await
+    }
+
+    async fn remove_state(&self, key: DialogueKey) -> Result<()> {
+        (**self).remove_state(key).await
+    }
+}
+
+/// The default [`DialogueStorage`], keeping track of dialogues in a `HashMap`
+#[derive(Debug, Default)]
+pub struct InMemoryDialogueStorage {
+    states: Mutex<HashMap<DialogueKey, Value>>,
+}
+
+impl InMemoryDialogueStorage {
+    /// Creates a new, empty, storage
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl DialogueStorage for InMemoryDialogueStorage {
+    async fn get_state(&self, key: DialogueKey) -> Result<Option<Value>> {
+        Ok(self.states.lock().get(&key).cloned())
+    }
+
+    async fn set_state(&self, key: DialogueKey, state: Value) -> Result<()> {
+        self.states.lock().insert(key, state);
+        Ok(())
+    }
+
+    async fn remove_state(&self, key: DialogueKey) -> Result<()> {
+        self.states.lock().remove(&key);
+        Ok(())
+    }
+}
+
+/// A [`DialogueStorage`] keeping track of dialogues in a JSON file, so that
+/// they are kept when restarting the bot. The whole file is rewritten every
+/// time a dialogue changes, so it is meant for bots with a modest amount of
+/// users.
+///
+/// The file is replaced atomically by writing a temporary file next to it
+/// first, so it isn't left half written when the bot stops during a write.
+#[derive(Debug)]
+pub struct JsonFileDialogueStorage {
+    path: PathBuf,
+    states: Mutex<HashMap<String, Value>>,
+    // held while writing the file, so writes don't overlap and the last one
+    // always contains the latest states
+    writing: tokio::sync::Mutex<()>,
+}
+
+impl JsonFileDialogueStorage {
+    /// Creates a storage using the file at the given path, loading the
+    /// dialogues in it if it exists
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        let states = if path.exists() {
+            serde_json::from_slice(&fs::read(&path)?)?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path,
+            states: Mutex::new(states),
+            writing: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    fn key(key: DialogueKey) -> String {
+        format!("{}:{}", key.chat_id, key.user_id)
+    }
+
+    async fn save(&self) -> Result<()> {
+        let _writing = self.writing.lock().await;
+        let data = serde_json::to_vec(&*self.states.lock())?;
+
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+        tokio::fs::write(&temp_path, data).await?;
+        tokio::fs::rename(&temp_path, &self.path).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DialogueStorage for JsonFileDialogueStorage {
+    async fn get_state(&self, key: DialogueKey) -> Result<Option<Value>> {
+        Ok(self.states.lock().get(&Self::key(key)).cloned())
+    }
+
+    async fn set_state(&self, key: DialogueKey, state: Value) -> Result<()> {
+        self.states.lock().insert(Self::key(key), state);
+        self.save().await
+    }
+
+    async fn remove_state(&self, key: DialogueKey) -> Result<()> {
+        let removed = self.states.lock().remove(&Self::key(key)).is_some();
+        if removed {
+            self.save().await?;
+        }
+        Ok(())
+    }
+}
+
+/// A [`DialogueStorage`] wrapping another storage, ending dialogues whose
+/// state hasn't changed within the timeout. Expired dialogues are removed from
+/// the wrapped storage when they are read, so the next message of a user who
+/// stopped responding isn't treated as part of the old dialogue.
+///
+/// The times the dialogues last changed are only kept in memory, so the
+/// timeout of a dialogue loaded from a persistent storage after restarting
+/// the bot starts when it is first read.
+///
+/// ```rust
+/// use std::{sync::Arc, time::Duration};
+/// use telexide::framework::{ExpiringDialogueStorage, Framework, InMemoryDialogueStorage};
+///
+/// let mut framework = Framework::new("bot");
+/// framework.set_dialogue_storage(Arc::new(ExpiringDialogueStorage::new(
+///     InMemoryDialogueStorage::new(),
+///     Duration::from_secs(300),
+/// )));
+/// ```
+pub struct ExpiringDialogueStorage<S> {
+    storage: S,
+    timeout: Duration,
+    clock: Arc<dyn Clock>,
+    changed: Mutex<HashMap<DialogueKey, Instant>>,
+}
+
+impl<S: DialogueStorage> ExpiringDialogueStorage<S> {
+    /// Wraps the storage, ending dialogues that didn't change state within
+    /// the timeout
+    pub fn new(storage: S, timeout: Duration) -> Self {
+        Self::with_clock(storage, timeout, Arc::new(SystemClock))
+    }
+
+    /// Wraps the storage like [`new`], getting the current time from the given
+    /// clock
+    ///
+    /// [`new`]: #method.new
+    pub fn with_clock(storage: S, timeout: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            storage,
+            timeout,
+            clock,
+            changed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Gets the timeout after which dialogues end
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+#[async_trait]
+impl<S: DialogueStorage> DialogueStorage for ExpiringDialogueStorage<S> {
+    async fn get_state(&self, key: DialogueKey) -> Result<Option<Value>> {
+        let Some(state) = self.storage.get_state(key).await? else {
+            self.changed.lock().remove(&key);
+            return Ok(None);
+        };
+
+        let now = self.clock.now();
+        let changed = *self.changed.lock().entry(key).or_insert(now);
+        let expired = now.duration_since(changed) >= self.timeout;
+        if expired {
+            debug!("dialogue {key:?} timed out");
+            self.remove_state(key).await?;
+            return Ok(None);
+        }
+        Ok(Some(state))
+    }
+
+    async fn set_state(&self, key: DialogueKey, state: Value) -> Result<()> {
+        self.storage.set_state(key, state).await?;
+        self.changed.lock().insert(key, self.clock.now());
+        Ok(())
+    }
+
+    async fn remove_state(&self, key: DialogueKey) -> Result<()> {
+        self.changed.lock().remove(&key);
+        self.storage.remove_state(key).await
+    }
+}
+
+/// A handle to the dialogue of a user in a chat, with the state of the
+/// dialogue being of the type `S`.
+///
+/// Get one using [`Context::dialogue`] in handlers called by the framework.
+/// While the dialogue is active, messages that aren't commands are passed to
+/// the dialogue handler registered for the variant of the current state using
+/// [`Framework::add_dialogue_handler`].
+///
+/// [`Context::dialogue`]: ../client/struct.Context.html#method.dialogue
+/// [`Framework::add_dialogue_handler`]: struct.Framework.html#method.add_dialogue_handler
+pub struct Dialogue<S> {
+    key: DialogueKey,
+    storage: Arc<dyn DialogueStorage>,
+    state: PhantomData<fn() -> S>,
+}
+
+impl<S> Clone for Dialogue<S> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key,
+            storage: self.storage.clone(),
+            state: PhantomData,
+        }
+    }
+}
+
+impl<S> Dialogue<S>
+where
+    S: Serialize + DeserializeOwned,
+{
+    /// Creates a handle to the dialogue with the given key in the storage
+    pub fn new(key: DialogueKey, storage: Arc<dyn DialogueStorage>) -> Self {
+        Self {
+            key,
+            storage,
+            state: PhantomData,
+        }
+    }
+
+    /// Gets the key of the dialogue
+    pub fn key(&self) -> DialogueKey {
+        self.key
+    }
+
+    /// Gets the current state of the dialogue, or `None` if it isn't active
+    pub async fn get(&self) -> Result<Option<S>> {
+        match self.storage.get_state(self.key).await? {
+            Some(state) => Ok(Some(serde_json::from_value(state)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Whether the dialogue is active
+    pub async fn is_active(&self) -> Result<bool> {
+        Ok(self.storage.get_state(self.key).await?.is_some())
+    }
+
+    /// Starts the dialogue with the given state, replacing the state of the
+    /// dialogue if it was already active
+    pub async fn enter(&self, state: S) -> Result<()> {
+        self.storage
+            .set_state(self.key, serde_json::to_value(state)?)
+            .await
+    }
+
+    /// Moves the active dialogue to the given state, returning an error if
+    /// the dialogue isn't active
+    pub async fn update(&self, state: S) -> Result<()> {
+        if !self.is_active().await? {
+            return Err(TelegramError::InvalidArgument(
+                "the dialogue can't be updated because it isn't active".to_owned(),
+            )
+            .into());
+        }
+        self.enter(state).await
+    }
+
+    /// Ends the dialogue
+    pub async fn exit(&self) -> Result<()> {
+        self.storage.remove_state(self.key).await
+    }
+}
+
+/// Gets the name of the enum variant the given state was serialized from,
+/// being the string of a unit variant or the only key of other variants
+pub(crate) fn get_state_variant(state: &Value) -> Option<&str> {
+    match state {
+        Value::String(variant) => Some(variant),
+        Value::Object(map) if map.len() == 1 => map.keys().next().map(String::as_str),
+        _ => None,
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    model::{ChatType, ContentKind, Message},
+    utils::result::{Result, TelegramError},
+};
+use regex::Regex;
+
+/// A condition a message has to meet for a message handler to be called,
+/// added using [`Framework::add_message_handler`] or
+/// [`ClientBuilder::add_message_handler`].
+///
+/// Filters can be combined using [`and`], [`or`] and [`not`], the combined
+/// filters are checked from left to right and stop as soon as the outcome is
+/// known, so cheap filters should go first.
+///
+/// ```
+/// use telexide::{framework::Filter, model::{ChatType, ContentKind}};
+///
+/// # fn main() -> telexide::Result<()> {
+/// let filter = Filter::chat_type(ChatType::SuperGroup)
+///     .and(Filter::has_content(ContentKind::Photo).or(Filter::text_matches("(?i)meme")?))
+///     .and(Filter::from_users([1234]).not());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Framework::add_message_handler`]: struct.Framework.html#method.add_message_handler
+/// [`ClientBuilder::add_message_handler`]: ../client/struct.ClientBuilder.html#method.add_message_handler
+/// [`and`]: #method.and
+/// [`or`]: #method.or
+/// [`not`]: #method.not
+#[derive(Debug, Clone)]
+pub struct Filter(FilterKind);
+
+#[derive(Debug, Clone)]
+enum FilterKind {
+    TextMatches(Regex),
+    ChatType(ChatType),
+    FromUsers(Vec<i64>),
+    HasContent(ContentKind),
+    Custom(fn(&Message) -> bool),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+}
+
+impl Filter {
+    /// Matches messages whose text or caption matches the regular
+    /// expression, returning an error if the regular expression is invalid
+    pub fn text_matches(pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|err| {
+            TelegramError::InvalidArgument(format!("invalid regular expression: {err}"))
+        })?;
+        Ok(Self(FilterKind::TextMatches(regex)))
+    }
+
+    /// Matches messages sent in a chat of the given type
+    pub fn chat_type(chat_type: ChatType) -> Self {
+        Self(FilterKind::ChatType(chat_type))
+    }
+
+    /// Matches messages sent by one of the users with the given ids
+    pub fn from_users<I: IntoIterator<Item = i64>>(users: I) -> Self {
+        Self(FilterKind::FromUsers(users.into_iter().collect()))
+    }
+
+    /// Matches messages with content of the given kind
+    pub fn has_content(kind: ContentKind) -> Self {
+        Self(FilterKind::HasContent(kind))
+    }
+
+    /// Matches messages for which the function returns true
+    pub fn custom(filter: fn(&Message) -> bool) -> Self {
+        Self(FilterKind::Custom(filter))
+    }
+
+    /// Matches messages matching both this filter and the other one, the
+    /// other filter is only checked if this one matches
+    #[must_use]
+    pub fn and(self, other: Filter) -> Self {
+        Self(FilterKind::And(Box::new(self), Box::new(other)))
+    }
+
+    /// Matches messages matching either this filter or the other one, the
+    /// other filter is only checked if this one doesn't match
+    #[must_use]
+    pub fn or(self, other: Filter) -> Self {
+        Self(FilterKind::Or(Box::new(self), Box::new(other)))
+    }
+
+    /// Matches messages not matching this filter
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Self(FilterKind::Not(Box::new(self)))
+    }
+
+    /// Checks whether the message matches the filter
+    pub fn matches(&self, message: &Message) -> bool {
+        match &self.0 {
+            FilterKind::TextMatches(regex) => {
+                message.get_text().is_some_and(|text| regex.is_match(&text))
+            },
+            FilterKind::ChatType(chat_type) => message.chat.get_type() == *chat_type,
+            FilterKind::FromUsers(users) => {
+                message.from.as_ref().is_some_and(|user| users.contains(&user.id))
+            },
+            FilterKind::HasContent(kind) => message.content.kind() == *kind,
+            FilterKind::Custom(filter) => filter(message),
+            FilterKind::And(a, b) => a.matches(message) && b.matches(message),
+            FilterKind::Or(a, b) => a.matches(message) || b.matches(message),
+            FilterKind::Not(filter) => !filter.matches(message),
+        }
+    }
+}
@@ -1,16 +1,107 @@
-use super::types::{CommandTypes, TelegramCommand};
+use super::{
+    cooldown::{CooldownKey, CooldownStorage, InMemoryCooldownStorage},
+    dialogue::{get_state_variant, Dialogue, DialogueKey, DialogueStorage, InMemoryDialogueStorage},
+    filters::Filter,
+    arguments::CommandArguments,
+    guards::{run_checks, ChatMemberCache},
+    help::{HelpCommand, HELP_COMMAND_NAME},
+    handlers::{
+        AfterHookFunc,
+        BeforeHookFunc,
+        CallbackHandlerFunc,
+        CheckFailureHandlerFunc,
+        ChosenInlineResultHandlerFunc,
+        CooldownHandlerFunc,
+        DialogueHandlerFunc,
+        ErasedCallbackHandler,
+        ErasedDialogueHandler,
+        InlineQueryHandlerFunc,
+        MessageHandlerFunc,
+        PreCheckoutQueryHandlerFunc,
+        ShippingQueryHandlerFunc,
+    },
+    types::{CheckResult, CommandOptions, CommandStatus, CommandTypes, TelegramCommand},
+};
 use crate::{
-    client::Context,
-    model::{Message, MessageContent, MessageEntity, Update, UpdateContent},
+    api::{types::SetMyCommands, API},
+    client::{Context, MessageKind},
+    model::{
+        BotCommand,
+        CallbackQuery,
+        Chat,
+        ChosenInlineResult,
+        InlineQuery,
+        Message,
+        MessageContent,
+        MessageEntity,
+        PreCheckoutQuery,
+        ShippingQuery,
+        Update,
+        UpdateContent,
+    },
+    utils::{
+        self,
+        get_callback_data_prefix,
+        result::{Result, TelegramError},
+        CallbackData,
+    },
 };
 use log::{debug, warn};
+use parking_lot::RwLock;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{borrow::Cow, collections::HashMap, sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
+
+/// The maximum amount of commands telegram accepts
+const MAX_BOT_COMMANDS: usize = 100;
+
+/// The description of the built-in help command as registered with telegram
+const HELP_COMMAND_DESCRIPTION: &str = "Lists the available commands";
+
+/// The command called in a message
+enum ParsedCommand {
+    /// A command addressed to this bot, with the text after it and the prefix
+    /// it was called with, if any
+    Command {
+        name: String,
+        tail: String,
+        prefix: Option<String>,
+    },
+    /// A command addressed to another bot, like `/start@otherbot`
+    OtherBot,
+    /// The message doesn't call a command
+    None,
+}
 
 /// A utility for easily managing commands.
 ///
 /// Refer to the [module-level documentation](index.html) for more detail
 pub struct Framework {
     commands: Vec<TelegramCommand>,
-    bot_name: String,
+    bot_name: RwLock<String>,
+    prefixes: Vec<String>,
+    case_insensitive: bool,
+    no_prefix_in_private: bool,
+    handle_edits: bool,
+    handle_channel_posts: bool,
+    help: bool,
+    owners: Arc<Vec<i64>>,
+    chat_member_cache: Arc<ChatMemberCache>,
+    check_failure_handler: Option<CheckFailureHandlerFunc>,
+    cooldown_handler: Option<CooldownHandlerFunc>,
+    cooldown_storage: Arc<dyn CooldownStorage>,
+    before_hook: Option<BeforeHookFunc>,
+    after_hook: Option<AfterHookFunc>,
+    unknown_command_handler: Option<MessageHandlerFunc>,
+    default_handler: Option<MessageHandlerFunc>,
+    message_handlers: Vec<(Filter, MessageHandlerFunc)>,
+    callback_handlers: Vec<(&'static str, ErasedCallbackHandler)>,
+    inline_query_handlers: Vec<(&'static str, InlineQueryHandlerFunc)>,
+    chosen_inline_result_handlers: Vec<(&'static str, ChosenInlineResultHandlerFunc)>,
+    shipping_query_handlers: Vec<(&'static str, ShippingQueryHandlerFunc)>,
+    pre_checkout_query_handlers: Vec<(&'static str, PreCheckoutQueryHandlerFunc)>,
+    dialogue_storage: Arc<dyn DialogueStorage>,
+    dialogue_handlers: Vec<(&'static str, ErasedDialogueHandler)>,
 }
 
 impl Framework {
@@ -18,56 +109,895 @@ impl Framework {
     pub fn new(bot_name: &str) -> Self {
         Self {
             commands: Vec::new(),
-            bot_name: bot_name.to_owned(),
+            bot_name: RwLock::new(bot_name.to_owned()),
+            prefixes: vec!["/".to_owned()],
+            case_insensitive: false,
+            no_prefix_in_private: false,
+            handle_edits: false,
+            handle_channel_posts: false,
+            help: false,
+            owners: Arc::new(Vec::new()),
+            chat_member_cache: Arc::new(ChatMemberCache::new(None)),
+            check_failure_handler: None,
+            cooldown_handler: None,
+            cooldown_storage: Arc::new(InMemoryCooldownStorage::new()),
+            before_hook: None,
+            after_hook: None,
+            unknown_command_handler: None,
+            default_handler: None,
+            message_handlers: Vec::new(),
+            callback_handlers: Vec::new(),
+            inline_query_handlers: Vec::new(),
+            chosen_inline_result_handlers: Vec::new(),
+            shipping_query_handlers: Vec::new(),
+            pre_checkout_query_handlers: Vec::new(),
+            dialogue_storage: Arc::new(InMemoryDialogueStorage::new()),
+            dialogue_handlers: Vec::new(),
+        }
+    }
+
+    /// Sets the prefixes that commands can be called with, replacing the
+    /// existing ones. By default only `/` is used.
+    ///
+    /// Commands using the `/` prefix are matched using the `bot_command`
+    /// entity telegram provides, other prefixes are matched on the text of
+    /// the message.
+    pub fn set_prefixes(&mut self, prefixes: Vec<&str>) -> &mut Self {
+        self.prefixes = prefixes.into_iter().map(ToOwned::to_owned).collect();
+        self
+    }
+
+    /// Adds a prefix that commands can be called with
+    pub fn add_prefix(&mut self, prefix: &str) -> &mut Self {
+        self.prefixes.push(prefix.to_owned());
+        self
+    }
+
+    /// Sets whether command names should be matched case-insensitively, so
+    /// that `/Start` and `/start` both call the `start` command
+    ///
+    /// # Panics
+    ///
+    /// Panics if the names or aliases of the added commands collide once they
+    /// are matched case-insensitively
+    pub fn set_case_insensitive(&mut self, case_insensitive: bool) -> &mut Self {
+        self.case_insensitive = case_insensitive;
+        let commands: Vec<_> = self.commands.iter().map(|c| c.options).collect();
+        self.check_name_collisions(&commands);
+        self
+    }
+
+    /// Sets whether commands can be called without a prefix in private chats,
+    /// so that sending `stats` to the bot calls the `stats` command.
+    /// Defaults to false.
+    pub fn set_no_prefix_in_private(&mut self, no_prefix: bool) -> &mut Self {
+        self.no_prefix_in_private = no_prefix;
+        self
+    }
+
+    /// Sets whether edited messages are passed through the commands as well,
+    /// defaults to false. Use [`Context::is_edit`] in commands to avoid doing
+    /// things twice for the same message. Edited messages aren't passed to
+    /// the dialogue and default handlers.
+    ///
+    /// [`Context::is_edit`]: ../client/struct.Context.html#method.is_edit
+    pub fn set_handle_edits(&mut self, handle_edits: bool) -> &mut Self {
+        self.handle_edits = handle_edits;
+        self
+    }
+
+    /// Sets whether posts in channels the bot is an admin of are passed
+    /// through the commands and handlers as well, defaults to false. Edited
+    /// channel posts are only handled if [`set_handle_edits`] is enabled too.
+    ///
+    /// [`set_handle_edits`]: #method.set_handle_edits
+    pub fn set_handle_channel_posts(&mut self, handle_channel_posts: bool) -> &mut Self {
+        self.handle_channel_posts = handle_channel_posts;
+        self
+    }
+
+    /// Enables the built-in `help` command, which lists the commands the user
+    /// calling it is allowed to use grouped by their category, or shows the
+    /// details of a single command when called like `/help ban`. A command
+    /// named `help` added to the framework takes precedence over it.
+    pub fn with_help(&mut self) -> &mut Self {
+        self.help = true;
+        self
+    }
+
+    /// Sets the ids of the users owning the bot, who are allowed to use
+    /// commands with the `#[owner_only]` attribute
+    pub fn set_owners(&mut self, owners: Vec<i64>) -> &mut Self {
+        self.owners = Arc::new(owners);
+        self
+    }
+
+    /// Adds the id of a user owning the bot, who is allowed to use commands
+    /// with the `#[owner_only]` attribute
+    pub fn add_owner(&mut self, owner: i64) -> &mut Self {
+        Arc::make_mut(&mut self.owners).push(owner);
+        self
+    }
+
+    /// Gets the ids of the users owning the bot
+    pub fn get_owners(&self) -> &[i64] {
+        &self.owners
+    }
+
+    /// Caches the chat members fetched for the `#[required_permissions(...)]`
+    /// attribute for the given duration, instead of calling `getChatMember`
+    /// every time such a command is used. Keep in mind that changes to the
+    /// permissions of a user only apply once the cached member expires.
+    /// Expired members are removed from the cache once per duration.
+    pub fn set_chat_member_cache_duration(&mut self, duration: Duration) -> &mut Self {
+        self.chat_member_cache = Arc::new(ChatMemberCache::new(Some(duration)));
+        self
+    }
+
+    /// Sets the function to be called when a check of a command fails, for
+    /// example to tell the user they are not allowed to use the command
+    pub fn set_check_failure_handler(&mut self, handler: CheckFailureHandlerFunc) -> &mut Self {
+        self.check_failure_handler = Some(handler);
+        self
+    }
+
+    /// Sets the function to be called when a command is used while it is on
+    /// cooldown, for example to tell the user how long they have to wait
+    pub fn set_cooldown_handler(&mut self, handler: CooldownHandlerFunc) -> &mut Self {
+        self.cooldown_handler = Some(handler);
+        self
+    }
+
+    /// Sets the storage used for keeping track of the cooldowns of commands,
+    /// by default an [`InMemoryCooldownStorage`] is used
+    ///
+    /// [`InMemoryCooldownStorage`]: struct.InMemoryCooldownStorage.html
+    pub fn set_cooldown_storage(&mut self, storage: Arc<dyn CooldownStorage>) -> &mut Self {
+        self.cooldown_storage = storage;
+        self
+    }
+
+    /// Sets the function to be called before a command is run, which can
+    /// prevent the command from running by returning false. It is called
+    /// with the arguments of the command already set on the [`Context`],
+    /// before the checks and cooldowns of the command.
+    ///
+    /// [`Context`]: ../client/struct.Context.html
+    pub fn set_before_hook(&mut self, hook: BeforeHookFunc) -> &mut Self {
+        self.before_hook = Some(hook);
+        self
+    }
+
+    /// Sets the function to be called after a message called a command, with
+    /// the result of the command or the reason it didn't run. The steps are
+    /// run in the following order, stopping at the first one that prevents
+    /// the command from running:
+    ///
+    /// 1. the before hook, see [`set_before_hook`]
+    /// 2. the checks of the command, calling the check failure handler when
+    ///    one fails
+    /// 3. the cooldowns of the command, calling the cooldown handler when it
+    ///    is on cooldown
+    /// 4. the command itself
+    ///
+    /// The after hook is always called last, also when a step prevented the
+    /// command from running. The built-in help command doesn't call the hooks.
+    ///
+    /// [`set_before_hook`]: #method.set_before_hook
+    pub fn set_after_hook(&mut self, hook: AfterHookFunc) -> &mut Self {
+        self.after_hook = Some(hook);
+        self
+    }
+
+    /// Sets the function to be called when a message calls a command that
+    /// doesn't exist, for example to point the user to the help command.
+    /// Commands addressed to other bots using `/command@otherbot` are ignored,
+    /// and so are edited messages, even when [`set_handle_edits`] is enabled.
+    ///
+    /// [`set_handle_edits`]: #method.set_handle_edits
+    pub fn set_unknown_command_handler(&mut self, handler: MessageHandlerFunc) -> &mut Self {
+        self.unknown_command_handler = Some(handler);
+        self
+    }
+
+    /// Sets the function to be called for text messages that don't call a
+    /// command and aren't part of a dialogue with a registered handler
+    pub fn set_default_handler(&mut self, handler: MessageHandlerFunc) -> &mut Self {
+        self.default_handler = Some(handler);
+        self
+    }
+
+    /// Adds a handler that is called for every message handled by the
+    /// framework that matches the filter, next to any command it calls. The
+    /// filter is checked before the handler is spawned.
+    pub fn add_message_handler(
+        &mut self,
+        filter: Filter,
+        handler: MessageHandlerFunc,
+    ) -> &mut Self {
+        self.message_handlers.push((filter, handler));
+        self
+    }
+
+    /// Adds a handler for callback queries with data of the type `T`, which is
+    /// called with the decoded data when the data has the prefix of `T`
+    pub fn add_callback_handler<T>(&mut self, handler: CallbackHandlerFunc<T>) -> &mut Self
+    where
+        T: CallbackData + 'static,
+    {
+        self.callback_handlers.push((
+            T::PREFIX,
+            Box::new(move |ctx, query| {
+                let data = query.decode_data::<T>()?;
+                Some(handler(ctx, query, data))
+            }),
+        ));
+        self
+    }
+
+    /// Adds a handler for inline queries starting with the given prefix,
+    /// followed by whitespace or the end of the query, which is called with the
+    /// prefix and any whitespace after it removed from the query. When the
+    /// prefixes of multiple handlers match, the longest one is used, so a
+    /// handler with an empty prefix catches all other queries.
+    pub fn add_inline_query_handler(
+        &mut self,
+        prefix: &'static str,
+        handler: InlineQueryHandlerFunc,
+    ) -> &mut Self {
+        self.inline_query_handlers.push((prefix, handler));
+        self
+    }
+
+    /// Adds a handler for chosen inline results whose id starts with the
+    /// given prefix. When the prefixes of multiple handlers match, the longest
+    /// one is used, so a handler with an empty prefix catches all other
+    /// results.
+    ///
+    /// Telegram only sends chosen inline results to bots that enabled inline
+    /// feedback using `@BotFather`.
+    pub fn add_chosen_inline_result_handler(
+        &mut self,
+        prefix: &'static str,
+        handler: ChosenInlineResultHandlerFunc,
+    ) -> &mut Self {
+        self.chosen_inline_result_handlers.push((prefix, handler));
+        self
+    }
+
+    /// Adds a handler for shipping queries of invoices whose payload starts
+    /// with the given prefix. When the prefixes of multiple handlers match,
+    /// the longest one is used, so a handler with an empty prefix catches all
+    /// other queries.
+    ///
+    /// Telegram only sends shipping queries for invoices with a flexible
+    /// price.
+    pub fn add_shipping_query_handler(
+        &mut self,
+        prefix: &'static str,
+        handler: ShippingQueryHandlerFunc,
+    ) -> &mut Self {
+        self.shipping_query_handlers.push((prefix, handler));
+        self
+    }
+
+    /// Adds a handler for pre-checkout queries of invoices whose payload
+    /// starts with the given prefix. When the prefixes of multiple handlers
+    /// match, the longest one is used, so a handler with an empty prefix
+    /// catches all other queries.
+    ///
+    /// Queries no handler answered can be declined automatically, see
+    /// [`ClientBuilder::set_auto_decline_pre_checkout`].
+    ///
+    /// [`ClientBuilder::set_auto_decline_pre_checkout`]: ../client/struct.ClientBuilder.html#method.set_auto_decline_pre_checkout
+    pub fn add_pre_checkout_query_handler(
+        &mut self,
+        prefix: &'static str,
+        handler: PreCheckoutQueryHandlerFunc,
+    ) -> &mut Self {
+        self.pre_checkout_query_handlers.push((prefix, handler));
+        self
+    }
+
+    /// Sets the storage used for keeping track of the states of dialogues, by
+    /// default an [`InMemoryDialogueStorage`] is used
+    ///
+    /// [`InMemoryDialogueStorage`]: struct.InMemoryDialogueStorage.html
+    pub fn set_dialogue_storage(&mut self, storage: Arc<dyn DialogueStorage>) -> &mut Self {
+        self.dialogue_storage = storage;
+        self
+    }
+
+    /// Adds a handler for messages that aren't commands, which is called when
+    /// the user sending the message is in a dialogue with a state of the type
+    /// `S`, and the current state is the enum variant with the given name.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use serde::{Deserialize, Serialize};
+    /// use telexide::{
+    ///     client::Context,
+    ///     framework::{Dialogue, Framework},
+    ///     macros::prepare_listener,
+    ///     model::Message,
+    /// };
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// enum Registration {
+    ///     AskName,
+    ///     AskAge { name: String },
+    /// }
+    ///
+    /// #[prepare_listener]
+    /// async fn ask_name(_c: Context, m: Message, d: Dialogue<Registration>, _s: Registration) {
+    ///     let name = m.get_text().unwrap_or_default();
+    ///     d.update(Registration::AskAge { name }).await.unwrap();
+    /// }
+    ///
+    /// #[prepare_listener]
+    /// async fn ask_age(_c: Context, _m: Message, d: Dialogue<Registration>, _s: Registration) {
+    ///     d.exit().await.unwrap();
+    /// }
+    ///
+    /// let mut framework = Framework::new("bot");
+    /// framework
+    ///     .add_dialogue_handler::<Registration>("AskName", ask_name)
+    ///     .add_dialogue_handler::<Registration>("AskAge", ask_age);
+    /// ```
+    pub fn add_dialogue_handler<S>(
+        &mut self,
+        variant: &'static str,
+        handler: DialogueHandlerFunc<S>,
+    ) -> &mut Self
+    where
+        S: Serialize + DeserializeOwned + 'static,
+    {
+        self.dialogue_handlers.push((
+            variant,
+            Arc::new(move |ctx, message, state, storage| {
+                let state = serde_json::from_value::<S>(state).ok()?;
+                let dialogue = Dialogue::new(DialogueKey::from_message(&message), storage);
+                Some(handler(ctx, message, dialogue, state))
+            }),
+        ));
+        self
+    }
+
+    /// Sets the username of the bot, used for only handling commands in the
+    /// format of `/command@username` that are addressed to this bot.
+    ///
+    /// This is done automatically using `getMe` when starting the [`Client`]
+    ///
+    /// [`Client`]: ../client/struct.Client.html
+    pub fn set_bot_name(&self, bot_name: &str) {
+        bot_name.clone_into(&mut self.bot_name.write());
+    }
+
+    /// Gets the username of the bot that is used for matching commands
+    pub fn get_bot_name(&self) -> String {
+        self.bot_name.read().clone()
+    }
+
+    /// Gets the prefixes that commands can be called with
+    pub fn get_prefixes(&self) -> &Vec<String> {
+        &self.prefixes
+    }
+
+    /// Gets the command called in the message, if there is one
+    fn parse_command(&self, message: &Message) -> ParsedCommand {
+        match &message.content {
+            MessageContent::Text {
+                content,
+                entities,
+            } => self.parse_text_command(
+                content,
+                entities,
+                self.no_prefix_in_private && matches!(message.chat, Chat::Private(_)),
+            ),
+            _ => ParsedCommand::None,
+        }
+    }
+
+    fn parse_text_command(
+        &self,
+        content: &str,
+        entities: &[MessageEntity],
+        allow_no_prefix: bool,
+    ) -> ParsedCommand {
+        let mut called = None;
+        let mut used_prefix = None;
+        for prefix in &self.prefixes {
+            if prefix == "/" {
+                called = entities.iter().find_map(|e| match e {
+                    MessageEntity::BotCommand(ref t) => Some((
+                        t.get_text(content).trim_start_matches('/').to_owned(),
+                        t.get_text_after(content),
+                    )),
+                    _ => None,
+                });
+            } else if let Some(rest) = content.strip_prefix(prefix.as_str()) {
+                let rest = rest.trim_start();
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                if end > 0 {
+                    called = Some((rest[..end].to_owned(), rest[end..].to_owned()));
+                }
+            }
+
+            if called.is_some() {
+                used_prefix = Some(prefix.clone());
+                break;
+            }
+        }
+
+        let prefixed = used_prefix.is_some();
+        if called.is_none() && allow_no_prefix {
+            let rest = content.trim_start();
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            if end > 0 {
+                called = Some((rest[..end].to_owned(), rest[end..].to_owned()));
+            }
+        }
+
+        let Some((called, tail)) = called else {
+            return ParsedCommand::None;
+        };
+        let mut split = called.splitn(2, '@');
+        let name = split.next().unwrap_or_default();
+        if let Some(username) = split.next() {
+            if !username.eq_ignore_ascii_case(&self.bot_name.read()) {
+                return if prefixed {
+                    ParsedCommand::OtherBot
+                } else {
+                    ParsedCommand::None
+                };
+            }
+        }
+
+        ParsedCommand::Command {
+            name: name.to_owned(),
+            tail,
+            prefix: used_prefix,
+        }
+    }
+
+    fn match_name(&self, called: &str, name: &str) -> bool {
+        if self.case_insensitive {
+            called.to_lowercase() == name.to_lowercase()
+        } else {
+            called == name
+        }
+    }
+
+    /// Whether the called name matches the name or one of the aliases of the
+    /// command
+    fn match_command(&self, called: &str, options: &CommandOptions) -> bool {
+        options.names().any(|name| self.match_name(called, name))
+    }
+
+    /// Panics if any of the names or aliases of the given commands, or of the
+    /// subcommands of a command, collide, or if an alias doesn't meet the
+    /// requirements of telegram for command names
+    fn check_name_collisions(&self, commands: &[&'static CommandOptions]) {
+        let mut seen: HashMap<Cow<'_, str>, &str> = HashMap::new();
+        for options in commands {
+            if let Some(alias) = options.aliases.iter().find(|a| !is_valid_command_name(a)) {
+                panic!(
+                    "the alias \"{}\" of the {} command has to be 1-32 lowercase latin letters, \
+                     digits or underscores",
+                    alias, options.name
+                );
+            }
+
+            for name in options.names() {
+                let key = if self.case_insensitive {
+                    Cow::Owned(name.to_lowercase())
+                } else {
+                    Cow::Borrowed(name)
+                };
+                if let Some(other) = seen.insert(key, options.name) {
+                    panic!(
+                        "the name or alias \"{}\" of the {} command collides with the {} command",
+                        name, options.name, other
+                    );
+                }
+            }
+
+            let subcommands: Vec<_> = options.subcommands.iter().map(|s| s.options).collect();
+            self.check_name_collisions(&subcommands);
         }
     }
 
-    fn match_command(&self, message: &Message, name: &str) -> bool {
-        if let MessageContent::Text {
-            entities,
-            content,
-        } = &message.content
+    #[allow(clippy::needless_pass_by_value, clippy::too_many_lines)]
+    fn fire_message_commands(&self, context: Context, message: Message) -> Vec<JoinHandle<()>> {
+        let mut handles = Vec::new();
+        let parsed = self.parse_command(&message);
+        if let ParsedCommand::Command {
+            name: called,
+            tail,
+            prefix,
+        } = parsed
         {
-            for entity in entities {
-                if let MessageEntity::BotCommand(ref t) = entity {
-                    let t = t.get_text(content);
-                    return t == format!("/{}", name)
-                        || t == format!("/{}@{} ", name, &self.bot_name);
+            let prefixed = prefix.is_some();
+            // commands called without a prefix are shown with the first one
+            let prefix = prefix
+                .or_else(|| self.prefixes.first().cloned())
+                .unwrap_or_else(|| "/".to_owned());
+
+            if self.help
+                && self.match_name(&called, HELP_COMMAND_NAME)
+                && !self
+                    .commands
+                    .iter()
+                    .any(|c| self.match_command(&called, c.options))
+            {
+                debug!("calling the help command");
+                handles.push(utils::spawn(
+                    self.help_command().reply(context, message, tail),
+                ));
+                return handles;
+            }
+
+            for command in &self.commands {
+                if !self.match_command(&called, command.options) {
+                    continue;
+                }
+
+                let (command, tail, options) = self.resolve_subcommand(command, &tail);
+                match command.command.clone() {
+                    CommandTypes::Default(c) => {
+                        let ctx = context.clone().with_command(&prefix, &tail);
+                        let msg = message.clone();
+                        let command_name = command.options.name;
+                        let failure_handler = self.check_failure_handler;
+                        let owners = self.owners.clone();
+                        let chat_member_cache = self.chat_member_cache.clone();
+                        let cooldowns = command.options.cooldowns;
+                        let cooldown_handler = self.cooldown_handler;
+                        let cooldown_storage = self.cooldown_storage.clone();
+                        let before_hook = self.before_hook;
+                        let after_hook = self.after_hook;
+
+                        handles.push(utils::spawn(async move {
+                            let status = async {
+                                if let Some(hook) = before_hook {
+                                    if !hook(ctx.clone(), msg.clone(), command_name).await {
+                                        debug!("command {} was vetoed", &command_name);
+                                        return CommandStatus::Vetoed;
+                                    }
+                                }
+
+                                for options in options {
+                                    if let CheckResult::Failure(reason) =
+                                        run_checks(options, &ctx, &msg, &owners, &chat_member_cache)
+                                            .await
+                                    {
+                                        debug!("check of command {} failed", &command_name);
+                                        if let Some(handler) = failure_handler {
+                                            handler(ctx.clone(), msg.clone(), reason.clone())
+                                                .await;
+                                        }
+                                        return CommandStatus::CheckFailed(reason);
+                                    }
+                                }
+
+                                if !cooldowns.is_empty() {
+                                    let keys: Vec<_> = cooldowns
+                                        .iter()
+                                        .map(|cooldown| {
+                                            let key = CooldownKey::from_message(
+                                                command_name,
+                                                cooldown.scope,
+                                                &msg,
+                                            );
+                                            (key, *cooldown)
+                                        })
+                                        .collect();
+
+                                    if let Some(remaining) =
+                                        cooldown_storage.check_cooldowns(&keys).await
+                                    {
+                                        debug!("command {} is on cooldown", &command_name);
+                                        if let Some(handler) = cooldown_handler {
+                                            handler(ctx.clone(), msg.clone(), remaining).await;
+                                        }
+                                        return CommandStatus::OnCooldown(remaining);
+                                    }
+                                }
+
+                                debug!("calling command {}", &command_name);
+                                let res = c(ctx.clone(), msg.clone()).await;
+                                if let Err(err) = &res {
+                                    warn!("command {} returned error: {}", &command_name, err.0);
+                                }
+                                CommandStatus::Completed(res)
+                            }
+                            .await;
+
+                            if let Some(hook) = after_hook {
+                                hook(ctx, msg, command_name, status).await;
+                            }
+                        }));
+                    },
+                }
+            }
+
+            if handles.is_empty() {
+                if !prefixed {
+                    handles.extend(self.fire_fallback_handlers(context, message));
+                } else if let Some(handler) =
+                    self.unknown_command_handler.filter(|_| !context.is_edit())
+                {
+                    debug!("calling the unknown command handler for {}", &called);
+                    handles.push(utils::spawn(handler(context, message)));
                 }
             }
+        } else if let ParsedCommand::OtherBot = parsed {
+            debug!("ignoring a command addressed to another bot");
+        } else {
+            handles.extend(self.fire_fallback_handlers(context, message));
         }
-        false
+
+        handles
     }
 
-    #[allow(clippy::needless_pass_by_value)]
-    fn fire_message_commands(&self, context: Context, message: Message) {
-        for command in &self.commands {
-            match command.command.clone() {
-                CommandTypes::Default(c) if self.match_command(&message, &command.options.name) => {
-                    let ctx = context.clone();
-                    let msg = message.clone();
-                    let command_name = command.options.name;
-                    debug!("calling command {}", &command_name);
-
-                    tokio::spawn(async move {
-                        let res = c(ctx, msg).await;
-                        if res.is_err() {
-                            warn!(
-                                "command {} returned error: {}",
-                                &command_name,
-                                res.unwrap_err().0
-                            )
-                        }
-                    });
+    /// Passes a message that doesn't call a command to the dialogue handlers,
+    /// or to the default handler if it is a text message that isn't part of a
+    /// dialogue
+    fn fire_fallback_handlers(&self, context: Context, message: Message) -> Option<JoinHandle<()>> {
+        if context.is_edit() {
+            return None;
+        }
+
+        let default_handler = self
+            .default_handler
+            .filter(|_| matches!(message.content, MessageContent::Text { .. }));
+
+        if self.dialogue_handlers.is_empty() {
+            return default_handler.map(|handler| {
+                debug!("calling the default message handler");
+                utils::spawn(handler(context, message))
+            });
+        }
+        Some(self.fire_dialogue_handlers(context, message, default_handler))
+    }
+
+    fn help_command(&self) -> HelpCommand {
+        HelpCommand {
+            commands: self.commands.iter().map(|c| c.options).collect(),
+            prefix: self.prefixes.first().cloned().unwrap_or_else(|| "/".to_owned()),
+            case_insensitive: self.case_insensitive,
+            owners: self.owners.clone(),
+            chat_member_cache: self.chat_member_cache.clone(),
+        }
+    }
+
+    /// Passes the message to the dialogue handler registered for the current
+    /// state of the dialogue of its sender, if there is one, otherwise to the
+    /// given default handler
+    fn fire_dialogue_handlers(
+        &self,
+        context: Context,
+        message: Message,
+        default_handler: Option<MessageHandlerFunc>,
+    ) -> JoinHandle<()> {
+        let storage = self.dialogue_storage.clone();
+        let handlers = self.dialogue_handlers.clone();
+
+        utils::spawn(async move {
+            let key = DialogueKey::from_message(&message);
+            let state = match storage.get_state(key).await {
+                Ok(Some(state)) => state,
+                Ok(None) => {
+                    if let Some(handler) = default_handler {
+                        debug!("calling the default message handler");
+                        handler(context, message).await;
+                    }
+                    return;
+                },
+                Err(err) => {
+                    warn!("couldn't get the state of dialogue {key:?}: {err}");
+                    return;
+                },
+            };
+
+            let Some(variant) = get_state_variant(&state) else {
+                warn!("the state of dialogue {key:?} isn't an enum variant");
+                return;
+            };
+
+            for (handler_variant, handler) in &handlers {
+                if *handler_variant != variant {
+                    continue;
+                }
+
+                let fut = handler(
+                    context.clone(),
+                    message.clone(),
+                    state.clone(),
+                    storage.clone(),
+                );
+                if let Some(fut) = fut {
+                    debug!("calling dialogue handler for state {variant}");
+                    fut.await;
+                    return;
+                }
+            }
+
+            debug!("no dialogue handler found for state {variant}");
+            if let Some(handler) = default_handler {
+                debug!("calling the default message handler");
+                handler(context, message).await;
+            }
+        })
+    }
+
+    /// Finds the (nested) subcommand called by the arguments of the command,
+    /// returning it with its arguments and the options of its parents and
+    /// itself, whose checks all need to pass. If no subcommand is called, the
+    /// command itself is returned.
+    fn resolve_subcommand<'a>(
+        &self,
+        command: &'a TelegramCommand,
+        tail: &str,
+    ) -> (&'a TelegramCommand, String, Vec<&'static CommandOptions>) {
+        let mut command = command;
+        let mut tail = tail.to_owned();
+        let mut options = vec![command.options];
+
+        loop {
+            let mut args = CommandArguments::new(&tail);
+            let subcommand = args.next().and_then(|called| {
+                command
+                    .options
+                    .subcommands
+                    .iter()
+                    .find(|sub| self.match_command(&called, sub.options))
+            });
+
+            match subcommand {
+                Some(sub) => {
+                    command = sub;
+                    args.rest().clone_into(&mut tail);
+                    options.push(command.options);
                 },
-                _ => (),
+                None => return (command, tail, options),
+            }
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn fire_callback_handlers(&self, context: Context, query: CallbackQuery) -> Vec<JoinHandle<()>> {
+        let prefix = match &query.data {
+            Some(data) => get_callback_data_prefix(data),
+            None => return Vec::new(),
+        };
+
+        let mut handles = Vec::new();
+        for (handler_prefix, handler) in &self.callback_handlers {
+            if *handler_prefix != prefix {
+                continue;
+            }
+
+            match handler(context.clone(), query.clone()) {
+                Some(fut) => handles.push(utils::spawn(fut)),
+                None => warn!(
+                    "couldn't decode callback data {:?} with prefix {}",
+                    &query.data, prefix
+                ),
             }
         }
+
+        handles
+    }
+
+    /// Finds the handler with the longest prefix matching the text
+    fn find_prefixed_handler<H: Copy>(
+        handlers: &[(&'static str, H)],
+        text: &str,
+    ) -> Option<(&'static str, H)> {
+        Self::find_longest_prefix(handlers, |prefix| text.starts_with(prefix))
+    }
+
+    /// Finds the handler with the longest prefix that the text starts with as
+    /// a whole word, so followed by whitespace or the end of the text. An
+    /// empty prefix matches any text.
+    fn find_word_prefixed_handler<H: Copy>(
+        handlers: &[(&'static str, H)],
+        text: &str,
+    ) -> Option<(&'static str, H)> {
+        Self::find_longest_prefix(handlers, |prefix| match text.strip_prefix(prefix) {
+            Some(rest) => {
+                prefix.is_empty() || rest.is_empty() || rest.starts_with(char::is_whitespace)
+            },
+            None => false,
+        })
+    }
+
+    fn find_longest_prefix<H: Copy>(
+        handlers: &[(&'static str, H)],
+        matches: impl Fn(&str) -> bool,
+    ) -> Option<(&'static str, H)> {
+        handlers
+            .iter()
+            .filter(|(prefix, _)| matches(prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .copied()
+    }
+
+    fn fire_inline_query_handlers(
+        &self,
+        context: Context,
+        mut query: InlineQuery,
+    ) -> Vec<JoinHandle<()>> {
+        let Some((prefix, handler)) =
+            Self::find_word_prefixed_handler(&self.inline_query_handlers, &query.query)
+        else {
+            debug!("no inline query handler found for {:?}", &query.query);
+            return Vec::new();
+        };
+
+        let rest = query.query[prefix.len()..].trim_start().len();
+        query.query.drain(..query.query.len() - rest);
+        vec![utils::spawn(handler(context, query))]
+    }
+
+    fn fire_chosen_inline_result_handlers(
+        &self,
+        context: Context,
+        result: ChosenInlineResult,
+    ) -> Vec<JoinHandle<()>> {
+        let Some((_, handler)) =
+            Self::find_prefixed_handler(&self.chosen_inline_result_handlers, &result.result_id)
+        else {
+            debug!("no chosen inline result handler found for {}", &result.result_id);
+            return Vec::new();
+        };
+
+        vec![utils::spawn(handler(context, result))]
+    }
+
+    fn fire_shipping_query_handlers(
+        &self,
+        context: Context,
+        query: ShippingQuery,
+    ) -> Vec<JoinHandle<()>> {
+        let Some((_, handler)) =
+            Self::find_prefixed_handler(&self.shipping_query_handlers, &query.invoice_payload)
+        else {
+            debug!("no shipping query handler found for {}", &query.invoice_payload);
+            return Vec::new();
+        };
+
+        vec![utils::spawn(handler(context, query))]
+    }
+
+    fn fire_pre_checkout_query_handlers(
+        &self,
+        context: Context,
+        query: PreCheckoutQuery,
+    ) -> Vec<JoinHandle<()>> {
+        let Some((_, handler)) =
+            Self::find_prefixed_handler(&self.pre_checkout_query_handlers, &query.invoice_payload)
+        else {
+            debug!("no pre-checkout query handler found for {}", &query.invoice_payload);
+            return Vec::new();
+        };
+
+        vec![utils::spawn(handler(context, query))]
     }
 
     /// add a command to the registered commands
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name or an alias of the command collides with the name
+    /// or an alias of an already added command, if those of its subcommands
+    /// collide with each other, or if an alias isn't 1-32 lowercase latin
+    /// letters, digits or underscores
     pub fn add_command(&mut self, command: &TelegramCommand) {
-        self.commands.push(command.clone())
+        self.commands.push(command.clone());
+        let commands: Vec<_> = self.commands.iter().map(|c| c.options).collect();
+        self.check_name_collisions(&commands);
     }
 
     /// get all registered commands
@@ -75,10 +1005,134 @@ impl Framework {
         &self.commands
     }
 
-    /// fires off all commands matching the content in the update
-    pub fn fire_commands(&self, context: Context, update: Update) {
-        if let UpdateContent::Message(c) = update.content {
-            self.fire_message_commands(context, c);
+    /// Gets the commands as they are registered with telegram by
+    /// [`register_commands_with_telegram`], without registering them. Hidden
+    /// commands are left out, and the help command is added when enabled.
+    ///
+    /// Returns an error if a command doesn't meet the requirements of
+    /// telegram: names of 1-32 lowercase latin letters, digits and
+    /// underscores, descriptions of 1-256 characters and at most 100
+    /// commands.
+    ///
+    /// [`register_commands_with_telegram`]: #method.register_commands_with_telegram
+    pub fn get_bot_commands(&self) -> Result<Vec<BotCommand>> {
+        let mut commands: Vec<_> = self
+            .commands
+            .iter()
+            .filter(|c| !c.options.hidden)
+            .map(TelegramCommand::get_bot_command)
+            .collect();
+        if self.help && !commands.iter().any(|c| c.command == HELP_COMMAND_NAME) {
+            commands.push(BotCommand {
+                command: HELP_COMMAND_NAME.to_owned(),
+                description: HELP_COMMAND_DESCRIPTION.to_owned(),
+            });
+        }
+
+        if commands.len() > MAX_BOT_COMMANDS {
+            return Err(TelegramError::InvalidArgument(format!(
+                "telegram accepts at most {MAX_BOT_COMMANDS} commands, but there are {}",
+                commands.len()
+            ))
+            .into());
         }
+        for command in &commands {
+            validate_bot_command(command)?;
+        }
+        Ok(commands)
+    }
+
+    /// Registers the commands with telegram using [`API::set_my_commands`], so
+    /// that they are suggested to users, returning the registered commands.
+    /// See [`get_bot_commands`] for which commands are registered.
+    ///
+    /// This is done automatically when starting the [`Client`], unless
+    /// disabled using [`ClientBuilder::set_register_commands`].
+    ///
+    /// [`API::set_my_commands`]: ../api/trait.API.html#method.set_my_commands
+    /// [`get_bot_commands`]: #method.get_bot_commands
+    /// [`Client`]: ../client/struct.Client.html
+    /// [`ClientBuilder::set_register_commands`]: ../client/struct.ClientBuilder.html#method.set_register_commands
+    pub async fn register_commands_with_telegram<A>(&self, api: &A) -> Result<Vec<BotCommand>>
+    where
+        A: API + ?Sized,
+    {
+        let commands = self.get_bot_commands()?;
+        api.set_my_commands(SetMyCommands::from(commands.clone())).await?;
+        Ok(commands)
+    }
+
+    /// fires off all commands and callback handlers matching the content in
+    /// the update
+    pub fn fire_commands(&self, context: Context, update: Update) {
+        self.fire_update(context, update);
+    }
+
+    /// fires off all commands and callback handlers matching the content in
+    /// the update, returning the handles of the spawned tasks
+    pub(crate) fn fire_update(&self, context: Context, update: Update) -> Vec<JoinHandle<()>> {
+        let context = context.with_dialogue_storage(self.dialogue_storage.clone());
+        let (kind, message) = match update.content {
+            UpdateContent::Message(m) => (MessageKind::Message, m),
+            UpdateContent::EditedMessage(m) if self.handle_edits => (MessageKind::EditedMessage, m),
+            UpdateContent::ChannelPost(m) if self.handle_channel_posts => {
+                (MessageKind::ChannelPost, m)
+            },
+            UpdateContent::EditedChannelPost(m)
+                if self.handle_edits && self.handle_channel_posts =>
+            {
+                (MessageKind::EditedChannelPost, m)
+            },
+            UpdateContent::CallbackQuery(q) => return self.fire_callback_handlers(context, q),
+            UpdateContent::InlineQuery(q) => return self.fire_inline_query_handlers(context, q),
+            UpdateContent::ChosenInlineResult(r) => {
+                return self.fire_chosen_inline_result_handlers(context, r)
+            },
+            UpdateContent::ShippingQuery(q) => return self.fire_shipping_query_handlers(context, q),
+            UpdateContent::PreCheckoutQuery(q) => {
+                return self.fire_pre_checkout_query_handlers(context, q)
+            },
+            _ => return Vec::new(),
+        };
+
+        let context = context.with_message_kind(kind);
+        let mut handles: Vec<_> = self
+            .message_handlers
+            .iter()
+            .filter(|(filter, _)| filter.matches(&message))
+            .map(|(_, handler)| utils::spawn(handler(context.clone(), message.clone())))
+            .collect();
+        handles.append(&mut self.fire_message_commands(context, message));
+        handles
+    }
+}
+
+/// Whether the name meets the requirements of telegram for command names,
+/// being 1-32 lowercase latin letters, digits and underscores
+fn is_valid_command_name(name: &str) -> bool {
+    (1..=32).contains(&name.len())
+        && name
+            .bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'_')
+}
+
+/// Checks that the command meets the requirements of telegram
+fn validate_bot_command(command: &BotCommand) -> Result<()> {
+    if !is_valid_command_name(&command.command) {
+        return Err(TelegramError::InvalidArgument(format!(
+            "the name of the {:?} command has to be 1-32 lowercase latin letters, digits or \
+             underscores",
+            command.command
+        ))
+        .into());
+    }
+
+    if !(1..=256).contains(&command.description.chars().count()) {
+        return Err(TelegramError::InvalidArgument(format!(
+            "the description of the {:?} command has to be 1-256 characters",
+            command.command
+        ))
+        .into());
     }
+    Ok(())
 }
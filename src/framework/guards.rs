@@ -0,0 +1,211 @@
+use super::types::{CheckResult, CommandOptions};
+use crate::{
+    api::types::GetChatMember,
+    client::Context,
+    model::{Chat, ChatMember, Message},
+};
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// The kind of chats a command can be used in, set using the
+/// `#[only_in(private)]` or `#[only_in(groups)]` attribute on the command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnlyIn {
+    /// The command can only be used in private chats with the bot
+    Private,
+    /// The command can only be used in groups and supergroups
+    Groups,
+}
+
+impl OnlyIn {
+    /// Whether a command limited to this kind of chats can be used in the
+    /// given chat
+    pub fn allows(self, chat: &Chat) -> bool {
+        match self {
+            OnlyIn::Private => matches!(chat, Chat::Private(_)),
+            OnlyIn::Groups => matches!(chat, Chat::Group(_) | Chat::SuperGroup(_)),
+        }
+    }
+}
+
+/// The permissions the user calling a command needs to have in the chat, set
+/// using the `#[required_permissions(...)]` attribute on the command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequiredPermissions {
+    /// The user has to be an administrator or the creator of the chat
+    Admin,
+    /// The user has to be the creator of the chat
+    Creator,
+}
+
+impl RequiredPermissions {
+    /// Whether the given member of a chat has these permissions
+    pub fn allows(self, member: &ChatMember) -> bool {
+        match self {
+            RequiredPermissions::Admin => matches!(
+                member,
+                ChatMember::Creator(_) | ChatMember::Administrator(_)
+            ),
+            RequiredPermissions::Creator => matches!(member, ChatMember::Creator(_)),
+        }
+    }
+}
+
+/// A cache of the results of `getChatMember`, used for checking the
+/// [`RequiredPermissions`] of commands without calling the API for every
+/// command
+#[derive(Debug)]
+pub(crate) struct ChatMemberCache {
+    duration: Option<Duration>,
+    members: Mutex<HashMap<(i64, i64), (ChatMember, Instant)>>,
+    last_cleanup: Mutex<Instant>,
+}
+
+impl ChatMemberCache {
+    pub(crate) fn new(duration: Option<Duration>) -> Self {
+        Self {
+            duration,
+            members: Mutex::new(HashMap::new()),
+            last_cleanup: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Caches the member, removing the expired members first once per cache
+    /// duration, so members that aren't looked up again don't stay cached
+    fn insert(&self, chat_id: i64, user_id: i64, member: ChatMember, duration: Duration) {
+        let now = Instant::now();
+        let mut members = self.members.lock();
+        let mut last_cleanup = self.last_cleanup.lock();
+        if now.duration_since(*last_cleanup) >= duration {
+            *last_cleanup = now;
+            members.retain(|_, (_, fetched)| now.duration_since(*fetched) < duration);
+        }
+        members.insert((chat_id, user_id), (member, now));
+    }
+
+    async fn get_chat_member(
+        &self,
+        ctx: &Context,
+        chat_id: i64,
+        user_id: i64,
+    ) -> crate::Result<ChatMember> {
+        if let Some(duration) = self.duration {
+            let mut members = self.members.lock();
+            match members.get(&(chat_id, user_id)) {
+                Some((member, fetched)) if fetched.elapsed() < duration => {
+                    return Ok(member.clone());
+                },
+                Some(_) => {
+                    members.remove(&(chat_id, user_id));
+                },
+                None => (),
+            }
+        }
+
+        let member = ctx
+            .api
+            .get_chat_member(GetChatMember {
+                chat_id,
+                user_id,
+            })
+            .await?;
+
+        if let Some(duration) = self.duration {
+            self.insert(chat_id, user_id, member.clone(), duration);
+        }
+
+        Ok(member)
+    }
+}
+
+/// Runs the built-in guards of a command followed by its checks, returning the
+/// first failure
+pub(crate) async fn run_checks(
+    options: &CommandOptions,
+    ctx: &Context,
+    message: &Message,
+    owners: &[i64],
+    cache: &ChatMemberCache,
+) -> CheckResult {
+    let mut res = check_guards(options, ctx, message, owners, cache).await;
+    for check in options.checks {
+        if !res.is_success() {
+            break;
+        }
+        res = check(ctx.clone(), message.clone()).await;
+    }
+    res
+}
+
+/// Runs the built-in guards of a command, being `#[owner_only]`,
+/// `#[only_in(...)]` and `#[required_permissions(...)]`
+async fn check_guards(
+    options: &CommandOptions,
+    ctx: &Context,
+    message: &Message,
+    owners: &[i64],
+    cache: &ChatMemberCache,
+) -> CheckResult {
+    if options.owner_only && !message.from.as_ref().is_some_and(|u| owners.contains(&u.id)) {
+        return CheckResult::failure("this command can only be used by the owners of the bot");
+    }
+
+    if let Some(only_in) = options.only_in {
+        if !only_in.allows(&message.chat) {
+            return CheckResult::failure(match only_in {
+                OnlyIn::Private => "this command can only be used in private chats",
+                OnlyIn::Groups => "this command can only be used in groups",
+            });
+        }
+    }
+
+    if let Some(permissions) = options.required_permissions {
+        return check_permissions(permissions, ctx, message, cache).await;
+    }
+
+    CheckResult::Success
+}
+
+async fn check_permissions(
+    permissions: RequiredPermissions,
+    ctx: &Context,
+    message: &Message,
+    cache: &ChatMemberCache,
+) -> CheckResult {
+    let denied = || match permissions {
+        RequiredPermissions::Admin => {
+            CheckResult::failure("this command can only be used by admins of the chat")
+        },
+        RequiredPermissions::Creator => {
+            CheckResult::failure("this command can only be used by the creator of the chat")
+        },
+    };
+
+    let chat_id = message.chat.get_id();
+    if let Chat::Private(_) = message.chat {
+        return denied();
+    }
+
+    // anonymous admins send messages on behalf of the chat itself, and
+    // channel posts are always made by admins
+    if message.sender_chat.as_ref().map(Chat::get_id) == Some(chat_id) {
+        return match permissions {
+            RequiredPermissions::Admin => CheckResult::Success,
+            RequiredPermissions::Creator => denied(),
+        };
+    }
+
+    let user_id = match &message.from {
+        Some(user) => user.id,
+        None => return denied(),
+    };
+
+    match cache.get_chat_member(ctx, chat_id, user_id).await {
+        Ok(member) if permissions.allows(&member) => CheckResult::Success,
+        Ok(_) => denied(),
+        Err(err) => CheckResult::failure(&format!("couldn't get the chat member: {err}")),
+    }
+}
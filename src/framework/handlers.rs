@@ -1,6 +1,104 @@
-use super::types::CommandResult;
-use crate::{client::Context, model::Message};
-use std::{future::Future, pin::Pin};
+use super::{
+    dialogue::{Dialogue, DialogueStorage},
+    types::{CheckResult, CommandResult, CommandStatus},
+};
+use crate::{
+    client::{Context, FutureOutcome},
+    model::{
+        CallbackQuery,
+        ChosenInlineResult,
+        InlineQuery,
+        Message,
+        PreCheckoutQuery,
+        ShippingQuery,
+    },
+};
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
 
 pub(crate) type CommandOutcome = Pin<Box<dyn Future<Output = CommandResult> + Send>>;
 pub(crate) type CommandHandlerFunc = fn(Context, Message) -> CommandOutcome;
+
+pub(crate) type CheckOutcome = Pin<Box<dyn Future<Output = CheckResult> + Send>>;
+
+/// A function that checks whether a command is allowed to be called, it
+/// receives a [`Context`] and the [`Message`] calling the command and returns
+/// a pinned future resolving to a [`CheckResult`]. Wrap an async function with
+/// `#[prepare_check]` for easier development.
+pub type CheckFunc = fn(Context, Message) -> CheckOutcome;
+
+/// A function that is called when a check of a command failed, it receives a
+/// [`Context`], the [`Message`] calling the command and the reason the check
+/// failed, if one was given. Wrap an async function with `#[prepare_listener]`
+/// for easier development.
+pub type CheckFailureHandlerFunc = fn(Context, Message, Option<String>) -> FutureOutcome;
+
+/// A function that is called when a command is used while it is on cooldown,
+/// it receives a [`Context`], the [`Message`] calling the command and the
+/// remaining duration of the cooldown. Wrap an async function with
+/// `#[prepare_listener]` for easier development.
+pub type CooldownHandlerFunc = fn(Context, Message, Duration) -> FutureOutcome;
+
+pub(crate) type BeforeHookOutcome = Pin<Box<dyn Future<Output = bool> + Send>>;
+
+/// A function that is called before a command is run, it receives a
+/// [`Context`], the [`Message`] calling the command and the name of the
+/// command, and returns whether the command may be run. Wrap an async function
+/// returning a `bool` with `#[prepare_listener]` for easier development.
+pub type BeforeHookFunc = fn(Context, Message, &'static str) -> BeforeHookOutcome;
+
+/// A function that is called after a message called a command, it receives a
+/// [`Context`], the [`Message`] calling the command, the name of the command
+/// and a [`CommandStatus`] with its result or the reason it wasn't run. Wrap an
+/// async function with `#[prepare_listener]` for easier development.
+pub type AfterHookFunc = fn(Context, Message, &'static str, CommandStatus) -> FutureOutcome;
+
+/// A function that handles a message the framework didn't call a command
+/// for, it receives a [`Context`] and the [`Message`]. Used for the unknown
+/// command handler and the default message handler. Wrap an async function
+/// with `#[prepare_listener]` for easier development.
+pub type MessageHandlerFunc = fn(Context, Message) -> FutureOutcome;
+
+/// A function that handles a callback query with data of the type `T`, it
+/// receives a [`Context`], the [`CallbackQuery`] and its decoded data. Wrap an
+/// async function with `#[prepare_listener]` for easier development.
+pub type CallbackHandlerFunc<T> = fn(Context, CallbackQuery, T) -> FutureOutcome;
+
+/// A function that handles an inline query, it receives a [`Context`] and the
+/// [`InlineQuery`] with the prefix it was matched by removed from its query.
+/// Wrap an async function with `#[prepare_listener]` for easier development.
+pub type InlineQueryHandlerFunc = fn(Context, InlineQuery) -> FutureOutcome;
+
+/// A function that handles an inline result chosen by a user, it receives a
+/// [`Context`] and the [`ChosenInlineResult`]. Wrap an async function with
+/// `#[prepare_listener]` for easier development.
+pub type ChosenInlineResultHandlerFunc = fn(Context, ChosenInlineResult) -> FutureOutcome;
+
+/// A function that handles a shipping query, it receives a [`Context`] and the
+/// [`ShippingQuery`], which it should answer using [`Context::answer_shipping`].
+/// Wrap an async function with `#[prepare_listener]` for easier development.
+///
+/// [`Context::answer_shipping`]: ../client/struct.Context.html#method.answer_shipping
+pub type ShippingQueryHandlerFunc = fn(Context, ShippingQuery) -> FutureOutcome;
+
+/// A function that handles a pre-checkout query, it receives a [`Context`] and
+/// the [`PreCheckoutQuery`], which it should answer within 10 seconds using
+/// [`Context::answer_pre_checkout`]. Wrap an async function with
+/// `#[prepare_listener]` for easier development.
+///
+/// [`Context::answer_pre_checkout`]: ../client/struct.Context.html#method.answer_pre_checkout
+pub type PreCheckoutQueryHandlerFunc = fn(Context, PreCheckoutQuery) -> FutureOutcome;
+
+pub(crate) type ErasedCallbackHandler =
+    Box<dyn Fn(Context, CallbackQuery) -> Option<FutureOutcome> + Send + Sync>;
+
+/// A function that handles a message that is part of a dialogue with a state
+/// of the type `S`, it receives a [`Context`], the [`Message`], a handle to
+/// the [`Dialogue`] and its current state. Wrap an async function with
+/// `#[prepare_listener]` for easier development.
+pub type DialogueHandlerFunc<S> = fn(Context, Message, Dialogue<S>, S) -> FutureOutcome;
+
+pub(crate) type ErasedDialogueHandler = Arc<
+    dyn Fn(Context, Message, serde_json::Value, Arc<dyn DialogueStorage>) -> Option<FutureOutcome>
+        + Send
+        + Sync,
+>;
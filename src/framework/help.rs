@@ -0,0 +1,173 @@
+use super::{
+    arguments::CommandArguments,
+    guards::{run_checks, ChatMemberCache},
+    types::CommandOptions,
+};
+use crate::{client::Context, model::Message, utils::FormattedTextBuilder};
+use log::warn;
+use std::sync::Arc;
+
+/// The name the help command is called with
+pub(crate) const HELP_COMMAND_NAME: &str = "help";
+
+/// The built-in help command, listing the commands of the framework that the
+/// user calling it is allowed to use
+pub(crate) struct HelpCommand {
+    pub(crate) commands: Vec<&'static CommandOptions>,
+    pub(crate) prefix: String,
+    pub(crate) case_insensitive: bool,
+    pub(crate) owners: Arc<Vec<i64>>,
+    pub(crate) chat_member_cache: Arc<ChatMemberCache>,
+}
+
+impl HelpCommand {
+    /// Replies to the message with the list of commands, or with the details
+    /// of the command given in the arguments
+    pub(crate) async fn reply(self, ctx: Context, message: Message, args: String) {
+        let mut args = CommandArguments::new(&args);
+        let commands = self.allowed(&ctx, &message, &self.commands).await;
+
+        let text = match args.next() {
+            None => {
+                let listed: Vec<_> = commands.into_iter().filter(|c| !c.hidden).collect();
+                self.render_list(&listed)
+            },
+            Some(called) => {
+                let mut path = vec![called];
+                path.extend(args);
+                self.render_details(&ctx, &message, &commands, &path).await
+            },
+        };
+
+        if let Err(err) = ctx.reply_formatted(&message, &text).await {
+            warn!("couldn't send the help message: {err}");
+        }
+    }
+
+    /// Filters the commands down to the ones whose checks pass for the message
+    async fn allowed(
+        &self,
+        ctx: &Context,
+        message: &Message,
+        commands: &[&'static CommandOptions],
+    ) -> Vec<&'static CommandOptions> {
+        let mut allowed = Vec::new();
+        for options in commands {
+            let res =
+                run_checks(options, ctx, message, &self.owners, &self.chat_member_cache).await;
+            if res.is_success() {
+                allowed.push(*options);
+            }
+        }
+        allowed
+    }
+
+    fn find(
+        &self,
+        commands: &[&'static CommandOptions],
+        called: &str,
+    ) -> Option<&'static CommandOptions> {
+        commands.iter().copied().find(|options| {
+            options.names().any(|name| {
+                if self.case_insensitive {
+                    name.to_lowercase() == called.to_lowercase()
+                } else {
+                    name == called
+                }
+            })
+        })
+    }
+
+    fn render_list(&self, commands: &[&'static CommandOptions]) -> FormattedTextBuilder {
+        let mut text = FormattedTextBuilder::new();
+        if commands.is_empty() {
+            text.text("There are no commands available");
+            return text;
+        }
+
+        let mut categories: Vec<Option<&str>> = Vec::new();
+        for options in commands {
+            if !categories.contains(&options.category) {
+                categories.push(options.category);
+            }
+        }
+        // commands without a category are listed first
+        categories.sort_by_key(Option::is_some);
+
+        for (i, category) in categories.into_iter().enumerate() {
+            if i > 0 {
+                text.text("\n\n");
+            }
+            text.bold(category.unwrap_or("Commands"));
+
+            for options in commands.iter().filter(|o| o.category == category) {
+                text.text("\n")
+                    .text(&format!("{}{}", self.prefix, options.name))
+                    .text(" - ")
+                    .text(options.description);
+            }
+        }
+
+        text.text("\n\nUse ")
+            .code(&format!("{}{} <command>", self.prefix, HELP_COMMAND_NAME))
+            .text(" for more information about a command");
+        text
+    }
+
+    async fn render_details(
+        &self,
+        ctx: &Context,
+        message: &Message,
+        commands: &[&'static CommandOptions],
+        path: &[String],
+    ) -> FormattedTextBuilder {
+        let mut text = FormattedTextBuilder::new();
+        let Some(mut options) = self.find(commands, &path[0]) else {
+            text.text("There is no command named ").code(&path[0]);
+            return text;
+        };
+        let mut names = vec![options.name];
+
+        for called in &path[1..] {
+            let subcommands: Vec<_> = options.subcommands.iter().map(|s| s.options).collect();
+            let subcommands = self.allowed(ctx, message, &subcommands).await;
+            let Some(sub) = self.find(&subcommands, called) else {
+                break;
+            };
+            options = sub;
+            names.push(sub.name);
+        }
+
+        let subcommands: Vec<_> = options.subcommands.iter().map(|s| s.options).collect();
+        let subcommands = self.allowed(ctx, message, &subcommands).await;
+
+        let name = format!("{}{}", self.prefix, names.join(" "));
+        text.bold(&name).text("\n").text(options.description);
+        if !options.aliases.is_empty()
+            || options.usage.is_some()
+            || options.category.is_some()
+            || !subcommands.is_empty()
+        {
+            text.text("\n");
+        }
+
+        if !options.aliases.is_empty() {
+            text.text("\nAliases: ").text(&options.aliases.join(", "));
+        }
+
+        if let Some(usage) = options.usage {
+            text.text("\nUsage: ").code(&format!("{} {}", name, usage()));
+        }
+
+        if let Some(category) = options.category {
+            text.text("\nCategory: ").text(category);
+        }
+
+        if !subcommands.is_empty() {
+            let names: Vec<_> = subcommands.iter().map(|s| s.name).collect();
+            text.text("\nSubcommands: ").text(&names.join(", "));
+        }
+
+        text
+    }
+}
@@ -1,5 +1,11 @@
 //! The framework provides a customizable way to manage your bots commands
 
+mod arguments;
+mod cooldown;
+mod dialogue;
+mod filters;
+mod guards;
+mod help;
 pub(crate) mod framework;
 
 // made public for the procedural macros to use
@@ -8,5 +14,41 @@ pub mod handlers;
 #[doc(hidden)]
 pub mod types;
 
+pub use arguments::{ArgumentError, CommandArguments, FromCommandArguments};
+#[doc(hidden)]
+pub use arguments::reply_argument_error;
+pub use cooldown::{
+    Clock,
+    Cooldown,
+    CooldownKey,
+    CooldownScope,
+    CooldownStorage,
+    InMemoryCooldownStorage,
+    SystemClock,
+};
+pub use dialogue::{
+    Dialogue,
+    DialogueKey,
+    DialogueStorage,
+    ExpiringDialogueStorage,
+    InMemoryDialogueStorage,
+    JsonFileDialogueStorage,
+};
+pub use filters::Filter;
 pub use framework::Framework;
-pub use types::{CommandError, CommandResult};
+pub use guards::{OnlyIn, RequiredPermissions};
+pub use handlers::{
+    AfterHookFunc,
+    BeforeHookFunc,
+    CallbackHandlerFunc,
+    CheckFailureHandlerFunc,
+    CheckFunc,
+    ChosenInlineResultHandlerFunc,
+    CooldownHandlerFunc,
+    DialogueHandlerFunc,
+    InlineQueryHandlerFunc,
+    MessageHandlerFunc,
+    PreCheckoutQueryHandlerFunc,
+    ShippingQueryHandlerFunc,
+};
+pub use types::{CheckResult, CommandError, CommandResult, CommandStatus};
@@ -1,5 +1,10 @@
-use super::handlers::CommandHandlerFunc;
+use super::{
+    cooldown::Cooldown,
+    guards::{OnlyIn, RequiredPermissions},
+    handlers::{CheckFunc, CommandHandlerFunc},
+};
 use crate::{model::BotCommand, utils::result::Error};
+use std::time::Duration;
 
 #[derive(Clone)]
 pub enum CommandTypes {
@@ -9,7 +14,24 @@ pub enum CommandTypes {
 #[derive(Clone)]
 pub struct CommandOptions {
     pub name: &'static str,
+    pub aliases: &'static [&'static str],
     pub description: &'static str,
+    pub category: Option<&'static str>,
+    pub hidden: bool,
+    pub usage: Option<fn() -> String>,
+    pub checks: &'static [CheckFunc],
+    pub owner_only: bool,
+    pub only_in: Option<OnlyIn>,
+    pub required_permissions: Option<RequiredPermissions>,
+    pub cooldowns: &'static [Cooldown],
+    pub subcommands: &'static [&'static TelegramCommand],
+}
+
+impl CommandOptions {
+    /// Gets the name of the command followed by its aliases
+    pub fn names(&self) -> impl Iterator<Item = &'static str> {
+        std::iter::once(self.name).chain(self.aliases.iter().copied())
+    }
 }
 
 #[derive(Clone)]
@@ -51,3 +73,75 @@ impl From<CommandError> for Error {
         Error::Command(d)
     }
 }
+
+/// What happened when a message called a command, passed to the after hook
+/// of the framework.
+///
+/// See [`Framework::set_after_hook`] for the order in which the hooks, checks
+/// and cooldowns are run.
+///
+/// [`Framework::set_after_hook`]: struct.Framework.html#method.set_after_hook
+#[derive(Debug, Clone)]
+pub enum CommandStatus {
+    /// The command was called, with the result it returned
+    Completed(CommandResult),
+    /// The before hook returned false, so the command wasn't called
+    Vetoed,
+    /// One of the checks of the command failed, optionally with a reason why
+    CheckFailed(Option<String>),
+    /// The command is on cooldown for the given remaining duration
+    OnCooldown(Duration),
+}
+
+impl CommandStatus {
+    /// Returns whether the command was called and returned `Ok`
+    pub fn is_success(&self) -> bool {
+        matches!(self, CommandStatus::Completed(Ok(())))
+    }
+}
+
+/// The result of a [`CheckFunc`], deciding whether a command is allowed to be
+/// called.
+///
+/// It can be formed from a `bool`, or from a `Result<bool, E>` where the error
+/// is used as the reason for the failure
+///
+/// [`CheckFunc`]: type.CheckFunc.html
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckResult {
+    /// The check passed and the command can be called
+    Success,
+    /// The check failed, optionally with a reason why
+    Failure(Option<String>),
+}
+
+impl CheckResult {
+    /// Creates a failed check result with the given reason
+    pub fn failure<T: ToString + ?Sized>(reason: &T) -> Self {
+        CheckResult::Failure(Some(reason.to_string()))
+    }
+
+    /// Returns whether the check passed
+    pub fn is_success(&self) -> bool {
+        *self == CheckResult::Success
+    }
+}
+
+impl From<bool> for CheckResult {
+    fn from(passed: bool) -> Self {
+        if passed {
+            CheckResult::Success
+        } else {
+            CheckResult::Failure(None)
+        }
+    }
+}
+
+impl<E: std::fmt::Display> From<::std::result::Result<bool, E>> for CheckResult {
+    fn from(res: ::std::result::Result<bool, E>) -> Self {
+        match res {
+            Ok(passed) => passed.into(),
+            Err(e) => CheckResult::failure(&e),
+        }
+    }
+}
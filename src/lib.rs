@@ -37,16 +37,25 @@
 //! telexide = "0.1"
 //! ```
 //!
+//! # Features
+//!
+//! - `passport-decrypt`: decrypting telegram passport data using openssl
+//! - `proxy`: sending the api requests through an http or SOCKS5 [`Proxy`]
+//! - `tracing`: [tracing] spans for every handled update, with its `update_id`
+//!   and `chat_id`, and for every API request, with the telegram method name
+//!
 //! [examples]: https://github.com/callieve/telexide/tree/master/examples
 //! [Github Repository]: https://github.com/callieve/telexide
 //! [crates.io]: https://crates.io/crates/telexide
 //! [tg docs]: https://core.telegram.org/bots/api
+//! [tracing]: https://docs.rs/tracing
 //! [client]: client/index.html
 //! [`ClientBuilder`]: client/struct.ClientBuilder.html
 //! [`Client`]: client/struct.Client.html
 //! [`Client::start`]: client/struct.Client.html#method.start
 //! [`APIClient`]: api/struct.APIClient.html
 //! [`API`]: api/trait.API.html
+//! [`Proxy`]: api/struct.Proxy.html
 //! [api]: api/index.html
 //! [tg_commands]: https://core.telegram.org/bots#commands
 //! [`Framework`]: framework/struct.Framework.html
@@ -67,12 +76,12 @@ pub mod api;
 pub mod client;
 pub mod framework;
 pub mod model;
-mod utils;
+pub mod utils;
 
 /// Macros for using the framework and helping with adding listeners
 pub mod macros {
     pub use super::create_framework;
-    pub use telexide_proc_macros::{command, prepare_listener};
+    pub use telexide_proc_macros::{command, prepare_check, prepare_listener, FromCommandArguments};
 }
 
 pub use client::Client;
@@ -99,11 +108,11 @@ pub mod prelude {
     pub use super::{
         client::{Client, ClientBuilder, Context},
         create_framework,
-        framework::CommandResult,
+        framework::{CommandArguments, CommandResult},
         model::{Message, Update},
         Error as TelexideError,
     };
-    pub use telexide_proc_macros::{command, prepare_listener};
+    pub use telexide_proc_macros::{command, prepare_check, prepare_listener, FromCommandArguments};
 }
 
 #[doc(hidden)]
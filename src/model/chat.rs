@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     raw::RawChat,
-    utils::unix_date_formatting,
+    utils::{unix_date_formatting, unknown::deserialize_member_status},
     User,
 };
 
@@ -16,16 +16,39 @@ pub struct PrivateChat {
     pub username: Option<String>,
     /// First name of the other party
     pub first_name: Option<String>,
-    /// Bio of the other party in a private chat. Returned only in [`get_chat`].
+    /// Bio of the other party in a private chat. Only set when converted from a
+    /// [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub bio: Option<String>,
     /// Last name of the other party
     pub last_name: Option<String>,
-    /// Chat photo. Returned only in [`get_chat`].
+    /// Chat photo. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub photo: Option<ChatPhoto>,
+    /// True, if privacy settings of the other party in the private chat allows
+    /// to use `tg://user?id=<user_id>` links only in chats with the user. Only
+    /// set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub has_private_forwards: Option<bool>,
+    /// True, if the privacy settings of the other party restrict sending voice
+    /// and video note messages in the private chat. Only set when converted
+    /// from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub has_restricted_voice_and_video_messages: Option<bool>,
+    /// The time after which all messages sent to the chat will be automatically
+    /// deleted; in seconds. Only set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub message_auto_delete_time: Option<i64>,
+    /// All active usernames of the chat. Only set when converted from a
+    /// [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub active_usernames: Option<Vec<String>>,
 }
 
 /// A Group chat object
@@ -34,24 +57,35 @@ pub struct GroupChat {
     pub id: i64,
     /// Title
     pub title: String,
-    /// Chat photo. Returned only in [`get_chat`].
+    /// Chat photo. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub photo: Option<ChatPhoto>,
-    /// Description. Returned only in [`get_chat`].
+    /// Description. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub description: Option<String>,
     /// Chat invite link
     pub invite_link: Option<String>,
-    /// Pinned message. Returned only in [`get_chat`].
+    /// Pinned message. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub pinned_message: Option<Box<super::Message>>,
-    /// Default chat member permissions. Returned only in [`get_chat`].
+    /// Default chat member permissions. Only set when converted from a
+    /// [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub permissions: Option<super::ChatPermissions>,
+    /// True, if messages from the chat can't be forwarded to other chats. Only
+    /// set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub has_protected_content: Option<bool>,
+    /// The time after which all messages sent to the chat will be automatically
+    /// deleted; in seconds. Only set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub message_auto_delete_time: Option<i64>,
 }
 
 /// A supergroup object (a group with more than 200 members)
@@ -62,49 +96,79 @@ pub struct SuperGroupChat {
     pub title: String,
     /// Username if available
     pub username: Option<String>,
-    /// Chat photo. Returned only in [`get_chat`].
+    /// True, if the supergroup is a forum (has topics enabled)
+    pub is_forum: Option<bool>,
+    /// Chat photo. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub photo: Option<ChatPhoto>,
-    /// Description. Returned only in [`get_chat`].
+    /// Description. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub description: Option<String>,
     /// Chat invite link
     pub invite_link: Option<String>,
-    /// Pinned message. Returned only in [`get_chat`].
+    /// Pinned message. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub pinned_message: Option<Box<super::Message>>,
-    /// Default chat member permissions. Returned only in [`get_chat`].
+    /// Default chat member permissions. Only set when converted from a
+    /// [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub permissions: Option<super::ChatPermissions>,
     /// The minimum allowed delay between consecutive messages sent by each
-    /// unprivileged user. Returned only in [`get_chat`].
+    /// unprivileged user. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub slow_mode_delay: Option<usize>,
-    /// Name of group sticker set. Returned only in [`get_chat`].
+    /// Name of group sticker set. Only set when converted from a
+    /// [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub sticker_set_name: Option<String>,
-    /// True, if the bot can change the group sticker set. Returned only in
-    /// [`get_chat`].
+    /// True, if the bot can change the group sticker set. Only set when
+    /// converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub can_set_sticker_set: Option<bool>,
     /// Unique identifier for the linked chat, i.e. the discussion group
     /// identifier for a channel and vice versa; for supergroups and channel
-    /// chats. Returned only in [`get_chat`].
+    /// chats. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub linked_chat_id: Option<i64>,
-    /// For supergroups, the location to which the supergroup is connected.
-    /// Returned only in [`get_chat`].
+    /// For supergroups, the location to which the supergroup is connected. Only
+    /// set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub location: Option<ChatLocation>,
+    /// True, if users need to join the supergroup before they can send
+    /// messages. Only set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub join_to_send_messages: Option<bool>,
+    /// True, if all users directly joining the supergroup need to be approved
+    /// by supergroup administrators. Only set when converted from a
+    /// [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub join_by_request: Option<bool>,
+    /// True, if messages from the chat can't be forwarded to other chats. Only
+    /// set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub has_protected_content: Option<bool>,
+    /// The time after which all messages sent to the chat will be automatically
+    /// deleted; in seconds. Only set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub message_auto_delete_time: Option<i64>,
+    /// All active usernames of the chat. Only set when converted from a
+    /// [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub active_usernames: Option<Vec<String>>,
 }
 
 /// A Channel object
@@ -115,26 +179,41 @@ pub struct ChannelChat {
     pub title: String,
     /// Username if available
     pub username: Option<String>,
-    /// Chat photo. Returned only in [`get_chat`].
+    /// Chat photo. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub photo: Option<ChatPhoto>,
-    /// Description. Returned only in [`get_chat`].
+    /// Description. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub description: Option<String>,
     /// Chat invite link
     pub invite_link: Option<String>,
-    /// Pinned message. Returned only in [`get_chat`].
+    /// Pinned message. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub pinned_message: Option<Box<super::Message>>,
     /// Unique identifier for the linked chat, i.e. the discussion group
     /// identifier for a channel and vice versa; for supergroups and channel
-    /// chats. Returned only in [`get_chat`].
+    /// chats. Only set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub linked_chat_id: Option<i64>,
+    /// True, if messages from the chat can't be forwarded to other chats. Only
+    /// set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub has_protected_content: Option<bool>,
+    /// The time after which all messages sent to the chat will be automatically
+    /// deleted; in seconds. Only set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub message_auto_delete_time: Option<i64>,
+    /// All active usernames of the chat. Only set when converted from a
+    /// [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub active_usernames: Option<Vec<String>>,
 }
 
 /// This object represents a chat. It can be a private, group, supergroup or
@@ -164,7 +243,25 @@ pub struct ChatLocation {
 
 /// Describes actions that a non-administrator user is allowed to take in a
 /// chat.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+///
+/// Use [`ChatPermissions::all`] or [`ChatPermissions::none`] for the common
+/// cases, or the [`ChatPermissionsBuilder`] to allow only some actions:
+/// ```
+/// use telexide::model::ChatPermissions;
+///
+/// let permissions = ChatPermissions::builder()
+///     .can_send_messages(true)
+///     .can_send_polls(true)
+///     .build();
+///
+/// assert!(permissions.can_send_polls);
+/// assert!(!permissions.can_send_media_messages);
+/// ```
+///
+/// [`ChatPermissions::all`]: #method.all
+/// [`ChatPermissions::none`]: #method.none
+/// [`ChatPermissionsBuilder`]: struct.ChatPermissionsBuilder.html
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct ChatPermissions {
     /// True, if the user is allowed to send text messages, contacts, locations
     /// and venues.
@@ -172,8 +269,30 @@ pub struct ChatPermissions {
     pub can_send_messages: bool,
     /// True, if the user is allowed to send audios, documents, photos, videos,
     /// video notes and voice notes, implies can_send_messages to be true.
+    ///
+    /// Telegram has split this permission into the `can_send_*` permissions
+    /// for each kind of media, this is kept for chats returning the old
+    /// permission.
     #[serde(default)]
     pub can_send_media_messages: bool,
+    /// True, if the user is allowed to send audios
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_audios: Option<bool>,
+    /// True, if the user is allowed to send documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_documents: Option<bool>,
+    /// True, if the user is allowed to send photos
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_photos: Option<bool>,
+    /// True, if the user is allowed to send videos
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_videos: Option<bool>,
+    /// True, if the user is allowed to send video notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_video_notes: Option<bool>,
+    /// True, if the user is allowed to send voice notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_voice_notes: Option<bool>,
     /// True, if the user is allowed to send polls, implies can_send_messages to
     /// be true.
     #[serde(default)]
@@ -199,6 +318,171 @@ pub struct ChatPermissions {
     pub can_pin_messages: bool,
 }
 
+impl ChatPermissions {
+    /// Permissions allowing the user to take every action
+    pub fn all() -> Self {
+        Self {
+            can_send_messages: true,
+            can_send_media_messages: true,
+            can_send_polls: true,
+            can_send_other_messages: true,
+            can_add_web_page_previews: true,
+            can_change_info: true,
+            can_invite_users: true,
+            can_pin_messages: true,
+            ..Self::default()
+        }
+    }
+
+    /// Permissions allowing the user to take no action at all, which makes
+    /// the chat read-only for them
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Permissions allowing the user to take every action, including sending
+    /// each kind of media when used with `use_independent_chat_permissions`
+    pub fn allow_all() -> Self {
+        Self::all().with_media(true)
+    }
+
+    /// Permissions allowing the user to take no action at all, explicitly
+    /// disallowing each kind of media when used with
+    /// `use_independent_chat_permissions`
+    pub fn restrict_all() -> Self {
+        Self::none().with_media(false)
+    }
+
+    fn with_media(mut self, allowed: bool) -> Self {
+        self.can_send_audios = Some(allowed);
+        self.can_send_documents = Some(allowed);
+        self.can_send_photos = Some(allowed);
+        self.can_send_videos = Some(allowed);
+        self.can_send_video_notes = Some(allowed);
+        self.can_send_voice_notes = Some(allowed);
+        self
+    }
+
+    /// Creates a builder for permissions, starting with every action being
+    /// disallowed
+    pub fn builder() -> ChatPermissionsBuilder {
+        ChatPermissionsBuilder::new()
+    }
+}
+
+/// A builder for easily creating [`ChatPermissions`] allowing only some
+/// actions, every action not set is disallowed
+///
+/// [`ChatPermissions`]: struct.ChatPermissions.html
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChatPermissionsBuilder {
+    permissions: ChatPermissions,
+}
+
+impl ChatPermissionsBuilder {
+    /// Creates a new builder, with every action being disallowed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the user may send text messages, contacts, locations and
+    /// venues
+    pub fn can_send_messages(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_send_messages = allowed;
+        self
+    }
+
+    /// Sets whether the user may send audios, documents, photos, videos,
+    /// video notes and voice notes, which implies being able to send messages
+    pub fn can_send_media_messages(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_send_media_messages = allowed;
+        self
+    }
+
+    /// Sets whether the user may send audios
+    pub fn can_send_audios(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_send_audios = Some(allowed);
+        self
+    }
+
+    /// Sets whether the user may send documents
+    pub fn can_send_documents(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_send_documents = Some(allowed);
+        self
+    }
+
+    /// Sets whether the user may send photos
+    pub fn can_send_photos(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_send_photos = Some(allowed);
+        self
+    }
+
+    /// Sets whether the user may send videos
+    pub fn can_send_videos(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_send_videos = Some(allowed);
+        self
+    }
+
+    /// Sets whether the user may send video notes
+    pub fn can_send_video_notes(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_send_video_notes = Some(allowed);
+        self
+    }
+
+    /// Sets whether the user may send voice notes
+    pub fn can_send_voice_notes(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_send_voice_notes = Some(allowed);
+        self
+    }
+
+    /// Sets whether the user may send polls, which implies being able to send
+    /// messages
+    pub fn can_send_polls(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_send_polls = allowed;
+        self
+    }
+
+    /// Sets whether the user may send animations, games, stickers and use
+    /// inline bots, which implies being able to send media messages
+    pub fn can_send_other_messages(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_send_other_messages = allowed;
+        self
+    }
+
+    /// Sets whether the user may add web page previews to their messages,
+    /// which implies being able to send media messages
+    pub fn can_add_web_page_previews(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_add_web_page_previews = allowed;
+        self
+    }
+
+    /// Sets whether the user may change the chat title, photo and other
+    /// settings
+    pub fn can_change_info(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_change_info = allowed;
+        self
+    }
+
+    /// Sets whether the user may invite new users to the chat
+    pub fn can_invite_users(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_invite_users = allowed;
+        self
+    }
+
+    /// Sets whether the user may pin messages
+    pub fn can_pin_messages(&mut self, allowed: bool) -> &mut Self {
+        self.permissions.can_pin_messages = allowed;
+        self
+    }
+
+    /// Creates the [`ChatPermissions`]
+    ///
+    /// [`ChatPermissions`]: struct.ChatPermissions.html
+    pub fn build(&self) -> ChatPermissions {
+        self.permissions.clone()
+    }
+}
+
 /// This object represents a chat photo.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ChatPhoto {
@@ -230,6 +514,135 @@ impl Chat {
             Chat::SuperGroup(c) => c.id,
         }
     }
+
+    /// Gets the type of the chat
+    pub fn get_type(&self) -> ChatType {
+        match self {
+            Chat::Private(_) => ChatType::Private,
+            Chat::Channel(_) => ChatType::Channel,
+            Chat::Group(_) => ChatType::Group,
+            Chat::SuperGroup(_) => ChatType::SuperGroup,
+        }
+    }
+
+    /// Whether the chat is a private chat with a user
+    pub fn is_private(&self) -> bool {
+        matches!(self, Chat::Private(_))
+    }
+
+    /// Whether the chat is a basic group
+    pub fn is_group(&self) -> bool {
+        matches!(self, Chat::Group(_))
+    }
+
+    /// Whether the chat is a supergroup
+    pub fn is_supergroup(&self) -> bool {
+        matches!(self, Chat::SuperGroup(_))
+    }
+
+    /// Whether the chat is a channel
+    pub fn is_channel(&self) -> bool {
+        matches!(self, Chat::Channel(_))
+    }
+
+    /// Whether the chat is a supergroup with topics enabled
+    pub fn is_forum(&self) -> bool {
+        match self {
+            Chat::SuperGroup(c) => c.is_forum.unwrap_or_default(),
+            _ => false,
+        }
+    }
+
+    /// Gets the title of the chat, private chats don't have one
+    pub fn title(&self) -> Option<&str> {
+        match self {
+            Chat::Channel(c) => Some(&c.title),
+            Chat::Group(c) => Some(&c.title),
+            Chat::SuperGroup(c) => Some(&c.title),
+            Chat::Private(_) => None,
+        }
+    }
+
+    /// Gets the username of the chat, basic groups don't have one
+    pub fn username(&self) -> Option<&str> {
+        match self {
+            Chat::Private(c) => c.username.as_deref(),
+            Chat::Channel(c) => c.username.as_deref(),
+            Chat::SuperGroup(c) => c.username.as_deref(),
+            Chat::Group(_) => None,
+        }
+    }
+
+    /// Gets the description of the chat, which is only set for chats converted
+    /// from a [`ChatFullInfo`]. Private chats have a bio instead.
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            Chat::Channel(c) => c.description.as_deref(),
+            Chat::Group(c) => c.description.as_deref(),
+            Chat::SuperGroup(c) => c.description.as_deref(),
+            Chat::Private(_) => None,
+        }
+    }
+
+    /// Gets the pinned message of the chat, which is only set for chats
+    /// converted from a [`ChatFullInfo`]
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub fn pinned_message(&self) -> Option<&super::Message> {
+        match self {
+            Chat::Channel(c) => c.pinned_message.as_deref(),
+            Chat::Group(c) => c.pinned_message.as_deref(),
+            Chat::SuperGroup(c) => c.pinned_message.as_deref(),
+            Chat::Private(_) => None,
+        }
+    }
+
+    /// Gets the photo of the chat, which is only set for chats converted from
+    /// a [`ChatFullInfo`]
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub fn photo(&self) -> Option<&ChatPhoto> {
+        match self {
+            Chat::Private(c) => c.photo.as_ref(),
+            Chat::Channel(c) => c.photo.as_ref(),
+            Chat::Group(c) => c.photo.as_ref(),
+            Chat::SuperGroup(c) => c.photo.as_ref(),
+        }
+    }
+
+    /// Gets the default permissions of the members of a group or supergroup,
+    /// which are only set for chats converted from a [`ChatFullInfo`]
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub fn permissions(&self) -> Option<&ChatPermissions> {
+        match self {
+            Chat::Group(c) => c.permissions.as_ref(),
+            Chat::SuperGroup(c) => c.permissions.as_ref(),
+            Chat::Private(_) | Chat::Channel(_) => None,
+        }
+    }
+
+    /// Gets a link users can open the chat with, which is
+    /// `https://t.me/<username>` for public chats and the invite link of the
+    /// chat otherwise. The invite link is only set for chats converted from a
+    /// [`ChatFullInfo`], so this returns `None` for chats without a username
+    /// received in messages.
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub fn invite_url(&self) -> Option<String> {
+        if let Some(username) = self.username() {
+            return Some(format!("https://t.me/{username}"));
+        }
+
+        match self {
+            Chat::Channel(c) => c.invite_link.clone(),
+            Chat::Group(c) => c.invite_link.clone(),
+            Chat::SuperGroup(c) => c.invite_link.clone(),
+            Chat::Private(_) => None,
+        }
+    }
 }
 
 impl From<RawChat> for Chat {
@@ -244,6 +657,9 @@ impl From<RawChat> for Chat {
                 pinned_message: raw.pinned_message.map(|m| Box::new((*m).into())),
                 invite_link: raw.invite_link,
                 linked_chat_id: raw.linked_chat_id,
+                has_protected_content: raw.has_protected_content,
+                message_auto_delete_time: raw.message_auto_delete_time,
+                active_usernames: raw.active_usernames,
             }),
             ChatType::Private => Chat::Private(PrivateChat {
                 id: raw.id,
@@ -252,6 +668,10 @@ impl From<RawChat> for Chat {
                 username: raw.username,
                 photo: raw.photo,
                 bio: raw.bio,
+                has_private_forwards: raw.has_private_forwards,
+                has_restricted_voice_and_video_messages: raw.has_restricted_voice_and_video_messages,
+                message_auto_delete_time: raw.message_auto_delete_time,
+                active_usernames: raw.active_usernames,
             }),
             ChatType::Group => Chat::Group(GroupChat {
                 id: raw.id,
@@ -261,11 +681,14 @@ impl From<RawChat> for Chat {
                 pinned_message: raw.pinned_message.map(|m| Box::new((*m).into())),
                 invite_link: raw.invite_link,
                 permissions: raw.permissions,
+                has_protected_content: raw.has_protected_content,
+                message_auto_delete_time: raw.message_auto_delete_time,
             }),
             ChatType::SuperGroup => Chat::SuperGroup(SuperGroupChat {
                 id: raw.id,
                 title: raw.title.unwrap_or_default(),
                 username: raw.username,
+                is_forum: raw.is_forum,
                 photo: raw.photo,
                 description: raw.description,
                 pinned_message: raw.pinned_message.map(|m| Box::new((*m).into())),
@@ -276,6 +699,11 @@ impl From<RawChat> for Chat {
                 slow_mode_delay: raw.slow_mode_delay,
                 linked_chat_id: raw.linked_chat_id,
                 location: raw.location,
+                join_to_send_messages: raw.join_to_send_messages,
+                join_by_request: raw.join_by_request,
+                has_protected_content: raw.has_protected_content,
+                message_auto_delete_time: raw.message_auto_delete_time,
+                active_usernames: raw.active_usernames,
             }),
             ChatType::Sender => unreachable!(),
         }
@@ -283,12 +711,14 @@ impl From<RawChat> for Chat {
 }
 
 impl From<Chat> for RawChat {
+    #[allow(clippy::too_many_lines)]
     fn from(chat: Chat) -> RawChat {
         match chat {
             Chat::Private(c) => RawChat {
                 chat_type: ChatType::Private,
                 first_name: c.first_name,
                 last_name: c.last_name,
+                is_forum: None,
                 id: c.id,
                 username: c.username,
                 photo: c.photo,
@@ -303,6 +733,13 @@ impl From<Chat> for RawChat {
                 slow_mode_delay: None,
                 linked_chat_id: None,
                 location: None,
+                has_private_forwards: c.has_private_forwards,
+                has_restricted_voice_and_video_messages: c.has_restricted_voice_and_video_messages,
+                join_to_send_messages: None,
+                join_by_request: None,
+                has_protected_content: None,
+                message_auto_delete_time: c.message_auto_delete_time,
+                active_usernames: c.active_usernames,
             },
             Chat::Group(c) => RawChat {
                 chat_type: ChatType::Group,
@@ -319,9 +756,17 @@ impl From<Chat> for RawChat {
                 slow_mode_delay: None,
                 first_name: None,
                 last_name: None,
+                is_forum: None,
                 bio: None,
                 linked_chat_id: None,
                 location: None,
+                has_private_forwards: None,
+                has_restricted_voice_and_video_messages: None,
+                join_to_send_messages: None,
+                join_by_request: None,
+                has_protected_content: c.has_protected_content,
+                message_auto_delete_time: c.message_auto_delete_time,
+                active_usernames: None,
             },
             Chat::SuperGroup(c) => RawChat {
                 chat_type: ChatType::SuperGroup,
@@ -341,6 +786,14 @@ impl From<Chat> for RawChat {
                 bio: None,
                 first_name: None,
                 last_name: None,
+                is_forum: c.is_forum,
+                has_private_forwards: None,
+                has_restricted_voice_and_video_messages: None,
+                join_to_send_messages: c.join_to_send_messages,
+                join_by_request: c.join_by_request,
+                has_protected_content: c.has_protected_content,
+                message_auto_delete_time: c.message_auto_delete_time,
+                active_usernames: c.active_usernames,
             },
             Chat::Channel(c) => RawChat {
                 chat_type: ChatType::Channel,
@@ -358,8 +811,16 @@ impl From<Chat> for RawChat {
                 slow_mode_delay: None,
                 first_name: None,
                 last_name: None,
+                is_forum: None,
                 bio: None,
                 location: None,
+                has_private_forwards: None,
+                has_restricted_voice_and_video_messages: None,
+                join_to_send_messages: None,
+                join_by_request: None,
+                has_protected_content: c.has_protected_content,
+                message_auto_delete_time: c.message_auto_delete_time,
+                active_usernames: c.active_usernames,
             },
         }
     }
@@ -381,6 +842,9 @@ pub enum ChatMember {
     Left(LeftMemberStatus),
     #[serde(rename = "kicked")]
     Kicked(KickedMemberStatus),
+    /// A status that isn't supported yet
+    #[serde(untagged)]
+    Unknown(UnknownMemberStatus),
 }
 
 /// Represents a [`ChatMember`] who is the creator of the [`Chat`].
@@ -433,6 +897,119 @@ pub struct AdministratorMemberStatus {
     /// True, if the administrator can manage voice chats
     #[serde(default)]
     pub can_manage_voice_chats: bool,
+    /// True, if the administrator can post in the channel; channels only
+    #[serde(default)]
+    pub can_post_messages: bool,
+    /// True, if the administrator can edit messages of other users and can pin
+    /// messages; channels only
+    #[serde(default)]
+    pub can_edit_messages: bool,
+    /// True, if the administrator can delete messages of other users
+    #[serde(default)]
+    pub can_delete_messages: bool,
+    /// True, if the administrator can restrict, ban or unban chat members
+    #[serde(default)]
+    pub can_restrict_members: bool,
+    /// True, if the administrator can add new administrators with a subset of
+    /// their own privileges or demote administrators that they have promoted
+    #[serde(default)]
+    pub can_promote_members: bool,
+    /// True, if the user is allowed to change the chat title, photo and other
+    /// settings
+    #[serde(default)]
+    pub can_change_info: bool,
+    /// True, if the user is allowed to invite new users to the chat
+    #[serde(default)]
+    pub can_invite_users: bool,
+    /// True, if the user is allowed to pin messages; groups and supergroups
+    /// only
+    #[serde(default)]
+    pub can_pin_messages: bool,
+}
+
+/// The rights of an administrator of a chat, as returned by
+/// [`ChatMember::admin_rights`]
+///
+/// [`ChatMember::admin_rights`]: enum.ChatMember.html#method.admin_rights
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ChatAdministratorRights {
+    /// True, if the user's presence in the chat is hidden
+    #[serde(default)]
+    pub is_anonymous: bool,
+    /// True, if the administrator can access the chat event log, chat
+    /// statistics, message statistics in channels, see channel members, see
+    /// anonymous administrators in supergroups and ignore slow mode.
+    /// Implied by any other administrator privilege
+    #[serde(default)]
+    pub can_manage_chat: bool,
+    /// True, if the administrator can delete messages of other users
+    #[serde(default)]
+    pub can_delete_messages: bool,
+    /// True, if the administrator can manage voice chats
+    #[serde(default)]
+    pub can_manage_voice_chats: bool,
+    /// True, if the administrator can restrict, ban or unban chat members
+    #[serde(default)]
+    pub can_restrict_members: bool,
+    /// True, if the administrator can add new administrators with a subset of
+    /// their own privileges or demote administrators that they have promoted
+    #[serde(default)]
+    pub can_promote_members: bool,
+    /// True, if the user is allowed to change the chat title, photo and other
+    /// settings
+    #[serde(default)]
+    pub can_change_info: bool,
+    /// True, if the user is allowed to invite new users to the chat
+    #[serde(default)]
+    pub can_invite_users: bool,
+    /// True, if the administrator can post in the channel; channels only
+    #[serde(default)]
+    pub can_post_messages: bool,
+    /// True, if the administrator can edit messages of other users and can pin
+    /// messages; channels only
+    #[serde(default)]
+    pub can_edit_messages: bool,
+    /// True, if the user is allowed to pin messages; groups and supergroups
+    /// only
+    #[serde(default)]
+    pub can_pin_messages: bool,
+}
+
+impl ChatAdministratorRights {
+    /// The rights of the creator of a chat, who can do everything
+    pub fn all(is_anonymous: bool) -> Self {
+        Self {
+            is_anonymous,
+            can_manage_chat: true,
+            can_delete_messages: true,
+            can_manage_voice_chats: true,
+            can_restrict_members: true,
+            can_promote_members: true,
+            can_change_info: true,
+            can_invite_users: true,
+            can_post_messages: true,
+            can_edit_messages: true,
+            can_pin_messages: true,
+        }
+    }
+}
+
+impl From<&AdministratorMemberStatus> for ChatAdministratorRights {
+    fn from(admin: &AdministratorMemberStatus) -> Self {
+        Self {
+            is_anonymous: admin.is_anonymous,
+            can_manage_chat: admin.can_manage_chat,
+            can_delete_messages: admin.can_delete_messages,
+            can_manage_voice_chats: admin.can_manage_voice_chats,
+            can_restrict_members: admin.can_restrict_members,
+            can_promote_members: admin.can_promote_members,
+            can_change_info: admin.can_change_info,
+            can_invite_users: admin.can_invite_users,
+            can_post_messages: admin.can_post_messages,
+            can_edit_messages: admin.can_edit_messages,
+            can_pin_messages: admin.can_pin_messages,
+        }
+    }
 }
 
 /// Represents a [`ChatMember`] who is a normal member of the [`Chat`] without
@@ -473,6 +1050,24 @@ pub struct RestrictedMemberStatus {
     /// video notes and voice notes
     #[serde(default)]
     pub can_send_media_messages: bool,
+    /// True, if the user is allowed to send audios
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_audios: Option<bool>,
+    /// True, if the user is allowed to send documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_documents: Option<bool>,
+    /// True, if the user is allowed to send photos
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_photos: Option<bool>,
+    /// True, if the user is allowed to send videos
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_videos: Option<bool>,
+    /// True, if the user is allowed to send video notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_video_notes: Option<bool>,
+    /// True, if the user is allowed to send voice notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_send_voice_notes: Option<bool>,
     /// True, if the user is allowed to send polls
     #[serde(default)]
     pub can_send_polls: bool,
@@ -485,6 +1080,123 @@ pub struct RestrictedMemberStatus {
     pub can_add_web_page_previews: bool,
 }
 
+impl RestrictedMemberStatus {
+    /// Whether the restricted user has the given right, the rights to send
+    /// each kind of media fall back to `can_send_media_messages` when
+    /// telegram doesn't send them
+    pub fn can(&self, right: ChatRight) -> bool {
+        let media = |allowed: Option<bool>| allowed.unwrap_or(self.can_send_media_messages);
+        match right {
+            ChatRight::SendMessages => self.can_send_messages,
+            ChatRight::SendMediaMessages => self.can_send_media_messages,
+            ChatRight::SendAudios => media(self.can_send_audios),
+            ChatRight::SendDocuments => media(self.can_send_documents),
+            ChatRight::SendPhotos => media(self.can_send_photos),
+            ChatRight::SendVideos => media(self.can_send_videos),
+            ChatRight::SendVideoNotes => media(self.can_send_video_notes),
+            ChatRight::SendVoiceNotes => media(self.can_send_voice_notes),
+            ChatRight::SendPolls => self.can_send_polls,
+            ChatRight::SendOtherMessages => self.can_send_other_messages,
+            ChatRight::AddWebPagePreviews => self.can_add_web_page_previews,
+            ChatRight::ChangeInfo => self.can_change_info,
+            ChatRight::InviteUsers => self.can_invite_users,
+            ChatRight::PinMessages => self.can_pin_messages,
+            _ => false,
+        }
+    }
+}
+
+/// A single right a [`ChatMember`] can have in a [`Chat`], used by
+/// [`ChatMember::can`]
+///
+/// [`ChatMember::can`]: enum.ChatMember.html#method.can
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChatRight {
+    /// Sending text messages, contacts, locations and venues
+    SendMessages,
+    /// Sending any kind of media
+    SendMediaMessages,
+    /// Sending audios
+    SendAudios,
+    /// Sending documents
+    SendDocuments,
+    /// Sending photos
+    SendPhotos,
+    /// Sending videos
+    SendVideos,
+    /// Sending video notes
+    SendVideoNotes,
+    /// Sending voice notes
+    SendVoiceNotes,
+    /// Sending polls
+    SendPolls,
+    /// Sending animations, games, stickers and using inline bots
+    SendOtherMessages,
+    /// Adding web page previews to messages
+    AddWebPagePreviews,
+    /// Changing the chat title, photo and other settings
+    ChangeInfo,
+    /// Inviting new users to the chat
+    InviteUsers,
+    /// Pinning messages
+    PinMessages,
+    /// Accessing the chat event log, statistics and members
+    ManageChat,
+    /// Deleting messages of other users
+    DeleteMessages,
+    /// Managing voice chats
+    ManageVoiceChats,
+    /// Restricting, banning and unbanning members
+    RestrictMembers,
+    /// Adding new administrators
+    PromoteMembers,
+    /// Posting in the channel
+    PostMessages,
+    /// Editing messages of other users in the channel
+    EditMessages,
+}
+
+impl ChatRight {
+    /// Whether the right is about sending messages, which every member that
+    /// isn't restricted has
+    pub fn is_sending(self) -> bool {
+        matches!(
+            self,
+            ChatRight::SendMessages
+                | ChatRight::SendMediaMessages
+                | ChatRight::SendAudios
+                | ChatRight::SendDocuments
+                | ChatRight::SendPhotos
+                | ChatRight::SendVideos
+                | ChatRight::SendVideoNotes
+                | ChatRight::SendVoiceNotes
+                | ChatRight::SendPolls
+                | ChatRight::SendOtherMessages
+                | ChatRight::AddWebPagePreviews
+        )
+    }
+}
+
+impl ChatAdministratorRights {
+    /// Whether the administrator has the given right, administrators can
+    /// always send messages
+    pub fn can(&self, right: ChatRight) -> bool {
+        match right {
+            ChatRight::ChangeInfo => self.can_change_info,
+            ChatRight::InviteUsers => self.can_invite_users,
+            ChatRight::PinMessages => self.can_pin_messages,
+            ChatRight::ManageChat => self.can_manage_chat,
+            ChatRight::DeleteMessages => self.can_delete_messages,
+            ChatRight::ManageVoiceChats => self.can_manage_voice_chats,
+            ChatRight::RestrictMembers => self.can_restrict_members,
+            ChatRight::PromoteMembers => self.can_promote_members,
+            ChatRight::PostMessages => self.can_post_messages,
+            ChatRight::EditMessages => self.can_edit_messages,
+            right => right.is_sending(),
+        }
+    }
+}
+
 /// Represents a [`ChatMember`] who left the [`Chat`].
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct LeftMemberStatus {
@@ -492,6 +1204,19 @@ pub struct LeftMemberStatus {
     pub user: User,
 }
 
+/// Represents a [`ChatMember`] with a status that isn't supported yet.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UnknownMemberStatus {
+    /// The status of the member as sent by telegram
+    #[serde(deserialize_with = "deserialize_member_status")]
+    pub status: String,
+    /// Information about the user
+    pub user: User,
+    /// Any other fields of the member
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
 /// Represents a [`ChatMember`] who has been kicked from the [`Chat`].
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct KickedMemberStatus {
@@ -512,8 +1237,90 @@ impl ChatMember {
             ChatMember::Left(m) => &m.user,
             ChatMember::Member(m) => &m.user,
             ChatMember::Restricted(m) => &m.user,
+            ChatMember::Unknown(m) => &m.user,
+        }
+    }
+
+    /// Whether the user is the creator or an administrator of the chat
+    pub fn is_admin(&self) -> bool {
+        matches!(self, ChatMember::Creator(_) | ChatMember::Administrator(_))
+    }
+
+    /// Whether the user is currently in the chat, including restricted users
+    /// that haven't left
+    pub fn is_member(&self) -> bool {
+        match self {
+            ChatMember::Creator(_) | ChatMember::Administrator(_) | ChatMember::Member(_) => true,
+            ChatMember::Restricted(m) => m.is_member,
+            ChatMember::Left(_) | ChatMember::Kicked(_) | ChatMember::Unknown(_) => false,
+        }
+    }
+
+    /// Whether the user is banned from the chat
+    pub fn is_banned(&self) -> bool {
+        matches!(self, ChatMember::Kicked(_))
+    }
+
+    /// Whether the user is restricted in the chat
+    pub fn is_restricted(&self) -> bool {
+        matches!(self, ChatMember::Restricted(_))
+    }
+
+    /// Whether the bot is allowed to edit the administrator privileges of the
+    /// user, which is only the case for administrators
+    pub fn can_be_edited(&self) -> bool {
+        match self {
+            ChatMember::Administrator(m) => m.can_be_edited,
+            _ => false,
+        }
+    }
+
+    /// Gets the rights of the user if they are the creator, who has every
+    /// right, or an administrator of the chat
+    pub fn admin_rights(&self) -> Option<ChatAdministratorRights> {
+        match self {
+            ChatMember::Creator(m) => Some(ChatAdministratorRights::all(m.is_anonymous)),
+            ChatMember::Administrator(m) => Some(m.into()),
+            _ => None,
+        }
+    }
+
+    /// Whether the user has the given right in the chat. The creator has
+    /// every right, and members that aren't restricted can send everything,
+    /// but the default permissions of the chat aren't taken into account, so
+    /// other rights are only granted to administrators. Users that left or
+    /// are banned don't have any rights.
+    pub fn can(&self, right: ChatRight) -> bool {
+        match self {
+            ChatMember::Creator(_) => true,
+            ChatMember::Administrator(m) => ChatAdministratorRights::from(m).can(right),
+            ChatMember::Member(_) => right.is_sending(),
+            ChatMember::Restricted(m) => m.can(right),
+            ChatMember::Left(_) | ChatMember::Kicked(_) | ChatMember::Unknown(_) => false,
+        }
+    }
+
+    /// Gets the custom title of the user, if they are the creator or an
+    /// administrator with one
+    pub fn custom_title(&self) -> Option<&str> {
+        match self {
+            ChatMember::Creator(m) => m.custom_title.as_deref(),
+            ChatMember::Administrator(m) => m.custom_title.as_deref(),
+            _ => None,
         }
     }
+
+    /// Gets the date the restriction or ban of the user will be lifted, if it
+    /// doesn't last forever
+    pub fn until_date(&self) -> Option<DateTime<Utc>> {
+        let until_date = match self {
+            ChatMember::Restricted(m) => m.until_date,
+            ChatMember::Kicked(m) => m.until_date,
+            _ => None,
+        };
+        // telegram uses 0 for restrictions that last forever
+        until_date.filter(|date| date.timestamp() > 0)
+    }
 }
 
 /// Represents an invite link for a chat.
@@ -554,6 +1361,35 @@ pub struct ChatMemberUpdated {
     /// Chat invite link, which was used by the user to join the chat; for
     /// joining by invite link events only.
     pub invite_link: Option<ChatInviteLink>,
+    /// True, if the user joined the chat via a chat folder invite link
+    #[serde(default)]
+    pub via_chat_folder_invite_link: bool,
+}
+
+impl ChatMemberUpdated {
+    /// Whether a bot was added to the chat, which for `my_chat_member` updates
+    /// means the bot itself was added
+    pub fn bot_was_added(&self) -> bool {
+        self.new_chat_member.get_user().is_bot && self.user_joined()
+    }
+
+    /// Whether a bot was removed from the chat, either by leaving or by being
+    /// banned, which for `my_chat_member` updates means the bot itself was
+    /// removed or blocked
+    pub fn bot_was_removed(&self) -> bool {
+        self.new_chat_member.get_user().is_bot && self.user_left()
+    }
+
+    /// Whether the user became a member of the chat
+    pub fn user_joined(&self) -> bool {
+        !self.old_chat_member.is_member() && self.new_chat_member.is_member()
+    }
+
+    /// Whether the user stopped being a member of the chat, either by leaving
+    /// or by being banned
+    pub fn user_left(&self) -> bool {
+        self.old_chat_member.is_member() && !self.new_chat_member.is_member()
+    }
 }
 
 /// The type of chat
@@ -0,0 +1,281 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    utils::unix_date_formatting,
+    ChannelChat,
+    Chat,
+    ChatLocation,
+    ChatPermissions,
+    ChatPhoto,
+    ChatType,
+    GroupChat,
+    Location,
+    Message,
+    PrivateChat,
+    Sticker,
+    SuperGroupChat,
+};
+
+/// This object contains full information about a chat, as returned by
+/// [`get_chat`]. Chats in messages and other updates only contain some of this
+/// information, use `Chat::from` to get the same kind of [`Chat`] as those.
+///
+/// [`get_chat`]: ../api/trait.API.html#method.get_chat
+/// [`Chat`]: enum.Chat.html
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatFullInfo {
+    /// Unique identifier for this chat
+    pub id: i64,
+    /// Type of the chat
+    #[serde(rename = "type")]
+    pub chat_type: ChatType,
+    /// Title, for supergroups, channels and group chats
+    pub title: Option<String>,
+    /// Username, for private chats, supergroups and channels if available
+    pub username: Option<String>,
+    /// First name of the other party in a private chat
+    pub first_name: Option<String>,
+    /// Last name of the other party in a private chat
+    pub last_name: Option<String>,
+    /// True, if the supergroup chat is a forum (has topics enabled)
+    pub is_forum: Option<bool>,
+    /// Identifier of the accent color for the chat name and backgrounds of the
+    /// chat photo, reply header, and link preview
+    pub accent_color_id: Option<i64>,
+    /// The maximum number of reactions that can be set on a message in the
+    /// chat
+    pub max_reaction_count: Option<i64>,
+    /// Chat photo
+    pub photo: Option<ChatPhoto>,
+    /// All active usernames of the chat
+    pub active_usernames: Option<Vec<String>>,
+    /// For private chats, the date of birth of the user
+    pub birthdate: Option<Birthdate>,
+    /// For private chats with business accounts, the intro of the business
+    pub business_intro: Option<BusinessIntro>,
+    /// For private chats with business accounts, the location of the business
+    pub business_location: Option<BusinessLocation>,
+    /// For private chats with business accounts, the opening hours of the
+    /// business
+    pub business_opening_hours: Option<BusinessOpeningHours>,
+    /// For private chats, the personal channel of the user
+    pub personal_chat: Option<Box<Chat>>,
+    /// List of available reactions allowed in the chat. If omitted, then all
+    /// emoji reactions are allowed.
+    pub available_reactions: Option<Vec<ReactionType>>,
+    /// Custom emoji identifier of the emoji chosen by the chat for the reply
+    /// header and link preview background
+    pub background_custom_emoji_id: Option<String>,
+    /// Identifier of the accent color for the chat's profile background
+    pub profile_accent_color_id: Option<i64>,
+    /// Custom emoji identifier of the emoji chosen by the chat for its profile
+    /// background
+    pub profile_background_custom_emoji_id: Option<String>,
+    /// Custom emoji identifier of the emoji status of the chat or the other
+    /// party in a private chat
+    pub emoji_status_custom_emoji_id: Option<String>,
+    /// Expiration date of the emoji status of the chat or the other party in
+    /// a private chat, if any
+    #[serde(default)]
+    #[serde(with = "unix_date_formatting::optional")]
+    pub emoji_status_expiration_date: Option<DateTime<Utc>>,
+    /// Bio of the other party in a private chat
+    pub bio: Option<String>,
+    /// True, if privacy settings of the other party in the private chat allows
+    /// to use `tg://user?id=<user_id>` links only in chats with the user
+    pub has_private_forwards: Option<bool>,
+    /// True, if the privacy settings of the other party restrict sending voice
+    /// and video note messages in the private chat
+    pub has_restricted_voice_and_video_messages: Option<bool>,
+    /// True, if users need to join the supergroup before they can send
+    /// messages
+    pub join_to_send_messages: Option<bool>,
+    /// True, if all users directly joining the supergroup without using an
+    /// invite link need to be approved by supergroup administrators
+    pub join_by_request: Option<bool>,
+    /// Description, for groups, supergroups and channel chats
+    pub description: Option<String>,
+    /// Primary invite link, for groups, supergroups and channel chats
+    pub invite_link: Option<String>,
+    /// The most recent pinned message (by sending date)
+    pub pinned_message: Option<Box<Message>>,
+    /// Default chat member permissions, for groups and supergroups
+    pub permissions: Option<ChatPermissions>,
+    /// True, if paid media messages can be sent or forwarded to the channel
+    /// chat
+    pub can_send_paid_media: Option<bool>,
+    /// For supergroups, the minimum allowed delay between consecutive messages
+    /// sent by each unprivileged user; in seconds
+    pub slow_mode_delay: Option<usize>,
+    /// For supergroups, the minimum number of boosts that a non-administrator
+    /// user needs to add in order to ignore slow mode and chat permissions
+    pub unrestrict_boost_count: Option<i64>,
+    /// The time after which all messages sent to the chat will be
+    /// automatically deleted; in seconds
+    pub message_auto_delete_time: Option<i64>,
+    /// True, if aggressive anti-spam checks are enabled in the supergroup
+    pub has_aggressive_anti_spam_enabled: Option<bool>,
+    /// True, if non-administrators can only get the list of bots and
+    /// administrators in the chat
+    pub has_hidden_members: Option<bool>,
+    /// True, if messages from the chat can't be forwarded to other chats
+    pub has_protected_content: Option<bool>,
+    /// True, if new chat members will have access to old messages
+    pub has_visible_history: Option<bool>,
+    /// For supergroups, name of the group sticker set
+    pub sticker_set_name: Option<String>,
+    /// True, if the bot can change the group sticker set
+    pub can_set_sticker_set: Option<bool>,
+    /// For supergroups, the name of the group's custom emoji sticker set.
+    /// Custom emoji from this set can be used by all users and bots in the
+    /// group.
+    pub custom_emoji_sticker_set_name: Option<String>,
+    /// Unique identifier for the linked chat, i.e. the discussion group
+    /// identifier for a channel and vice versa; for supergroups and channel
+    /// chats
+    pub linked_chat_id: Option<i64>,
+    /// For supergroups, the location to which the supergroup is connected
+    pub location: Option<ChatLocation>,
+}
+
+/// Describes the birthdate of a user
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Birthdate {
+    /// Day of the user's birth; 1-31
+    pub day: u8,
+    /// Month of the user's birth; 1-12
+    pub month: u8,
+    /// Year of the user's birth, if they chose to share it
+    pub year: Option<i32>,
+}
+
+/// Contains information about the start page settings of a Telegram Business
+/// account
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BusinessIntro {
+    /// Title text of the business intro
+    pub title: Option<String>,
+    /// Message text of the business intro
+    pub message: Option<String>,
+    /// Sticker of the business intro
+    pub sticker: Option<Sticker>,
+}
+
+/// Contains information about the location of a Telegram Business account
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BusinessLocation {
+    /// Address of the business
+    pub address: String,
+    /// Location of the business
+    pub location: Option<Location>,
+}
+
+/// Describes the opening hours of a business
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BusinessOpeningHours {
+    /// Unique name of the time zone for which the opening hours are defined
+    pub time_zone_name: String,
+    /// List of time intervals describing business opening hours
+    pub opening_hours: Vec<BusinessOpeningHoursInterval>,
+}
+
+/// Describes an interval of time during which a business is open
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BusinessOpeningHoursInterval {
+    /// The minute's sequence number in a week, starting on Monday, marking the
+    /// start of the time interval during which the business is open; 0 - 7 *
+    /// 24 * 60
+    pub opening_minute: u32,
+    /// The minute's sequence number in a week, starting on Monday, marking the
+    /// end of the time interval during which the business is open; 0 - 8 *
+    /// 24 * 60
+    pub closing_minute: u32,
+}
+
+/// The type of a reaction to a message
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum ReactionType {
+    /// A reaction with a normal emoji
+    #[serde(rename = "emoji")]
+    Emoji {
+        /// The emoji of the reaction
+        emoji: String,
+    },
+    /// A reaction with a custom emoji
+    #[serde(rename = "custom_emoji")]
+    CustomEmoji {
+        /// Custom emoji identifier
+        custom_emoji_id: String,
+    },
+    /// A paid reaction
+    #[serde(rename = "paid")]
+    Paid,
+}
+
+impl From<ChatFullInfo> for Chat {
+    fn from(info: ChatFullInfo) -> Chat {
+        match info.chat_type {
+            ChatType::Channel => Chat::Channel(ChannelChat {
+                id: info.id,
+                title: info.title.unwrap_or_default(),
+                username: info.username,
+                photo: info.photo,
+                description: info.description,
+                invite_link: info.invite_link,
+                pinned_message: info.pinned_message,
+                linked_chat_id: info.linked_chat_id,
+                has_protected_content: info.has_protected_content,
+                message_auto_delete_time: info.message_auto_delete_time,
+                active_usernames: info.active_usernames,
+            }),
+            ChatType::Group => Chat::Group(GroupChat {
+                id: info.id,
+                title: info.title.unwrap_or_default(),
+                photo: info.photo,
+                description: info.description,
+                invite_link: info.invite_link,
+                pinned_message: info.pinned_message,
+                permissions: info.permissions,
+                has_protected_content: info.has_protected_content,
+                message_auto_delete_time: info.message_auto_delete_time,
+            }),
+            ChatType::SuperGroup => Chat::SuperGroup(SuperGroupChat {
+                id: info.id,
+                title: info.title.unwrap_or_default(),
+                username: info.username,
+                is_forum: info.is_forum,
+                photo: info.photo,
+                description: info.description,
+                invite_link: info.invite_link,
+                pinned_message: info.pinned_message,
+                permissions: info.permissions,
+                slow_mode_delay: info.slow_mode_delay,
+                sticker_set_name: info.sticker_set_name,
+                can_set_sticker_set: info.can_set_sticker_set,
+                linked_chat_id: info.linked_chat_id,
+                location: info.location,
+                join_to_send_messages: info.join_to_send_messages,
+                join_by_request: info.join_by_request,
+                has_protected_content: info.has_protected_content,
+                message_auto_delete_time: info.message_auto_delete_time,
+                active_usernames: info.active_usernames,
+            }),
+            // telegram never returns the sender type for full chats
+            ChatType::Private | ChatType::Sender => Chat::Private(PrivateChat {
+                id: info.id,
+                username: info.username,
+                first_name: info.first_name,
+                bio: info.bio,
+                last_name: info.last_name,
+                photo: info.photo,
+                has_private_forwards: info.has_private_forwards,
+                has_restricted_voice_and_video_messages: info
+                    .has_restricted_voice_and_video_messages,
+                message_auto_delete_time: info.message_auto_delete_time,
+                active_usernames: info.active_usernames,
+            }),
+        }
+    }
+}
@@ -42,3 +42,11 @@ pub struct GameHighScore {
     /// Score
     pub score: i64,
 }
+
+impl GameHighScore {
+    /// Returns the position of the user in the high score table, if they are
+    /// in it
+    pub fn rank_of(scores: &[GameHighScore], user_id: i64) -> Option<i64> {
+        scores.iter().find(|s| s.user.id == user_id).map(|s| s.position)
+    }
+}
@@ -6,6 +6,7 @@
 
 use super::{Location, User, ChatType};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// This object represents an incoming inline query.
 /// When the user sends an empty query, your bot could return some default or
@@ -29,6 +30,28 @@ pub struct InlineQuery {
     pub chat_type: Option<ChatType>,
 }
 
+impl InlineQuery {
+    /// Parses the offset as a cursor set using
+    /// [`AnswerInlineQuery::set_next_cursor`], returning `None` for the first
+    /// query or an offset that isn't a valid cursor
+    ///
+    /// [`AnswerInlineQuery::set_next_cursor`]: ../api/types/struct.AnswerInlineQuery.html#method.set_next_cursor
+    pub fn cursor<C: FromStr>(&self) -> Option<C> {
+        if self.offset.is_empty() {
+            return None;
+        }
+        self.offset.parse().ok()
+    }
+
+    /// Gets the page of results requested by the client, as set using
+    /// [`AnswerInlineQuery::set_next_page`], starting at 0 for the first query
+    ///
+    /// [`AnswerInlineQuery::set_next_page`]: ../api/types/struct.AnswerInlineQuery.html#method.set_next_page
+    pub fn page(&self) -> usize {
+        self.cursor().unwrap_or_default()
+    }
+}
+
 /// Represents a result of an inline query that was chosen by the user and sent
 /// to their chat partner.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
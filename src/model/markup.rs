@@ -1,4 +1,8 @@
-use super::{CallbackGame, LoginUrl};
+use super::{CallbackGame, ChatAdministratorRights, ChatShared, LoginUrl, PollType, UsersShared};
+use crate::utils::{
+    result::{Result, TelegramError},
+    MAX_CALLBACK_DATA_LENGTH,
+};
 use serde::{Deserialize, Serialize};
 
 /// This object represents an [inline keyboard] that appears right next to the
@@ -12,6 +16,146 @@ pub struct InlineKeyboardMarkup {
     pub inline_keyboard: Vec<Vec<InlineKeyboardButton>>,
 }
 
+impl InlineKeyboardMarkup {
+    /// Creates a new [`InlineKeyboardBuilder`] for building an inline keyboard
+    pub fn builder() -> InlineKeyboardBuilder {
+        InlineKeyboardBuilder::new()
+    }
+}
+
+/// A builder for easily creating an [`InlineKeyboardMarkup`], adding buttons to
+/// the current row and starting a new row using [`row`].
+///
+/// ```rust
+/// use telexide::model::InlineKeyboardMarkup;
+///
+/// let markup = InlineKeyboardMarkup::builder()
+///     .callback("Yes", "yes")
+///     .callback("No", "no")
+///     .row()
+///     .url("Docs", "https://docs.rs/telexide")
+///     .build()
+///     .expect("invalid keyboard");
+///
+/// assert_eq!(markup.inline_keyboard.len(), 2);
+/// ```
+///
+/// [`row`]: #method.row
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InlineKeyboardBuilder {
+    rows: Vec<Vec<InlineKeyboardButton>>,
+}
+
+impl InlineKeyboardBuilder {
+    /// Creates a new builder without any buttons
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new row, new buttons will be added to this row
+    pub fn row(&mut self) -> &mut Self {
+        self.rows.push(Vec::new());
+        self
+    }
+
+    /// Adds the button to the current row
+    pub fn button(&mut self, button: InlineKeyboardButton) -> &mut Self {
+        match self.rows.last_mut() {
+            Some(row) => row.push(button),
+            None => self.rows.push(vec![button]),
+        }
+        self
+    }
+
+    /// Adds a button sending a callback query with the given data
+    pub fn callback(&mut self, text: &str, callback_data: &str) -> &mut Self {
+        self.button(InlineKeyboardButton::callback(text, callback_data))
+    }
+
+    /// Adds a button opening the given url
+    pub fn url(&mut self, text: &str, url: &str) -> &mut Self {
+        self.button(InlineKeyboardButton::url(text, url))
+    }
+
+    /// Adds a button authorizing the user using the given [`LoginUrl`]
+    pub fn login_url(&mut self, text: &str, login_url: LoginUrl) -> &mut Self {
+        self.button(InlineKeyboardButton::login_url(text, login_url))
+    }
+
+    /// Adds a button switching to inline mode in a chat selected by the user
+    pub fn switch_inline(&mut self, text: &str, query: &str) -> &mut Self {
+        self.button(InlineKeyboardButton::switch_inline(text, query))
+    }
+
+    /// Adds a button switching to inline mode in the current chat
+    pub fn switch_inline_current_chat(&mut self, text: &str, query: &str) -> &mut Self {
+        self.button(InlineKeyboardButton::switch_inline_current_chat(
+            text, query,
+        ))
+    }
+
+    /// Adds a button switching to inline mode in a chat of the types allowed
+    /// by the given [`SwitchInlineQueryChosenChat`], selected by the user
+    pub fn switch_inline_chosen_chat(
+        &mut self,
+        text: &str,
+        chosen_chat: SwitchInlineQueryChosenChat,
+    ) -> &mut Self {
+        self.button(InlineKeyboardButton::switch_inline_chosen_chat(
+            text,
+            chosen_chat,
+        ))
+    }
+
+    /// Adds a button launching the game of the message
+    pub fn callback_game(&mut self, text: &str) -> &mut Self {
+        self.button(InlineKeyboardButton::callback_game(text))
+    }
+
+    /// Adds a pay button
+    pub fn pay(&mut self, text: &str) -> &mut Self {
+        self.button(InlineKeyboardButton::pay(text))
+    }
+
+    /// Builds the [`InlineKeyboardMarkup`], skipping empty rows.
+    ///
+    /// Returns an error if the callback data of a button is empty or longer
+    /// than [`MAX_CALLBACK_DATA_LENGTH`] bytes, or if a button doesn't have
+    /// exactly one of its optional fields set, which telegram doesn't allow
+    ///
+    /// [`MAX_CALLBACK_DATA_LENGTH`]: ../utils/constant.MAX_CALLBACK_DATA_LENGTH.html
+    pub fn build(&self) -> Result<InlineKeyboardMarkup> {
+        for button in self.rows.iter().flatten() {
+            if button.action_count() != 1 {
+                return Err(TelegramError::InvalidArgument(format!(
+                    "the button \"{}\" must have exactly one of its optional fields set",
+                    button.text
+                ))
+                .into());
+            }
+            if let Some(data) = &button.callback_data {
+                if data.is_empty() || data.len() > MAX_CALLBACK_DATA_LENGTH {
+                    return Err(TelegramError::InvalidArgument(format!(
+                        "the callback data of the button \"{}\" must be between 1 and \
+                         {MAX_CALLBACK_DATA_LENGTH} bytes",
+                        button.text
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        Ok(InlineKeyboardMarkup {
+            inline_keyboard: self
+                .rows
+                .iter()
+                .filter(|r| !r.is_empty())
+                .cloned()
+                .collect(),
+        })
+    }
+}
+
 /// This object represents one button of an inline keyboard.
 /// You **must** use exactly one of the optional fields.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -19,16 +163,19 @@ pub struct InlineKeyboardButton {
     /// Label text on the button
     pub text: String,
     /// HTTP or tg:// url to be opened when button is pressed
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
     /// An HTTP URL used to automatically authorize the user.
     /// Can be used as a replacement for the [Telegram Login Widget][widget].
     ///
     /// [widget]: https://core.telegram.org/widgets/login
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub login_url: Option<LoginUrl>,
     /// Data to be sent in a [callback query] to the bot when button is pressed,
     /// 1-64 bytes
     ///
     /// [callback query]: ../model/struct.CallbackQuery.html
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub callback_data: Option<String>,
     /// If set, pressing the button will prompt the user to select one of their
     /// chats, open that chat and insert the bot‘s username and the
@@ -43,6 +190,7 @@ pub struct InlineKeyboardButton {
     ///
     /// [inline mode]: https://core.telegram.org/bots/inline
     /// [switch_pm]: ../api/trait.API.html#method.answer_callback_query
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub switch_inline_query: Option<String>,
     /// If set, pressing the button will insert the bot‘s username and the
     /// specified inline query in the current chat's input field. Can be
@@ -50,12 +198,19 @@ pub struct InlineKeyboardButton {
     ///
     /// This offers a quick way for the user to open your bot in inline mode in
     /// the same chat – good for selecting something from multiple options.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub switch_inline_query_current_chat: Option<String>,
+    /// If set, pressing the button will prompt the user to select one of their
+    /// chats of the specified type, open that chat and insert the bot's
+    /// username and the specified inline query in the input field
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub switch_inline_query_chosen_chat: Option<SwitchInlineQueryChosenChat>,
     /// Description of the game that will be launched when the user presses the
     /// button.
     ///
     /// **NOTE:** This type of button must always be the first button in the
     /// first row.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub callback_game: Option<CallbackGame>,
     /// Specify True, to send a [Pay button].
     ///
@@ -64,9 +219,173 @@ pub struct InlineKeyboardButton {
     ///
     /// [Pay button]: https://core.telegram.org/bots/api#payments
     #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub pay: bool,
 }
 
+impl InlineKeyboardButton {
+    fn with_text(text: &str) -> Self {
+        Self {
+            text: text.to_owned(),
+            url: None,
+            login_url: None,
+            callback_data: None,
+            switch_inline_query: None,
+            switch_inline_query_current_chat: None,
+            switch_inline_query_chosen_chat: None,
+            callback_game: None,
+            pay: false,
+        }
+    }
+
+    /// Returns the amount of optional fields that are set on the button,
+    /// telegram requires this to be exactly one
+    fn action_count(&self) -> usize {
+        [
+            self.url.is_some(),
+            self.login_url.is_some(),
+            self.callback_data.is_some(),
+            self.switch_inline_query.is_some(),
+            self.switch_inline_query_current_chat.is_some(),
+            self.switch_inline_query_chosen_chat.is_some(),
+            self.callback_game.is_some(),
+            self.pay,
+        ]
+        .iter()
+        .filter(|set| **set)
+        .count()
+    }
+
+    /// Creates a button sending a callback query with the given data, 1-64
+    /// bytes
+    pub fn callback(text: &str, callback_data: &str) -> Self {
+        Self {
+            callback_data: Some(callback_data.to_owned()),
+            ..Self::with_text(text)
+        }
+    }
+
+    /// Creates a button opening the given url
+    pub fn url(text: &str, url: &str) -> Self {
+        Self {
+            url: Some(url.to_owned()),
+            ..Self::with_text(text)
+        }
+    }
+
+    /// Creates a button authorizing the user using the given [`LoginUrl`]
+    pub fn login_url(text: &str, login_url: LoginUrl) -> Self {
+        Self {
+            login_url: Some(login_url),
+            ..Self::with_text(text)
+        }
+    }
+
+    /// Creates a button switching to inline mode in a chat selected by the
+    /// user, with the given query
+    pub fn switch_inline(text: &str, query: &str) -> Self {
+        Self {
+            switch_inline_query: Some(query.to_owned()),
+            ..Self::with_text(text)
+        }
+    }
+
+    /// Creates a button switching to inline mode in the current chat, with the
+    /// given query
+    pub fn switch_inline_current_chat(text: &str, query: &str) -> Self {
+        Self {
+            switch_inline_query_current_chat: Some(query.to_owned()),
+            ..Self::with_text(text)
+        }
+    }
+
+    /// Creates a button switching to inline mode in a chat of the types
+    /// allowed by the given [`SwitchInlineQueryChosenChat`], selected by the
+    /// user
+    pub fn switch_inline_chosen_chat(
+        text: &str,
+        chosen_chat: SwitchInlineQueryChosenChat,
+    ) -> Self {
+        Self {
+            switch_inline_query_chosen_chat: Some(chosen_chat),
+            ..Self::with_text(text)
+        }
+    }
+
+    /// Creates a button launching the game of the message
+    pub fn callback_game(text: &str) -> Self {
+        Self {
+            callback_game: Some(CallbackGame {}),
+            ..Self::with_text(text)
+        }
+    }
+
+    /// Creates a pay button
+    pub fn pay(text: &str) -> Self {
+        Self {
+            pay: true,
+            ..Self::with_text(text)
+        }
+    }
+}
+
+/// This object represents an inline button that switches the current user to
+/// inline mode in a chosen chat, with an optional default inline query.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SwitchInlineQueryChosenChat {
+    /// The default inline query to be inserted in the input field. If left
+    /// empty, only the bot's username will be inserted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// True, if private chats with users can be chosen
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_user_chats: bool,
+    /// True, if private chats with bots can be chosen
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_bot_chats: bool,
+    /// True, if group and supergroup chats can be chosen
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_group_chats: bool,
+    /// True, if channel chats can be chosen
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_channel_chats: bool,
+}
+
+impl SwitchInlineQueryChosenChat {
+    /// Creates a new [`SwitchInlineQueryChosenChat`] with the given default
+    /// query, not allowing any chat types yet
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: Some(query.to_owned()),
+            ..Self::default()
+        }
+    }
+
+    /// Sets whether private chats with users can be chosen
+    pub fn set_allow_user_chats(&mut self, allow: bool) -> &mut Self {
+        self.allow_user_chats = allow;
+        self
+    }
+
+    /// Sets whether private chats with bots can be chosen
+    pub fn set_allow_bot_chats(&mut self, allow: bool) -> &mut Self {
+        self.allow_bot_chats = allow;
+        self
+    }
+
+    /// Sets whether group and supergroup chats can be chosen
+    pub fn set_allow_group_chats(&mut self, allow: bool) -> &mut Self {
+        self.allow_group_chats = allow;
+        self
+    }
+
+    /// Sets whether channel chats can be chosen
+    pub fn set_allow_channel_chats(&mut self, allow: bool) -> &mut Self {
+        self.allow_channel_chats = allow;
+        self
+    }
+}
+
 /// This object represents a custom keyboard with reply options
 /// (see [Introduction to bots][keyboards] for details and examples).
 ///
@@ -101,6 +420,152 @@ pub struct ReplyKeyboardMarkup {
     /// [`Message`]: ../model/struct.Message.html
     #[serde(default)]
     pub selective: bool,
+    /// Requests clients to always show the keyboard when the regular keyboard
+    /// is hidden. Defaults to false, in which case the custom keyboard can be
+    /// hidden and opened with a keyboard icon.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_persistent: bool,
+    /// The placeholder to be shown in the input field when the keyboard is
+    /// active; 1-64 characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_field_placeholder: Option<String>,
+}
+
+impl ReplyKeyboardMarkup {
+    /// Creates a new [`ReplyKeyboardBuilder`] for building a custom keyboard
+    pub fn builder() -> ReplyKeyboardBuilder {
+        ReplyKeyboardBuilder::new()
+    }
+}
+
+/// A builder for easily creating a [`ReplyKeyboardMarkup`], adding buttons to
+/// the current row and starting a new row using [`row`].
+///
+/// ```rust
+/// use telexide::model::ReplyKeyboardMarkup;
+///
+/// let markup = ReplyKeyboardMarkup::builder()
+///     .text("hello")
+///     .row()
+///     .request_location("share location")
+///     .set_one_time_keyboard(true)
+///     .build();
+///
+/// assert_eq!(markup.keyboard.len(), 2);
+/// ```
+///
+/// [`row`]: #method.row
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplyKeyboardBuilder {
+    rows: Vec<Vec<KeyboardButton>>,
+    resize_keyboard: bool,
+    one_time_keyboard: bool,
+    selective: bool,
+    is_persistent: bool,
+    input_field_placeholder: Option<String>,
+}
+
+impl ReplyKeyboardBuilder {
+    /// Creates a new builder without any buttons
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new row, new buttons will be added to this row
+    pub fn row(&mut self) -> &mut Self {
+        self.rows.push(Vec::new());
+        self
+    }
+
+    /// Adds the button to the current row
+    pub fn button(&mut self, button: KeyboardButton) -> &mut Self {
+        match self.rows.last_mut() {
+            Some(row) => row.push(button),
+            None => self.rows.push(vec![button]),
+        }
+        self
+    }
+
+    /// Adds a button sending its text as a message
+    pub fn text(&mut self, text: &str) -> &mut Self {
+        self.button(KeyboardButton::text(text))
+    }
+
+    /// Adds a button sending the user's phone number as a contact
+    pub fn request_contact(&mut self, text: &str) -> &mut Self {
+        self.button(KeyboardButton::request_contact(text))
+    }
+
+    /// Adds a button sending the user's current location
+    pub fn request_location(&mut self, text: &str) -> &mut Self {
+        self.button(KeyboardButton::request_location(text))
+    }
+
+    /// Adds a button asking the user to create a poll, of the given type if
+    /// provided
+    pub fn request_poll(&mut self, text: &str, poll_type: Option<PollType>) -> &mut Self {
+        self.button(KeyboardButton::request_poll(text, poll_type))
+    }
+
+    /// Adds a button asking the user to pick one or more users to share
+    pub fn request_users(&mut self, text: &str, request: KeyboardButtonRequestUsers) -> &mut Self {
+        self.button(KeyboardButton::request_users(text, request))
+    }
+
+    /// Adds a button asking the user to pick a chat to share
+    pub fn request_chat(&mut self, text: &str, request: KeyboardButtonRequestChat) -> &mut Self {
+        self.button(KeyboardButton::request_chat(text, request))
+    }
+
+    /// Sets whether clients should resize the keyboard vertically for optimal
+    /// fit
+    pub fn set_resize_keyboard(&mut self, resize_keyboard: bool) -> &mut Self {
+        self.resize_keyboard = resize_keyboard;
+        self
+    }
+
+    /// Sets whether clients should hide the keyboard as soon as it's been used
+    pub fn set_one_time_keyboard(&mut self, one_time_keyboard: bool) -> &mut Self {
+        self.one_time_keyboard = one_time_keyboard;
+        self
+    }
+
+    /// Sets whether the keyboard should only be shown to specific users
+    pub fn set_selective(&mut self, selective: bool) -> &mut Self {
+        self.selective = selective;
+        self
+    }
+
+    /// Sets whether clients should keep showing the keyboard when the regular
+    /// keyboard is hidden
+    pub fn set_is_persistent(&mut self, is_persistent: bool) -> &mut Self {
+        self.is_persistent = is_persistent;
+        self
+    }
+
+    /// Sets the placeholder shown in the input field while the keyboard is
+    /// active, which has to be 1-64 characters
+    pub fn set_input_field_placeholder(&mut self, placeholder: &str) -> &mut Self {
+        self.input_field_placeholder = Some(placeholder.to_owned());
+        self
+    }
+
+    /// Builds the [`ReplyKeyboardMarkup`], skipping empty rows
+    pub fn build(&self) -> ReplyKeyboardMarkup {
+        ReplyKeyboardMarkup {
+            keyboard: self
+                .rows
+                .iter()
+                .filter(|r| !r.is_empty())
+                .cloned()
+                .collect(),
+            resize_keyboard: self.resize_keyboard,
+            one_time_keyboard: self.one_time_keyboard,
+            selective: self.selective,
+            is_persistent: self.is_persistent,
+            input_field_placeholder: self.input_field_placeholder.clone(),
+        }
+    }
 }
 
 /// Upon receiving a message with this object, Telegram clients will remove
@@ -159,14 +624,36 @@ pub struct ForceReply {
     /// [`Message`]: ../model/struct.Message.html
     #[serde(default)]
     pub selective: bool,
+    /// The placeholder to be shown in the input field when the reply is
+    /// active; 1-64 characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_field_placeholder: Option<String>,
+}
+
+impl ForceReply {
+    /// Creates a [`ForceReply`], for all users if `selective` is false
+    pub fn new(selective: bool) -> Self {
+        Self {
+            force_reply: true,
+            selective,
+            input_field_placeholder: None,
+        }
+    }
+
+    /// Sets the placeholder shown in the input field while the reply is
+    /// active, which has to be 1-64 characters
+    pub fn set_input_field_placeholder(&mut self, placeholder: &str) -> &mut Self {
+        self.input_field_placeholder = Some(placeholder.to_owned());
+        self
+    }
 }
 
 /// This object represents one button of the reply keyboard.
 /// For simple text buttons String can be used instead of this object to specify
 /// text of the button.
 ///
-/// **Note:** Optional fields `request_contact`, `request_location`, and
-/// `request_poll` are mutually exclusive.
+/// **Note:** Optional fields `request_contact`, `request_location`,
+/// `request_poll`, `request_users` and `request_chat` are mutually exclusive.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct KeyboardButton {
     /// Text of the button. If none of the optional fields are used,
@@ -180,7 +667,200 @@ pub struct KeyboardButton {
     pub request_location: bool,
     /// If specified, the user will be asked to create a poll and send it to the
     /// bot when the button is pressed. Available in private chats only
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub request_poll: Option<KeyboardButtonPollType>,
+    /// If specified, pressing the button will open a list of suitable users.
+    /// Identifiers of the selected users will be sent to the bot in a
+    /// `users_shared` service message. Available in private chats only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_users: Option<KeyboardButtonRequestUsers>,
+    /// If specified, pressing the button will open a list of suitable chats.
+    /// Tapping on a chat will send its identifier to the bot in a
+    /// `chat_shared` service message. Available in private chats only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_chat: Option<KeyboardButtonRequestChat>,
+}
+
+impl KeyboardButton {
+    /// Creates a button sending its text as a message
+    pub fn text(text: &str) -> Self {
+        Self {
+            text: text.to_owned(),
+            request_contact: false,
+            request_location: false,
+            request_poll: None,
+            request_users: None,
+            request_chat: None,
+        }
+    }
+
+    /// Creates a button sending the user's phone number as a contact
+    pub fn request_contact(text: &str) -> Self {
+        Self {
+            request_contact: true,
+            ..Self::text(text)
+        }
+    }
+
+    /// Creates a button sending the user's current location
+    pub fn request_location(text: &str) -> Self {
+        Self {
+            request_location: true,
+            ..Self::text(text)
+        }
+    }
+
+    /// Creates a button asking the user to create a poll, of the given type if
+    /// provided
+    pub fn request_poll(text: &str, poll_type: Option<PollType>) -> Self {
+        Self {
+            request_poll: Some(KeyboardButtonPollType {
+                poll_type,
+            }),
+            ..Self::text(text)
+        }
+    }
+
+    /// Creates a button asking the user to pick one or more users to share
+    /// with the bot
+    pub fn request_users(text: &str, request: KeyboardButtonRequestUsers) -> Self {
+        Self {
+            request_users: Some(request),
+            ..Self::text(text)
+        }
+    }
+
+    /// Creates a button asking the user to pick a chat to share with the bot
+    pub fn request_chat(text: &str, request: KeyboardButtonRequestChat) -> Self {
+        Self {
+            request_chat: Some(request),
+            ..Self::text(text)
+        }
+    }
+}
+
+/// This object defines the criteria used to request suitable users. The
+/// identifiers of the selected users will be shared with the bot when the
+/// corresponding button is pressed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KeyboardButtonRequestUsers {
+    /// Signed 32-bit identifier of the request, which will be received back in
+    /// the [`UsersShared`] object. Must be unique within the message
+    pub request_id: i64,
+    /// Pass True to request bots, pass False to request regular users. If not
+    /// specified, no additional restrictions are applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_is_bot: Option<bool>,
+    /// Pass True to request premium users, pass False to request non-premium
+    /// users. If not specified, no additional restrictions are applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_is_premium: Option<bool>,
+    /// The maximum number of users to be selected; 1-10. Defaults to 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_quantity: Option<i64>,
+    /// Pass True to request the users' first and last names
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub request_name: bool,
+    /// Pass True to request the users' usernames
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub request_username: bool,
+    /// Pass True to request the users' photos
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub request_photo: bool,
+}
+
+impl KeyboardButtonRequestUsers {
+    /// Creates a request for a single user, without any restrictions
+    pub fn new(request_id: i64) -> Self {
+        Self {
+            request_id,
+            user_is_bot: None,
+            user_is_premium: None,
+            max_quantity: None,
+            request_name: false,
+            request_username: false,
+            request_photo: false,
+        }
+    }
+
+    /// Returns true if the shared users are the answer to this request
+    pub fn is_answered_by(&self, shared: &UsersShared) -> bool {
+        self.request_id == shared.request_id
+    }
+}
+
+/// This object defines the criteria used to request a suitable chat. The
+/// identifier of the selected chat will be shared with the bot when the
+/// corresponding button is pressed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KeyboardButtonRequestChat {
+    /// Signed 32-bit identifier of the request, which will be received back in
+    /// the [`ChatShared`] object. Must be unique within the message
+    pub request_id: i64,
+    /// Pass True to request a channel chat, pass False to request a group or a
+    /// supergroup chat.
+    pub chat_is_channel: bool,
+    /// Pass True to request a forum supergroup, pass False to request a
+    /// non-forum chat. If not specified, no additional restrictions are
+    /// applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_is_forum: Option<bool>,
+    /// Pass True to request a supergroup or a channel with a username, pass
+    /// False to request a chat without a username. If not specified, no
+    /// additional restrictions are applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_has_username: Option<bool>,
+    /// Pass True to request a chat owned by the user. Otherwise, no additional
+    /// restrictions are applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_is_created: Option<bool>,
+    /// The required administrator rights of the user in the chat. If not
+    /// specified, no additional restrictions are applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_administrator_rights: Option<ChatAdministratorRights>,
+    /// The required administrator rights of the bot in the chat. The rights
+    /// must be a subset of `user_administrator_rights`. If not specified, no
+    /// additional restrictions are applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_administrator_rights: Option<ChatAdministratorRights>,
+    /// Pass True to request a chat with the bot as a member. Otherwise, no
+    /// additional restrictions are applied.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bot_is_member: bool,
+    /// Pass True to request the chat's title
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub request_title: bool,
+    /// Pass True to request the chat's username
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub request_username: bool,
+    /// Pass True to request the chat's photo
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub request_photo: bool,
+}
+
+impl KeyboardButtonRequestChat {
+    /// Creates a request for a group or supergroup chat, or a channel chat if
+    /// `chat_is_channel` is true
+    pub fn new(request_id: i64, chat_is_channel: bool) -> Self {
+        Self {
+            request_id,
+            chat_is_channel,
+            chat_is_forum: None,
+            chat_has_username: None,
+            chat_is_created: None,
+            user_administrator_rights: None,
+            bot_administrator_rights: None,
+            bot_is_member: false,
+            request_title: false,
+            request_username: false,
+            request_photo: false,
+        }
+    }
+
+    /// Returns true if the shared chat is the answer to this request
+    pub fn is_answered_by(&self, shared: &ChatShared) -> bool {
+        self.request_id == shared.request_id
+    }
 }
 
 /// This object represents type of a poll, which is allowed to be created and
@@ -191,5 +871,6 @@ pub struct KeyboardButtonPollType {
     /// quiz mode. If regular is passed, only regular polls will be allowed.
     /// Otherwise, the user will be allowed to create a poll of any type.
     #[serde(rename = "type")]
-    pub poll_type: super::PollType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll_type: Option<PollType>,
 }
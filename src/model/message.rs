@@ -5,6 +5,7 @@ use super::{
     message_contents::*,
     message_entity::*,
     raw::*,
+    utils::{unix_date_formatting, unknown::warn_unknown},
     Game,
     InlineKeyboardMarkup,
     Invoice,
@@ -19,6 +20,9 @@ use super::{
 pub struct Message {
     /// Unique message identifier inside this chat
     pub message_id: i64,
+    /// Unique identifier of the message thread or forum topic the message
+    /// belongs to; for supergroups only
+    pub message_thread_id: Option<i64>,
     /// Sender, empty for messages sent to channels
     pub from: Option<super::User>,
     /// Sender of the message, sent on behalf of a chat. The channel itself for
@@ -30,9 +34,13 @@ pub struct Message {
     pub date: DateTime<Utc>,
     /// Conversation the message belongs to
     pub chat: super::Chat,
+    /// True, if the message is sent to a forum topic
+    pub is_topic_message: bool,
 
     /// Data about what message it was forwarded from
     pub forward_data: Option<ForwardData>,
+    /// Information about the original message for forwarded messages
+    pub forward_origin: Option<MessageOrigin>,
 
     pub reply_to_message: Option<Box<Message>>,
     /// Bot through which the message was sent
@@ -54,9 +62,13 @@ pub struct Message {
     pub reply_markup: Option<InlineKeyboardMarkup>,
 }
 
-/// The content of a [`Message`]
+/// The content of a [`Message`].
+///
+/// New message types are regularly added to telegram and thus to this enum, so
+/// it is marked as non-exhaustive.
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum MessageContent {
     Text {
         /// The actual UTF-8 text of the message, 0-4096 characters
@@ -91,6 +103,8 @@ pub enum MessageContent {
         /// Special entities like usernames, URLs, bot commands, etc. that
         /// appear in the caption
         caption_entities: Option<Vec<MessageEntity>>,
+        /// True, if the media is covered by a spoiler animation
+        has_media_spoiler: bool,
     },
     Video {
         /// Information about the video
@@ -103,6 +117,8 @@ pub enum MessageContent {
         /// The unique identifier of a media message group this message belongs
         /// to
         media_group_id: Option<String>,
+        /// True, if the media is covered by a spoiler animation
+        has_media_spoiler: bool,
     },
     Voice {
         /// Information about the voice file
@@ -124,6 +140,8 @@ pub enum MessageContent {
         /// The unique identifier of a media message group this message belongs
         /// to
         media_group_id: Option<String>,
+        /// True, if the media is covered by a spoiler animation
+        has_media_spoiler: bool,
     },
 
     Game {
@@ -230,6 +248,50 @@ pub enum MessageContent {
         /// Service message: new participants invited to a voice chat
         content: VoiceChatParticipantsInvited,
     },
+    UsersShared {
+        /// Service message: users were shared with the bot
+        content: UsersShared,
+    },
+    ChatShared {
+        /// Service message: a chat was shared with the bot
+        content: ChatShared,
+    },
+    WriteAccessAllowed {
+        /// Service message: the user allowed the bot to write messages
+        content: WriteAccessAllowed,
+    },
+    BoostAdded {
+        /// Service message: the user boosted the chat
+        content: ChatBoostAdded,
+    },
+    ChatBackgroundSet {
+        /// Service message: the chat background was set
+        content: ChatBackground,
+    },
+    ForumTopicCreated {
+        /// Service message: a forum topic was created
+        content: ForumTopicCreated,
+    },
+    ForumTopicEdited {
+        /// Service message: a forum topic was edited
+        content: ForumTopicEdited,
+    },
+    ForumTopicClosed {
+        /// Service message: a forum topic was closed
+        content: ForumTopicClosed,
+    },
+    ForumTopicReopened {
+        /// Service message: a forum topic was reopened
+        content: ForumTopicReopened,
+    },
+    GeneralForumTopicHidden {
+        /// Service message: the General forum topic was hidden
+        content: GeneralForumTopicHidden,
+    },
+    GeneralForumTopicUnhidden {
+        /// Service message: the General forum topic was unhidden
+        content: GeneralForumTopicUnhidden,
+    },
 
     /// Service message: the chat photo was deleted
     DeleteChatPhoto,
@@ -251,6 +313,158 @@ pub enum MessageContent {
     Unknown,
 }
 
+/// The kind of content of a message, without the content itself. Used to for
+/// example filter messages by their content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ContentKind {
+    Text,
+    Audio,
+    Document,
+    Animation,
+    Video,
+    Voice,
+    Photo,
+    Game,
+    Sticker,
+    VideoNote,
+    Contact,
+    Location,
+    Venue,
+    Poll,
+    Dice,
+    NewChatMembers,
+    LeftChatMember,
+    NewChatTitle,
+    NewChatPhoto,
+    MessageAutoDeleteTimerChanged,
+    MigrateToChatID,
+    MigrateFromChatID,
+    PinnedMessage,
+    Invoice,
+    SuccessfulPayment,
+    ProximityAlertTriggered,
+    VoiceChatScheduled,
+    VoiceChatStarted,
+    VoiceChatEnded,
+    VoiceChatParticipantsInvited,
+    UsersShared,
+    ChatShared,
+    WriteAccessAllowed,
+    BoostAdded,
+    ChatBackgroundSet,
+    ForumTopicCreated,
+    ForumTopicEdited,
+    ForumTopicClosed,
+    ForumTopicReopened,
+    GeneralForumTopicHidden,
+    GeneralForumTopicUnhidden,
+    DeleteChatPhoto,
+    GroupChatCreated,
+    SupergroupChatCreated,
+    ChannelChatCreated,
+    Unknown,
+}
+
+impl MessageContent {
+    /// Gets the kind of the content
+    pub fn kind(&self) -> ContentKind {
+        match self {
+            MessageContent::Text { .. } => ContentKind::Text,
+            MessageContent::Audio { .. } => ContentKind::Audio,
+            MessageContent::Document { .. } => ContentKind::Document,
+            MessageContent::Animation { .. } => ContentKind::Animation,
+            MessageContent::Video { .. } => ContentKind::Video,
+            MessageContent::Voice { .. } => ContentKind::Voice,
+            MessageContent::Photo { .. } => ContentKind::Photo,
+            MessageContent::Game { .. } => ContentKind::Game,
+            MessageContent::Sticker { .. } => ContentKind::Sticker,
+            MessageContent::VideoNote { .. } => ContentKind::VideoNote,
+            MessageContent::Contact { .. } => ContentKind::Contact,
+            MessageContent::Location { .. } => ContentKind::Location,
+            MessageContent::Venue { .. } => ContentKind::Venue,
+            MessageContent::Poll { .. } => ContentKind::Poll,
+            MessageContent::Dice { .. } => ContentKind::Dice,
+            MessageContent::NewChatMembers { .. } => ContentKind::NewChatMembers,
+            MessageContent::LeftChatMember { .. } => ContentKind::LeftChatMember,
+            MessageContent::NewChatTitle { .. } => ContentKind::NewChatTitle,
+            MessageContent::NewChatPhoto { .. } => ContentKind::NewChatPhoto,
+            MessageContent::MessageAutoDeleteTimerChanged { .. } => {
+                ContentKind::MessageAutoDeleteTimerChanged
+            },
+            MessageContent::MigrateToChatID { .. } => ContentKind::MigrateToChatID,
+            MessageContent::MigrateFromChatID { .. } => ContentKind::MigrateFromChatID,
+            MessageContent::PinnedMessage { .. } => ContentKind::PinnedMessage,
+            MessageContent::Invoice { .. } => ContentKind::Invoice,
+            MessageContent::SuccessfulPayment { .. } => ContentKind::SuccessfulPayment,
+            MessageContent::ProximityAlertTriggered { .. } => ContentKind::ProximityAlertTriggered,
+            MessageContent::VoiceChatScheduled { .. } => ContentKind::VoiceChatScheduled,
+            MessageContent::VoiceChatStarted { .. } => ContentKind::VoiceChatStarted,
+            MessageContent::VoiceChatEnded { .. } => ContentKind::VoiceChatEnded,
+            MessageContent::VoiceChatParticipantsInvited { .. } => {
+                ContentKind::VoiceChatParticipantsInvited
+            },
+            MessageContent::UsersShared { .. } => ContentKind::UsersShared,
+            MessageContent::ChatShared { .. } => ContentKind::ChatShared,
+            MessageContent::WriteAccessAllowed { .. } => ContentKind::WriteAccessAllowed,
+            MessageContent::BoostAdded { .. } => ContentKind::BoostAdded,
+            MessageContent::ChatBackgroundSet { .. } => ContentKind::ChatBackgroundSet,
+            MessageContent::ForumTopicCreated { .. } => ContentKind::ForumTopicCreated,
+            MessageContent::ForumTopicEdited { .. } => ContentKind::ForumTopicEdited,
+            MessageContent::ForumTopicClosed { .. } => ContentKind::ForumTopicClosed,
+            MessageContent::ForumTopicReopened { .. } => ContentKind::ForumTopicReopened,
+            MessageContent::GeneralForumTopicHidden { .. } => ContentKind::GeneralForumTopicHidden,
+            MessageContent::GeneralForumTopicUnhidden { .. } => {
+                ContentKind::GeneralForumTopicUnhidden
+            },
+            MessageContent::DeleteChatPhoto => ContentKind::DeleteChatPhoto,
+            MessageContent::GroupChatCreated => ContentKind::GroupChatCreated,
+            MessageContent::SupergroupChatCreated => ContentKind::SupergroupChatCreated,
+            MessageContent::ChannelChatCreated => ContentKind::ChannelChatCreated,
+            MessageContent::Unknown => ContentKind::Unknown,
+        }
+    }
+}
+
+impl ContentKind {
+    /// Whether the kind is a service message, sent by telegram about a change
+    /// in the chat rather than by a user
+    pub fn is_service(self) -> bool {
+        matches!(
+            self,
+            ContentKind::NewChatMembers
+                | ContentKind::LeftChatMember
+                | ContentKind::NewChatTitle
+                | ContentKind::NewChatPhoto
+                | ContentKind::MessageAutoDeleteTimerChanged
+                | ContentKind::MigrateToChatID
+                | ContentKind::MigrateFromChatID
+                | ContentKind::PinnedMessage
+                | ContentKind::SuccessfulPayment
+                | ContentKind::ProximityAlertTriggered
+                | ContentKind::VoiceChatScheduled
+                | ContentKind::VoiceChatStarted
+                | ContentKind::VoiceChatEnded
+                | ContentKind::VoiceChatParticipantsInvited
+                | ContentKind::UsersShared
+                | ContentKind::ChatShared
+                | ContentKind::WriteAccessAllowed
+                | ContentKind::BoostAdded
+                | ContentKind::ChatBackgroundSet
+                | ContentKind::ForumTopicCreated
+                | ContentKind::ForumTopicEdited
+                | ContentKind::ForumTopicClosed
+                | ContentKind::ForumTopicReopened
+                | ContentKind::GeneralForumTopicHidden
+                | ContentKind::GeneralForumTopicUnhidden
+                | ContentKind::DeleteChatPhoto
+                | ContentKind::GroupChatCreated
+                | ContentKind::SupergroupChatCreated
+                | ContentKind::ChannelChatCreated
+        )
+    }
+}
+
 /// Holds information about the forwarded message
 #[derive(Debug, Clone, PartialEq)]
 pub struct ForwardData {
@@ -272,39 +486,378 @@ pub struct ForwardData {
     pub date: DateTime<Utc>,
 }
 
+/// This object describes the origin of a message.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum MessageOrigin {
+    #[serde(rename = "user")]
+    User(MessageOriginUser),
+    #[serde(rename = "hidden_user")]
+    HiddenUser(MessageOriginHiddenUser),
+    #[serde(rename = "chat")]
+    Chat(MessageOriginChat),
+    #[serde(rename = "channel")]
+    Channel(MessageOriginChannel),
+}
+
+/// The message was originally sent by a known user.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MessageOriginUser {
+    /// Date the message was sent originally
+    #[serde(with = "unix_date_formatting")]
+    pub date: DateTime<Utc>,
+    /// User that sent the message originally
+    pub sender_user: User,
+}
+
+/// The message was originally sent by an unknown user.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MessageOriginHiddenUser {
+    /// Date the message was sent originally
+    #[serde(with = "unix_date_formatting")]
+    pub date: DateTime<Utc>,
+    /// Name of the user that sent the message originally
+    pub sender_user_name: String,
+}
+
+/// The message was originally sent on behalf of a chat to a group chat.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MessageOriginChat {
+    /// Date the message was sent originally
+    #[serde(with = "unix_date_formatting")]
+    pub date: DateTime<Utc>,
+    /// Chat that sent the message originally
+    pub sender_chat: super::Chat,
+    /// For messages originally sent by an anonymous chat administrator,
+    /// original message author signature
+    pub author_signature: Option<String>,
+}
+
+/// The message was originally sent to a channel chat.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MessageOriginChannel {
+    /// Date the message was sent originally
+    #[serde(with = "unix_date_formatting")]
+    pub date: DateTime<Utc>,
+    /// Channel chat to which the message was originally sent
+    pub chat: super::Chat,
+    /// Unique message identifier inside the chat
+    pub message_id: i64,
+    /// Signature of the original post author
+    pub author_signature: Option<String>,
+}
+
+impl MessageOrigin {
+    /// Date the message was sent originally
+    pub fn date(&self) -> DateTime<Utc> {
+        match self {
+            MessageOrigin::User(o) => o.date,
+            MessageOrigin::HiddenUser(o) => o.date,
+            MessageOrigin::Chat(o) => o.date,
+            MessageOrigin::Channel(o) => o.date,
+        }
+    }
+
+    /// The chat the message originally came from, if it was sent on behalf of
+    /// a chat or to a channel
+    pub fn chat(&self) -> Option<&super::Chat> {
+        match self {
+            MessageOrigin::Chat(o) => Some(&o.sender_chat),
+            MessageOrigin::Channel(o) => Some(&o.chat),
+            _ => None,
+        }
+    }
+}
+
+impl From<MessageOrigin> for ForwardData {
+    fn from(origin: MessageOrigin) -> ForwardData {
+        let mut data = ForwardData {
+            from: None,
+            from_chat: None,
+            from_message_id: None,
+            signature: None,
+            sender_name: None,
+            date: origin.date(),
+        };
+
+        match origin {
+            MessageOrigin::User(o) => data.from = Some(o.sender_user),
+            MessageOrigin::HiddenUser(o) => data.sender_name = Some(o.sender_user_name),
+            MessageOrigin::Chat(o) => {
+                data.from_chat = Some(o.sender_chat);
+                data.signature = o.author_signature;
+            },
+            MessageOrigin::Channel(o) => {
+                data.from_chat = Some(o.chat);
+                data.from_message_id = Some(o.message_id);
+                data.signature = o.author_signature;
+            },
+        }
+
+        data
+    }
+}
+
+impl ForwardData {
+    /// Converts the legacy forward fields into a [`MessageOrigin`], returns
+    /// `None` if they don't describe any known origin
+    pub fn to_origin(&self) -> Option<MessageOrigin> {
+        let date = self.date;
+        match (&self.from_chat, self.from_message_id, &self.from, &self.sender_name) {
+            (Some(chat), Some(message_id), ..) => Some(MessageOrigin::Channel(MessageOriginChannel {
+                date,
+                chat: chat.clone(),
+                message_id,
+                author_signature: self.signature.clone(),
+            })),
+            (Some(chat), None, ..) => Some(MessageOrigin::Chat(MessageOriginChat {
+                date,
+                sender_chat: chat.clone(),
+                author_signature: self.signature.clone(),
+            })),
+            (None, _, Some(user), _) => Some(MessageOrigin::User(MessageOriginUser {
+                date,
+                sender_user: user.clone(),
+            })),
+            (None, _, None, Some(name)) => {
+                Some(MessageOrigin::HiddenUser(MessageOriginHiddenUser {
+                    date,
+                    sender_user_name: name.clone(),
+                }))
+            },
+            _ => None,
+        }
+    }
+}
+
 impl Message {
+    /// Returns true if the message was forwarded from somewhere else
+    pub fn is_forwarded(&self) -> bool {
+        self.forward_origin.is_some() || self.forward_data.is_some()
+    }
+
+    /// The chat the message was originally sent in or on behalf of, if it was
+    /// forwarded from a chat or channel
+    pub fn forward_origin_chat(&self) -> Option<&super::Chat> {
+        self.forward_origin.as_ref().and_then(MessageOrigin::chat)
+    }
+
     pub fn get_text(&self) -> Option<String> {
+        self.formatted_text().map(|(text, _)| text.to_owned())
+    }
+
+    /// Gets the text of the message, or the caption if it is a media message,
+    /// without copying it like [`Message::get_text`]
+    pub fn text_or_caption(&self) -> Option<&str> {
+        self.formatted_text().map(|(text, _)| text)
+    }
+
+    /// Gets the user that sent the message, which is empty for messages sent
+    /// to channels
+    pub fn from_user(&self) -> Option<&User> {
+        self.from.as_ref()
+    }
+
+    /// Gets the file id of the media of the message, for photos this is the
+    /// largest size. This covers photos, videos, animations, documents, audio,
+    /// voice messages, video notes and stickers.
+    pub fn media_file_id(&self) -> Option<&str> {
+        match &self.content {
+            MessageContent::Photo {
+                content, ..
+            } => content.last().map(|p| p.file_id.as_str()),
+            MessageContent::Video {
+                content, ..
+            } => Some(&content.file_id),
+            MessageContent::Animation {
+                content, ..
+            } => Some(&content.file_id),
+            MessageContent::Document {
+                content, ..
+            } => Some(&content.file_id),
+            MessageContent::Audio {
+                content, ..
+            } => Some(&content.file_id),
+            MessageContent::Voice {
+                content, ..
+            } => Some(&content.file_id),
+            MessageContent::VideoNote {
+                content,
+            } => Some(&content.file_id),
+            MessageContent::Sticker {
+                content,
+            } => Some(&content.file_id),
+            _ => None,
+        }
+    }
+
+    /// Whether the message is a service message, sent by telegram about a
+    /// change in the chat rather than by a user
+    pub fn is_service_message(&self) -> bool {
+        self.content.kind().is_service()
+    }
+
+    /// Gets the id of the forum topic the message was sent to, which is `None`
+    /// for messages outside of forums and for replies in threads that aren't
+    /// topics
+    pub fn topic_id(&self) -> Option<i64> {
+        self.message_thread_id.filter(|_| self.is_topic_message)
+    }
+
+    /// Gets the venue of the message, if it is a venue message
+    pub fn get_venue(&self) -> Option<&Venue> {
+        match self.content {
+            MessageContent::Venue {
+                ref content,
+            } => Some(content),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the photo, video or animation of the message is covered
+    /// by a spoiler animation
+    pub fn has_media_spoiler(&self) -> bool {
+        match self.content {
+            MessageContent::Animation {
+                has_media_spoiler, ..
+            }
+            | MessageContent::Video {
+                has_media_spoiler, ..
+            }
+            | MessageContent::Photo {
+                has_media_spoiler, ..
+            } => has_media_spoiler,
+            _ => false,
+        }
+    }
+
+    /// Gets the entities of the text or the caption of the message
+    pub fn get_entities(&self) -> Option<&[MessageEntity]> {
+        self.formatted_text().map(|(_, entities)| entities)
+    }
+
+    /// Gets the text or caption of the message together with its entities,
+    /// which can be used to send the text again with the same formatting
+    pub fn formatted_text(&self) -> Option<(&str, &[MessageEntity])> {
         match self.content {
             MessageContent::Text {
-                ref content, ..
-            } => Some(content.clone()),
+                ref content,
+                ref entities,
+            } => Some((content, entities)),
             MessageContent::Audio {
-                ref caption, ..
+                ref caption,
+                ref caption_entities,
+                ..
             }
             | MessageContent::Document {
-                ref caption, ..
+                ref caption,
+                ref caption_entities,
+                ..
             }
             | MessageContent::Animation {
-                ref caption, ..
+                ref caption,
+                ref caption_entities,
+                ..
             }
             | MessageContent::Video {
-                ref caption, ..
+                ref caption,
+                ref caption_entities,
+                ..
             }
             | MessageContent::Voice {
-                ref caption, ..
+                ref caption,
+                ref caption_entities,
+                ..
             }
             | MessageContent::Photo {
-                ref caption, ..
-            } => caption.clone(),
+                ref caption,
+                ref caption_entities,
+                ..
+            } => Some((
+                caption.as_deref()?,
+                caption_entities.as_deref().unwrap_or_default(),
+            )),
             _ => None,
         }
     }
+
+    /// Gets the part of the text or caption of the message that the given
+    /// entity applies to, correctly handling the UTF-16 based offsets
+    pub fn get_entity_text(&self, entity: &MessageEntity) -> Option<String> {
+        entity.get_text(&self.get_text()?)
+    }
+
+    /// Gets all the entities of the text or caption of the message that are of
+    /// the given type
+    pub fn entities_of_type(
+        &self,
+        entity_type: MessageEntityType,
+    ) -> impl Iterator<Item = &MessageEntity> {
+        self.get_entities()
+            .into_iter()
+            .flatten()
+            .filter(move |e| e.get_type() == entity_type)
+    }
+
+    /// Gets the first bot command in the message as it was written, for
+    /// example `/start@bot_name`
+    pub fn extract_command(&self) -> Option<String> {
+        self.entities_of_type(MessageEntityType::BotCommand)
+            .next()
+            .and_then(|e| self.get_entity_text(e))
+    }
+
+    /// Gets all the urls in the message, both the ones written in the text and
+    /// the ones behind text links
+    pub fn extract_urls(&self) -> Vec<String> {
+        self.get_entities()
+            .into_iter()
+            .flatten()
+            .filter_map(|e| match e {
+                MessageEntity::Url(_) => self.get_entity_text(e),
+                MessageEntity::TextLink(l) => Some(l.url.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Gets the `t.me` link to the message, which is
+    /// `https://t.me/<username>/<message_id>` for public supergroups and
+    /// channels and `https://t.me/c/<id>/<message_id>` for private ones.
+    /// Returns `None` for private chats and basic groups, which don't have
+    /// message links.
+    pub fn url(&self) -> Option<String> {
+        self.link(&self.chat)
+    }
+
+    /// Gets the `t.me` link to the message like [`Message::url`], but using
+    /// the given chat, for example one with more information retrieved using
+    /// [`get_chat`]. The chat should be the chat the message was sent in.
+    ///
+    /// [`get_chat`]: ../api/trait.API.html#method.get_chat
+    pub fn link(&self, chat: &super::Chat) -> Option<String> {
+        let (id, username) = match chat {
+            super::Chat::SuperGroup(c) => (c.id, &c.username),
+            super::Chat::Channel(c) => (c.id, &c.username),
+            _ => return None,
+        };
+
+        if let Some(username) = username {
+            return Some(format!("https://t.me/{}/{}", username, self.message_id));
+        }
+
+        // the ids of supergroups and channels are their internal id prefixed by -100
+        let short_id = id.to_string().strip_prefix("-100")?.to_owned();
+        Some(format!("https://t.me/c/{}/{}", short_id, self.message_id))
+    }
 }
 
 impl From<RawMessage> for Message {
     #[allow(clippy::too_many_lines)]
     fn from(raw: RawMessage) -> Message {
         let message_id = raw.message_id;
+        let message_thread_id = raw.message_thread_id;
+        let is_topic_message = raw.is_topic_message;
         let from = raw.from;
         let sender_chat = raw.sender_chat.map(|c| c.into());
         let date = raw.date;
@@ -327,16 +880,22 @@ impl From<RawMessage> for Message {
                 date: d,
             })
         } else {
-            None
+            raw.forward_origin.clone().map(ForwardData::from)
         };
+        let forward_origin = raw
+            .forward_origin
+            .or_else(|| forward_data.as_ref().and_then(ForwardData::to_origin));
 
         let fill_in_content = |content: MessageContent| Self {
             message_id,
+            message_thread_id,
             from,
             sender_chat,
             date,
             chat,
+            is_topic_message,
             forward_data,
+            forward_origin,
             reply_to_message,
             via_bot,
             edit_date,
@@ -358,6 +917,7 @@ impl From<RawMessage> for Message {
                 caption: raw.caption,
                 caption_entities: raw.caption_entities,
                 media_group_id: raw.media_group_id,
+                has_media_spoiler: raw.has_media_spoiler,
             });
         } else if let Some(c) = raw.photo {
             return fill_in_content(MessageContent::Photo {
@@ -365,6 +925,7 @@ impl From<RawMessage> for Message {
                 caption: raw.caption,
                 caption_entities: raw.caption_entities,
                 media_group_id: raw.media_group_id,
+                has_media_spoiler: raw.has_media_spoiler,
             });
         } else if let Some(c) = raw.pinned_message {
             return fill_in_content(MessageContent::PinnedMessage {
@@ -402,8 +963,16 @@ impl From<RawMessage> for Message {
             };
         }
 
+        if let Some(c) = raw.animation {
+            return fill_in_content(MessageContent::Animation {
+                content: c,
+                caption: raw.caption,
+                caption_entities: raw.caption_entities,
+                has_media_spoiler: raw.has_media_spoiler,
+            });
+        }
+
         content_with_captions!(raw.audio, Audio);
-        content_with_captions!(raw.animation, Animation);
         content_with_captions!(raw.document, Document);
         content_with_captions!(raw.voice, Voice);
 
@@ -411,8 +980,9 @@ impl From<RawMessage> for Message {
         content!(raw.sticker, Sticker);
         content!(raw.video_note, VideoNote);
         content!(raw.contact, Contact);
-        content!(raw.location, Location);
+        // venue messages also have the location of the venue set
         content!(raw.venue, Venue);
+        content!(raw.location, Location);
         content!(raw.poll, Poll);
         content!(raw.dice, Dice);
         content!(raw.new_chat_members, NewChatMembers);
@@ -435,12 +1005,24 @@ impl From<RawMessage> for Message {
             raw.voice_chat_participants_invited,
             VoiceChatParticipantsInvited
         );
+        content!(raw.users_shared, UsersShared);
+        content!(raw.chat_shared, ChatShared);
+        content!(raw.write_access_allowed, WriteAccessAllowed);
+        content!(raw.boost_added, BoostAdded);
+        content!(raw.chat_background_set, ChatBackgroundSet);
+        content!(raw.forum_topic_created, ForumTopicCreated);
+        content!(raw.forum_topic_edited, ForumTopicEdited);
+        content!(raw.forum_topic_closed, ForumTopicClosed);
+        content!(raw.forum_topic_reopened, ForumTopicReopened);
+        content!(raw.general_forum_topic_hidden, GeneralForumTopicHidden);
+        content!(raw.general_forum_topic_unhidden, GeneralForumTopicUnhidden);
 
         bool_content!(raw.delete_chat_photo, DeleteChatPhoto);
         bool_content!(raw.group_chat_created, GroupChatCreated);
         bool_content!(raw.supergroup_chat_created, SupergroupChatCreated);
         bool_content!(raw.channel_chat_created, ChannelChatCreated);
 
+        warn_unknown("message content", "no supported content field was set");
         fill_in_content(MessageContent::Unknown)
     }
 }
@@ -450,10 +1032,12 @@ impl From<Message> for RawMessage {
     fn from(message: Message) -> RawMessage {
         let mut ret = Self {
             message_id: message.message_id,
+            message_thread_id: message.message_thread_id,
             from: message.from,
             sender_chat: message.sender_chat.map(|c| c.into()),
             date: message.date,
             chat: message.chat.into(),
+            is_topic_message: message.is_topic_message,
             reply_to_message: message.reply_to_message.map(|r| Box::new((*r).into())),
             via_bot: message.via_bot,
             edit_date: message.edit_date,
@@ -466,6 +1050,7 @@ impl From<Message> for RawMessage {
             forward_from_message_id: None,
             forward_from: None,
             forward_from_chat: None,
+            forward_origin: message.forward_origin,
 
             text: None,
             entities: None,
@@ -480,6 +1065,7 @@ impl From<Message> for RawMessage {
             voice: None,
             video_note: None,
             caption: None,
+            has_media_spoiler: false,
             contact: None,
             location: None,
             venue: None,
@@ -504,6 +1090,17 @@ impl From<Message> for RawMessage {
             voice_chat_started: None,
             voice_chat_ended: None,
             voice_chat_participants_invited: None,
+            users_shared: None,
+            chat_shared: None,
+            write_access_allowed: None,
+            boost_added: None,
+            chat_background_set: None,
+            forum_topic_created: None,
+            forum_topic_edited: None,
+            forum_topic_closed: None,
+            forum_topic_reopened: None,
+            general_forum_topic_hidden: None,
+            general_forum_topic_unhidden: None,
 
             connected_website: message.connected_website,
             passport_data: message.passport_data,
@@ -517,6 +1114,13 @@ impl From<Message> for RawMessage {
             ret.forward_from_message_id = d.from_message_id;
             ret.forward_from = d.from;
             ret.forward_from_chat = d.from_chat.map(|c| c.into());
+        } else if let Some(d) = ret.forward_origin.clone().map(ForwardData::from) {
+            ret.forward_date = Some(d.date);
+            ret.forward_sender_name = d.sender_name;
+            ret.forward_signature = d.signature;
+            ret.forward_from_message_id = d.from_message_id;
+            ret.forward_from = d.from;
+            ret.forward_from_chat = d.from_chat.map(Into::into);
         }
 
         match message.content {
@@ -552,10 +1156,12 @@ impl From<Message> for RawMessage {
                 content,
                 caption,
                 caption_entities,
+                has_media_spoiler,
             } => {
                 ret.animation = Some(content);
                 ret.caption = caption;
                 ret.caption_entities = caption_entities;
+                ret.has_media_spoiler = has_media_spoiler;
                 ret
             },
             MessageContent::Voice {
@@ -573,11 +1179,13 @@ impl From<Message> for RawMessage {
                 caption,
                 caption_entities,
                 media_group_id,
+                has_media_spoiler,
             } => {
                 ret.video = Some(content);
                 ret.caption = caption;
                 ret.caption_entities = caption_entities;
                 ret.media_group_id = media_group_id;
+                ret.has_media_spoiler = has_media_spoiler;
                 ret
             },
             MessageContent::Photo {
@@ -585,11 +1193,13 @@ impl From<Message> for RawMessage {
                 caption,
                 caption_entities,
                 media_group_id,
+                has_media_spoiler,
             } => {
                 ret.photo = Some(content);
                 ret.caption = caption;
                 ret.caption_entities = caption_entities;
                 ret.media_group_id = media_group_id;
+                ret.has_media_spoiler = has_media_spoiler;
                 ret
             },
             MessageContent::Game {
@@ -730,6 +1340,72 @@ impl From<Message> for RawMessage {
                 ret.voice_chat_participants_invited = Some(content);
                 ret
             },
+            MessageContent::UsersShared {
+                content,
+            } => {
+                ret.users_shared = Some(content);
+                ret
+            },
+            MessageContent::ChatShared {
+                content,
+            } => {
+                ret.chat_shared = Some(content);
+                ret
+            },
+            MessageContent::WriteAccessAllowed {
+                content,
+            } => {
+                ret.write_access_allowed = Some(content);
+                ret
+            },
+            MessageContent::BoostAdded {
+                content,
+            } => {
+                ret.boost_added = Some(content);
+                ret
+            },
+            MessageContent::ChatBackgroundSet {
+                content,
+            } => {
+                ret.chat_background_set = Some(content);
+                ret
+            },
+            MessageContent::ForumTopicCreated {
+                content,
+            } => {
+                ret.forum_topic_created = Some(content);
+                ret
+            },
+            MessageContent::ForumTopicEdited {
+                content,
+            } => {
+                ret.forum_topic_edited = Some(content);
+                ret
+            },
+            MessageContent::ForumTopicClosed {
+                content,
+            } => {
+                ret.forum_topic_closed = Some(content);
+                ret
+            },
+            MessageContent::ForumTopicReopened {
+                content,
+            } => {
+                ret.forum_topic_reopened = Some(content);
+                ret
+            },
+            MessageContent::GeneralForumTopicHidden {
+                content,
+            } => {
+                ret.general_forum_topic_hidden = Some(content);
+                ret
+            },
+            MessageContent::GeneralForumTopicUnhidden {
+                content,
+            } => {
+                ret.general_forum_topic_unhidden = Some(content);
+                ret
+            },
             MessageContent::DeleteChatPhoto => {
                 ret.delete_chat_photo = true;
                 ret
@@ -771,6 +1447,104 @@ impl Serialize for Message {
     }
 }
 
+/// This object describes a message that was deleted or is otherwise
+/// inaccessible to the bot.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InaccessibleMessage {
+    /// Chat the message belonged to
+    pub chat: super::Chat,
+    /// Unique message identifier inside the chat
+    pub message_id: i64,
+    /// Always 0. The field can be used to differentiate regular and
+    /// inaccessible messages.
+    #[serde(default)]
+    pub date: i64,
+}
+
+/// This object describes a message that can be inaccessible to the bot, like
+/// the message of a [`CallbackQuery`] on a message that is too old.
+///
+/// [`CallbackQuery`]: struct.CallbackQuery.html
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum MaybeInaccessibleMessage {
+    Message(Message),
+    Inaccessible(InaccessibleMessage),
+}
+
+impl MaybeInaccessibleMessage {
+    /// Unique message identifier inside the chat
+    pub fn message_id(&self) -> i64 {
+        match self {
+            Self::Message(m) => m.message_id,
+            Self::Inaccessible(m) => m.message_id,
+        }
+    }
+
+    /// The chat the message belongs to
+    pub fn chat(&self) -> &super::Chat {
+        match self {
+            Self::Message(m) => &m.chat,
+            Self::Inaccessible(m) => &m.chat,
+        }
+    }
+
+    /// The id of the chat the message belongs to
+    pub fn chat_id(&self) -> i64 {
+        self.chat().get_id()
+    }
+
+    /// Gets the message, if it is accessible
+    pub fn as_message(&self) -> Option<&Message> {
+        match self {
+            Self::Message(m) => Some(m),
+            Self::Inaccessible(_) => None,
+        }
+    }
+
+    /// Returns true if the message is accessible to the bot
+    pub fn is_accessible(&self) -> bool {
+        matches!(self, Self::Message(_))
+    }
+}
+
+impl From<Message> for MaybeInaccessibleMessage {
+    fn from(message: Message) -> Self {
+        Self::Message(message)
+    }
+}
+
+impl<'de> Deserialize<'de> for MaybeInaccessibleMessage {
+    fn deserialize<D>(deserializer: D) -> Result<MaybeInaccessibleMessage, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        if value.get("date").and_then(serde_json::Value::as_i64) == Some(0) {
+            serde_json::from_value(value)
+                .map(Self::Inaccessible)
+                .map_err(serde::de::Error::custom)
+        } else {
+            serde_json::from_value(value)
+                .map(Self::Message)
+                .map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+impl Serialize for MaybeInaccessibleMessage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Message(m) => m.serialize(serializer),
+            Self::Inaccessible(m) => m.serialize(serializer),
+        }
+    }
+}
+
 /// This object represents a unique message identifier.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct MessageId {
@@ -2,6 +2,7 @@ use super::{utils::unix_date_formatting, User};
 use crate::model::MessageEntity;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::{convert::TryFrom, time::Duration};
 
 /// This object represents an audio file to be treated as music by the Telegram
 /// clients.
@@ -208,16 +209,20 @@ pub struct Venue {
     /// Address of the venue
     pub address: String,
     /// Foursquare identifier of the venue
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foursquare_id: Option<String>,
     /// Foursquare type of the venue.
     /// (For example, “arts_entertainment/default”,
     /// “arts_entertainment/aquarium” or “food/icecream”.)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foursquare_type: Option<String>,
     /// Google Places identifier of the venue
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub google_place_id: Option<String>,
     /// Google Places type of the venue. (See [supported types].)
     ///
     /// [supported types]: https://developers.google.com/places/web-service/supported_types
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub google_place_type: Option<String>,
 }
 
@@ -263,17 +268,148 @@ pub struct Poll {
     pub close_date: Option<DateTime<Utc>>,
 }
 
+impl Poll {
+    /// Gets the total amount of votes on the options of the poll, which is
+    /// higher than [`total_voter_count`] when users chose several answers
+    ///
+    /// [`total_voter_count`]: #structfield.total_voter_count
+    pub fn total_votes(&self) -> usize {
+        self.options.iter().map(|o| o.voter_count).sum()
+    }
+
+    /// Gets the option with the most votes, or `None` if nobody voted or if
+    /// several options share the most votes. The result is only final once
+    /// the poll [`is_closed`].
+    ///
+    /// [`is_closed`]: #structfield.is_closed
+    pub fn winning_option(&self) -> Option<&PollOption> {
+        let most_votes = self.options.iter().map(|o| o.voter_count).max()?;
+        let mut leading = self.options.iter().filter(|o| o.voter_count == most_votes);
+        match (leading.next(), leading.next()) {
+            (Some(option), None) if most_votes > 0 => Some(option),
+            _ => None,
+        }
+    }
+
+    /// Gets the correct option of a quiz, if it is known
+    pub fn correct_option(&self) -> Option<&PollOption> {
+        self.options.get(self.correct_option_id?)
+    }
+}
+
 /// This object represents a dice with a random value from 1 to 6 for currently
 /// supported base emoji.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Dice {
     /// Emoji on which the dice throw animation is based
-    pub emoji: String,
+    pub emoji: DiceEmoji,
     /// Value of the dice, 1-6 for “🎲”, “🎯” and “🎳” base emoji, 1-5 for “🏀”
     /// and “⚽” base emoji, 1-64 for “🎰” base emoji
     pub value: u8,
 }
 
+impl Dice {
+    /// Gets the highest value the dice can have, or `None` for emoji that
+    /// aren't known yet
+    pub fn max_value(&self) -> Option<u8> {
+        self.emoji.max_value()
+    }
+
+    /// Whether the throw is a win: a 6 for “🎲”, a bullseye for “🎯”, a
+    /// basket for “🏀”, a goal for “⚽”, a strike for “🎳” and three of a kind
+    /// for “🎰”. Always false for emoji that aren't known yet.
+    pub fn is_win(&self) -> bool {
+        match self.emoji {
+            DiceEmoji::Dice | DiceEmoji::Darts | DiceEmoji::Bowling => self.value == 6,
+            DiceEmoji::Basketball => self.value >= 4,
+            DiceEmoji::Football => self.value >= 3,
+            DiceEmoji::SlotMachine => SLOT_MACHINE_THREE_OF_A_KIND.contains(&self.value),
+            DiceEmoji::Other(_) => false,
+        }
+    }
+
+    /// Whether the throw is the jackpot of the “🎰” slot machine, being three
+    /// sevens
+    pub fn is_jackpot(&self) -> bool {
+        self.emoji == DiceEmoji::SlotMachine && self.value == 64
+    }
+}
+
+/// The values of the “🎰” slot machine showing three of the same symbols: bars,
+/// grapes, lemons and sevens
+const SLOT_MACHINE_THREE_OF_A_KIND: [u8; 4] = [1, 22, 43, 64];
+
+/// The emoji on which the animation of a [`Dice`] is based
+///
+/// [`Dice`]: struct.Dice.html
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub enum DiceEmoji {
+    /// “🎲”, with values 1-6
+    Dice,
+    /// “🎯”, with values 1-6
+    Darts,
+    /// “🏀”, with values 1-5
+    Basketball,
+    /// “⚽”, with values 1-5
+    Football,
+    /// “🎳”, with values 1-6
+    Bowling,
+    /// “🎰”, with values 1-64
+    SlotMachine,
+    /// An emoji that was added after this version of the library
+    Other(String),
+}
+
+impl DiceEmoji {
+    /// Gets the emoji as a string
+    pub fn as_str(&self) -> &str {
+        match self {
+            DiceEmoji::Dice => "🎲",
+            DiceEmoji::Darts => "🎯",
+            DiceEmoji::Basketball => "🏀",
+            DiceEmoji::Football => "⚽",
+            DiceEmoji::Bowling => "🎳",
+            DiceEmoji::SlotMachine => "🎰",
+            DiceEmoji::Other(emoji) => emoji,
+        }
+    }
+
+    /// Gets the highest value a dice with this emoji can have, or `None` for
+    /// emoji that aren't known yet
+    pub fn max_value(&self) -> Option<u8> {
+        match self {
+            DiceEmoji::Dice | DiceEmoji::Darts | DiceEmoji::Bowling => Some(6),
+            DiceEmoji::Basketball | DiceEmoji::Football => Some(5),
+            DiceEmoji::SlotMachine => Some(64),
+            DiceEmoji::Other(_) => None,
+        }
+    }
+}
+
+impl From<String> for DiceEmoji {
+    fn from(emoji: String) -> Self {
+        match emoji.as_str() {
+            "🎲" => DiceEmoji::Dice,
+            "🎯" => DiceEmoji::Darts,
+            "🏀" => DiceEmoji::Basketball,
+            "⚽" => DiceEmoji::Football,
+            "🎳" => DiceEmoji::Bowling,
+            "🎰" => DiceEmoji::SlotMachine,
+            _ => DiceEmoji::Other(emoji),
+        }
+    }
+}
+
+impl From<DiceEmoji> for String {
+    fn from(emoji: DiceEmoji) -> Self {
+        match emoji {
+            DiceEmoji::Other(emoji) => emoji,
+            emoji => emoji.as_str().to_owned(),
+        }
+    }
+}
+
 /// This object contains information about one answer option in a poll.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct PollOption {
@@ -342,6 +478,18 @@ pub struct LoginUrl {
     pub request_write_access: bool,
 }
 
+/// Describes a [Web App] to be opened by a button
+///
+/// [Web App]: https://core.telegram.org/bots/webapps
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WebAppInfo {
+    /// An HTTPS URL of the Web App to be opened with additional data as
+    /// specified in [Initializing Web Apps]
+    ///
+    /// [Initializing Web Apps]: https://core.telegram.org/bots/webapps#initializing-web-apps
+    pub url: String,
+}
+
 /// This object represents the content of a service message, sent whenever a
 /// user in the chat triggers a proximity alert set by another user.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -385,10 +533,267 @@ pub struct VoiceChatParticipantsInvited {
     pub users: Option<Vec<User>>,
 }
 
+/// This object contains information about the users whose identifiers were
+/// shared with the bot using a [`KeyboardButtonRequestUsers`] button.
+///
+/// [`KeyboardButtonRequestUsers`]: struct.KeyboardButtonRequestUsers.html
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UsersShared {
+    /// Identifier of the request
+    pub request_id: i64,
+    /// Information about users shared with the bot
+    pub users: Vec<SharedUser>,
+}
+
+impl UsersShared {
+    /// Gets the identifiers of the shared users
+    pub fn user_ids(&self) -> Vec<i64> {
+        self.users.iter().map(|u| u.user_id).collect()
+    }
+}
+
+/// This object contains information about a user that was shared with the bot
+/// using a [`KeyboardButtonRequestUsers`] button.
+///
+/// [`KeyboardButtonRequestUsers`]: struct.KeyboardButtonRequestUsers.html
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SharedUser {
+    /// Identifier of the shared user. The bot may not have access to the user
+    /// and could be unable to use this identifier, unless the user is already
+    /// known to the bot by some other means.
+    pub user_id: i64,
+    /// First name of the user, if the name was requested by the bot
+    pub first_name: Option<String>,
+    /// Last name of the user, if the name was requested by the bot
+    pub last_name: Option<String>,
+    /// Username of the user, if the username was requested by the bot
+    pub username: Option<String>,
+    /// Available sizes of the chat photo, if the photo was requested by the bot
+    pub photo: Option<Vec<PhotoSize>>,
+}
+
+/// This object contains information about a chat that was shared with the bot
+/// using a [`KeyboardButtonRequestChat`] button.
+///
+/// [`KeyboardButtonRequestChat`]: struct.KeyboardButtonRequestChat.html
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatShared {
+    /// Identifier of the request
+    pub request_id: i64,
+    /// Identifier of the shared chat. The bot may not have access to the chat
+    /// and could be unable to use this identifier, unless the chat is already
+    /// known to the bot by some other means.
+    pub chat_id: i64,
+    /// Title of the chat, if the title was requested by the bot
+    pub title: Option<String>,
+    /// Username of the chat, if the username was requested by the bot and
+    /// available
+    pub username: Option<String>,
+    /// Available sizes of the chat photo, if the photo was requested by the bot
+    pub photo: Option<Vec<PhotoSize>>,
+}
+
 /// This object represents a service message about a change in auto-delete timer
 /// settings.
+///
+/// Bots can't change the timer themselves, as telegram doesn't offer a method
+/// for it, but the current timer of a chat can be read from
+/// [`ChatFullInfo::message_auto_delete_time`] using [`get_chat`].
+///
+/// [`ChatFullInfo::message_auto_delete_time`]: struct.ChatFullInfo.html#structfield.message_auto_delete_time
+/// [`get_chat`]: ../api/trait.API.html#method.get_chat
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct MessageAutoDeleteTimerChanged {
-    /// New auto-delete time for messages in the chat
+    /// New auto-delete time for messages in the chat; in seconds, 0 if the
+    /// timer was disabled
     pub message_auto_delete_time: i64,
 }
+
+impl MessageAutoDeleteTimerChanged {
+    /// Whether the auto-delete timer was disabled
+    pub fn is_disabled(&self) -> bool {
+        self.message_auto_delete_time <= 0
+    }
+
+    /// Gets the time after which new messages in the chat are deleted, or
+    /// `None` if the timer was disabled
+    pub fn duration(&self) -> Option<Duration> {
+        u64::try_from(self.message_auto_delete_time)
+            .ok()
+            .filter(|&seconds| seconds > 0)
+            .map(Duration::from_secs)
+    }
+}
+
+/// This object represents a service message about a user allowing a bot to
+/// write messages after adding it to the attachment menu, launching a Web App
+/// from a link, or accepting an explicit request from a Web App.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct WriteAccessAllowed {
+    /// True, if the access was granted after the user accepted an explicit
+    /// request from a Web App
+    #[serde(default)]
+    pub from_request: bool,
+    /// Name of the Web App, if the access was granted when the Web App was
+    /// launched from a link
+    pub web_app_name: Option<String>,
+    /// True, if the access was granted when the bot was added to the
+    /// attachment or side menu
+    #[serde(default)]
+    pub from_attachment_menu: bool,
+}
+
+/// This object represents a service message about a user boosting a chat.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatBoostAdded {
+    /// Number of boosts added by the user
+    #[serde(default)]
+    pub boost_count: i64,
+}
+
+/// This object represents a chat background.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatBackground {
+    /// Type of the background
+    #[serde(rename = "type")]
+    pub background_type: BackgroundType,
+}
+
+/// The type of a [`ChatBackground`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum BackgroundType {
+    /// The background is automatically filled based on the selected colors
+    #[serde(rename = "fill")]
+    Fill {
+        /// The background fill
+        fill: BackgroundFill,
+        /// Dimming of the background in dark themes, as a percentage; 0-100
+        #[serde(default)]
+        dark_theme_dimming: u8,
+    },
+    /// The background is a wallpaper in the JPEG format
+    #[serde(rename = "wallpaper")]
+    Wallpaper {
+        /// Document with the wallpaper
+        document: Document,
+        /// Dimming of the background in dark themes, as a percentage; 0-100
+        #[serde(default)]
+        dark_theme_dimming: u8,
+        /// True, if the wallpaper is downscaled to fit in a 450x450 square and
+        /// then box-blurred with radius 12
+        #[serde(default)]
+        is_blurred: bool,
+        /// True, if the background moves slightly when the device is tilted
+        #[serde(default)]
+        is_moving: bool,
+    },
+    /// The background is a PNG or TGV (gzipped subset of SVG with MIME type
+    /// “application/x-tgwallpattern”) pattern to be combined with the
+    /// background fill chosen by the user
+    #[serde(rename = "pattern")]
+    Pattern {
+        /// Document with the pattern
+        document: Document,
+        /// The background fill that is combined with the pattern
+        fill: BackgroundFill,
+        /// Intensity of the pattern when it is shown above the filled
+        /// background; 0-100
+        #[serde(default)]
+        intensity: u8,
+        /// True, if the background fill must be applied only to the pattern
+        /// itself. All other pixels are black in this case. For dark themes
+        /// only
+        #[serde(default)]
+        is_inverted: bool,
+        /// True, if the background moves slightly when the device is tilted
+        #[serde(default)]
+        is_moving: bool,
+    },
+    /// The background is taken directly from a built-in chat theme
+    #[serde(rename = "chat_theme")]
+    ChatTheme {
+        /// Name of the chat theme, which is usually an emoji
+        theme_name: String,
+    },
+    /// A type of background that isn't supported yet
+    #[serde(untagged)]
+    Unknown(serde_json::Map<String, serde_json::Value>),
+}
+
+/// The way a background is filled, used by [`BackgroundType`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum BackgroundFill {
+    /// The background is filled using the selected color
+    #[serde(rename = "solid")]
+    Solid {
+        /// The color of the background fill in the RGB24 format
+        color: u32,
+    },
+    /// The background is a gradient fill
+    #[serde(rename = "gradient")]
+    Gradient {
+        /// Top color of the gradient in the RGB24 format
+        top_color: u32,
+        /// Bottom color of the gradient in the RGB24 format
+        bottom_color: u32,
+        /// Clockwise rotation angle of the background fill in degrees; 0-359
+        #[serde(default)]
+        rotation_angle: u16,
+    },
+    /// The background is a freeform gradient that rotates after every message
+    /// in the chat
+    #[serde(rename = "freeform_gradient")]
+    FreeformGradient {
+        /// A list of the 3 or 4 base colors that are used to generate the
+        /// freeform gradient in the RGB24 format
+        colors: Vec<u32>,
+    },
+    /// A type of fill that isn't supported yet
+    #[serde(untagged)]
+    Unknown(serde_json::Map<String, serde_json::Value>),
+}
+
+/// This object represents a service message about a new forum topic created in
+/// the chat.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ForumTopicCreated {
+    /// Name of the topic
+    pub name: String,
+    /// Color of the topic icon in RGB format
+    #[serde(default)]
+    pub icon_color: u32,
+    /// Unique identifier of the custom emoji shown as the topic icon
+    pub icon_custom_emoji_id: Option<String>,
+}
+
+/// This object represents a service message about an edited forum topic.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ForumTopicEdited {
+    /// New name of the topic, if it was edited
+    pub name: Option<String>,
+    /// New identifier of the custom emoji shown as the topic icon, if it was
+    /// edited; an empty string if the icon was removed
+    pub icon_custom_emoji_id: Option<String>,
+}
+
+/// This object represents a service message about a forum topic closed in the
+/// chat. Currently holds no information.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ForumTopicClosed {}
+
+/// This object represents a service message about a forum topic reopened in
+/// the chat. Currently holds no information.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ForumTopicReopened {}
+
+/// This object represents a service message about the General forum topic
+/// hidden in the chat. Currently holds no information.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GeneralForumTopicHidden {}
+
+/// This object represents a service message about the General forum topic
+/// unhidden in the chat. Currently holds no information.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GeneralForumTopicUnhidden {}
@@ -1,9 +1,12 @@
 pub use super::utils::TextBlock;
-use super::User;
+use super::{utils::unknown::deserialize_entity_type, User};
 use serde::{Deserialize, Serialize};
 
 /// This object represents one special entity in a text message.
 /// For example, hashtags, usernames, URLs, etc.
+///
+/// Entity types that aren't supported yet are kept as
+/// [`MessageEntity::Unknown`] instead of failing to parse the message.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type")]
 pub enum MessageEntity {
@@ -11,10 +14,10 @@ pub enum MessageEntity {
     #[serde(rename = "mention")]
     Mention(TextBlock),
     /// A hashtag (`#hashtag`)
-    #[serde(rename = "hash_tag")]
+    #[serde(rename = "hashtag", alias = "hash_tag")]
     HashTag(TextBlock),
     /// A cashtag (`$USD`)
-    #[serde(rename = "cash_tag")]
+    #[serde(rename = "cashtag", alias = "cash_tag")]
     CashTag(TextBlock),
     /// A bot command (`/start@bot_name`)
     #[serde(rename = "bot_command")]
@@ -38,8 +41,11 @@ pub enum MessageEntity {
     #[serde(rename = "underline")]
     Underline(TextBlock),
     /// strikethrough text
-    #[serde(rename = "strike_through")]
+    #[serde(rename = "strikethrough", alias = "strike_through")]
     StrikeThrough(TextBlock),
+    /// Spoiler message
+    #[serde(rename = "spoiler")]
+    Spoiler(TextBlock),
     /// A monowidth code string
     #[serde(rename = "code")]
     Code(TextBlock),
@@ -52,6 +58,12 @@ pub enum MessageEntity {
     /// A mention of users without usernames
     #[serde(rename = "text_mention")]
     TextMention(TextMention),
+    /// An inline custom emoji sticker
+    #[serde(rename = "custom_emoji")]
+    CustomEmoji(CustomEmoji),
+    /// An entity type that isn't supported yet
+    #[serde(untagged)]
+    Unknown(UnknownEntity),
 }
 
 /// A monowidth code block
@@ -61,7 +73,8 @@ pub struct Pre {
     #[serde(flatten)]
     pub text_block: TextBlock,
     /// The programming language of the entity text
-    pub language: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// A clickable text URL
@@ -83,3 +96,107 @@ pub struct TextMention {
     /// The mentioned user
     pub user: User,
 }
+
+/// An inline custom emoji sticker
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CustomEmoji {
+    /// The part of the text that is replaced by the custom emoji
+    #[serde(flatten)]
+    pub text_block: TextBlock,
+    /// Unique identifier of the custom emoji
+    pub custom_emoji_id: String,
+}
+
+/// An entity of a type that isn't supported yet
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UnknownEntity {
+    /// The type of the entity as sent by telegram
+    #[serde(rename = "type", deserialize_with = "deserialize_entity_type")]
+    pub kind: String,
+    /// The part of the text the entity applies to
+    #[serde(flatten)]
+    pub text_block: TextBlock,
+    /// Any other fields of the entity
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The type of a [`MessageEntity`], without the data it contains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageEntityType {
+    Mention,
+    HashTag,
+    CashTag,
+    BotCommand,
+    Url,
+    Email,
+    PhoneNumber,
+    Bold,
+    Italic,
+    Underline,
+    StrikeThrough,
+    Spoiler,
+    Code,
+    Pre,
+    TextLink,
+    TextMention,
+    CustomEmoji,
+    Unknown,
+}
+
+impl MessageEntity {
+    /// Gets the type of the entity
+    pub fn get_type(&self) -> MessageEntityType {
+        match self {
+            Self::Mention(_) => MessageEntityType::Mention,
+            Self::HashTag(_) => MessageEntityType::HashTag,
+            Self::CashTag(_) => MessageEntityType::CashTag,
+            Self::BotCommand(_) => MessageEntityType::BotCommand,
+            Self::Url(_) => MessageEntityType::Url,
+            Self::Email(_) => MessageEntityType::Email,
+            Self::PhoneNumber(_) => MessageEntityType::PhoneNumber,
+            Self::Bold(_) => MessageEntityType::Bold,
+            Self::Italic(_) => MessageEntityType::Italic,
+            Self::Underline(_) => MessageEntityType::Underline,
+            Self::StrikeThrough(_) => MessageEntityType::StrikeThrough,
+            Self::Spoiler(_) => MessageEntityType::Spoiler,
+            Self::Code(_) => MessageEntityType::Code,
+            Self::Pre(_) => MessageEntityType::Pre,
+            Self::TextLink(_) => MessageEntityType::TextLink,
+            Self::TextMention(_) => MessageEntityType::TextMention,
+            Self::CustomEmoji(_) => MessageEntityType::CustomEmoji,
+            Self::Unknown(_) => MessageEntityType::Unknown,
+        }
+    }
+
+    /// Gets the [`TextBlock`] describing which part of the text the entity
+    /// applies to
+    pub fn get_text_block(&self) -> &TextBlock {
+        match self {
+            Self::Mention(t)
+            | Self::HashTag(t)
+            | Self::CashTag(t)
+            | Self::BotCommand(t)
+            | Self::Url(t)
+            | Self::Email(t)
+            | Self::PhoneNumber(t)
+            | Self::Bold(t)
+            | Self::Italic(t)
+            | Self::Underline(t)
+            | Self::StrikeThrough(t)
+            | Self::Spoiler(t)
+            | Self::Code(t) => t,
+            Self::Pre(p) => &p.text_block,
+            Self::TextLink(l) => &l.text_block,
+            Self::TextMention(m) => &m.text_block,
+            Self::CustomEmoji(e) => &e.text_block,
+            Self::Unknown(e) => &e.text_block,
+        }
+    }
+
+    /// Gets the part of the given text that the entity applies to, returning
+    /// `None` if the entity lies outside of the text or splits a character
+    pub fn get_text(&self, text: &str) -> Option<String> {
+        self.get_text_block().get_str(text).map(str::to_owned)
+    }
+}
@@ -3,6 +3,7 @@
 pub(crate) mod utils;
 
 mod chat;
+mod chat_full_info;
 mod games;
 mod inline;
 mod markup;
@@ -10,6 +11,8 @@ mod message;
 mod message_contents;
 mod message_entity;
 mod other;
+#[cfg(feature = "passport-decrypt")]
+mod passport_decryption;
 mod payments;
 pub mod raw;
 mod stickers;
@@ -18,6 +21,7 @@ mod update;
 mod user;
 
 pub use chat::*;
+pub use chat_full_info::*;
 pub use games::*;
 pub use inline::*;
 pub use markup::*;
@@ -25,6 +29,8 @@ pub use message::*;
 pub use message_contents::*;
 pub use message_entity::*;
 pub use other::*;
+#[cfg(feature = "passport-decrypt")]
+pub use passport_decryption::*;
 pub use payments::*;
 pub use stickers::*;
 pub use telegram_passport::*;
@@ -2,14 +2,14 @@ use super::{
     utils::unix_date_formatting,
     ForceReply,
     InlineKeyboardMarkup,
-    Message,
+    MaybeInaccessibleMessage,
     ReplyKeyboardMarkup,
     ReplyKeyboardRemove,
     User,
 };
-use crate::api::types::UpdateType;
+use crate::{api::types::UpdateType, utils::CallbackData, Result};
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 /// This object represents an incoming callback query from a callback button in
 /// an [inline keyboard][kb]. If the button that originated the query was
@@ -26,13 +26,13 @@ pub struct CallbackQuery {
     pub id: String,
     /// Sender
     pub from: User,
-    /// Message with the callback button that originated the query.
-    /// Note that message content and message date will not be available if the
-    /// message is too old
-    pub message: Option<Message>,
+    /// Message with the callback button that originated the query. If the
+    /// message is too old it is inaccessible, and only its chat and id are
+    /// available
+    pub message: Option<MaybeInaccessibleMessage>,
     /// Identifier of the message sent via the bot in inline mode, that
     /// originated the query.
-    pub inline_message_id: Option<Message>,
+    pub inline_message_id: Option<String>,
     /// Global identifier, uniquely corresponding to the chat to which the
     /// message with the callback button was sent. Useful for high scores in [games](https://core.telegram.org/bots/api#games).
     pub chat_instance: String,
@@ -46,6 +46,35 @@ pub struct CallbackQuery {
     pub game_short_name: Option<String>,
 }
 
+impl CallbackQuery {
+    /// Gets the id of the chat of the message with the callback button, this
+    /// is also available if the message is too old to be accessible
+    pub fn chat_id(&self) -> Option<i64> {
+        self.message.as_ref().map(MaybeInaccessibleMessage::chat_id)
+    }
+
+    /// Gets the id of the message with the callback button, this is also
+    /// available if the message is too old to be accessible
+    pub fn message_id(&self) -> Option<i64> {
+        self.message.as_ref().map(MaybeInaccessibleMessage::message_id)
+    }
+
+    /// Parses the data associated with the callback button as JSON, returns
+    /// None if the query doesn't have any data
+    pub fn data_as<T: DeserializeOwned>(&self) -> Option<Result<T>> {
+        self.data
+            .as_ref()
+            .map(|d| serde_json::from_str(d).map_err(Into::into))
+    }
+
+    /// Decodes the data associated with the callback button as the given
+    /// [`CallbackData`], returns None if the query doesn't have any data or it
+    /// couldn't be decoded
+    pub fn decode_data<T: CallbackData>(&self) -> Option<T> {
+        self.data.as_deref().and_then(T::decode)
+    }
+}
+
 /// A bot command
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct BotCommand {
@@ -61,13 +90,54 @@ pub struct BotCommand {
 /// will render them accordingly. You can use either markdown-style or
 /// HTML-style formatting.
 ///
-/// note: `Markdown` only exists for backwards-compatibility reasons, please use
-/// `MarkdownV2`
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// `ParseMode::default()` returns `HTML`, as it is the easiest to escape
+/// correctly using [`escape_html`]. Messages are still sent without a parse
+/// mode unless one is set on them or using
+/// [`ClientBuilder::set_default_parse_mode`].
+///
+/// [`escape_html`]: ../utils/fn.escape_html.html
+/// [`ClientBuilder::set_default_parse_mode`]: ../client/struct.ClientBuilder.html#method.set_default_parse_mode
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub enum ParseMode {
+    /// Telegram's current markdown style, in which all reserved characters
+    /// have to be escaped using [`escape_markdown_v2`]
+    ///
+    /// [`escape_markdown_v2`]: ../utils/fn.escape_markdown_v2.html
     MarkdownV2,
+    /// The legacy markdown style, which only exists for backwards
+    /// compatibility. It has different escaping rules than `MarkdownV2`,
+    /// see [`escape_markdown`], and doesn't support underlined,
+    /// strikethrough, spoiler or nested entities.
+    ///
+    /// [`escape_markdown`]: ../utils/fn.escape_markdown.html
+    #[deprecated(
+        note = "legacy Markdown has different escaping rules, use MarkdownV2 with \
+                escape_markdown_v2 instead"
+    )]
     Markdown,
+    /// HTML style formatting, in which `<`, `>`, `&` and `"` have to be
+    /// escaped using [`escape_html`]
+    ///
+    /// [`escape_html`]: ../utils/fn.escape_html.html
+    #[default]
     HTML,
+    /// Sends the text as is, without parsing any entities. Only needed to
+    /// override the default parse mode set using
+    /// [`ClientBuilder::set_default_parse_mode`]
+    ///
+    /// [`ClientBuilder::set_default_parse_mode`]: ../client/struct.ClientBuilder.html#method.set_default_parse_mode
+    #[serde(rename = "")]
+    None,
+}
+
+impl ParseMode {
+    // used to leave the parse mode out of requests when it's `ParseMode::None`,
+    // as telegram doesn't accept an empty parse mode. serde passes the field by
+    // reference, so it has to take an `&Option`
+    #[allow(clippy::ref_option)]
+    pub(crate) fn is_unset(parse_mode: &Option<Self>) -> bool {
+        matches!(parse_mode, None | Some(Self::None))
+    }
 }
 
 /// An action indicating to a user what they are about to receive
@@ -107,6 +177,9 @@ pub enum ChatAction {
 
 /// Enum object for an inline keyboard, custom reply keyboard, instructions to
 /// remove reply keyboard or to force a reply from the user.
+///
+/// All of these types can be converted into a `ReplyMarkup` using `into()`, so
+/// they can be passed directly to methods taking an `Into<ReplyMarkup>`.
 #[allow(clippy::large_enum_variant)]
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
@@ -117,6 +190,21 @@ pub enum ReplyMarkup {
     ForceReply(ForceReply),
 }
 
+macro_rules! impl_from_markup {
+    ($name:ident) => {
+        impl From<$name> for ReplyMarkup {
+            fn from(markup: $name) -> Self {
+                ReplyMarkup::$name(markup)
+            }
+        }
+    };
+}
+
+impl_from_markup!(InlineKeyboardMarkup);
+impl_from_markup!(ReplyKeyboardMarkup);
+impl_from_markup!(ReplyKeyboardRemove);
+impl_from_markup!(ForceReply);
+
 /// This object represents a file ready to be downloaded.
 /// The file can be downloaded via the link `https://api.telegram.org/file/bot<token>/<file_path>`.
 /// It is guaranteed that the link will be valid for at least 1 hour.
@@ -155,11 +243,17 @@ pub struct WebhookInfo {
     pub pending_update_count: i64,
     /// Unix time for the most recent error that happened when trying to deliver
     /// an update via webhook
+    #[serde(default)]
     #[serde(with = "unix_date_formatting::optional")]
     pub last_error_date: Option<DateTime<Utc>>,
     /// Error message in human-readable format for the most recent error that
     /// happened when trying to deliver an update via webhook
     pub last_error_message: Option<String>,
+    /// Unix time of the most recent error that happened when trying to
+    /// synchronize available updates with Telegram datacenters
+    #[serde(default)]
+    #[serde(with = "unix_date_formatting::optional")]
+    pub last_synchronization_error_date: Option<DateTime<Utc>>,
     /// Maximum allowed number of simultaneous HTTPS connections to the webhook
     /// for update delivery
     pub max_connections: Option<i64>,
@@ -169,3 +263,13 @@ pub struct WebhookInfo {
     /// Currently used webhook IP address
     pub ip_address: Option<String>,
 }
+
+impl WebhookInfo {
+    /// Whether a webhook is set up, if not the bot receives its updates using
+    /// [`get_updates`]
+    ///
+    /// [`get_updates`]: ../api/trait.API.html#method.get_updates
+    pub fn is_set(&self) -> bool {
+        !self.url.is_empty()
+    }
+}
@@ -0,0 +1,437 @@
+use super::{
+    EncryptedCredentials,
+    EncryptedPassportElement,
+    PassportData,
+    PassportFile,
+    TelegramPassportElement,
+};
+use openssl::{
+    base64::decode_block,
+    rsa::{Padding, Rsa},
+    sha::{sha256, sha512},
+    symm::{Cipher, Crypter, Mode},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An error returned when decrypting Telegram Passport data fails
+#[derive(Debug, Clone, PartialEq)]
+pub enum PassportDecryptionError {
+    /// The credentials secret couldn't be decrypted with the given private
+    /// key, meaning the data was encrypted for another key
+    WrongKey,
+    /// The decrypted data doesn't match its accompanying hash
+    HashMismatch,
+    /// The data, the private key or the decrypted payload isn't in the
+    /// expected format
+    Malformed(String),
+}
+
+impl std::fmt::Display for PassportDecryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongKey => {
+                f.write_str("the passport data wasn't encrypted for this private key")
+            },
+            Self::HashMismatch => {
+                f.write_str("the decrypted passport data doesn't match its hash")
+            },
+            Self::Malformed(e) => write!(f, "malformed passport data: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PassportDecryptionError {}
+
+/// The decrypted credentials of [`PassportData`], containing the secrets of
+/// every element shared with the bot
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Credentials {
+    /// Credentials for encrypted data, by the type of the element they belong
+    /// to
+    pub secure_data: HashMap<TelegramPassportElement, SecureValue>,
+    /// Bot-specified nonce
+    pub nonce: String,
+}
+
+/// The credentials required to decrypt the data and files of a single
+/// passport element
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SecureValue {
+    /// Credentials for encrypted Telegram Passport data
+    pub data: Option<DataCredentials>,
+    /// Credentials for an encrypted document's front side
+    pub front_side: Option<FileCredentials>,
+    /// Credentials for an encrypted document's reverse side
+    pub reverse_side: Option<FileCredentials>,
+    /// Credentials for an encrypted selfie of the user with a document
+    pub selfie: Option<FileCredentials>,
+    /// Credentials for an encrypted translation of the document
+    #[serde(default)]
+    pub translation: Vec<FileCredentials>,
+    /// Credentials for encrypted files
+    #[serde(default)]
+    pub files: Vec<FileCredentials>,
+}
+
+/// The credentials required to decrypt the data field of an
+/// [`EncryptedPassportElement`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DataCredentials {
+    /// Checksum of encrypted data
+    pub data_hash: String,
+    /// Secret of encrypted data
+    pub secret: String,
+}
+
+/// The credentials required to decrypt a [`PassportFile`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FileCredentials {
+    /// Checksum of encrypted file
+    pub file_hash: String,
+    /// Secret of encrypted file
+    pub secret: String,
+}
+
+/// The personal details of a user, shared in the `personal_details` element
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PersonalDetails {
+    /// First Name
+    pub first_name: String,
+    /// Last Name
+    pub last_name: String,
+    /// Middle Name
+    pub middle_name: Option<String>,
+    /// Date of birth in DD.MM.YYYY format
+    pub birth_date: String,
+    /// Gender, male or female
+    pub gender: String,
+    /// Citizenship (ISO 3166-1 alpha-2 country code)
+    pub country_code: String,
+    /// Country of residence (ISO 3166-1 alpha-2 country code)
+    pub residence_country_code: String,
+    /// First Name in the language of the user's country of residence
+    pub first_name_native: Option<String>,
+    /// Last Name in the language of the user's country of residence
+    pub last_name_native: Option<String>,
+    /// Middle Name in the language of the user's country of residence
+    pub middle_name_native: Option<String>,
+}
+
+/// The residential address of a user, shared in the `address` element
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ResidentialAddress {
+    /// First line for the address
+    pub street_line1: String,
+    /// Second line for the address
+    pub street_line2: Option<String>,
+    /// City
+    pub city: String,
+    /// State
+    pub state: Option<String>,
+    /// ISO 3166-1 alpha-2 country code
+    pub country_code: String,
+    /// Address post code
+    pub post_code: String,
+}
+
+/// The data of an identity document, shared in the `passport`,
+/// `driver_license`, `identity_card` and `internal_passport` elements
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IdDocumentData {
+    /// Document number
+    pub document_no: String,
+    /// Date of expiry, in DD.MM.YYYY format
+    pub expiry_date: Option<String>,
+}
+
+/// The decrypted data field of a passport element, typed by the element type
+#[derive(Debug, Clone, PartialEq)]
+pub enum PassportElementData {
+    /// The data of a `personal_details` element
+    PersonalDetails(PersonalDetails),
+    /// The data of a `passport`, `driver_license`, `identity_card` or
+    /// `internal_passport` element
+    IdDocument(IdDocumentData),
+    /// The data of an `address` element
+    Address(ResidentialAddress),
+}
+
+/// A passport file together with the credentials needed to decrypt it once
+/// it has been downloaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecryptedPassportFile {
+    /// The file as sent by telegram, used to download the encrypted file
+    pub file: PassportFile,
+    /// The credentials to decrypt the downloaded file with
+    pub credentials: FileCredentials,
+}
+
+impl DecryptedPassportFile {
+    /// decrypts the downloaded contents of this file, see
+    /// [`decrypt_passport_file`]
+    pub fn decrypt(&self, encrypted: &[u8]) -> Result<Vec<u8>, PassportDecryptionError> {
+        decrypt_passport_file(encrypted, &self.credentials)
+    }
+}
+
+/// A passport element with its data decrypted and its files paired with
+/// their credentials
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecryptedPassportElement {
+    /// Element type
+    pub element_type: TelegramPassportElement,
+    /// The decrypted data, available for `personal_details`, `passport`,
+    /// `driver_license`, `identity_card`, `internal_passport` and `address`
+    /// types
+    pub data: Option<PassportElementData>,
+    /// User's verified phone number, available only for `phone_number` type
+    pub phone_number: Option<String>,
+    /// User's verified email address, available only for `email` type
+    pub email: Option<String>,
+    /// Files with documents provided by the user
+    pub files: Vec<DecryptedPassportFile>,
+    /// The front side of the document
+    pub front_side: Option<DecryptedPassportFile>,
+    /// The reverse side of the document
+    pub reverse_side: Option<DecryptedPassportFile>,
+    /// The selfie of the user holding the document
+    pub selfie: Option<DecryptedPassportFile>,
+    /// Translated versions of the document
+    pub translation: Vec<DecryptedPassportFile>,
+    /// Base64-encoded element hash, as given in the encrypted element
+    pub hash: String,
+}
+
+/// The result of decrypting [`PassportData`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecryptedPassportData {
+    /// The decrypted elements shared with the bot
+    pub elements: Vec<DecryptedPassportElement>,
+    /// The decrypted credentials
+    pub credentials: Credentials,
+}
+
+impl DecryptedPassportData {
+    /// Returns the element of the given type, if it was shared
+    pub fn element(
+        &self,
+        element_type: TelegramPassportElement,
+    ) -> Option<&DecryptedPassportElement> {
+        self.elements.iter().find(|e| e.element_type == element_type)
+    }
+}
+
+impl PassportData {
+    /// Decrypts the credentials with the bot's PEM encoded private key and
+    /// uses them to decrypt and verify the data of every element.
+    ///
+    /// Files aren't part of the update, so they're returned together with
+    /// their credentials to be decrypted after downloading them.
+    pub fn decrypt(
+        &self,
+        private_key_pem: &[u8],
+    ) -> Result<DecryptedPassportData, PassportDecryptionError> {
+        let credentials = self.credentials.decrypt(private_key_pem)?;
+        let elements = self
+            .data
+            .iter()
+            .map(|e| e.decrypt(&credentials))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(DecryptedPassportData {
+            elements,
+            credentials,
+        })
+    }
+}
+
+impl EncryptedCredentials {
+    /// Decrypts the credentials with the bot's PEM encoded private key
+    pub fn decrypt(&self, private_key_pem: &[u8]) -> Result<Credentials, PassportDecryptionError> {
+        let key = Rsa::private_key_from_pem(private_key_pem)
+            .map_err(|_| malformed("the private key isn't a valid PEM encoded RSA key"))?;
+
+        let encrypted_secret = decode(&self.secret, "credentials secret")?;
+        let mut secret = vec![0; key.size() as usize];
+        let len = key
+            .private_decrypt(&encrypted_secret, &mut secret, Padding::PKCS1_OAEP)
+            .map_err(|_| PassportDecryptionError::WrongKey)?;
+        secret.truncate(len);
+
+        let data = decrypt_data(
+            &decode(&self.data, "credentials data")?,
+            &secret,
+            &decode(&self.hash, "credentials hash")?,
+        )?;
+        serde_json::from_slice(&data).map_err(|e| malformed(format!("invalid credentials: {e}")))
+    }
+}
+
+impl EncryptedPassportElement {
+    fn decrypt(
+        &self,
+        credentials: &Credentials,
+    ) -> Result<DecryptedPassportElement, PassportDecryptionError> {
+        let default = SecureValue::default();
+        let value = credentials
+            .secure_data
+            .get(&self.element_type)
+            .unwrap_or(&default);
+
+        let data = match (&self.data, &value.data) {
+            (Some(data), Some(creds)) => Some(self.decrypt_data(data, creds)?),
+            (Some(_), None) => {
+                return Err(malformed(format!(
+                    "missing data credentials for {:?}",
+                    self.element_type
+                )))
+            },
+            (None, _) => None,
+        };
+
+        Ok(DecryptedPassportElement {
+            element_type: self.element_type,
+            data,
+            phone_number: self.phone_number.clone(),
+            email: self.email.clone(),
+            files: pair_files(&self.files, &value.files)?,
+            front_side: pair_file(self.front_side.as_ref(), value.front_side.as_ref())?,
+            reverse_side: pair_file(self.reverse_side.as_ref(), value.reverse_side.as_ref())?,
+            selfie: pair_file(self.selfie.as_ref(), value.selfie.as_ref())?,
+            translation: pair_files(&self.translation, &value.translation)?,
+            hash: self.hash.clone(),
+        })
+    }
+
+    fn decrypt_data(
+        &self,
+        data: &str,
+        credentials: &DataCredentials,
+    ) -> Result<PassportElementData, PassportDecryptionError> {
+        let decrypted = decrypt_data(
+            &decode(data, "element data")?,
+            &decode(&credentials.secret, "data secret")?,
+            &decode(&credentials.data_hash, "data hash")?,
+        )?;
+
+        let parsed = match self.element_type {
+            TelegramPassportElement::PersonalDetails => {
+                serde_json::from_slice(&decrypted).map(PassportElementData::PersonalDetails)
+            },
+            TelegramPassportElement::Address => {
+                serde_json::from_slice(&decrypted).map(PassportElementData::Address)
+            },
+            TelegramPassportElement::Passport
+            | TelegramPassportElement::DriverLicense
+            | TelegramPassportElement::IdentityCard
+            | TelegramPassportElement::InternalPassport => {
+                serde_json::from_slice(&decrypted).map(PassportElementData::IdDocument)
+            },
+            t => return Err(malformed(format!("{t:?} elements don't have data"))),
+        };
+
+        parsed.map_err(|e| malformed(format!("invalid {:?} data: {}", self.element_type, e)))
+    }
+}
+
+/// Decrypts a downloaded passport file using the credentials that belong to
+/// it, verifying the decrypted contents against the file hash
+pub fn decrypt_passport_file(
+    encrypted: &[u8],
+    credentials: &FileCredentials,
+) -> Result<Vec<u8>, PassportDecryptionError> {
+    decrypt_data(
+        encrypted,
+        &decode(&credentials.secret, "file secret")?,
+        &decode(&credentials.file_hash, "file hash")?,
+    )
+}
+
+fn pair_file(
+    file: Option<&PassportFile>,
+    credentials: Option<&FileCredentials>,
+) -> Result<Option<DecryptedPassportFile>, PassportDecryptionError> {
+    match (file, credentials) {
+        (Some(file), Some(credentials)) => Ok(Some(DecryptedPassportFile {
+            file: file.clone(),
+            credentials: credentials.clone(),
+        })),
+        (Some(file), None) => Err(malformed(format!(
+            "missing credentials for file {}",
+            file.file_id
+        ))),
+        (None, _) => Ok(None),
+    }
+}
+
+fn pair_files(
+    files: &[PassportFile],
+    credentials: &[FileCredentials],
+) -> Result<Vec<DecryptedPassportFile>, PassportDecryptionError> {
+    if files.len() != credentials.len() {
+        return Err(malformed(format!(
+            "got credentials for {} out of {} files",
+            credentials.len(),
+            files.len()
+        )));
+    }
+
+    Ok(files
+        .iter()
+        .zip(credentials)
+        .map(|(file, credentials)| DecryptedPassportFile {
+            file: file.clone(),
+            credentials: credentials.clone(),
+        })
+        .collect())
+}
+
+/// decrypts data using the AES256-CBC key and iv derived from the secret and
+/// hash, verifies it against the hash and strips the random padding
+fn decrypt_data(
+    encrypted: &[u8],
+    secret: &[u8],
+    hash: &[u8],
+) -> Result<Vec<u8>, PassportDecryptionError> {
+    if encrypted.is_empty() || !encrypted.len().is_multiple_of(16) {
+        return Err(malformed("the encrypted data isn't a multiple of the block size"));
+    }
+
+    let secret_hash = sha512(&[secret, hash].concat());
+    let mut crypter = Crypter::new(
+        Cipher::aes_256_cbc(),
+        Mode::Decrypt,
+        &secret_hash[..32],
+        Some(&secret_hash[32..48]),
+    )
+    .map_err(|e| malformed(e.to_string()))?;
+    crypter.pad(false);
+
+    let mut decrypted = vec![0; encrypted.len() + 16];
+    let mut len = crypter
+        .update(encrypted, &mut decrypted)
+        .map_err(|e| malformed(e.to_string()))?;
+    len += crypter
+        .finalize(&mut decrypted[len..])
+        .map_err(|e| malformed(e.to_string()))?;
+    decrypted.truncate(len);
+
+    if sha256(&decrypted)[..] != *hash {
+        return Err(PassportDecryptionError::HashMismatch);
+    }
+
+    let padding = decrypted[0] as usize;
+    if padding == 0 || padding > decrypted.len() {
+        return Err(malformed("invalid padding length"));
+    }
+    Ok(decrypted.split_off(padding))
+}
+
+fn decode(value: &str, name: &str) -> Result<Vec<u8>, PassportDecryptionError> {
+    decode_block(value).map_err(|_| malformed(format!("the {name} isn't valid base64")))
+}
+
+fn malformed<S: Into<String>>(msg: S) -> PassportDecryptionError {
+    PassportDecryptionError::Malformed(msg.into())
+}
@@ -121,6 +121,31 @@ pub struct ShippingOption {
     pub prices: Vec<LabeledPrice>,
 }
 
+impl ShippingOption {
+    /// Creates a shipping option without any prices, which can be added
+    /// using [`add_price`]
+    ///
+    /// [`add_price`]: #method.add_price
+    pub fn new(id: &str, title: &str) -> Self {
+        Self {
+            id: id.to_owned(),
+            title: title.to_owned(),
+            prices: Vec::new(),
+        }
+    }
+
+    /// Adds a portion of the price of the shipping option
+    pub fn add_price(&mut self, label: &str, amount: i64) -> &mut Self {
+        self.prices.push(LabeledPrice::new(label, amount));
+        self
+    }
+
+    /// Gets the total price of the shipping option
+    pub fn total_amount(&self) -> i64 {
+        self.prices.iter().map(|p| p.amount).sum()
+    }
+}
+
 /// This object represents a portion of the price for goods or services.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct LabeledPrice {
@@ -133,3 +158,14 @@ pub struct LabeledPrice {
     /// (2 for the majority of currencies).
     pub amount: i64,
 }
+
+impl LabeledPrice {
+    /// Creates a portion of a price with the label and the amount in the
+    /// smallest units of the currency
+    pub fn new(label: &str, amount: i64) -> Self {
+        Self {
+            label: label.to_owned(),
+            amount,
+        }
+    }
+}
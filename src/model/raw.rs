@@ -29,11 +29,14 @@ use super::{
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct RawMessage {
     pub message_id: i64,
+    pub message_thread_id: Option<i64>,
     pub from: Option<super::User>,
     pub sender_chat: Option<RawChat>,
     #[serde(with = "unix_date_formatting")]
     pub date: DateTime<Utc>,
     pub chat: RawChat,
+    #[serde(default)]
+    pub is_topic_message: bool,
 
     pub forward_from: Option<super::User>,
     pub forward_from_chat: Option<RawChat>,
@@ -43,6 +46,7 @@ pub struct RawMessage {
     #[serde(default)]
     #[serde(with = "unix_date_formatting::optional")]
     pub forward_date: Option<DateTime<Utc>>,
+    pub forward_origin: Option<super::MessageOrigin>,
 
     pub reply_to_message: Option<Box<RawMessage>>,
     pub via_bot: Option<User>,
@@ -67,6 +71,8 @@ pub struct RawMessage {
     pub voice: Option<Voice>,
     pub video_note: Option<VideoNote>,
     pub caption: Option<String>,
+    #[serde(default)]
+    pub has_media_spoiler: bool,
     pub contact: Option<Contact>,
     pub location: Option<Location>,
     pub venue: Option<Venue>,
@@ -104,6 +110,19 @@ pub struct RawMessage {
     pub voice_chat_started: Option<VoiceChatStarted>,
     pub voice_chat_ended: Option<VoiceChatEnded>,
     pub voice_chat_participants_invited: Option<VoiceChatParticipantsInvited>,
+
+    pub users_shared: Option<UsersShared>,
+    pub chat_shared: Option<ChatShared>,
+    pub write_access_allowed: Option<WriteAccessAllowed>,
+    pub boost_added: Option<ChatBoostAdded>,
+    pub chat_background_set: Option<ChatBackground>,
+
+    pub forum_topic_created: Option<ForumTopicCreated>,
+    pub forum_topic_edited: Option<ForumTopicEdited>,
+    pub forum_topic_closed: Option<ForumTopicClosed>,
+    pub forum_topic_reopened: Option<ForumTopicReopened>,
+    pub general_forum_topic_hidden: Option<GeneralForumTopicHidden>,
+    pub general_forum_topic_unhidden: Option<GeneralForumTopicUnhidden>,
 }
 
 /// The raw chat, for most usages the [`Chat`] object is easier to use
@@ -123,65 +142,109 @@ pub struct RawChat {
     pub first_name: Option<String>,
     /// Last name of the other party in a private chat
     pub last_name: Option<String>,
-    /// Chat photo. Returned only in getChat.
+    /// True, if the supergroup chat is a forum (has topics enabled)
+    pub is_forum: Option<bool>,
+    /// Chat photo. Only set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub photo: Option<ChatPhoto>,
-    /// Bio of the other party in a private chat. Returned only in [`get_chat`].
+    /// Bio of the other party in a private chat. Only set when converted from a
+    /// [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub bio: Option<String>,
-    /// Description, for groups, supergroups and channel chats. Returned only in
-    /// [`get_chat`].
+    /// Description, for groups, supergroups and channel chats. Only set when
+    /// converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub description: Option<String>,
     /// Chat invite link, for groups, supergroups and channel chats.
     pub invite_link: Option<String>,
-    /// Pinned message, for groups, supergroups and channels. Returned only in
-    /// [`get_chat`].
+    /// Pinned message, for groups, supergroups and channels. Only set when
+    /// converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub pinned_message: Option<Box<RawMessage>>,
-    /// Default chat member permissions, for groups and supergroups. Returned
-    /// only in [`get_chat`].
+    /// Default chat member permissions, for groups and supergroups. Only set
+    /// when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub permissions: Option<super::ChatPermissions>,
     /// For supergroups, the minimum allowed delay between consecutive messages
-    /// sent by each unpriviledged user. Returned only in [`get_chat`].
+    /// sent by each unpriviledged user. Only set when converted from a
+    /// [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub slow_mode_delay: Option<usize>,
-    /// For supergroups, name of group sticker set. Returned only in
-    /// [`get_chat`].
+    /// For supergroups, name of group sticker set. Only set when converted from
+    /// a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub sticker_set_name: Option<String>,
-    /// True, if the bot can change the group sticker set. Returned only in
-    /// [`get_chat`].
+    /// True, if the bot can change the group sticker set. Only set when
+    /// converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub can_set_sticker_set: Option<bool>,
     /// Unique identifier for the linked chat, i.e. the discussion group
     /// identifier for a channel and vice versa; for supergroups and channel
-    /// chats. This identifier may be greater than 32 bits and some
-    /// programming languages may have difficulty/silent defects in interpreting
-    /// it. But it is smaller than 52 bits, so a signed 64 bit integer or
-    /// double-precision float type are safe for storing this identifier.
-    /// Returned only in [`get_chat`].
+    /// chats. This identifier may be greater than 32 bits and some programming
+    /// languages may have difficulty/silent defects in interpreting it. But it
+    /// is smaller than 52 bits, so a signed 64 bit integer or double-precision
+    /// float type are safe for storing this identifier. Only set when converted
+    /// from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub linked_chat_id: Option<i64>,
-    /// For supergroups, the location to which the supergroup is connected.
-    /// Returned only in [`get_chat`].
+    /// For supergroups, the location to which the supergroup is connected. Only
+    /// set when converted from a [`ChatFullInfo`].
     ///
-    /// [`get_chat`]: ../../api/trait.API.html#method.get_chat
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
     pub location: Option<ChatLocation>,
+    /// True, if privacy settings of the other party in a private chat allows to
+    /// use `tg://user?id=<user_id>` links only in chats with the user. Only set
+    /// when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub has_private_forwards: Option<bool>,
+    /// True, if the privacy settings of the other party in a private chat
+    /// restrict sending voice and video note messages. Only set when converted
+    /// from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub has_restricted_voice_and_video_messages: Option<bool>,
+    /// For supergroups, true, if users need to join the supergroup before they
+    /// can send messages. Only set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub join_to_send_messages: Option<bool>,
+    /// For supergroups, true, if all users directly joining the supergroup need
+    /// to be approved by supergroup administrators. Only set when converted
+    /// from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub join_by_request: Option<bool>,
+    /// True, if messages from the chat can't be forwarded to other chats. Only
+    /// set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub has_protected_content: Option<bool>,
+    /// The time after which all messages sent to the chat will be automatically
+    /// deleted; in seconds. Only set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub message_auto_delete_time: Option<i64>,
+    /// For private chats, supergroups and channels, all active usernames of the
+    /// chat. Only set when converted from a [`ChatFullInfo`].
+    ///
+    /// [`ChatFullInfo`]: struct.ChatFullInfo.html
+    pub active_usernames: Option<Vec<String>>,
 }
 
 /// The raw update, for most usages the [`Update`] object is easier to use
 ///
 /// [`Update`]: super::Update
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct RawUpdate {
     /// The update's unique identifier. Update identifiers start from a certain
     /// positive number and increase sequentially. If there are no new
@@ -222,4 +285,8 @@ pub struct RawUpdate {
     /// administrator in the chat and must explicitly specify “chat_member”
     /// in the list of allowed_updates to receive these updates.
     pub chat_member: Option<ChatMemberUpdated>,
+    /// Any other fields of the update, which aren't supported yet. This
+    /// allows handling update types that were recently added to telegram.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
 }
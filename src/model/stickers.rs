@@ -26,6 +26,8 @@ pub struct Sticker {
     pub set_name: Option<String>,
     /// For mask stickers, the position where the mask should be placed
     pub mask_position: Option<MaskPosition>,
+    /// For custom emoji stickers, unique identifier of the custom emoji
+    pub custom_emoji_id: Option<String>,
     /// File size
     pub file_size: Option<usize>,
 }
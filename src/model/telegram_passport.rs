@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 pub struct PassportData {
     /// Vec with information about documents and other Telegram Passport
     /// elements that was shared with the bot
-    pub data: Vec<EncryptedCredentials>,
+    pub data: Vec<EncryptedPassportElement>,
     /// Encrypted credentials required to decrypt the data
     pub credentials: EncryptedCredentials,
 }
@@ -37,6 +37,7 @@ pub struct PassportFile {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct EncryptedPassportElement {
     /// Element type.
+    #[serde(rename = "type")]
     pub element_type: TelegramPassportElement,
     /// Base64-encoded encrypted Telegram Passport element data provided by the
     /// user, available for “personal_details”, “passport”,
@@ -46,27 +47,30 @@ pub struct EncryptedPassportElement {
     pub data: Option<String>,
     /// User's verified phone number, available only for “phone_number” type
     pub phone_number: Option<String>,
+    /// User's verified email address, available only for “email” type
+    pub email: Option<String>,
     /// Array of encrypted files with documents provided by the user, available
     /// for “utility_bill”, “bank_statement”, “rental_agreement”,
     /// “passport_registration” and “temporary_registration” types.
     /// Files can be decrypted and verified using the accompanying
     /// [EncryptedCredentials].
+    #[serde(default)]
     pub files: Vec<PassportFile>,
     /// Encrypted file with the front side of the document, provided by the
     /// user. Available for “passport”, “driver_license”, “identity_card”
     /// and “internal_passport”. The file can be decrypted and verified
     /// using the accompanying [EncryptedCredentials].
-    pub front_side: PassportFile,
+    pub front_side: Option<PassportFile>,
     /// Encrypted file with the reverse side of the document, provided by the
     /// user. Available for “driver_license” and “identity_card”.
     /// The file can be decrypted and verified using the accompanying
     /// [EncryptedCredentials].
-    pub reverse_side: PassportFile,
+    pub reverse_side: Option<PassportFile>,
     /// Encrypted file with the selfie of the user holding a document, provided
     /// by the user; available for “passport”, “driver_license”,
     /// “identity_card” and “internal_passport”. The file can be decrypted
     /// and verified using the accompanying [EncryptedCredentials].
-    pub selfie: PassportFile,
+    pub selfie: Option<PassportFile>,
     /// Array of encrypted files with translated versions of documents provided
     /// by the user. Available if requested for “passport”,
     /// “driver_license”, “identity_card”, “internal_passport”,
@@ -74,6 +78,7 @@ pub struct EncryptedPassportElement {
     /// “passport_registration” and “temporary_registration” types.
     /// Files can be decrypted and verified using the accompanying
     /// [EncryptedCredentials].
+    #[serde(default)]
     pub translation: Vec<PassportFile>,
     /// Base64-encoded element hash for using in
     /// [PassportElementErrorUnspecified]
@@ -101,7 +106,7 @@ pub struct EncryptedCredentials {
 }
 
 /// The type of a telegram passport element
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TelegramPassportElement {
     #[serde(rename = "personal_details")]
     PersonalDetails,
@@ -1,16 +1,21 @@
 use super::{
     raw::RawUpdate,
     CallbackQuery,
+    Chat,
     ChatMemberUpdated,
     ChosenInlineResult,
     InlineQuery,
+    MaybeInaccessibleMessage,
     Message,
     Poll,
     PollAnswer,
     PreCheckoutQuery,
     ShippingQuery,
+    User,
 };
+use super::utils::unknown::warn_unknown;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::Arc;
 
 /// This object represents an incoming update
 #[derive(Debug, Clone, PartialEq)]
@@ -25,11 +30,21 @@ pub struct Update {
     pub update_id: i64,
     /// The content of the incoming update
     pub content: UpdateContent,
+    /// The JSON telegram sent for the update, only kept when enabled using
+    /// [`ClientBuilder::set_keep_raw_json`]. It isn't used when serializing
+    /// the update.
+    ///
+    /// [`ClientBuilder::set_keep_raw_json`]: ../client/struct.ClientBuilder.html#method.set_keep_raw_json
+    pub raw_json: Option<Arc<str>>,
 }
 
-/// The content of an [`Update`]
+/// The content of an [`Update`].
+///
+/// New update types are regularly added to telegram and thus to this enum, so
+/// it is marked as non-exhaustive.
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum UpdateContent {
     /// New incoming message of any kind — text, photo, sticker, etc.
     Message(Message),
@@ -69,16 +84,86 @@ pub enum UpdateContent {
     /// administrator in the chat and must explicitly specify “chat_member”
     /// in the list of allowed_updates to receive these updates.
     ChatMember(ChatMemberUpdated),
-    /// An unknown update content
-    Unknown,
+    /// An update content that isn't supported yet, containing the raw update
+    /// so it can still be handled. Any unsupported fields will be in
+    /// [`RawUpdate::other`].
+    Unknown(Box<RawUpdate>),
+}
+
+impl Update {
+    /// Gets the JSON telegram sent for the update, if it was kept
+    pub fn raw_json(&self) -> Option<&str> {
+        self.raw_json.as_deref()
+    }
+
+    /// Gets the id of the chat the update belongs to, if it belongs to one
+    pub fn get_chat_id(&self) -> Option<i64> {
+        match &self.content {
+            UpdateContent::Message(m)
+            | UpdateContent::EditedMessage(m)
+            | UpdateContent::ChannelPost(m)
+            | UpdateContent::EditedChannelPost(m) => Some(m.chat.get_id()),
+            UpdateContent::CallbackQuery(q) => q.chat_id(),
+            UpdateContent::MyChatMember(c) | UpdateContent::ChatMember(c) => Some(c.chat.get_id()),
+            _ => None,
+        }
+    }
+
+    /// Gets the message the update is about, being the (edited) message or
+    /// channel post, or the message with the button of a callback query, if
+    /// it is available
+    pub fn effective_message(&self) -> Option<&Message> {
+        match &self.content {
+            UpdateContent::Message(m)
+            | UpdateContent::EditedMessage(m)
+            | UpdateContent::ChannelPost(m)
+            | UpdateContent::EditedChannelPost(m) => Some(m),
+            UpdateContent::CallbackQuery(q) => q
+                .message
+                .as_ref()
+                .and_then(MaybeInaccessibleMessage::as_message),
+            _ => None,
+        }
+    }
+
+    /// Gets the chat the update belongs to, if it belongs to one
+    pub fn chat(&self) -> Option<&Chat> {
+        match &self.content {
+            UpdateContent::MyChatMember(c) | UpdateContent::ChatMember(c) => Some(&c.chat),
+            UpdateContent::CallbackQuery(q) => {
+                q.message.as_ref().map(MaybeInaccessibleMessage::chat)
+            },
+            _ => self.effective_message().map(|m| &m.chat),
+        }
+    }
+
+    /// Gets the user that caused the update, if there is one. For channel
+    /// posts this is usually empty, as they are sent on behalf of the channel
+    pub fn from_user(&self) -> Option<&User> {
+        match &self.content {
+            UpdateContent::Message(m)
+            | UpdateContent::EditedMessage(m)
+            | UpdateContent::ChannelPost(m)
+            | UpdateContent::EditedChannelPost(m) => m.from.as_ref(),
+            UpdateContent::InlineQuery(q) => Some(&q.from),
+            UpdateContent::ChosenInlineResult(r) => Some(&r.from),
+            UpdateContent::CallbackQuery(q) => Some(&q.from),
+            UpdateContent::ShippingQuery(q) => Some(&q.from),
+            UpdateContent::PreCheckoutQuery(q) => Some(&q.from),
+            UpdateContent::PollAnswer(a) => Some(&a.user),
+            UpdateContent::MyChatMember(c) | UpdateContent::ChatMember(c) => Some(&c.from),
+            _ => None,
+        }
+    }
 }
 
 impl From<RawUpdate> for Update {
-    fn from(raw: RawUpdate) -> Update {
+    fn from(mut raw: RawUpdate) -> Update {
         let update_id = raw.update_id;
         let make_update = |content: UpdateContent| Self {
             update_id,
             content,
+            raw_json: None,
         };
 
         macro_rules! set_content {
@@ -89,21 +174,24 @@ impl From<RawUpdate> for Update {
             };
         }
 
-        set_content!(raw.message, Message);
-        set_content!(raw.edited_message, EditedMessage);
-        set_content!(raw.channel_post, ChannelPost);
-        set_content!(raw.edited_channel_post, EditedChannelPost);
-        set_content!(raw.inline_query, InlineQuery);
-        set_content!(raw.chosen_inline_result, ChosenInlineResult);
-        set_content!(raw.callback_query, CallbackQuery);
-        set_content!(raw.shipping_query, ShippingQuery);
-        set_content!(raw.pre_checkout_query, PreCheckoutQuery);
-        set_content!(raw.poll, Poll);
-        set_content!(raw.poll_answer, PollAnswer);
-        set_content!(raw.my_chat_member, MyChatMember);
-        set_content!(raw.chat_member, ChatMember);
-
-        make_update(UpdateContent::Unknown)
+        set_content!(raw.message.take(), Message);
+        set_content!(raw.edited_message.take(), EditedMessage);
+        set_content!(raw.channel_post.take(), ChannelPost);
+        set_content!(raw.edited_channel_post.take(), EditedChannelPost);
+        set_content!(raw.inline_query.take(), InlineQuery);
+        set_content!(raw.chosen_inline_result.take(), ChosenInlineResult);
+        set_content!(raw.callback_query.take(), CallbackQuery);
+        set_content!(raw.shipping_query.take(), ShippingQuery);
+        set_content!(raw.pre_checkout_query.take(), PreCheckoutQuery);
+        set_content!(raw.poll.take(), Poll);
+        set_content!(raw.poll_answer.take(), PollAnswer);
+        set_content!(raw.my_chat_member.take(), MyChatMember);
+        set_content!(raw.chat_member.take(), ChatMember);
+
+        for kind in raw.other.keys() {
+            warn_unknown("update type", kind);
+        }
+        make_update(UpdateContent::Unknown(Box::new(raw)))
     }
 }
 
@@ -124,6 +212,7 @@ impl From<Update> for RawUpdate {
             poll_answer: None,
             my_chat_member: None,
             chat_member: None,
+            other: serde_json::Map::new(),
         };
 
         match update.content {
@@ -179,7 +268,10 @@ impl From<Update> for RawUpdate {
                 ret.chat_member = Some(c);
                 ret
             },
-            UpdateContent::Unknown => ret,
+            UpdateContent::Unknown(raw) => Self {
+                update_id: update.update_id,
+                ..*raw
+            },
         }
     }
 }
@@ -11,24 +11,82 @@ pub struct User {
     /// User‘s or bot’s first name
     pub first_name: String,
     /// User‘s or bot’s last name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_name: Option<String>,
     /// User‘s or bot’s username
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
     /// [IETF language tag](https://en.wikipedia.org/wiki/IETF_language_tag) of the user's language
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub language_code: Option<String>,
     /// True, if the bot can be invited to groups. Returned only in [`get_me`].
     ///
     /// [`get_me`]: ../api/struct.API.html#method.get_me
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_join_groups: Option<bool>,
     /// True, if privacy mode is disabled for the bot. Returned only in
     /// [`get_me`].
     ///
     /// [`get_me`]: ../api/struct.API.html#method.get_me
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub can_read_all_group_messages: Option<bool>,
     /// True, if the bot supports inline queries. Returned only in [`get_me`].
     ///
     /// [`get_me`]: ../api/struct.API.html#method.get_me
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub supports_inline_queries: Option<bool>,
+    /// True, if this user is a Telegram Premium user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_premium: Option<bool>,
+    /// True, if this user added the bot to the attachment menu
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added_to_attachment_menu: Option<bool>,
+}
+
+/// This object represents the bot itself, as returned by [`get_me`]. Next to
+/// the fields of a [`User`], it describes the settings of the bot.
+///
+/// [`get_me`]: ../api/trait.API.html#method.get_me
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BotUser {
+    /// Unique identifier for this bot
+    pub id: i64,
+    /// True, as this user is a bot
+    pub is_bot: bool,
+    /// Bot's first name
+    pub first_name: String,
+    /// Bot's last name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    /// Bot's username
+    pub username: String,
+    /// True, if the bot can be invited to groups
+    #[serde(default)]
+    pub can_join_groups: bool,
+    /// True, if privacy mode is disabled for the bot
+    #[serde(default)]
+    pub can_read_all_group_messages: bool,
+    /// True, if the bot supports inline queries
+    #[serde(default)]
+    pub supports_inline_queries: bool,
+}
+
+impl From<BotUser> for User {
+    fn from(bot: BotUser) -> Self {
+        Self {
+            id: bot.id,
+            is_bot: bot.is_bot,
+            first_name: bot.first_name,
+            last_name: bot.last_name,
+            username: Some(bot.username),
+            language_code: None,
+            can_join_groups: Some(bot.can_join_groups),
+            can_read_all_group_messages: Some(bot.can_read_all_group_messages),
+            supports_inline_queries: Some(bot.supports_inline_queries),
+            is_premium: None,
+            added_to_attachment_menu: None,
+        }
+    }
 }
 
 /// This object represent a user's profile pictures.
@@ -1,4 +1,5 @@
 pub mod text;
 pub mod unix_date_formatting;
+pub mod unknown;
 
 pub use text::TextBlock;
@@ -1,3 +1,4 @@
+use crate::utils::utf16_offset_to_byte;
 use serde::{Deserialize, Serialize};
 
 /// An object describing a part of a text
@@ -10,19 +11,26 @@ pub struct TextBlock {
 }
 
 impl TextBlock {
-    /// Gets the part of the text described by the `TextBlock`
-    pub fn get_text(&self, text: &str) -> String {
-        let mut res: Vec<u16> = Vec::with_capacity(self.length);
+    /// Gets the part of the text described by the `TextBlock` without copying
+    /// it. Returns `None` if the `TextBlock` doesn't describe a valid part of
+    /// the text, for example because it was sent for another text.
+    pub fn get_str<'a>(&self, text: &'a str) -> Option<&'a str> {
+        let start = utf16_offset_to_byte(text, self.offset)?;
+        let length = utf16_offset_to_byte(&text[start..], self.length)?;
+        Some(&text[start..start + length])
+    }
 
-        for (i, point) in text.encode_utf16().enumerate() {
-            if (self.offset + self.length) <= i {
-                break;
-            }
-            if self.offset <= i {
-                res.push(point)
-            }
-        }
+    /// Gets the part of the text described by the `TextBlock`, which is
+    /// empty if it doesn't describe a valid part of the text
+    pub fn get_text(&self, text: &str) -> String {
+        self.get_str(text).unwrap_or_default().to_owned()
+    }
 
-        String::from_utf16_lossy(res.as_slice())
+    /// Gets the part of the text after the end of the `TextBlock`, which is
+    /// empty if the `TextBlock` doesn't end within the text
+    pub fn get_text_after(&self, text: &str) -> String {
+        utf16_offset_to_byte(text, self.offset + self.length)
+            .map_or("", |end| &text[end..])
+            .to_owned()
     }
 }
@@ -1,18 +1,25 @@
 use chrono::{DateTime, TimeZone, Utc};
-use serde::{self, Deserialize, Deserializer, Serializer};
+use serde::{self, de::Error, Deserialize, Deserializer, Serializer};
+
+// telegram sends and expects dates as unix time in seconds
+fn from_timestamp<E: Error>(timestamp: i64) -> Result<DateTime<Utc>, E> {
+    Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .ok_or_else(|| E::custom(format!("invalid unix time: {timestamp}")))
+}
 
 pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    serializer.serialize_i64(date.timestamp_millis())
+    serializer.serialize_i64(date.timestamp())
 }
 
 pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    Ok(Utc.timestamp_millis(i64::deserialize(deserializer)?))
+    from_timestamp(i64::deserialize(deserializer)?)
 }
 
 pub mod optional {
@@ -23,7 +30,7 @@ pub mod optional {
         S: Serializer,
     {
         match date {
-            Some(d) => serializer.serialize_i64(d.timestamp_millis()),
+            Some(d) => serializer.serialize_i64(d.timestamp()),
             None => serializer.serialize_none(),
         }
     }
@@ -32,6 +39,8 @@ pub mod optional {
     where
         D: Deserializer<'de>,
     {
-        Ok(Some(Utc.timestamp_millis(i64::deserialize(deserializer)?)))
+        Option::<i64>::deserialize(deserializer)?
+            .map(from_timestamp)
+            .transpose()
     }
 }
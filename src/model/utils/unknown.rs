@@ -0,0 +1,76 @@
+use parking_lot::Mutex;
+use serde::{de::Error, Deserialize, Deserializer};
+use std::collections::BTreeSet;
+
+// the kinds that were already warned about, so a new kind of update doesn't
+// log a warning for every single update containing it
+static WARNED: Mutex<BTreeSet<(&'static str, String)>> = parking_lot::const_mutex(BTreeSet::new());
+
+/// Logs a warning that telegram sent a kind of `category` that isn't supported
+/// yet, only the first time that kind is received
+pub fn warn_unknown(category: &'static str, kind: &str) {
+    if WARNED.lock().insert((category, kind.to_owned())) {
+        log::warn!("received a {category} that isn't supported yet ({kind}), keeping it as unknown");
+    }
+}
+
+/// The entity types that have their own `MessageEntity` variant, including
+/// their aliases
+const KNOWN_ENTITY_TYPES: &[&str] = &[
+    "mention",
+    "hashtag",
+    "hash_tag",
+    "cashtag",
+    "cash_tag",
+    "bot_command",
+    "url",
+    "email",
+    "phone_number",
+    "bold",
+    "italic",
+    "underline",
+    "strikethrough",
+    "strike_through",
+    "spoiler",
+    "code",
+    "pre",
+    "text_link",
+    "text_mention",
+    "custom_emoji",
+];
+
+/// The statuses that have their own `ChatMember` variant
+const KNOWN_MEMBER_STATUSES: &[&str] =
+    &["creator", "administrator", "member", "restricted", "left", "kicked"];
+
+/// Deserializes the kind of an unknown value, failing if the kind is known,
+/// as those only fall back to unknown when their fields couldn't be parsed
+fn deserialize_unknown_kind<'de, D>(
+    deserializer: D,
+    category: &'static str,
+    known: &[&str],
+) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let kind = String::deserialize(deserializer)?;
+    if known.contains(&kind.as_str()) {
+        return Err(D::Error::custom(format!("invalid {category} {kind}")));
+    }
+    warn_unknown(category, &kind);
+    Ok(kind)
+}
+
+pub fn deserialize_entity_type<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_unknown_kind(deserializer, "message entity type", KNOWN_ENTITY_TYPES)
+}
+
+pub fn deserialize_member_status<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_unknown_kind(deserializer, "chat member status", KNOWN_MEMBER_STATUSES)
+}
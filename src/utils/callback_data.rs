@@ -0,0 +1,121 @@
+use super::result::{Result, TelegramError};
+
+/// The separator between the prefix and the fields of encoded callback data
+pub const CALLBACK_DATA_SEPARATOR: char = ':';
+
+/// The maximum length in bytes of the callback data of an inline keyboard
+/// button
+pub const MAX_CALLBACK_DATA_LENGTH: usize = 64;
+
+/// Data that can be encoded into the compact string used as the
+/// `callback_data` of an [`InlineKeyboardButton`], and decoded again from
+/// [`CallbackQuery::data`].
+///
+/// The data is encoded as its prefix followed by its fields, joined with
+/// [`CALLBACK_DATA_SEPARATOR`], so neither the prefix nor the fields can
+/// contain the separator. The prefix is used to tell different types of data
+/// apart, allowing the [`Framework`] to call the right callback handler.
+///
+/// # Example
+/// ```rust
+/// use telexide::utils::CallbackData;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Vote {
+///     Up(i64),
+///     Down(i64),
+/// }
+///
+/// impl CallbackData for Vote {
+///     const PREFIX: &'static str = "vote";
+///
+///     fn to_fields(&self) -> Vec<String> {
+///         match self {
+///             Vote::Up(id) => vec!["up".to_owned(), id.to_string()],
+///             Vote::Down(id) => vec!["down".to_owned(), id.to_string()],
+///         }
+///     }
+///
+///     fn from_fields(fields: &[&str]) -> Option<Self> {
+///         match fields {
+///             ["up", id] => Some(Vote::Up(id.parse().ok()?)),
+///             ["down", id] => Some(Vote::Down(id.parse().ok()?)),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let data = Vote::Up(12).encode().unwrap();
+/// assert_eq!(data, "vote:up:12");
+/// assert_eq!(Vote::decode(&data), Some(Vote::Up(12)));
+/// ```
+///
+/// [`InlineKeyboardButton`]: ../model/struct.InlineKeyboardButton.html
+/// [`CallbackQuery::data`]: ../model/struct.CallbackQuery.html#structfield.data
+/// [`Framework`]: ../framework/struct.Framework.html
+pub trait CallbackData: Sized {
+    /// The prefix identifying this type of data
+    const PREFIX: &'static str;
+
+    /// Converts the data into the fields to encode
+    fn to_fields(&self) -> Vec<String>;
+
+    /// Creates the data from the decoded fields, returning None if they are
+    /// invalid
+    fn from_fields(fields: &[&str]) -> Option<Self>;
+
+    /// Encodes the data, returning an error if the prefix or any of the fields
+    /// contain the separator or if the encoded data is longer than
+    /// [`MAX_CALLBACK_DATA_LENGTH`] bytes
+    fn encode(&self) -> Result<String> {
+        let mut res = Self::PREFIX.to_owned();
+        if res.contains(CALLBACK_DATA_SEPARATOR) {
+            return Err(TelegramError::InvalidArgument(format!(
+                "callback data prefix \"{}\" can't contain '{CALLBACK_DATA_SEPARATOR}'",
+                Self::PREFIX
+            ))
+            .into());
+        }
+
+        for field in self.to_fields() {
+            if field.contains(CALLBACK_DATA_SEPARATOR) {
+                return Err(TelegramError::InvalidArgument(format!(
+                    "callback data field \"{field}\" can't contain '{CALLBACK_DATA_SEPARATOR}'"
+                ))
+                .into());
+            }
+
+            res.push(CALLBACK_DATA_SEPARATOR);
+            res.push_str(&field);
+        }
+
+        if res.is_empty() || res.len() > MAX_CALLBACK_DATA_LENGTH {
+            return Err(TelegramError::InvalidArgument(format!(
+                "encoded callback data \"{res}\" is {} bytes long, while it has to be between 1 \
+                 and {MAX_CALLBACK_DATA_LENGTH} bytes",
+                res.len()
+            ))
+            .into());
+        }
+
+        Ok(res)
+    }
+
+    /// Decodes the data, returning None if it has a different prefix or its
+    /// fields are invalid
+    fn decode(data: &str) -> Option<Self> {
+        let mut split = data.split(CALLBACK_DATA_SEPARATOR);
+        if split.next()? != Self::PREFIX {
+            return None;
+        }
+
+        Self::from_fields(&split.collect::<Vec<_>>())
+    }
+}
+
+/// Gets the prefix of encoded callback data
+pub(crate) fn get_callback_data_prefix(data: &str) -> &str {
+    data.split(CALLBACK_DATA_SEPARATOR)
+        .next()
+        .unwrap_or_default()
+}
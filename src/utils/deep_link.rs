@@ -0,0 +1,89 @@
+//! Helpers for [deep links] starting a conversation with a bot, like
+//! `https://t.me/<bot_username>?start=<payload>`.
+//!
+//! Telegram only allows payloads of up to [`MAX_START_PAYLOAD_LENGTH`]
+//! characters from `A-Z`, `a-z`, `0-9`, `_` and `-`, so other data has to be
+//! encoded first, for example using [`encode_payload`].
+//!
+//! ```rust
+//! use telexide::utils::deep_link::{build_start_link, decode_payload, encode_payload};
+//!
+//! let payload = encode_payload(b"ref=42").unwrap();
+//! let link = build_start_link("my_bot", &payload).unwrap();
+//! assert_eq!(link, "https://t.me/my_bot?start=cmVmPTQy");
+//! assert_eq!(decode_payload(&payload).unwrap(), b"ref=42");
+//! ```
+//!
+//! [deep links]: https://core.telegram.org/bots/features#deep-linking
+
+use super::result::{Result, TelegramError};
+use crate::model::Message;
+
+/// The maximum length of the payload of a deep link
+pub const MAX_START_PAYLOAD_LENGTH: usize = 64;
+
+/// Whether the payload can be used in a deep link, being 1-64 characters from
+/// `A-Z`, `a-z`, `0-9`, `_` and `-`
+pub fn is_valid_payload(payload: &str) -> bool {
+    (1..=MAX_START_PAYLOAD_LENGTH).contains(&payload.len())
+        && payload
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+/// Builds a link that starts a conversation with the bot, sending `/start
+/// <payload>` to it. Returns an error if the payload isn't valid, see
+/// [`is_valid_payload`].
+pub fn build_start_link(bot_username: &str, payload: &str) -> Result<String> {
+    if !is_valid_payload(payload) {
+        return Err(TelegramError::InvalidArgument(format!(
+            "the deep link payload \"{payload}\" must be 1-{MAX_START_PAYLOAD_LENGTH} characters \
+             from A-Z, a-z, 0-9, _ and -"
+        ))
+        .into());
+    }
+
+    Ok(format!(
+        "https://t.me/{}?start={payload}",
+        bot_username.trim_start_matches('@')
+    ))
+}
+
+/// Gets the payload of a `/start <payload>` message sent when a user opened a
+/// deep link, returning `None` if the message isn't a start command with a
+/// valid payload
+pub fn parse_start_payload(message: &Message) -> Option<String> {
+    let text = message.get_text()?;
+    let (command, payload) = text.split_once(char::is_whitespace)?;
+
+    let command = command.split('@').next()?;
+    let payload = payload.trim();
+    if command != "/start" || !is_valid_payload(payload) {
+        return None;
+    }
+    Some(payload.to_owned())
+}
+
+/// Encodes the data as unpadded base64url, so it can be used as the payload of
+/// a deep link. Returns an error if the encoded payload is longer than
+/// [`MAX_START_PAYLOAD_LENGTH`] characters, which is the case for more than 48
+/// bytes of data.
+pub fn encode_payload(data: &[u8]) -> Result<String> {
+    let encoded = base64::encode_config(data, base64::URL_SAFE_NO_PAD);
+
+    if !is_valid_payload(&encoded) {
+        return Err(TelegramError::InvalidArgument(format!(
+            "the encoded deep link payload must be 1-{MAX_START_PAYLOAD_LENGTH} characters, got \
+             {}",
+            encoded.len()
+        ))
+        .into());
+    }
+    Ok(encoded)
+}
+
+/// Decodes a payload encoded using [`encode_payload`], returning `None` if it
+/// isn't valid unpadded base64url
+pub fn decode_payload(payload: &str) -> Option<Vec<u8>> {
+    base64::decode_config(payload, base64::URL_SAFE_NO_PAD).ok()
+}
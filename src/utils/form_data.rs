@@ -34,6 +34,12 @@ impl FormDataFile {
         let mut bytes = Vec::new();
         file.read_to_end(&mut bytes)?;
 
+        Self::new_from_bytes(bytes, file_name)
+    }
+
+    /// Creates a file from its contents, guessing the media type from the
+    /// extension of the file name
+    pub fn new_from_bytes(bytes: Vec<u8>, file_name: &str) -> Result<Self> {
         Ok(Self {
             bytes,
             name: file_name
@@ -0,0 +1,320 @@
+use crate::model::{
+    MessageEntity,
+    ParseMode,
+    Pre,
+    TextBlock,
+    TextLink,
+    TextMention,
+    User,
+};
+
+const MARKDOWN_V2_RESERVED: &[char] = &[
+    '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!', '\\',
+];
+
+const MARKDOWN_RESERVED: &[char] = &['_', '*', '`', '['];
+
+fn escape_chars(text: &str, reserved: &[char]) -> String {
+    let mut res = String::with_capacity(text.len());
+    for c in text.chars() {
+        if reserved.contains(&c) {
+            res.push('\\');
+        }
+        res.push(c);
+    }
+    res
+}
+
+/// Escapes all the characters that are reserved in telegram's `MarkdownV2`
+/// formatting, so that the text will be displayed exactly as given. Text sent
+/// using the legacy `Markdown` has to be escaped using [`escape_markdown`]
+/// instead.
+///
+/// [`escape_markdown`]: fn.escape_markdown.html
+pub fn escape_markdown_v2(text: &str) -> String {
+    escape_chars(text, MARKDOWN_V2_RESERVED)
+}
+
+/// Escapes the characters that are reserved in telegram's legacy `Markdown`
+/// formatting, so that the text will be displayed exactly as given. Text sent
+/// using `MarkdownV2` has to be escaped using [`escape_markdown_v2`] instead.
+///
+/// [`escape_markdown_v2`]: fn.escape_markdown_v2.html
+pub fn escape_markdown(text: &str) -> String {
+    escape_chars(text, MARKDOWN_RESERVED)
+}
+
+/// Escapes the characters that are reserved in telegram's `HTML` formatting,
+/// so that the text will be displayed exactly as given
+pub fn escape_html(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            _ => res.push(c),
+        }
+    }
+    res
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Bold(String),
+    Italic(String),
+    Underline(String),
+    StrikeThrough(String),
+    Spoiler(String),
+    Code(String),
+    Pre(String, Option<String>),
+    Link(String, String),
+    Mention(String, User),
+}
+
+impl Segment {
+    fn text(&self) -> &str {
+        match self {
+            Segment::Text(t)
+            | Segment::Bold(t)
+            | Segment::Italic(t)
+            | Segment::Underline(t)
+            | Segment::StrikeThrough(t)
+            | Segment::Spoiler(t)
+            | Segment::Code(t)
+            | Segment::Pre(t, _)
+            | Segment::Link(t, _)
+            | Segment::Mention(t, _) => t,
+        }
+    }
+}
+
+/// A builder for composing formatted text, without having to worry about
+/// escaping or calculating entity offsets.
+///
+/// The text can be outputted either as a formatted string together with the
+/// [`ParseMode`] to send it with, or as plain text together with the
+/// [`MessageEntity`]s describing the formatting.
+///
+/// # Example
+/// ```rust
+/// use telexide::{model::ParseMode, utils::FormattedTextBuilder};
+///
+/// let (text, parse_mode) = FormattedTextBuilder::new()
+///     .bold("hello")
+///     .text(" world!")
+///     .to_markdown_v2();
+///
+/// assert_eq!(text, "*hello* world\\!");
+/// assert_eq!(parse_mode, ParseMode::MarkdownV2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormattedTextBuilder {
+    segments: Vec<Segment>,
+}
+
+impl FormattedTextBuilder {
+    /// Creates a new, empty, builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds plain text
+    pub fn text(&mut self, text: &str) -> &mut Self {
+        self.segments.push(Segment::Text(text.to_owned()));
+        self
+    }
+
+    /// Adds bold text
+    pub fn bold(&mut self, text: &str) -> &mut Self {
+        self.segments.push(Segment::Bold(text.to_owned()));
+        self
+    }
+
+    /// Adds italic text
+    pub fn italic(&mut self, text: &str) -> &mut Self {
+        self.segments.push(Segment::Italic(text.to_owned()));
+        self
+    }
+
+    /// Adds underlined text
+    pub fn underline(&mut self, text: &str) -> &mut Self {
+        self.segments.push(Segment::Underline(text.to_owned()));
+        self
+    }
+
+    /// Adds strikethrough text
+    pub fn strikethrough(&mut self, text: &str) -> &mut Self {
+        self.segments.push(Segment::StrikeThrough(text.to_owned()));
+        self
+    }
+
+    /// Adds text hidden behind a spoiler
+    pub fn spoiler(&mut self, text: &str) -> &mut Self {
+        self.segments.push(Segment::Spoiler(text.to_owned()));
+        self
+    }
+
+    /// Adds a monowidth code string
+    pub fn code(&mut self, text: &str) -> &mut Self {
+        self.segments.push(Segment::Code(text.to_owned()));
+        self
+    }
+
+    /// Adds a monowidth code block, optionally in the given programming
+    /// language
+    pub fn pre(&mut self, text: &str, language: Option<&str>) -> &mut Self {
+        self.segments.push(Segment::Pre(
+            text.to_owned(),
+            language.map(ToOwned::to_owned),
+        ));
+        self
+    }
+
+    /// Adds text that will open the url when clicked
+    pub fn link(&mut self, text: &str, url: &str) -> &mut Self {
+        self.segments
+            .push(Segment::Link(text.to_owned(), url.to_owned()));
+        self
+    }
+
+    /// Adds text that mentions the given user, which also works for users
+    /// without a username
+    pub fn mention(&mut self, text: &str, user: &User) -> &mut Self {
+        self.segments
+            .push(Segment::Mention(text.to_owned(), user.clone()));
+        self
+    }
+
+    /// Adds a mention of the given user using their full name as the text,
+    /// which also works for users without a username
+    pub fn mention_user(&mut self, user: &User) -> &mut Self {
+        let name = match &user.last_name {
+            Some(last_name) => format!("{} {}", user.first_name, last_name),
+            None => user.first_name.clone(),
+        };
+        self.mention(&name, user)
+    }
+
+    /// Outputs the text formatted using `MarkdownV2`, together with the
+    /// [`ParseMode`] to send it with
+    pub fn to_markdown_v2(&self) -> (String, ParseMode) {
+        let res = self
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(t) => escape_markdown_v2(t),
+                Segment::Bold(t) => format!("*{}*", escape_markdown_v2(t)),
+                Segment::Italic(t) => format!("_{}_\r", escape_markdown_v2(t)),
+                Segment::Underline(t) => format!("__{}__\r", escape_markdown_v2(t)),
+                Segment::StrikeThrough(t) => format!("~{}~", escape_markdown_v2(t)),
+                Segment::Spoiler(t) => format!("||{}||", escape_markdown_v2(t)),
+                Segment::Code(t) => format!("`{}`", escape_chars(t, &['`', '\\'])),
+                Segment::Pre(t, language) => format!(
+                    "```{}\n{}```",
+                    language.as_deref().unwrap_or_default(),
+                    escape_chars(t, &['`', '\\'])
+                ),
+                Segment::Link(t, url) => format!(
+                    "[{}]({})",
+                    escape_markdown_v2(t),
+                    escape_chars(url, &[')', '\\'])
+                ),
+                Segment::Mention(t, user) => format!(
+                    "[{}](tg://user?id={})",
+                    escape_markdown_v2(t),
+                    user.id
+                ),
+            })
+            .collect();
+
+        (res, ParseMode::MarkdownV2)
+    }
+
+    /// Outputs the text formatted using `HTML`, together with the
+    /// [`ParseMode`] to send it with
+    pub fn to_html(&self) -> (String, ParseMode) {
+        let res = self
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(t) => escape_html(t),
+                Segment::Bold(t) => format!("<b>{}</b>", escape_html(t)),
+                Segment::Italic(t) => format!("<i>{}</i>", escape_html(t)),
+                Segment::Underline(t) => format!("<u>{}</u>", escape_html(t)),
+                Segment::StrikeThrough(t) => format!("<s>{}</s>", escape_html(t)),
+                Segment::Spoiler(t) => format!("<tg-spoiler>{}</tg-spoiler>", escape_html(t)),
+                Segment::Code(t) => format!("<code>{}</code>", escape_html(t)),
+                Segment::Pre(t, Some(language)) => format!(
+                    "<pre><code class=\"language-{}\">{}</code></pre>",
+                    escape_html(language),
+                    escape_html(t)
+                ),
+                Segment::Pre(t, None) => format!("<pre>{}</pre>", escape_html(t)),
+                Segment::Link(t, url) => format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape_html(url),
+                    escape_html(t)
+                ),
+                Segment::Mention(t, user) => format!(
+                    "<a href=\"tg://user?id={}\">{}</a>",
+                    user.id,
+                    escape_html(t)
+                ),
+            })
+            .collect();
+
+        (res, ParseMode::HTML)
+    }
+
+    /// Outputs the text without any formatting, together with the
+    /// [`MessageEntity`]s describing the formatting, to be used as the
+    /// `entities` of a message.
+    ///
+    /// The offsets and lengths of the entities are calculated in UTF-16 code
+    /// units, as telegram requires.
+    pub fn to_entities(&self) -> (String, Vec<MessageEntity>) {
+        let mut res = String::new();
+        let mut entities = Vec::new();
+        let mut offset = 0;
+
+        for segment in &self.segments {
+            let text = segment.text();
+            let length = text.encode_utf16().count();
+            let text_block = TextBlock {
+                offset,
+                length,
+            };
+
+            let entity = match segment {
+                Segment::Text(_) => None,
+                Segment::Bold(_) => Some(MessageEntity::Bold(text_block)),
+                Segment::Italic(_) => Some(MessageEntity::Italic(text_block)),
+                Segment::Underline(_) => Some(MessageEntity::Underline(text_block)),
+                Segment::StrikeThrough(_) => Some(MessageEntity::StrikeThrough(text_block)),
+                Segment::Spoiler(_) => Some(MessageEntity::Spoiler(text_block)),
+                Segment::Code(_) => Some(MessageEntity::Code(text_block)),
+                Segment::Pre(_, language) => Some(MessageEntity::Pre(Pre {
+                    text_block,
+                    language: language.clone(),
+                })),
+                Segment::Link(_, url) => Some(MessageEntity::TextLink(TextLink {
+                    text_block,
+                    url: url.clone(),
+                })),
+                Segment::Mention(_, user) => Some(MessageEntity::TextMention(TextMention {
+                    text_block,
+                    user: user.clone(),
+                })),
+            };
+
+            entities.extend(entity);
+            res.push_str(text);
+            offset += length;
+        }
+
+        (res, entities)
+    }
+}
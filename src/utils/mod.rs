@@ -1,5 +1,21 @@
+//! Utilities for working with telegram, like formatting text
+
+mod callback_data;
 mod form_data;
+mod formatting;
+mod spawn;
+mod split;
+mod utf16;
+pub mod deep_link;
 pub mod macros;
 pub mod result;
 
-pub(crate) use form_data::{encode_multipart_form_data, AsFormData, FormDataFile, BOUNDARY};
+pub use callback_data::{CallbackData, CALLBACK_DATA_SEPARATOR, MAX_CALLBACK_DATA_LENGTH};
+pub use form_data::FormDataFile;
+pub use formatting::{escape_html, escape_markdown, escape_markdown_v2, FormattedTextBuilder};
+pub use split::{split_text, MAX_CAPTION_LENGTH, MAX_MESSAGE_LENGTH};
+pub use utf16::{entity_text, utf16_offset_to_byte};
+
+pub(crate) use callback_data::get_callback_data_prefix;
+pub(crate) use form_data::{encode_multipart_form_data, AsFormData, BOUNDARY};
+pub(crate) use spawn::spawn;
@@ -29,17 +29,44 @@ pub enum TelegramError {
     InvalidEndpoint,
     InvalidCommandType,
     WebhookError,
+    WebhookConflict,
+    Timeout,
     InvalidArgument(String),
+    Validation(ValidationError),
     APIResponseError(String),
     Unknown(String),
 }
 
+/// An error returned before sending a request, when a field of it is longer
+/// than telegram allows. The length is counted in UTF-16 code units, like
+/// telegram does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The name of the field that is too long, like `caption`
+    pub field: String,
+    /// The maximum length telegram allows for the field
+    pub limit: usize,
+    /// The length of the field in the request
+    pub length: usize,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} can be at most {} characters long, got {}",
+            self.field, self.limit, self.length
+        )
+    }
+}
+
 impl TelegramError {
     pub fn description(&self) -> String {
         match *self {
             TelegramError::NoToken => "No token provided to login to telegram".to_owned(),
             TelegramError::InvalidToken => {
-                "Invalid token provided for logging in to telegram".to_owned()
+                "Unauthorized: the bot token is invalid, check the token given by @BotFather"
+                    .to_owned()
             },
             TelegramError::MissingPermission => {
                 "Missing permission to execute action in chat".to_owned()
@@ -49,11 +76,18 @@ impl TelegramError {
                 "The telegram server returned a 500 status code".to_owned()
             },
             TelegramError::WebhookError => "An error occurred in the webhook handling".to_owned(),
+            TelegramError::WebhookConflict => {
+                "Conflict: updates can't be polled while a webhook is set, delete it using \
+                 deleteWebhook first"
+                    .to_owned()
+            },
+            TelegramError::Timeout => "The operation timed out".to_owned(),
             TelegramError::InvalidEndpoint => "The requested endpoint does not exist".to_owned(),
             TelegramError::InvalidCommandType => {
                 "This action cannot be done on this command type".to_owned()
             },
             TelegramError::InvalidArgument(ref e) => format!("Invalid argument provided: {}", e),
+            TelegramError::Validation(ref e) => format!("Invalid request: {e}"),
             TelegramError::APIResponseError(ref e) => {
                 format!("the telegram api returned an error: {}", e)
             },
@@ -0,0 +1,15 @@
+use std::future::Future;
+use tokio::task::JoinHandle;
+
+/// Spawns the future like [`tokio::spawn`], but with the `tracing` feature
+/// enabled it stays in the current span, so the logs of a handler can be
+/// correlated with the update it handles
+pub(crate) fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    #[cfg(feature = "tracing")]
+    let future = tracing::Instrument::in_current_span(future);
+    tokio::spawn(future)
+}
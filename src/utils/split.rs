@@ -0,0 +1,245 @@
+use crate::{
+    model::ParseMode,
+    utils::result::{Result, TelegramError},
+};
+
+/// The maximum length of the text of a message, in UTF-16 code units
+pub const MAX_MESSAGE_LENGTH: usize = 4096;
+/// The maximum length of the caption of a media message, in UTF-16 code units
+pub const MAX_CAPTION_LENGTH: usize = 1024;
+
+/// Where a text can be split, ordered from the least to the most preferred
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Break {
+    Anywhere,
+    Whitespace,
+    Newline,
+}
+
+/// Splits the text into parts of at most `max_length` UTF-16 code units, like
+/// telegram counts them, so that each can be sent as a separate message. Use
+/// [`MAX_MESSAGE_LENGTH`] to split the text of a message.
+///
+/// The text is split at the last newline that fits, otherwise the last
+/// whitespace, and only in the middle of a word if there is no whitespace to
+/// split on. The whitespace a text is split on is left out. When using a parse
+/// mode, the text is never split inside an entity or a tag, so every part is
+/// formatted on its own. If a formatted part of the text is longer than
+/// `max_length` it can't be split, and [`TelegramError::InvalidArgument`] is
+/// returned.
+///
+/// A text that doesn't have to be split is returned as is.
+///
+/// [`MAX_MESSAGE_LENGTH`]: constant.MAX_MESSAGE_LENGTH.html
+/// [`TelegramError::InvalidArgument`]: result/enum.TelegramError.html#variant.InvalidArgument
+pub fn split_text<'a>(
+    text: &'a str,
+    parse_mode: Option<&ParseMode>,
+    max_length: usize,
+) -> Result<Vec<&'a str>> {
+    if text.encode_utf16().count() <= max_length {
+        return Ok(vec![text]);
+    }
+
+    // the places the text can be split at, with their offset in UTF-16 units
+    let mut breaks = Vec::new();
+    let mut units = 0;
+    for ((index, c), splittable) in text.char_indices().zip(splittable_chars(text, parse_mode)) {
+        if splittable {
+            let kind = match c {
+                '\n' => Break::Newline,
+                c if c.is_whitespace() => Break::Whitespace,
+                _ => Break::Anywhere,
+            };
+            breaks.push((index, units, kind));
+        }
+        units += c.len_utf16();
+    }
+    breaks.push((text.len(), units, Break::Newline));
+
+    let mut parts = Vec::new();
+    let mut start = (0, 0);
+    let mut next = 0;
+    while start.0 < text.len() {
+        // the best place to split in the breaks that fit in this part
+        let mut best: Option<(usize, usize, Break)> = None;
+        while let Some(&(index, units, kind)) = breaks.get(next) {
+            if units - start.1 > max_length {
+                break;
+            }
+            if index > start.0 && !matches!(best, Some((.., best_kind)) if kind < best_kind) {
+                best = Some((index, units, kind));
+            }
+            next += 1;
+        }
+
+        let (end, end_units, _) = best.ok_or_else(|| {
+            TelegramError::InvalidArgument(format!(
+                "the text has a formatted part longer than {max_length} characters, which can't \
+                 be split"
+            ))
+        })?;
+        let part = text[start.0..end].trim_end();
+        if !part.is_empty() {
+            parts.push(part);
+        }
+
+        // skip the whitespace the text is split on
+        start = (end, end_units);
+        for c in text[end..].chars().take_while(|c| c.is_whitespace()) {
+            start = (start.0 + c.len_utf8(), start.1 + c.len_utf16());
+        }
+        next = breaks.partition_point(|&(index, ..)| index <= start.0);
+    }
+    Ok(parts)
+}
+
+/// Gets for every char of the text whether it can be split before it, which
+/// isn't the case for chars inside of entities or tags of the parse mode
+fn splittable_chars(text: &str, parse_mode: Option<&ParseMode>) -> Vec<bool> {
+    match parse_mode {
+        Some(ParseMode::HTML) => splittable_html(text),
+        Some(ParseMode::MarkdownV2) => splittable_markdown(text, true),
+        #[allow(deprecated)]
+        Some(ParseMode::Markdown) => splittable_markdown(text, false),
+        Some(ParseMode::None) | None => vec![true; text.chars().count()],
+    }
+}
+
+fn splittable_html(text: &str) -> Vec<bool> {
+    let mut splittable = Vec::new();
+    let mut depth = 0_usize;
+    let mut tag: Option<String> = None;
+    let mut in_character_reference = false;
+
+    for c in text.chars() {
+        splittable.push(depth == 0 && tag.is_none() && !in_character_reference);
+        if let Some(name) = &mut tag {
+            if c == '>' {
+                if name.starts_with('/') {
+                    depth = depth.saturating_sub(1);
+                } else if !name.ends_with('/') {
+                    depth += 1;
+                }
+                tag = None;
+            } else {
+                name.push(c);
+            }
+        } else if in_character_reference {
+            in_character_reference = c != ';' && !c.is_whitespace();
+        } else if c == '<' {
+            tag = Some(String::new());
+        } else if c == '&' {
+            in_character_reference = true;
+        }
+    }
+    splittable
+}
+
+#[derive(Default)]
+struct MarkdownState {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+    spoiler: bool,
+    code: bool,
+    pre: bool,
+    link_depth: usize,
+    in_url: bool,
+    in_quote: bool,
+}
+
+impl MarkdownState {
+    fn is_formatted(&self) -> bool {
+        self.bold
+            || self.italic
+            || self.underline
+            || self.strikethrough
+            || self.spoiler
+            || self.code
+            || self.pre
+            || self.link_depth > 0
+            || self.in_url
+    }
+}
+
+/// Handles both `MarkdownV2` and the legacy `Markdown`, which only supports
+/// bold, italic, code and links
+fn splittable_markdown(text: &str, v2: bool) -> Vec<bool> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut splittable = Vec::with_capacity(chars.len());
+    let mut state = MarkdownState::default();
+    let is_at = |i: usize, token: &str| {
+        token
+            .chars()
+            .enumerate()
+            .all(|(n, c)| chars.get(i + n) == Some(&c))
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let line_start = i == 0 || chars[i - 1] == '\n';
+        if v2 && line_start && !state.is_formatted() && (c == '>' || is_at(i, "**>")) {
+            state.in_quote = true;
+        }
+        // a quote lasts until the end of its line, where it can be split
+        splittable.push(!state.is_formatted() && (!state.in_quote || c == '\n'));
+
+        // legacy markdown only supports escaping outside of entities
+        let length = if c == '\\' && (v2 || !state.is_formatted()) {
+            2
+        } else if state.pre {
+            if is_at(i, "```") {
+                state.pre = false;
+                3
+            } else {
+                1
+            }
+        } else if state.code {
+            state.code = c != '`';
+            1
+        } else if state.in_url {
+            state.in_url = c != ')';
+            1
+        } else if is_at(i, "```") {
+            state.pre = true;
+            3
+        } else {
+            match c {
+                '\n' => state.in_quote = false,
+                '`' => state.code = true,
+                '*' => state.bold = !state.bold,
+                '_' if v2 && is_at(i, "__") => state.underline = !state.underline,
+                '_' => state.italic = !state.italic,
+                '~' if v2 => state.strikethrough = !state.strikethrough,
+                '|' if v2 && is_at(i, "||") => {
+                    // an expandable quote ends with || at the end of its line
+                    let quote_end = matches!(chars.get(i + 2), None | Some('\n'));
+                    if !(state.in_quote && !state.spoiler && quote_end) {
+                        state.spoiler = !state.spoiler;
+                    }
+                },
+                '[' => state.link_depth += 1,
+                ']' if state.link_depth > 0 => {
+                    state.link_depth -= 1;
+                    state.in_url = is_at(i, "](");
+                },
+                _ => {},
+            }
+            match c {
+                '_' if v2 && is_at(i, "__") => 2,
+                '|' if v2 && is_at(i, "||") => 2,
+                ']' if state.in_url => 2,
+                _ => 1,
+            }
+        };
+
+        // chars inside of a token can't be split on
+        let length = length.min(chars.len() - i);
+        splittable.resize(splittable.len() + length - 1, false);
+        i += length;
+    }
+    splittable
+}
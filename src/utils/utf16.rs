@@ -0,0 +1,28 @@
+use crate::model::MessageEntity;
+
+/// Converts an offset in UTF-16 code units, like the offsets telegram uses for
+/// entities, into a byte offset in the text that can be used to slice it.
+///
+/// Returns `None` if the offset lies past the end of the text, or in the
+/// middle of a character that takes up two UTF-16 code units, like most
+/// emoji.
+pub fn utf16_offset_to_byte(text: &str, offset: usize) -> Option<usize> {
+    let mut units = 0;
+    for (index, c) in text.char_indices() {
+        if units >= offset {
+            return (units == offset).then_some(index);
+        }
+        units += c.len_utf16();
+    }
+    (units == offset).then_some(text.len())
+}
+
+/// Gets the part of the text the entity applies to, without copying it.
+/// Returns `None` if the entity doesn't describe a valid part of the text,
+/// for example because it was sent for another text. See
+/// [`TextBlock::get_str`].
+///
+/// [`TextBlock::get_str`]: ../model/struct.TextBlock.html#method.get_str
+pub fn entity_text<'a>(text: &'a str, entity: &MessageEntity) -> Option<&'a str> {
+    entity.get_text_block().get_str(text)
+}
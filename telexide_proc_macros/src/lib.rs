@@ -11,17 +11,18 @@ extern crate proc_macro;
 use quote::quote;
 use proc_macro::TokenStream;
 use syn::{
-    parse_macro_input
+    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, PathArguments, Type
 };
 use crate::structs::{
-    ListenerFunc, CommandFunc
+    ListenerFunc, CommandFunc, CheckFunc
 };
-use utils::{add_suffix, PunctuatedNamedArgs};
+use utils::{add_suffix, CooldownArgs, CooldownSpec, ParenthesisedItems, PunctuatedNamedArgs};
 
 /// A function attribute macro for making event listeners easier.
 ///
 /// This macro transforms an async function into a function returning a pinned box containing a future,
-/// which is used internally by telexide to store the function.
+/// which is used internally by telexide to store the function. The future resolves to the return
+/// value of the function, which is needed for listeners like the framework's before hook.
 #[proc_macro_attribute]
 pub fn prepare_listener(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let listener = parse_macro_input!(item as ListenerFunc);
@@ -30,6 +31,19 @@ pub fn prepare_listener(_attr: TokenStream, item: TokenStream) -> TokenStream {
     }).into()
 }
 
+/// A function attribute macro for making command checks easier.
+///
+/// This macro transforms an async function returning a `bool`, `Result<bool, E>` or `CheckResult`
+/// into a function returning a pinned box containing a future resolving to a `CheckResult`,
+/// which can then be added to a command using the `#[check(...)]` attribute.
+#[proc_macro_attribute]
+pub fn prepare_check(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let check = parse_macro_input!(item as CheckFunc);
+    (quote!{
+        #check
+    }).into()
+}
+
 /// A function attribute macro for making commands.
 ///
 /// This macro will prepare your commands, which can then be added to your framework using
@@ -47,6 +61,107 @@ pub fn prepare_listener(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// |-------------|----------------------------------|---------------------------------------------------------------------------------------------|
 /// | Description | description = "your description" | The description of the command as to be displayed in telegram, 3-256 characters             |
 /// | Name        | name = "the command name"        | The name to be used within telegram, 1-32 characters                                        |
+/// | Category    | category = "the category"        | The category the command is listed under in the framework's help command                    |
+///
+/// # Aliases
+///
+/// Other names the command can be called with can be added using the `aliases` attribute. Only
+/// the name of the command is registered with telegram, and the framework panics when a name or
+/// alias collides with that of another command.
+/// ```rust,ignore
+/// #[command(description = "shows the stats")]
+/// #[aliases("stat", "s")]
+/// async fn stats(ctx: Context, message: Message) { ... }
+/// ```
+///
+/// # Hidden commands
+///
+/// Commands with the `hidden` attribute can still be called, but aren't registered with telegram
+/// and aren't listed by the framework's help command.
+/// ```rust,ignore
+/// #[command(description = "shuts the bot down")]
+/// #[hidden]
+/// #[owner_only]
+/// async fn shutdown(ctx: Context, message: Message) { ... }
+/// ```
+///
+/// # Checks
+///
+/// Checks that need to pass before the command is called can be added using the `check` attribute,
+/// listing functions prepared using the `#[prepare_check]` macro. The checks are run in order,
+/// and the framework's check failure handler is called with the reason of the first failing check.
+/// ```rust,ignore
+/// #[command(description = "bans the user")]
+/// #[check(is_admin, in_group)]
+/// async fn ban(ctx: Context, message: Message) { ... }
+/// ```
+///
+/// # Guards
+///
+/// Commonly used checks are built in as attributes, and are run before the checks added using the
+/// `check` attribute. When one fails, the framework's check failure handler is called with a
+/// reason describing why.
+///
+/// | Attribute                         | Description                                                                    |
+/// |-----------------------------------|--------------------------------------------------------------------------------|
+/// | `#[owner_only]`                   | Only the owners set on the framework can use the command                       |
+/// | `#[only_in(private)]`             | The command can only be used in private chats                                  |
+/// | `#[only_in(groups)]`              | The command can only be used in groups and supergroups                         |
+/// | `#[required_permissions(admin)]`  | Only admins of the chat, including anonymous admins, can use the command       |
+/// | `#[required_permissions(creator)]`| Only the creator of the chat can use the command                               |
+///
+/// ```rust,ignore
+/// #[command(description = "bans the user")]
+/// #[only_in(groups)]
+/// #[required_permissions(admin)]
+/// async fn ban(ctx: Context, message: Message) { ... }
+/// ```
+///
+/// # Cooldowns
+///
+/// Cooldowns can be added to the command using the `cooldown` attribute, limiting how often it can
+/// be used per `user`, per `chat` or `global`ly. A limit like `"30s"` allows one use every 30
+/// seconds, while `"5/60s"` allows 5 uses within any 60 seconds, with `ms`, `s`, `m` and `h` being
+/// the supported units. When the command is used while on cooldown, the framework's cooldown
+/// handler is called with the remaining duration. The shorthand `#[cooldown(30, user)]` is the
+/// same as `#[cooldown(user = "30s")]`.
+/// ```rust,ignore
+/// #[command(description = "sends a meme")]
+/// #[cooldown(user = "30s", chat = "5/60s")]
+/// async fn meme(ctx: Context, message: Message) { ... }
+/// ```
+///
+/// # Subcommands
+///
+/// Subcommands can be added using the `subcommands` attribute, listing other commands made using
+/// this macro. When the first argument of the command matches the name of a subcommand, the
+/// subcommand is called with the remaining arguments instead, which works for nested subcommands
+/// as well. Otherwise the command itself is called. The checks of the command also apply to its
+/// subcommands, and only the command itself should be added to the framework.
+/// ```rust,ignore
+/// #[command(description = "bans the user")]
+/// async fn ban(ctx: Context, message: Message) { ... }
+///
+/// #[command(description = "admin commands")]
+/// #[subcommands(ban, unban)]
+/// async fn admin(ctx: Context, message: Message) { ... }
+/// ```
+///
+/// # Arguments
+///
+/// When the command function takes a third parameter, implementing `FromCommandArguments`, it
+/// will be parsed from the arguments of the command before the command is called. When parsing
+/// fails, the command replies with the error and the usage of the command instead.
+/// ```rust,ignore
+/// #[derive(FromCommandArguments)]
+/// struct BanArgs {
+///     user: i64,
+///     reason: Option<String>,
+/// }
+///
+/// #[command(description = "bans the user")]
+/// async fn ban(ctx: Context, message: Message, args: BanArgs) { ... }
+/// ```
 ///
 /// # Notes
 ///
@@ -59,11 +174,16 @@ pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let mut telegram_command_name = command_fun.name.to_string();
     let mut description = String::new();
+    let mut category = quote!(None);
 
     for arg in args.0 {
         match arg.name.as_str() {
             "name" => telegram_command_name =  arg.value.clone(),
             "description" => description = arg.value.clone(),
+            "category" => {
+                let value = arg.value.clone();
+                category = quote!(Some(#value))
+            },
             _ => ()
         }
     }
@@ -75,6 +195,87 @@ pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
         )
     }
 
+    let mut checks = Vec::new();
+    let mut subcommands = Vec::new();
+    let mut aliases = Vec::new();
+    let mut cooldowns = Vec::new();
+    let mut owner_only = false;
+    let mut hidden = false;
+    let mut only_in = quote!(None);
+    let mut required_permissions = quote!(None);
+    for attr in &command_fun.attributes {
+        if attr.path.is_ident("check") {
+            match syn::parse2::<ParenthesisedItems<syn::Path>>(attr.tokens.clone()) {
+                Ok(ParenthesisedItems(paths)) => checks.extend(paths),
+                Err(e) => return e.to_compile_error().into(),
+            }
+        } else if attr.path.is_ident("subcommands") {
+            match syn::parse2::<ParenthesisedItems<syn::Path>>(attr.tokens.clone()) {
+                Ok(ParenthesisedItems(paths)) => subcommands.extend(paths.into_iter().map(|mut p| {
+                    if let Some(last) = p.segments.last_mut() {
+                        last.ident = add_suffix(&last.ident, "COMMAND");
+                    }
+                    p
+                })),
+                Err(e) => return e.to_compile_error().into(),
+            }
+        } else if attr.path.is_ident("aliases") {
+            match syn::parse2::<ParenthesisedItems<syn::LitStr>>(attr.tokens.clone()) {
+                Ok(ParenthesisedItems(names)) => aliases.extend(names),
+                Err(e) => return e.to_compile_error().into(),
+            }
+        } else if attr.path.is_ident("owner_only") {
+            owner_only = true;
+        } else if attr.path.is_ident("hidden") {
+            hidden = true;
+        } else if attr.path.is_ident("only_in") {
+            match parse_ident_option(attr, &[("private", "Private"), ("groups", "Groups")]) {
+                Ok(variant) => only_in = quote!(Some(telexide::framework::OnlyIn::#variant)),
+                Err(e) => return e.to_compile_error().into(),
+            }
+        } else if attr.path.is_ident("required_permissions") {
+            match parse_ident_option(attr, &[("admin", "Admin"), ("creator", "Creator")]) {
+                Ok(variant) => required_permissions = quote!(
+                    Some(telexide::framework::RequiredPermissions::#variant)
+                ),
+                Err(e) => return e.to_compile_error().into(),
+            }
+        } else if attr.path.is_ident("cooldown") {
+            match syn::parse2::<CooldownArgs>(attr.tokens.clone()) {
+                Ok(CooldownArgs(specs)) => cooldowns.extend(specs.into_iter().map(
+                    |CooldownSpec { scope, uses, millis }| quote!(
+                        telexide::framework::Cooldown {
+                            uses: #uses,
+                            duration: ::std::time::Duration::from_millis(#millis),
+                            scope: telexide::framework::CooldownScope::#scope,
+                        }
+                    )
+                )),
+                Err(e) => return e.to_compile_error().into(),
+            }
+        }
+    }
+
+    let usage = match command_fun.args.get(2) {
+        Some(syn::FnArg::Typed(t)) => {
+            let args_type = &t.ty;
+            quote!(Some(<#args_type as telexide::framework::FromCommandArguments>::usage))
+        },
+        _ => quote!(None),
+    };
+
+    let command_tokens = match command_fun.args.len() {
+        2 => quote!(#command_fun),
+        3 => match command_fun.with_arguments(&telegram_command_name) {
+            Ok(f) => f,
+            Err(e) => return e.to_compile_error().into(),
+        },
+        _ => panic!(
+            "The {} command has to take a Context, a Message and optionally its arguments",
+            telegram_command_name
+        ),
+    };
+
     let fun_name = command_fun.name.clone();
     let command_name = add_suffix(&fun_name,"COMMAND");
     let options_name = add_suffix(&fun_name, "COMMAND_OPTIONS");
@@ -90,7 +291,17 @@ pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
         #(#options_cooked)*
         pub static #options_name: #options_struct_path = #options_struct_path {
             name: #telegram_command_name,
+            aliases: &[#(#aliases),*],
             description: #description,
+            category: #category,
+            hidden: #hidden,
+            usage: #usage,
+            checks: &[#(#checks),*],
+            owner_only: #owner_only,
+            only_in: #only_in,
+            required_permissions: #required_permissions,
+            cooldowns: &[#(#cooldowns),*],
+            subcommands: &[#(&#subcommands),*],
         };
 
         #(#command_cooked)*
@@ -99,6 +310,115 @@ pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
             command: #default_command_type_path(#fun_name),
         };
 
-        #command_fun
+        #command_tokens
     }).into()
 }
+
+/// A derive macro for parsing a struct with named fields from the arguments of a command.
+///
+/// Every field is parsed in order as a single argument using its `FromStr` implementation, where
+/// an argument can contain whitespace by surrounding it with double quotes. Fields with an
+/// `Option` type are optional and should come after the required fields. The usage of the command
+/// is generated from the names of the fields, for example `<user> [reason]`.
+/// ```rust,ignore
+/// #[derive(FromCommandArguments)]
+/// struct BanArgs {
+///     user: i64,
+///     reason: Option<String>,
+/// }
+/// ```
+#[proc_macro_derive(FromCommandArguments)]
+pub fn derive_from_command_arguments(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let name = input.ident;
+
+    let fields = match input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            _ => return syn::Error::new_spanned(
+                name,
+                "FromCommandArguments can only be derived for structs with named fields"
+            ).to_compile_error().into(),
+        },
+        _ => return syn::Error::new_spanned(
+            name,
+            "FromCommandArguments can only be derived for structs"
+        ).to_compile_error().into(),
+    };
+
+    let mut parsers = Vec::new();
+    let mut usage = Vec::new();
+    for field in fields {
+        let ident = field.ident.expect("named fields have an ident");
+        let arg_name = ident.to_string();
+
+        match option_inner_type(&field.ty) {
+            Some(inner) => {
+                usage.push(format!("[{}]", arg_name));
+                parsers.push(quote!(
+                    #ident: args.optional_arg::<#inner>().map_err(|e| e.named(#arg_name))?
+                ));
+            },
+            None => {
+                let ty = field.ty;
+                usage.push(format!("<{}>", arg_name));
+                parsers.push(quote!(
+                    #ident: args.next_arg::<#ty>().map_err(|e| e.named(#arg_name))?
+                ));
+            },
+        }
+    }
+
+    let usage = usage.join(" ");
+
+    (quote!{
+        impl telexide::framework::FromCommandArguments for #name {
+            fn from_arguments(
+                args: &mut telexide::framework::CommandArguments,
+            ) -> ::std::result::Result<Self, telexide::framework::ArgumentError> {
+                ::std::result::Result::Ok(Self {
+                    #(#parsers),*
+                })
+            }
+
+            fn usage() -> ::std::string::String {
+                ::std::string::String::from(#usage)
+            }
+        }
+    }).into()
+}
+
+/// Parses an attribute like `#[only_in(private)]`, mapping the given ident to the name of an
+/// enum variant
+fn parse_ident_option(attr: &syn::Attribute, options: &[(&str, &str)]) -> syn::Result<syn::Ident> {
+    let ParenthesisedItems(idents) = syn::parse2::<ParenthesisedItems<syn::Ident>>(attr.tokens.clone())?;
+    let expected = options.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ");
+
+    match idents.first() {
+        Some(ident) if idents.len() == 1 => options
+            .iter()
+            .find(|(name, _)| ident == name)
+            .map(|(_, variant)| syn::Ident::new(variant, ident.span()))
+            .ok_or_else(|| syn::Error::new(ident.span(), format!("expected one of {}", expected))),
+        _ => Err(syn::Error::new_spanned(attr, format!("expected one of {}", expected))),
+    }
+}
+
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(p) => p.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        },
+        _ => None,
+    }
+}
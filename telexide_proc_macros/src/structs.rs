@@ -1,6 +1,6 @@
 use proc_macro2::TokenStream as TokenStream2;
 use syn::parse::{Parse, ParseStream, Result};
-use syn::{Attribute, Ident, Stmt, Visibility, ReturnType, braced, FnArg, Token, Block, Type};
+use syn::{Attribute, Ident, Stmt, Visibility, ReturnType, braced, FnArg, Token, Block, Type, Error};
 use quote::{quote, ToTokens};
 use super::utils::ParenthesisedItems;
 
@@ -12,6 +12,7 @@ pub struct ListenerFunc {
     pub cooked: Vec<Attribute>,
     pub visibility: Visibility,
     pub name: Ident,
+    pub ret: Option<Type>,
     pub args: Vec<FnArg>,
     pub body: Vec<Stmt>,
 }
@@ -31,12 +32,9 @@ impl Parse for ListenerFunc {
 
         let ParenthesisedItems(args) = input.parse::<ParenthesisedItems<FnArg>>()?;
 
-        match input.parse::<ReturnType>()? {
-            ReturnType::Type(_, _) => {
-                return Err(input
-                    .error("expected a default return value"))
-            },
-            ReturnType::Default => ()
+        let ret = match input.parse::<ReturnType>()? {
+            ReturnType::Type(_, t) => Some(*t),
+            ReturnType::Default => None
         };
 
         let body_content;
@@ -50,6 +48,7 @@ impl Parse for ListenerFunc {
             cooked,
             visibility,
             name,
+            ret,
             args,
             body,
         })
@@ -59,17 +58,20 @@ impl Parse for ListenerFunc {
 impl ToTokens for ListenerFunc {
     fn to_tokens(&self, stream: &mut TokenStream2) {
         let Self {
-            attributes: _,
+            attributes,
             cooked,
             visibility,
             name,
+            ret,
             args,
             body,
         } = self;
 
+        let ret = ret.as_ref().map_or_else(|| quote!(()), |t| quote!(#t));
         stream.extend(quote! {
             #(#cooked)*
-            #visibility fn #name (#(#args),*) -> ::std::pin::Pin<::std::boxed::Box<(dyn ::std::future::Future<Output = ()> + ::std::marker::Send )>> {
+            #(#attributes)*
+            #visibility fn #name (#(#args),*) -> ::std::pin::Pin<::std::boxed::Box<(dyn ::std::future::Future<Output = #ret> + ::std::marker::Send )>> {
                 ::std::boxed::Box::pin(async move {
                     #(#body)*
             })
@@ -154,3 +156,131 @@ impl ToTokens for CommandFunc {
         });
     }
 }
+
+impl CommandFunc {
+    /// Outputs the command function, parsing its third parameter from the arguments of the
+    /// command before running the body, or replying with the usage of the command if that fails
+    pub fn with_arguments(&self, command_name: &str) -> Result<TokenStream2> {
+        let Self {
+            attributes: _,
+            cooked,
+            visibility,
+            ret,
+            name,
+            args,
+            body,
+        } = self;
+
+        let mut pats = Vec::new();
+        let mut types = Vec::new();
+        for arg in args {
+            match arg {
+                FnArg::Typed(t) => {
+                    pats.push(t.pat.clone());
+                    types.push(t.ty.clone());
+                },
+                FnArg::Receiver(r) => return Err(Error::new_spanned(r, "commands can't take self")),
+            }
+        }
+
+        let (ctx_type, msg_type, args_type) = (&types[0], &types[1], &types[2]);
+
+        Ok(quote! {
+            #(#cooked)*
+            #visibility fn #name (__ctx: #ctx_type, __message: #msg_type) -> ::std::pin::Pin<::std::boxed::Box<(dyn ::std::future::Future<Output = #ret> + ::std::marker::Send )>> {
+                ::std::boxed::Box::pin(async move {
+                    let __args = match <#args_type as telexide::framework::FromCommandArguments>::from_arguments(&mut __ctx.args()) {
+                        ::std::result::Result::Ok(args) => args,
+                        ::std::result::Result::Err(err) => {
+                            let usage = <#args_type as telexide::framework::FromCommandArguments>::usage();
+                            return telexide::framework::reply_argument_error(&__ctx, &__message, #command_name, &usage, &err).await;
+                        },
+                    };
+                    let (#(#pats),*): (#(#types),*) = (__ctx, __message, __args);
+                    #(#body)*
+            })
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CheckFunc {
+    /// `#[...]`-style attributes.
+    pub attributes: Vec<Attribute>,
+    /// Populated by `#[cfg(...)]` type attributes.
+    pub cooked: Vec<Attribute>,
+    pub visibility: Visibility,
+    pub name: Ident,
+    pub ret: Type,
+    pub args: Vec<FnArg>,
+    pub body: Vec<Stmt>,
+}
+
+impl Parse for CheckFunc {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
+
+        let (cooked, attributes): (Vec<_>, Vec<_>) =
+            attributes.into_iter().partition(|a| a.path.is_ident("cfg"));
+
+        let visibility = input.parse::<Visibility>()?;
+
+        input.parse::<Token![async]>()?;
+        input.parse::<Token![fn]>()?;
+        let name = input.parse()?;
+
+        let ParenthesisedItems(args) = input.parse::<ParenthesisedItems<FnArg>>()?;
+
+        let ret = match input.parse::<ReturnType>()? {
+            ReturnType::Type(_, t) => {
+                *t
+            },
+            ReturnType::Default => return Err(input
+                .error("expected a bool, Result<bool> or CheckResult return value"))
+        };
+
+        let body_content;
+        braced!(body_content in input);
+        let body: Vec<Stmt> = body_content.call(Block::parse_within)?;
+
+        let args = args.into_iter().collect::<Vec<FnArg>>();
+
+        Ok(Self {
+            attributes,
+            cooked,
+            visibility,
+            name,
+            ret,
+            args,
+            body,
+        })
+    }
+}
+
+impl ToTokens for CheckFunc {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        let Self {
+            attributes,
+            cooked,
+            visibility,
+            ret,
+            name,
+            args,
+            body,
+        } = self;
+
+        stream.extend(quote! {
+            #(#cooked)*
+            #(#attributes)*
+            #visibility fn #name (#(#args),*) -> ::std::pin::Pin<::std::boxed::Box<(dyn ::std::future::Future<Output = telexide::framework::CheckResult> + ::std::marker::Send )>> {
+                ::std::boxed::Box::pin(async move {
+                    let res: #ret = async move {
+                        #(#body)*
+                    }.await;
+                    res.into()
+            })
+            }
+        });
+    }
+}
@@ -1,10 +1,13 @@
-use proc_macro2::{Ident, Literal};
+use proc_macro2::{Ident, Literal, Span};
 use quote::format_ident;
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
     token::Comma,
+    Error,
+    LitInt,
+    LitStr,
     Token,
 };
 
@@ -46,6 +49,100 @@ impl Parse for NamedArgs {
     }
 }
 
+/// A single cooldown of a command, using `uses` uses per `millis` milliseconds
+pub struct CooldownSpec {
+    pub scope: Ident,
+    pub uses: u32,
+    pub millis: u64,
+}
+
+/// The arguments of the `cooldown` attribute, being either `(seconds, scope)`
+/// or a list of `scope = "limit"` pairs like `(user = "30s", chat = "5/60s")`
+pub struct CooldownArgs(pub Vec<CooldownSpec>);
+
+impl Parse for CooldownArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        if content.peek(LitInt) {
+            let seconds = content.parse::<LitInt>()?.base10_parse::<u64>()?;
+            content.parse::<Token![,]>()?;
+            let scope = parse_cooldown_scope(&content.parse::<Ident>()?)?;
+
+            return Ok(Self(vec![CooldownSpec {
+                scope,
+                uses: 1,
+                millis: seconds * 1000,
+            }]));
+        }
+
+        let mut cooldowns = Vec::new();
+        loop {
+            let scope = parse_cooldown_scope(&content.parse::<Ident>()?)?;
+            content.parse::<Token![=]>()?;
+            let limit = content.parse::<LitStr>()?;
+            let (uses, millis) = parse_cooldown_limit(&limit.value())
+                .ok_or_else(|| Error::new(
+                    limit.span(),
+                    "expected the cooldown to look like \"30s\" or \"5/60s\", using ms, s, m or h"
+                ))?;
+
+            cooldowns.push(CooldownSpec {
+                scope,
+                uses,
+                millis,
+            });
+
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+            if content.is_empty() {
+                break;
+            }
+        }
+
+        Ok(Self(cooldowns))
+    }
+}
+
+fn parse_cooldown_scope(scope: &Ident) -> Result<Ident> {
+    match scope.to_string().as_str() {
+        "user" => Ok(Ident::new("User", Span::call_site())),
+        "chat" => Ok(Ident::new("Chat", Span::call_site())),
+        "global" => Ok(Ident::new("Global", Span::call_site())),
+        _ => Err(Error::new(
+            scope.span(),
+            "expected the cooldown scope to be one of user, chat or global"
+        )),
+    }
+}
+
+/// Parses a limit like `30s` or `5/60s` into the amount of uses and the
+/// duration in milliseconds, a duration without a unit being in seconds
+fn parse_cooldown_limit(limit: &str) -> Option<(u32, u64)> {
+    let (uses, duration) = match limit.split_once('/') {
+        Some((uses, duration)) => (uses.trim().parse().ok()?, duration.trim()),
+        None => (1, limit.trim()),
+    };
+
+    let split = duration.find(|c: char| !c.is_ascii_digit()).unwrap_or(duration.len());
+    let amount: u64 = duration[..split].parse().ok()?;
+    let millis = match &duration[split..] {
+        "ms" => amount,
+        "" | "s" => amount * 1000,
+        "m" => amount * 60 * 1000,
+        "h" => amount * 60 * 60 * 1000,
+        _ => return None,
+    };
+
+    if uses == 0 || millis == 0 {
+        return None;
+    }
+    Some((uses, millis))
+}
+
 pub fn add_suffix(ident: &Ident, suffix: &str) -> Ident {
     format_ident!("{}_{}", ident.to_string(), suffix)
 }
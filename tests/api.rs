@@ -0,0 +1,1376 @@
+use telexide::{
+    api::{
+        types::{
+            AnswerInlineQuery,
+            BanChatMember,
+            CreateInvoiceLink,
+            EditMessageMedia,
+            GetChatMemberCount,
+            GetCustomEmojiStickers,
+            GetGameHighScores,
+            InlineQueryResultsButton,
+            InputFile,
+            InputMedia,
+            InputMediaAnimation,
+            InputMediaVideo,
+            MediaGroup,
+            SendLocation,
+            SendMediaGroup,
+            SendMessage,
+            SendAnimation,
+            SendAudio,
+            SendPhoto,
+            SendPoll,
+            SendVideoNote,
+            SendVoice,
+            SetGameScore,
+            TrueOrObject,
+            MAX_CUSTOM_EMOJI_STICKERS,
+        },
+        APIClient,
+        APIEndpoint,
+        Response,
+        API,
+    },
+    model::{
+        CustomEmoji,
+        File,
+        ForceReply,
+        GameHighScore,
+        InlineKeyboardMarkup,
+        LabeledPrice,
+        Message,
+        MessageContent,
+        MessageEntity,
+        ParseMode,
+        PollType,
+        ReplyKeyboardMarkup,
+        ReplyMarkup,
+        TextBlock,
+        TextMention,
+        User,
+    },
+    utils::{
+        result::{TelegramError, ValidationError},
+        FormDataFile,
+    },
+    Error,
+};
+
+fn mention_entity() -> MessageEntity {
+    MessageEntity::TextMention(TextMention {
+        text_block: TextBlock {
+            offset: 0,
+            length: 4,
+        },
+        user: User {
+            id: 456,
+            is_bot: false,
+            first_name: "test".to_owned(),
+            last_name: None,
+            username: None,
+            language_code: None,
+            can_join_groups: None,
+            can_read_all_group_messages: None,
+            supports_inline_queries: None,
+            is_premium: None,
+            added_to_attachment_menu: None,
+        },
+    })
+}
+
+#[test]
+fn serialize_message_entities() -> serde_json::Result<()> {
+    let mut message = SendMessage::new(10, "test");
+    message.set_entities(vec![mention_entity()]);
+
+    assert_eq!(
+        serde_json::to_value(&message)?["entities"],
+        serde_json::json!([{
+            "type": "text_mention",
+            "offset": 0,
+            "length": 4,
+            "user": {
+                "id": 456,
+                "is_bot": false,
+                "first_name": "test"
+            }
+        }])
+    );
+    Ok(())
+}
+
+#[test]
+fn send_message_builder() {
+    let inline = InlineKeyboardMarkup::builder()
+        .callback("Yes", "yes")
+        .build()
+        .unwrap();
+
+    let message = SendMessage::builder(10, "test")
+        .parse_mode(ParseMode::HTML)
+        .reply_to(42)
+        .reply_markup(inline.clone())
+        .disable_web_page_preview(true)
+        .build();
+
+    let mut expected = SendMessage::new(10, "test");
+    expected
+        .set_parse_mode(&ParseMode::HTML)
+        .set_reply_to_message_id(42)
+        .set_reply_markup(inline)
+        .toggle_disable_web_page_preview();
+    assert_eq!(message, expected);
+
+    let keyboard = ReplyKeyboardMarkup::builder().text("hi").build();
+    let message = SendMessage::builder(10, "test")
+        .entities(vec![mention_entity()])
+        .reply_markup(keyboard.clone())
+        .build();
+    assert_eq!(message.entities, Some(vec![mention_entity()]));
+    assert_eq!(
+        message.reply_markup,
+        Some(ReplyMarkup::ReplyKeyboardMarkup(keyboard))
+    );
+}
+
+#[test]
+fn no_parse_mode_is_left_out() -> serde_json::Result<()> {
+    let mut message = SendMessage::new(10, "*as is*");
+    message.set_parse_mode(&ParseMode::None);
+    assert!(serde_json::to_value(&message)?.get("parse_mode").is_none());
+
+    message.set_parse_mode(&ParseMode::MarkdownV2);
+    assert_eq!(serde_json::to_value(&message)?["parse_mode"], "MarkdownV2");
+
+    let mut quiz = SendPoll::new_quiz(10, "2 + 2?", &["3", "4"], 1).unwrap();
+    quiz.explanation = Some("*as is*".to_owned());
+    quiz.explanation_parse_mode = Some(ParseMode::None);
+    assert!(serde_json::to_value(&quiz)?
+        .get("explanation_parse_mode")
+        .is_none());
+
+    quiz.explanation_parse_mode = Some(ParseMode::HTML);
+    assert_eq!(serde_json::to_value(&quiz)?["explanation_parse_mode"], "HTML");
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_parse_mode_with_entities() {
+    let mut message = SendMessage::new(10, "test");
+    message
+        .set_entities(vec![mention_entity()])
+        .set_parse_mode(&ParseMode::HTML);
+
+    let res = APIClient::new_default("test").send_message(message).await;
+
+    match res {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("parse_mode and entities"))
+        },
+        _ => panic!("expected the request to be rejected"),
+    }
+}
+
+#[tokio::test]
+async fn reject_too_long_texts() {
+    let client = APIClient::new_default("test");
+    let too_long = |res: telexide::Result<Message>, field: &str, limit: usize, length: usize| {
+        match res {
+            Err(Error::Telegram(TelegramError::Validation(e))) => assert_eq!(
+                e,
+                ValidationError {
+                    field: field.to_owned(),
+                    limit,
+                    length,
+                }
+            ),
+            res => panic!("expected the {} to be too long, got {:?}", field, res),
+        }
+    };
+
+    let text = "a".repeat(4097);
+    too_long(client.send_message(SendMessage::new(10, &text)).await, "text", 4096, 4097);
+
+    // emoji take up two UTF-16 code units, so 513 of them are too long for a
+    // caption even though they are only 513 chars
+    let mut photo = SendPhoto::new(10, "photo".to_owned());
+    photo.set_caption(&"😀".repeat(513));
+    too_long(client.send_photo(photo).await, "caption", 1024, 1026);
+
+    let video = InputMedia::Video(InputMediaVideo {
+        media: InputFile::file_id("a"),
+        caption: Some("a".repeat(1025)),
+        parse_mode: None,
+        caption_entities: None,
+        duration: None,
+        width: None,
+        height: None,
+        supports_streaming: false,
+        has_spoiler: None,
+    });
+    let group = SendMediaGroup::new(10, vec![video.clone(), video]);
+    match client.send_media_group(group).await {
+        Err(Error::Telegram(TelegramError::Validation(e))) => {
+            assert_eq!(e.to_string(), "caption can be at most 1024 characters long, got 1025")
+        },
+        res => panic!("expected the album caption to be too long, got {:?}", res),
+    }
+
+    // requests that aren't validated are sent, and fail to connect instead
+    let not_validated = |res: telexide::Result<Message>| match res {
+        Err(Error::Telegram(TelegramError::Validation(e))) => {
+            panic!("expected the text not to be validated, got {:?}", e)
+        },
+        res => assert!(res.is_err()),
+    };
+    let mut client = APIClient::new_default("test");
+    client.set_base_url("http://127.0.0.1:1");
+
+    // telegram counts the length of texts with a parse mode without their
+    // markup, and buttons have their own limits
+    let mut message = SendMessage::new(10, &format!("<b>{}</b>", "a".repeat(4096)));
+    message.set_parse_mode(&ParseMode::HTML);
+    not_validated(client.send_message(message).await);
+    let mut message = SendMessage::new(10, "buttons");
+    message.set_reply_markup(ReplyKeyboardMarkup::builder().text(&text).build());
+    not_validated(client.send_message(message).await);
+
+    client.set_text_length_validation(false);
+    assert!(!client.get_text_length_validation());
+    not_validated(client.send_message(SendMessage::new(10, &text)).await);
+}
+
+#[tokio::test]
+async fn reject_invoice_links_with_mismatched_currency() {
+    let client = APIClient::new_default("test");
+    let rejected = |data: CreateInvoiceLink, reason: &'static str| {
+        let client = &client;
+        async move {
+            match client.create_invoice_link(data).await {
+                Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+                    assert!(e.contains(reason), "unexpected error {:?}", e)
+                },
+                res => panic!("expected the invoice to be rejected, got {:?}", res),
+            }
+        }
+    };
+
+    let mut stars = CreateInvoiceLink::stars("Boost", "A profile boost", "order", 50);
+    stars.provider_token = Some("provider".to_owned());
+    rejected(stars.clone(), "can't have a provider token").await;
+
+    stars.provider_token = None;
+    stars.prices.push(LabeledPrice::new("Tax", 5));
+    rejected(stars.clone(), "exactly one price").await;
+
+    stars.prices.pop();
+    stars.max_tip_amount = Some(10);
+    rejected(stars, "don't support tips").await;
+
+    let mut fiat = CreateInvoiceLink::new("Shirt", "A shirt", "order", "", "EUR", Vec::new());
+    rejected(fiat.clone(), "a provider token is required").await;
+    fiat.provider_token = None;
+    rejected(fiat, "a provider token is required").await;
+}
+
+#[tokio::test]
+async fn reject_invalid_inline_query_button() {
+    let client = APIClient::new_default("test");
+    let mut answer = AnswerInlineQuery::new("q", Vec::new());
+
+    let mut button = InlineQueryResultsButton::web_app("Open", "https://example.com");
+    button.start_parameter = Some("start".to_owned());
+    answer.set_button(button.clone());
+    match client.answer_inline_query(answer.clone()).await {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("exactly one of web_app and start_parameter"))
+        },
+        _ => panic!("expected a button with both targets to be rejected"),
+    }
+
+    button.web_app = None;
+    button.start_parameter = None;
+    answer.set_button(button);
+    match client.answer_inline_query(answer.clone()).await {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("exactly one of web_app and start_parameter"))
+        },
+        _ => panic!("expected a button without a target to be rejected"),
+    }
+
+    answer.button = None;
+    answer.set_next_cursor(Some("x".repeat(65)));
+    match client.answer_inline_query(answer.clone()).await {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("longer than 64 bytes"))
+        },
+        _ => panic!("expected the long offset to be rejected"),
+    }
+    answer.next_offset = None;
+
+    #[allow(deprecated)]
+    {
+        answer.button = Some(InlineQueryResultsButton::start_parameter("a", "b"));
+        answer.switch_pm_text = Some("c".to_owned());
+    }
+    match client.answer_inline_query(answer).await {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("button and switch_pm_text"))
+        },
+        _ => panic!("expected the deprecated fields to conflict with the button"),
+    }
+}
+
+#[tokio::test]
+async fn reject_long_input_field_placeholder() {
+    let client = APIClient::new_default("test");
+    let mut message = SendMessage::new(10, "name?");
+    let mut force = ForceReply::new(false);
+    force.set_input_field_placeholder(&"a".repeat(65));
+    message.set_reply_markup(force);
+
+    match client.send_message(message).await {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("input_field_placeholder must be 1-64 characters, got 65"))
+        },
+        _ => panic!("expected the request to be rejected"),
+    }
+}
+
+#[tokio::test]
+async fn reject_live_location_out_of_range() {
+    let client = APIClient::new_default("test");
+    let mut location = SendLocation {
+        chat_id: 10,
+        latitude: 52.37,
+        longitude: 4.89,
+        horizontal_accuracy: None,
+        live_period: Some(900),
+        heading: Some(361),
+        proximity_alert_radius: None,
+        disable_notification: false,
+        reply_to_message_id: None,
+        allow_sending_without_reply: false,
+        reply_markup: None,
+    };
+
+    match client.send_location(location.clone()).await {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("heading must be between 1 and 360"))
+        },
+        _ => panic!("expected the request to be rejected"),
+    }
+
+    location.heading = Some(360);
+    location.proximity_alert_radius = Some(0);
+    match client.send_location(location).await {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("proximity_alert_radius must be between 1 and 100000"))
+        },
+        _ => panic!("expected the request to be rejected"),
+    }
+}
+
+#[tokio::test]
+async fn poll_constructors_and_close_options() -> telexide::Result<()> {
+    let quiz = SendPoll::new_quiz(10, "2 + 2?", &["3", "4", "5"], 1)?;
+    assert_eq!(quiz.poll_type, Some(PollType::Quiz));
+    assert_eq!(quiz.correct_option_id, Some(1));
+    assert_eq!(quiz.options, vec!["3", "4", "5"]);
+    let data = serde_json::to_value(&quiz)?;
+    assert!(data.get("close_date").is_none());
+    assert!(data.get("explanation").is_none());
+
+    let regular = SendPoll::new_regular(10, "lunch?", &["pizza", "sushi"])?;
+    assert_eq!(regular.poll_type, Some(PollType::Regular));
+    assert_eq!(regular.correct_option_id, None);
+
+    let too_long = "x".repeat(101);
+    assert!(SendPoll::new_quiz(10, "2 + 2?", &["3", "4"], 2).is_err());
+    assert!(SendPoll::new_regular(10, "lunch?", &["pizza"]).is_err());
+    assert!(SendPoll::new_regular(10, "lunch?", &["a"; 11]).is_err());
+    assert!(SendPoll::new_regular(10, "", &["pizza", "sushi"]).is_err());
+    assert!(SendPoll::new_regular(10, "lunch?", &["pizza", &too_long]).is_err());
+
+    let mut poll = regular;
+    poll.open_period = Some(60);
+    poll.close_date = Some(chrono::Utc::now() + chrono::Duration::seconds(60));
+    match APIClient::new_default("test").send_poll(poll).await {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("open_period and close_date"))
+        },
+        _ => panic!("expected the request to be rejected"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn input_file_from_memory() -> telexide::Result<()> {
+    let from_bytes = InputFile::from_bytes("chart.png", vec![1, 2, 3])?;
+    let from_reader = InputFile::from_reader("chart.png", &[1u8, 2, 3][..]).await?;
+    assert_eq!(from_bytes, from_reader);
+
+    match &from_bytes {
+        InputFile::File(file) => {
+            assert_eq!(file.bytes, vec![1, 2, 3]);
+            assert_eq!(file.file_name.as_deref(), Some("chart.png"));
+            assert_eq!(file.media_type.as_deref(), Some("image/png"));
+        },
+        InputFile::Url(_) | InputFile::FileId(_) => panic!("expected a file to be uploaded"),
+    }
+
+    let mut photo = SendPhoto::new(10, String::new());
+    photo.photo = from_bytes;
+    assert_eq!(
+        serde_json::to_value(&photo)?["photo"],
+        serde_json::json!("attach://chart.png")
+    );
+    Ok(())
+}
+
+#[test]
+fn input_file_variants() -> serde_json::Result<()> {
+    assert_eq!(
+        InputFile::new("https://example.com/a.png"),
+        InputFile::Url("https://example.com/a.png".to_owned())
+    );
+    assert_eq!(InputFile::new("AgACAgQAAx0"), InputFile::FileId("AgACAgQAAx0".to_owned()));
+    assert_eq!(
+        serde_json::to_value(InputFile::url("https://example.com/a.png"))?,
+        serde_json::json!("https://example.com/a.png")
+    );
+    assert_eq!(
+        serde_json::to_value(InputFile::file_id("AgACAgQAAx0"))?,
+        serde_json::json!("AgACAgQAAx0")
+    );
+    Ok(())
+}
+
+#[derive(Default)]
+struct UploadAPI {
+    requests: parking_lot::Mutex<Vec<(serde_json::Value, Option<Vec<FormDataFile>>)>>,
+    endpoints: parking_lot::Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl API for UploadAPI {
+    async fn get(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> telexide::Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> telexide::Result<Response> {
+        self.post_file(endpoint, data, None).await
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+        files: Option<Vec<FormDataFile>>,
+    ) -> telexide::Result<Response> {
+        let message = serde_json::json!({
+            "message_id": 1,
+            "date": 0,
+            "chat": {"id": 10, "type": "private"},
+            "text": "photo",
+        });
+        let result = match endpoint {
+            APIEndpoint::SendMediaGroup => serde_json::json!([message]),
+            _ => message,
+        };
+        self.endpoints.lock().push(endpoint.as_str().to_owned());
+        self.requests.lock().push((data.unwrap_or_default(), files));
+        Ok(Response {
+            ok: true,
+            description: None,
+            result: Some(serde_json::value::to_raw_value(&result)?),
+        })
+    }
+}
+
+#[tokio::test]
+async fn send_photo_by_file_id_or_upload() -> telexide::Result<()> {
+    let api = UploadAPI::default();
+
+    api.send_photo(SendPhoto::new(10, "AgACAgQAAx0".to_owned()))
+        .await?;
+
+    let mut photo = SendPhoto::new(10, String::new());
+    photo.photo = InputFile::from_bytes("chart.png", vec![1, 2, 3])?;
+    api.send_photo(photo).await?;
+
+    let requests = api.requests.lock();
+    assert_eq!(requests[0].0["photo"], serde_json::json!("AgACAgQAAx0"));
+    assert!(requests[0].1.is_none());
+    assert_eq!(requests[1].0["photo"], serde_json::json!("attach://chart.png"));
+    assert_eq!(requests[1].1.as_ref().map(Vec::len), Some(1));
+    Ok(())
+}
+
+#[tokio::test]
+async fn send_audio_voice_and_video_notes() -> telexide::Result<()> {
+    let api = UploadAPI::default();
+    let received: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 1,
+        "date": 0,
+        "chat": {"id": 10, "type": "private", "first_name": "a"},
+        "audio": {
+            "file_id": "audio",
+            "file_unique_id": "a",
+            "duration": 180,
+            "performer": "band",
+            "title": "song",
+        },
+    }))
+    .unwrap();
+    let audio = match received.content {
+        MessageContent::Audio {
+            content, ..
+        } => content,
+        content => panic!("expected an audio message, got {:?}", content),
+    };
+
+    api.send_audio(SendAudio::from_audio(10, &audio)).await?;
+    api.send_voice(SendVoice::new(10, "voice".to_owned())).await?;
+    let mut note = SendVideoNote::new(10, "note".to_owned());
+    note.length = Some(240);
+    api.send_video_note(note).await?;
+
+    assert_eq!(*api.endpoints.lock(), vec!["sendAudio", "sendVoice", "sendVideoNote"]);
+    let requests = api.requests.lock();
+    assert_eq!(requests[0].0["audio"], "audio");
+    assert_eq!(requests[0].0["duration"], 180);
+    assert_eq!(requests[0].0["performer"], "band");
+    assert_eq!(requests[0].0["title"], "song");
+    assert_eq!(requests[1].0["voice"], "voice");
+    assert!(requests[1].0.get("thumb").is_none());
+    assert_eq!(requests[2].0["length"], 240);
+    assert!(requests[2].0.get("caption").is_none());
+    Ok(())
+}
+
+#[allow(deprecated)]
+#[tokio::test]
+async fn send_animations() -> telexide::Result<()> {
+    let api = UploadAPI::default();
+    let received: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 1,
+        "date": 0,
+        "chat": {"id": 10, "type": "private", "first_name": "a"},
+        "animation": {
+            "file_id": "gif",
+            "file_unique_id": "g",
+            "width": 320,
+            "height": 240,
+            "duration": 3,
+            "file_name": "cat.gif",
+            "mime_type": "image/gif",
+        },
+        "document": {"file_id": "gif", "file_unique_id": "g"},
+    }))
+    .unwrap();
+    let animation = match received.content {
+        MessageContent::Animation {
+            content, ..
+        } => content,
+        content => panic!("expected an animation, got {:?}", content),
+    };
+    assert_eq!(animation.file_name.as_deref(), Some("cat.gif"));
+    assert_eq!(animation.mime_type.as_deref(), Some("image/gif"));
+    api.send_animation(SendAnimation::from_animation(10, &animation))
+        .await?;
+
+    let mut upload = SendAnimation::new(10, String::new());
+    upload.animation = InputFile::from_bytes("cat.gif", vec![1, 2, 3])?;
+    upload.thumb = Some(InputFile::from_bytes("thumb.jpg", vec![4, 5])?);
+    upload.title = Some("ignored".to_owned());
+    upload
+        .set_caption("cat")
+        .set_show_caption_above_media(true)
+        .set_has_spoiler(true);
+    api.send_animation(upload).await?;
+
+    assert_eq!(*api.endpoints.lock(), vec!["sendAnimation", "sendAnimation"]);
+    let requests = api.requests.lock();
+    assert_eq!(requests[0].0["animation"], "gif");
+    assert_eq!(requests[0].0["width"], 320);
+    assert_eq!(requests[0].0["height"], 240);
+    assert_eq!(requests[0].0["duration"], 3);
+    assert!(requests[0].0.get("show_caption_above_media").is_none());
+    assert_eq!(requests[1].0["animation"], "attach://cat.gif");
+    assert_eq!(requests[1].0["thumb"], "attach://thumb.jpg");
+    assert_eq!(requests[1].0["show_caption_above_media"], true);
+    assert_eq!(requests[1].0["has_spoiler"], true);
+    assert!(requests[1].0.get("title").is_none());
+    assert_eq!(requests[1].1.as_ref().map(Vec::len), Some(2));
+    Ok(())
+}
+
+#[tokio::test]
+async fn edit_message_media_uploads_animations() -> telexide::Result<()> {
+    let api = UploadAPI::default();
+
+    let mut animation = InputMediaAnimation::new(InputFile::from_bytes("cat.gif", vec![1, 2])?);
+    animation.thumb = Some(InputFile::from_bytes("thumb.jpg", vec![3])?);
+    animation.caption = Some("cat".to_owned());
+    animation.show_caption_above_media = Some(true);
+    api.edit_message_media(EditMessageMedia::new(10, 1, &InputMedia::Animation(animation)))
+        .await?;
+
+    let resent = InputMedia::Animation(InputMediaAnimation::new(InputFile::file_id("gif")));
+    api.edit_message_media(EditMessageMedia::new(10, 1, &resent))
+        .await?;
+
+    assert_eq!(*api.endpoints.lock(), vec!["editMessageMedia", "editMessageMedia"]);
+    let requests = api.requests.lock();
+    let media = &requests[0].0["media"];
+    assert_eq!(media["type"], "animation");
+    assert_eq!(media["media"], "attach://cat.gif");
+    assert_eq!(media["thumb"], "attach://thumb.jpg");
+    assert_eq!(media["show_caption_above_media"], true);
+    let files: Vec<_> = requests[0].1.iter().flatten().map(|f| f.name.clone()).collect();
+    assert_eq!(files, vec!["cat.gif", "thumb.jpg"]);
+
+    assert_eq!(requests[1].0["media"]["media"], "gif");
+    assert!(requests[1].0["media"].get("thumb").is_none());
+    assert!(requests[1].1.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn media_group_builder() -> telexide::Result<()> {
+    let rejected = |res: telexide::Result<MediaGroup>, reason: &str| match res {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains(reason), "{}", e)
+        },
+        res => panic!("expected the media group to be rejected, got {:?}", res),
+    };
+
+    rejected(MediaGroup::builder().photo(InputFile::file_id("a")).build(), "2-10 items");
+    let mut too_many = MediaGroup::builder();
+    for _ in 0..11 {
+        too_many.photo(InputFile::file_id("a"));
+    }
+    rejected(too_many.build(), "2-10 items");
+    rejected(
+        MediaGroup::builder()
+            .photo(InputFile::file_id("a"))
+            .document(InputFile::file_id("b"))
+            .build(),
+        "photos and videos can't be mixed",
+    );
+    rejected(
+        MediaGroup::builder()
+            .audio(InputFile::file_id("a"))
+            .document(InputFile::file_id("b"))
+            .build(),
+        "audio files and documents can't be mixed",
+    );
+    rejected(
+        MediaGroup::builder()
+            .photo(InputFile::from_bytes("a.png", vec![1])?)
+            .photo(InputFile::from_bytes("a.png", vec![2])?)
+            .build(),
+        "unique names",
+    );
+    let mut captioned = InputMedia::Video(InputMediaVideo {
+        media: InputFile::file_id("a"),
+        caption: Some("own".to_owned()),
+        parse_mode: None,
+        caption_entities: None,
+        duration: None,
+        width: None,
+        height: None,
+        supports_streaming: true,
+        has_spoiler: None,
+    });
+    rejected(
+        MediaGroup::builder()
+            .media(captioned.clone())
+            .photo(InputFile::file_id("b"))
+            .caption("album")
+            .build(),
+        "already has a caption",
+    );
+
+    // per-item captions are fine when the album itself has none
+    let per_item = MediaGroup::builder()
+        .media(captioned.clone())
+        .media(captioned.clone())
+        .build()?;
+    assert_eq!(per_item.media[1].get_caption(), Some("own"));
+
+    if let InputMedia::Video(ref mut video) = captioned {
+        video.caption = None;
+    }
+    let album = MediaGroup::builder()
+        .media(captioned)
+        .photo(InputFile::from_bytes("chart.png", vec![1, 2, 3])?)
+        .caption("*album*")
+        .parse_mode(ParseMode::MarkdownV2)
+        .build()?;
+
+    let api = UploadAPI::default();
+    api.send_media_group(album.into_request(10)).await?;
+    let requests = api.requests.lock();
+    assert_eq!(
+        requests[0].0["media"],
+        serde_json::json!([
+            {
+                "type": "video",
+                "media": "a",
+                "caption": "*album*",
+                "parse_mode": "MarkdownV2",
+                "supports_streaming": true,
+            },
+            {"type": "photo", "media": "attach://chart.png"},
+        ])
+    );
+    let files = requests[0].1.as_ref().unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].file_name.as_deref(), Some("chart.png"));
+
+    // documents are sent without setting content type detection
+    let documents = MediaGroup::builder()
+        .document(InputFile::file_id("a"))
+        .document(InputFile::file_id("b"))
+        .build()?;
+    match &documents.media[0] {
+        InputMedia::Document(d) => assert!(!d.disable_content_type_detection),
+        m => panic!("expected a document, got {:?}", m),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn round_trip_message_entities() -> telexide::Result<()> {
+    let incoming: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 1,
+        "date": 0,
+        "chat": {"id": 40, "type": "private"},
+        "text": "hello bold link",
+        "entities": [
+            {"type": "bold", "offset": 6, "length": 4},
+            {"type": "text_link", "offset": 11, "length": 4, "url": "https://example.com"}
+        ]
+    }))?;
+
+    let outgoing = SendMessage::from_text_message(50, &incoming).unwrap();
+    assert_eq!(outgoing.text, "hello bold link");
+    assert_eq!(outgoing.parse_mode, None);
+    assert_eq!(
+        serde_json::to_value(&outgoing)?["entities"],
+        serde_json::json!([
+            {"type": "bold", "offset": 6, "length": 4},
+            {"type": "text_link", "offset": 11, "length": 4, "url": "https://example.com"}
+        ])
+    );
+
+    let mut photo = SendPhoto::new(50, "file_id".to_owned());
+    photo
+        .set_caption(&outgoing.text)
+        .set_caption_entities(outgoing.entities.unwrap())
+        .set_parse_mode(&ParseMode::MarkdownV2);
+    let res = APIClient::new_default("test").send_photo(photo).await;
+    match res {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("parse_mode and caption_entities"))
+        },
+        _ => panic!("expected the request to be rejected"),
+    }
+    Ok(())
+}
+
+#[test]
+fn copy_formatting_of_caption() -> telexide::Result<()> {
+    let incoming: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 1,
+        "date": 0,
+        "chat": {"id": 40, "type": "private"},
+        "photo": [{"file_id": "a", "file_unique_id": "b", "width": 1, "height": 1}],
+        "caption": "a bold caption",
+        "caption_entities": [{"type": "bold", "offset": 2, "length": 4}]
+    }))?;
+
+    let (text, entities) = incoming.formatted_text().unwrap();
+    assert_eq!(text, "a bold caption");
+    assert_eq!(entities, &[MessageEntity::Bold(TextBlock {
+        offset: 2,
+        length: 4,
+    })]);
+
+    let mut outgoing = SendMessage::new(50, "*old*");
+    outgoing
+        .set_parse_mode(&ParseMode::MarkdownV2)
+        .with_formatting_of(&incoming);
+    assert_eq!(outgoing.text, "a bold caption");
+    assert_eq!(outgoing.entities.as_deref(), Some(entities));
+    assert_eq!(outgoing.parse_mode, None);
+
+    let dice: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 2,
+        "date": 0,
+        "chat": {"id": 40, "type": "private"},
+        "dice": {"emoji": "🎲", "value": 3}
+    }))?;
+    assert_eq!(dice.formatted_text(), None);
+    outgoing.with_formatting_of(&dice);
+    assert_eq!(outgoing.text, "a bold caption");
+    Ok(())
+}
+
+#[test]
+fn media_spoilers() -> telexide::Result<()> {
+    let incoming: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 1,
+        "date": 0,
+        "chat": {"id": 40, "type": "private"},
+        "photo": [{"file_id": "a", "file_unique_id": "b", "width": 1, "height": 1}],
+        "has_media_spoiler": true
+    }))?;
+    assert!(incoming.has_media_spoiler());
+    assert_eq!(serde_json::to_value(&incoming)?["has_media_spoiler"], true);
+
+    let mut photo = SendPhoto::new(50, "a".to_owned());
+    assert!(serde_json::to_value(&photo)?.get("has_spoiler").is_none());
+    photo.set_has_spoiler(incoming.has_media_spoiler());
+    assert_eq!(serde_json::to_value(&photo)?["has_spoiler"], true);
+
+    let media = InputMedia::Video(InputMediaVideo {
+        media: InputFile::file_id("v"),
+        caption: None,
+        parse_mode: None,
+        caption_entities: None,
+        duration: None,
+        width: None,
+        height: None,
+        supports_streaming: false,
+        has_spoiler: Some(true),
+    });
+    assert_eq!(serde_json::to_value(&media)?["has_spoiler"], true);
+
+    let plain: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 2,
+        "date": 0,
+        "chat": {"id": 40, "type": "private"},
+        "animation": {
+            "file_id": "c",
+            "file_unique_id": "d",
+            "width": 1,
+            "height": 1,
+            "duration": 1
+        }
+    }))?;
+    assert!(!plain.has_media_spoiler());
+    Ok(())
+}
+
+#[tokio::test]
+async fn custom_base_url() -> telexide::Result<()> {
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body,
+        Response as HttpResponse,
+        Server,
+    };
+    use std::convert::Infallible;
+
+    let make_service = make_service_fn(|_| async {
+        Ok::<_, Infallible>(service_fn(|req: hyper::Request<Body>| async move {
+            let body = match req.uri().path() {
+                "/bottest/getMe" => serde_json::json!({
+                    "ok": true,
+                    "result": {
+                        "id": 5,
+                        "is_bot": true,
+                        "first_name": "test",
+                        "username": "test_bot",
+                        "can_join_groups": true,
+                        "supports_inline_queries": false,
+                    }
+                })
+                .to_string(),
+                "/file/bottest/photos/file_1.jpg" => "image".to_owned(),
+                _ => {
+                    return Ok::<_, Infallible>(
+                        HttpResponse::builder().status(404).body(Body::empty()).unwrap(),
+                    )
+                },
+            };
+            Ok(HttpResponse::new(Body::from(body)))
+        }))
+    });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let base_url = format!("http://{}/", server.local_addr());
+    tokio::spawn(server);
+
+    let mut client = APIClient::new_default("test");
+    assert_eq!(client.get_base_url(), "https://api.telegram.org");
+    client.set_base_url(&base_url);
+    assert_eq!(client.get_base_url(), base_url.trim_end_matches('/'));
+    assert_eq!(client.get_me().await?.username, "test_bot");
+
+    let mut file = File {
+        file_id: "id".to_owned(),
+        file_unique_id: "unique".to_owned(),
+        file_size: None,
+        file_path: Some("photos/file_1.jpg".to_owned()),
+    };
+    assert_eq!(client.download_file(&file).await?, b"image");
+
+    file.file_path = Some("photos/missing.jpg".to_owned());
+    assert!(matches!(
+        client.download_file(&file).await,
+        Err(Error::Telegram(TelegramError::NotFound))
+    ));
+
+    // a custom server in local mode returns an absolute path on its machine
+    let path = std::env::temp_dir().join(format!("telexide_file_{}", std::process::id()));
+    std::fs::write(&path, b"local")?;
+    file.file_path = Some(path.to_string_lossy().into_owned());
+    assert_eq!(client.download_file(&file).await?, b"local");
+    std::fs::remove_file(&path)?;
+
+    file.file_path = None;
+    assert!(client.download_file(&file).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn request_timeouts() -> telexide::Result<()> {
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body,
+        Response as HttpResponse,
+        Server,
+    };
+    use std::{convert::Infallible, time::Duration};
+    use telexide::api::types::GetUpdates;
+
+    // every request takes 300ms to be answered
+    let make_service = make_service_fn(|_| async {
+        Ok::<_, Infallible>(service_fn(|_req: hyper::Request<Body>| async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            let body = serde_json::json!({"ok": true, "result": []}).to_string();
+            Ok::<_, Infallible>(HttpResponse::new(Body::from(body)))
+        }))
+    });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let base_url = format!("http://{}", server.local_addr());
+    tokio::spawn(server);
+
+    let mut client = APIClient::new(
+        Some(APIClient::hyper_client_with_connect_timeout(Duration::from_secs(1))),
+        "test",
+    );
+    client
+        .set_base_url(&base_url)
+        .set_request_timeout(Duration::from_millis(100));
+
+    assert!(matches!(
+        client.get_me().await,
+        Err(Error::Telegram(TelegramError::Timeout))
+    ));
+
+    // long polling requests may take as long as their timeout
+    let mut data = GetUpdates::new();
+    data.set_timeout(1);
+    assert!(client.get_updates(data).await?.is_empty());
+    Ok(())
+}
+
+#[cfg(feature = "proxy")]
+#[tokio::test]
+async fn proxied_requests() -> telexide::Result<()> {
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body,
+        Response as HttpResponse,
+        Server,
+    };
+    use std::convert::Infallible;
+    use telexide::api::{Proxy, ProxyConnector, ProxyKind};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    let make_service = make_service_fn(|_| async {
+        Ok::<_, Infallible>(service_fn(|_| async {
+            let body = serde_json::json!({
+                "ok": true,
+                "result": {"id": 5, "is_bot": true, "first_name": "test", "username": "test_bot"}
+            });
+            Ok::<_, Infallible>(HttpResponse::new(Body::from(body.to_string())))
+        }))
+    });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let base_url = format!("http://{}", server.local_addr());
+    let target = format!("{}:{}", server.local_addr().ip(), server.local_addr().port());
+    tokio::spawn(server);
+
+    // a SOCKS5 proxy only accepting the user "user" with password "p@ss"
+    let socks = TcpListener::bind("127.0.0.1:0").await?;
+    let socks_addr = socks.local_addr()?;
+    let expected_target = target.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = socks.accept().await {
+            let mut greeting = [0_u8; 2];
+            stream.read_exact(&mut greeting).await.unwrap();
+            let mut methods = vec![0_u8; usize::from(greeting[1])];
+            stream.read_exact(&mut methods).await.unwrap();
+            assert_eq!(greeting[0], 5);
+            assert!(methods.contains(&2));
+            stream.write_all(&[5, 2]).await.unwrap();
+
+            let mut auth = [0_u8; 11];
+            stream.read_exact(&mut auth).await.unwrap();
+            assert_eq!(&auth, b"\x01\x04user\x04p@ss");
+            stream.write_all(&[1, 0]).await.unwrap();
+
+            let mut request = [0_u8; 4];
+            stream.read_exact(&mut request).await.unwrap();
+            // the target is either an ipv4 address or a domain name
+            let host_len = match request[3] {
+                1 => 4,
+                3 => usize::from(stream.read_u8().await.unwrap()),
+                other => panic!("unexpected address type {}", other),
+            };
+            let mut host = vec![0_u8; host_len + 2];
+            stream.read_exact(&mut host).await.unwrap();
+            let port = u16::from_be_bytes([host[host_len], host[host_len + 1]]);
+            let host = match request[3] {
+                1 => std::net::Ipv4Addr::new(host[0], host[1], host[2], host[3]).to_string(),
+                _ => String::from_utf8(host[..host_len].to_vec()).unwrap(),
+            };
+            assert_eq!(format!("{host}:{port}"), expected_target);
+            stream.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).await.unwrap();
+
+            let mut upstream = TcpStream::connect(&expected_target).await.unwrap();
+            tokio::spawn(async move {
+                tokio::io::copy_bidirectional(&mut stream, &mut upstream).await.ok();
+            });
+        }
+    });
+
+    // an http proxy that expects the user "user" with password "pass"
+    let http = TcpListener::bind("127.0.0.1:0").await?;
+    let http_addr = http.local_addr()?;
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = http.accept().await {
+            let mut request = Vec::new();
+            while !request.ends_with(b"\r\n\r\n") {
+                request.push(stream.read_u8().await.unwrap());
+            }
+            let request = String::from_utf8(request).unwrap();
+            assert!(request.starts_with(&format!("CONNECT {target} HTTP/1.1\r\n")));
+            if !request.contains("proxy-authorization: Basic dXNlcjpwYXNz\r\n") {
+                stream.write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n").await.ok();
+                continue;
+            }
+            stream.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").await.unwrap();
+
+            let mut upstream = TcpStream::connect(&target).await.unwrap();
+            tokio::spawn(async move {
+                tokio::io::copy_bidirectional(&mut stream, &mut upstream).await.ok();
+            });
+        }
+    });
+
+    let proxied_client = |proxy: Proxy| {
+        let hyper_client = hyper::Client::builder()
+            .build(hyper_tls::HttpsConnector::new_with_connector(ProxyConnector::new(proxy)));
+        let mut client = APIClient::with_hyper_client(hyper_client, "test");
+        client.set_base_url(&base_url);
+        client
+    };
+
+    let proxy = Proxy::new(&format!("socks5://user:p%40ss@{socks_addr}"))?;
+    assert_eq!(proxy.kind(), ProxyKind::Socks5);
+    assert_eq!(proxy.username(), Some("user"));
+    assert!(!format!("{proxy:?}").contains("p@ss"));
+    assert_eq!(proxied_client(proxy).get_me().await?.username, "test_bot");
+
+    let proxy = Proxy::new(&format!("http://user:pass@{http_addr}"))?;
+    assert_eq!(proxied_client(proxy).get_me().await?.username, "test_bot");
+
+    let proxy = Proxy::new(&format!("http://user:wrong@{http_addr}"))?;
+    assert!(proxied_client(proxy).get_me().await.is_err());
+
+    assert_eq!(Proxy::new("socks5://localhost")?.port(), 1080);
+    assert!(Proxy::new("ftp://localhost:21").is_err());
+    assert!(Proxy::new("localhost:1080").is_err());
+    Ok(())
+}
+
+#[derive(Default)]
+struct GameAPI {
+    requests: parking_lot::Mutex<Vec<(APIEndpoint, serde_json::Value)>>,
+}
+
+#[async_trait::async_trait]
+impl API for GameAPI {
+    async fn get(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> telexide::Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> telexide::Result<Response> {
+        let data = data.unwrap_or_default();
+        let result = match endpoint {
+            APIEndpoint::GetGameHighScores => serde_json::json!([
+                {"position": 1, "user": {"id": 2, "is_bot": false, "first_name": "b"}, "score": 90},
+                {"position": 2, "user": {"id": 1, "is_bot": false, "first_name": "a"}, "score": 40},
+            ]),
+            // telegram only returns the edited message if it was sent by the bot
+            _ if data.get("chat_id").is_some() => serde_json::json!({
+                "message_id": data["message_id"],
+                "date": 0,
+                "chat": {"id": data["chat_id"], "type": "private"},
+                "text": "game",
+            }),
+            _ => serde_json::json!(true),
+        };
+        self.requests.lock().push((endpoint, data));
+        Ok(Response {
+            ok: true,
+            description: None,
+            result: Some(serde_json::value::to_raw_value(&result)?),
+        })
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> telexide::Result<Response> {
+        self.post(endpoint, data).await
+    }
+}
+
+#[tokio::test]
+async fn game_scores_in_chats_and_inline_messages() -> telexide::Result<()> {
+    let api = GameAPI::default();
+
+    match api.set_game_score(SetGameScore::new(1, 50, 10, 3)).await? {
+        TrueOrObject::Object(message) => assert_eq!(message.message_id, 3),
+        res => panic!("expected the edited message, got {:?}", res),
+    }
+    assert!(matches!(
+        api.set_game_score(SetGameScore::new_inline(1, 50, "inline")).await?,
+        TrueOrObject::True(true)
+    ));
+
+    // lowering a score is only allowed with force
+    let mut lowered = SetGameScore::new_inline(1, 20, "inline");
+    lowered.set_force(true).set_disable_edit_message(true);
+    api.set_game_score(lowered).await?;
+
+    let scores = api.get_game_high_scores(GetGameHighScores::new_inline(1, "inline")).await?;
+    assert_eq!(GameHighScore::rank_of(&scores, 1), Some(2));
+    assert_eq!(GameHighScore::rank_of(&scores, 3), None);
+    api.get_game_high_scores(GetGameHighScores::new(1, 10, 3)).await?;
+
+    let requests = api.requests.lock();
+    assert_eq!(requests[0].1["chat_id"], 10);
+    assert_eq!(requests[0].1["force"], false);
+    assert!(requests[0].1.get("inline_message_id").is_none());
+    assert_eq!(requests[1].1["inline_message_id"], "inline");
+    assert!(requests[1].1.get("chat_id").is_none());
+    assert_eq!(requests[2].1["score"], 20);
+    assert_eq!(requests[2].1["force"], true);
+    assert_eq!(requests[2].1["disable_edit_message"], true);
+    assert_eq!(requests[4].1["message_id"], 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_ambiguous_game_message_targets() {
+    let api = GameAPI::default();
+    let rejected = |res: telexide::Result<_>| match res {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("exactly one of chat_id with message_id and inline_message_id"))
+        },
+        _ => panic!("expected the request to be rejected"),
+    };
+
+    let mut both = SetGameScore::new(1, 50, 10, 3);
+    both.inline_message_id = Some("inline".to_owned());
+    rejected(api.set_game_score(both).await.map(|_| ()));
+
+    let mut neither = GetGameHighScores::new_inline(1, "inline");
+    neither.inline_message_id = None;
+    rejected(api.get_game_high_scores(neither.clone()).await.map(|_| ()));
+
+    neither.chat_id = Some(10);
+    rejected(api.get_game_high_scores(neither).await.map(|_| ()));
+    assert!(api.requests.lock().is_empty());
+}
+
+#[derive(Default)]
+struct StickerAPI {
+    requests: parking_lot::Mutex<Vec<serde_json::Value>>,
+}
+
+#[async_trait::async_trait]
+impl API for StickerAPI {
+    async fn get(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> telexide::Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> telexide::Result<Response> {
+        assert_eq!(endpoint.as_str(), "getCustomEmojiStickers");
+        let data = data.unwrap_or_default();
+        let stickers: Vec<_> = data["custom_emoji_ids"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|id| {
+                serde_json::json!({
+                    "file_id": "file",
+                    "file_unique_id": "unique",
+                    "width": 100,
+                    "height": 100,
+                    "emoji": "😀",
+                    "custom_emoji_id": id,
+                })
+            })
+            .collect();
+        self.requests.lock().push(data);
+        Ok(Response {
+            ok: true,
+            description: None,
+            result: Some(serde_json::value::to_raw_value(&serde_json::Value::Array(stickers))?),
+        })
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> telexide::Result<Response> {
+        self.post(endpoint, data).await
+    }
+}
+
+#[tokio::test]
+async fn custom_emoji_stickers() -> telexide::Result<()> {
+    let api = StickerAPI::default();
+    let emoji = |offset, id: &str| {
+        MessageEntity::CustomEmoji(CustomEmoji {
+            text_block: TextBlock {
+                offset,
+                length: 2,
+            },
+            custom_emoji_id: id.to_owned(),
+        })
+    };
+
+    let data = GetCustomEmojiStickers::from_entities(&[
+        emoji(0, "1"),
+        mention_entity(),
+        emoji(3, "2"),
+        emoji(6, "1"),
+    ]);
+    assert_eq!(data.custom_emoji_ids, vec!["1", "2"]);
+
+    let stickers = api.get_custom_emoji_stickers(data).await?;
+    assert_eq!(stickers.len(), 2);
+    assert_eq!(stickers[1].custom_emoji_id.as_deref(), Some("2"));
+
+    let too_many = (0..=MAX_CUSTOM_EMOJI_STICKERS).map(|i| i.to_string()).collect();
+    match api.get_custom_emoji_stickers(GetCustomEmojiStickers::new(too_many)).await {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => assert!(e.contains("200")),
+        _ => panic!("expected more than 200 ids to be rejected"),
+    }
+    assert_eq!(api.requests.lock().len(), 1);
+    Ok(())
+}
+
+#[derive(Default)]
+struct EndpointAPI {
+    endpoints: parking_lot::Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl API for EndpointAPI {
+    async fn get(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> telexide::Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(
+        &self,
+        endpoint: APIEndpoint,
+        _data: Option<serde_json::Value>,
+    ) -> telexide::Result<Response> {
+        let result = match endpoint {
+            APIEndpoint::GetChatMemberCount => serde_json::json!(3),
+            _ => serde_json::json!(true),
+        };
+        self.endpoints.lock().push(endpoint.as_str().to_owned());
+        Ok(Response {
+            ok: true,
+            description: None,
+            result: Some(serde_json::value::to_raw_value(&result)?),
+        })
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> telexide::Result<Response> {
+        self.post(endpoint, data).await
+    }
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn renamed_methods_use_new_endpoints() -> telexide::Result<()> {
+    let api = EndpointAPI::default();
+
+    assert!(api.ban_chat_member(BanChatMember::new(-100, 5)).await?);
+    assert!(api.kick_chat_member(BanChatMember::new(-100, 5)).await?);
+    let count = GetChatMemberCount {
+        chat_id: -100,
+    };
+    assert_eq!(api.get_chat_member_count(count.clone()).await?, 3);
+    assert_eq!(api.get_members_count(count).await?, 3);
+
+    assert_eq!(
+        *api.endpoints.lock(),
+        vec!["banChatMember", "banChatMember", "getChatMemberCount", "getChatMemberCount"]
+    );
+    Ok(())
+}
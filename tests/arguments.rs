@@ -0,0 +1,65 @@
+use telexide::{
+    framework::{ArgumentError, CommandArguments, FromCommandArguments},
+    macros::FromCommandArguments,
+};
+
+#[test]
+fn parse_quoted_arguments() {
+    let args = CommandArguments::new("  one \"two words\" \"say \\\"hi\\\"\" \"unclosed quote");
+    assert_eq!(
+        args.collect::<Vec<_>>(),
+        vec!["one", "two words", "say \"hi\"", "unclosed quote"]
+    );
+}
+
+#[test]
+fn parse_typed_arguments() {
+    let mut args = CommandArguments::new("12 abc rest of the text");
+    assert_eq!(args.next_arg::<i64>(), Ok(12));
+    assert!(matches!(
+        args.next_arg::<u8>(),
+        Err(ArgumentError::Invalid { ref value, .. }) if value == "abc"
+    ));
+    assert_eq!(args.rest(), "rest of the text");
+    assert_eq!(args.raw(), "12 abc rest of the text");
+
+    let mut args = CommandArguments::new("");
+    assert!(args.is_empty());
+    assert_eq!(args.optional_arg::<i64>(), Ok(None));
+    assert_eq!(args.next_arg::<i64>(), Err(ArgumentError::Missing(None)));
+}
+
+#[derive(FromCommandArguments, Debug, PartialEq)]
+struct BanArgs {
+    user: i64,
+    reason: Option<String>,
+}
+
+#[test]
+fn derive_from_command_arguments() {
+    assert_eq!(BanArgs::usage(), "<user> [reason]");
+
+    assert_eq!(
+        BanArgs::from_arguments(&mut CommandArguments::new("3")),
+        Ok(BanArgs {
+            user: 3,
+            reason: None,
+        })
+    );
+    assert_eq!(
+        BanArgs::from_arguments(&mut CommandArguments::new("3 \"being rude\"")),
+        Ok(BanArgs {
+            user: 3,
+            reason: Some("being rude".to_owned()),
+        })
+    );
+
+    let err = BanArgs::from_arguments(&mut CommandArguments::new("")).unwrap_err();
+    assert_eq!(err, ArgumentError::Missing(Some("user".to_owned())));
+    assert_eq!(err.to_string(), "missing argument user");
+
+    let err = BanArgs::from_arguments(&mut CommandArguments::new("me")).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("invalid value \"me\" for argument user"));
+}
@@ -0,0 +1,81 @@
+use telexide::{
+    utils::{CallbackData, MAX_CALLBACK_DATA_LENGTH},
+    Error,
+};
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Vote {
+        poll: i64,
+        up: bool,
+    },
+    Page(u32),
+    Cancel,
+    Comment(String),
+}
+
+impl CallbackData for Action {
+    const PREFIX: &'static str = "a";
+
+    fn to_fields(&self) -> Vec<String> {
+        match self {
+            Action::Vote {
+                poll,
+                up,
+            } => vec!["v".to_owned(), poll.to_string(), (*up as u8).to_string()],
+            Action::Page(page) => vec!["p".to_owned(), page.to_string()],
+            Action::Cancel => vec!["c".to_owned()],
+            Action::Comment(text) => vec!["t".to_owned(), text.clone()],
+        }
+    }
+
+    fn from_fields(fields: &[&str]) -> Option<Self> {
+        match fields {
+            ["v", poll, up] => Some(Action::Vote {
+                poll: poll.parse().ok()?,
+                up: *up == "1",
+            }),
+            ["p", page] => Some(Action::Page(page.parse().ok()?)),
+            ["c"] => Some(Action::Cancel),
+            ["t", text] => Some(Action::Comment((*text).to_owned())),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn encode_and_decode_variants() {
+    let actions = vec![
+        Action::Vote {
+            poll: -1_000_000_000_123,
+            up: true,
+        },
+        Action::Page(u32::MAX),
+        Action::Cancel,
+        Action::Comment("hi there".to_owned()),
+    ];
+
+    for action in actions {
+        let data = action.encode().unwrap();
+        assert_eq!(Action::decode(&data), Some(action));
+    }
+
+    assert_eq!(Action::Page(3).encode().unwrap(), "a:p:3");
+    assert_eq!(Action::decode("b:p:3"), None);
+    assert_eq!(Action::decode("a:p:x"), None);
+    assert_eq!(Action::decode("a:p:3:4"), None);
+}
+
+#[test]
+fn encode_validates_data() {
+    let long = Action::Comment("x".repeat(MAX_CALLBACK_DATA_LENGTH));
+    match long.encode() {
+        Err(Error::Telegram(err)) => assert!(err.to_string().contains("64 bytes")),
+        res => panic!("expected an error, got {:?}", res),
+    }
+
+    let fits = Action::Comment("x".repeat(MAX_CALLBACK_DATA_LENGTH - 4));
+    assert_eq!(fits.encode().unwrap().len(), MAX_CALLBACK_DATA_LENGTH);
+
+    assert!(Action::Comment("a:b".to_owned()).encode().is_err());
+}
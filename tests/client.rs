@@ -1,7 +1,20 @@
-use std::sync::atomic::{AtomicUsize, Ordering};
+mod common;
+
+use common::{chat_message, text_update};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use telexide::{
-    client::{ClientBuilder, Context},
-    model::{Update, UpdateContent},
+    api::{types::UpdateType, APIEndpoint, ApiObserver, Response, API},
+    client::{ClientBuilder, Context, DispatchMode},
+    framework::Framework,
+    model::{BotUser, ChatMemberUpdated, Update, UpdateContent, User},
+    utils::{result::TelegramError, FormDataFile},
+    Error,
     Result,
 };
 
@@ -18,7 +31,8 @@ async fn update_handler_gets_called() -> Result<()> {
 
     c.fire_handlers(Update {
         update_id: 10,
-        content: UpdateContent::Unknown,
+        raw_json: None,
+        content: UpdateContent::Unknown(Box::default()),
     });
 
     tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
@@ -46,7 +60,8 @@ async fn test_using_func() -> Result<()> {
 
     c.fire_handlers(Update {
         update_id: 10,
-        content: UpdateContent::Unknown,
+        raw_json: None,
+        content: UpdateContent::Unknown(Box::default()),
     });
 
     tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
@@ -54,3 +69,601 @@ async fn test_using_func() -> Result<()> {
     assert_eq!(FUNC_B.load(Ordering::Relaxed), 10);
     Ok(())
 }
+
+#[test]
+fn framework_keeps_allowed_updates() {
+    let fr = Arc::new(Framework::new("test"));
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(fr.clone())
+        .build();
+    assert!(c.allowed_updates.is_empty());
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(fr)
+        .add_allowed_updates(UpdateType::CallbackQuery)
+        .build();
+    assert_eq!(
+        c.allowed_updates,
+        vec![UpdateType::CallbackQuery, UpdateType::Message]
+    );
+    assert_eq!(
+        serde_json::to_value(&c.allowed_updates).unwrap(),
+        serde_json::json!(["callback_query", "message"])
+    );
+}
+
+#[test]
+fn chat_member_handler_requests_chat_member_updates() {
+    fn handler(
+        _c: Context,
+        _u: ChatMemberUpdated,
+    ) -> ::std::pin::Pin<Box<dyn Send + ::std::future::Future<Output = ()>>> {
+        Box::pin(async {})
+    }
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .add_my_chat_member_handler(handler)
+        .build();
+    assert!(c.allowed_updates.is_empty());
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .add_chat_member_handler(handler)
+        .build();
+    let mut expected = UpdateType::defaults();
+    expected.push(UpdateType::ChatMember);
+    assert_eq!(c.allowed_updates, expected);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .add_allowed_updates(UpdateType::CallbackQuery)
+        .add_chat_member_handler(handler)
+        .build();
+    assert_eq!(
+        c.allowed_updates,
+        vec![UpdateType::CallbackQuery, UpdateType::ChatMember]
+    );
+}
+
+static MY_CHAT_MEMBER: AtomicUsize = AtomicUsize::new(0);
+static CHAT_MEMBER: AtomicUsize = AtomicUsize::new(0);
+
+#[tokio::test]
+async fn chat_member_updates_are_dispatched() {
+    let mut c = ClientBuilder::new().set_token("test").build();
+    c.subscribe_my_chat_member_handler(|_, u| {
+        Box::pin(async move {
+            assert!(u.bot_was_added());
+            MY_CHAT_MEMBER.fetch_add(1, Ordering::SeqCst);
+        })
+    });
+    c.subscribe_chat_member_handler(|_, u| {
+        Box::pin(async move {
+            assert!(u.user_left());
+            CHAT_MEMBER.fetch_add(1, Ordering::SeqCst);
+        })
+    });
+
+    let update = |kind: &str, is_bot: bool, old: &str, new: &str| -> Update {
+        let user = serde_json::json!({"id": 7, "is_bot": is_bot, "first_name": "a"});
+        serde_json::from_value(serde_json::json!({
+            "update_id": 1,
+            kind: {
+                "chat": {"id": -100, "type": "group", "title": "group"},
+                "from": {"id": 1, "is_bot": false, "first_name": "admin"},
+                "date": 0,
+                "old_chat_member": {"status": old, "user": user},
+                "new_chat_member": {"status": new, "user": user, "until_date": 0},
+            },
+        }))
+        .unwrap()
+    };
+    c.fire_handlers(update("my_chat_member", true, "left", "member"));
+    c.fire_handlers(update("chat_member", false, "member", "kicked"));
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    assert_eq!(MY_CHAT_MEMBER.load(Ordering::SeqCst), 1);
+    assert_eq!(CHAT_MEMBER.load(Ordering::SeqCst), 1);
+}
+
+fn chat_update(update_id: i64, chat_id: i64) -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": update_id,
+        "message": {
+            "message_id": update_id,
+            "date": 0,
+            "chat": {"id": chat_id, "type": "private"},
+            "text": "hi",
+        },
+    }))
+    .unwrap()
+}
+
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+static MAX_ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+#[tokio::test]
+async fn limit_concurrent_updates() {
+    let mut c = ClientBuilder::new()
+        .set_token("test")
+        .set_max_concurrent_updates(2)
+        .build();
+
+    c.subscribe_handler_func(|_c, _u| {
+        Box::pin(async move {
+            let active = ACTIVE.fetch_add(1, Ordering::SeqCst) + 1;
+            MAX_ACTIVE.fetch_max(active, Ordering::SeqCst);
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+            ACTIVE.fetch_sub(1, Ordering::SeqCst);
+        })
+    });
+
+    for update_id in 0..6 {
+        c.fire_handlers(chat_update(update_id, update_id));
+    }
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
+    assert_eq!(MAX_ACTIVE.load(Ordering::SeqCst), 2);
+}
+
+static ORDER: parking_lot::Mutex<Vec<(i64, i64)>> = parking_lot::const_mutex(Vec::new());
+
+#[tokio::test]
+async fn keep_order_per_chat() {
+    let mut c = ClientBuilder::new()
+        .set_token("test")
+        .set_dispatch_mode(DispatchMode::OrderedPerChat)
+        .build();
+
+    c.subscribe_handler_func(|_c, u| {
+        Box::pin(async move {
+            // later updates finish sooner, which would reorder them if the
+            // updates of a chat weren't handled in order
+            let delay = 40 - u.update_id as u64 * 5;
+            tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+            ORDER.lock().push((u.get_chat_id().unwrap(), u.update_id));
+        })
+    });
+
+    for update_id in 0..6 {
+        c.fire_handlers(chat_update(update_id, update_id % 2));
+    }
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+
+    let order = ORDER.lock();
+    assert_eq!(order.len(), 6);
+    for chat_id in 0..2 {
+        let chat_order: Vec<i64> = order
+            .iter()
+            .filter(|(chat, _)| *chat == chat_id)
+            .map(|(_, update_id)| *update_id)
+            .collect();
+        assert_eq!(chat_order, (0..6).filter(|id| id % 2 == chat_id).collect::<Vec<_>>());
+    }
+
+    // updates of different chats are still handled concurrently
+    assert_eq!(order[0], (1, 1));
+}
+
+struct GetMeAPI {
+    requests: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl API for GetMeAPI {
+    async fn get(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(
+        &self,
+        _endpoint: APIEndpoint,
+        _data: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        Ok(Response {
+            ok: true,
+            description: None,
+            result: Some(serde_json::value::to_raw_value(&serde_json::json!({
+                "id": 5,
+                "is_bot": true,
+                "first_name": "test",
+                "username": "test_bot",
+                "can_join_groups": true,
+                "supports_inline_queries": false,
+            }))?),
+        })
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+}
+
+#[tokio::test]
+async fn get_me_is_cached() -> Result<()> {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let c = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(GetMeAPI {
+            requests: requests.clone(),
+        })))
+        .build();
+
+    assert_eq!(c.me(), None);
+    let me = c.get_me().await?;
+    assert_eq!(me, BotUser {
+        id: 5,
+        is_bot: true,
+        first_name: "test".to_owned(),
+        last_name: None,
+        username: "test_bot".to_owned(),
+        can_join_groups: true,
+        can_read_all_group_messages: false,
+        supports_inline_queries: false,
+    });
+    assert_eq!(c.get_me().await?, me);
+    assert_eq!(c.me(), Some(me.clone()));
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    assert_eq!(User::from(me).username.as_deref(), Some("test_bot"));
+    Ok(())
+}
+
+struct UnauthorizedAPI {
+    requests: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl API for UnauthorizedAPI {
+    async fn get(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(
+        &self,
+        _endpoint: APIEndpoint,
+        _data: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        Ok(Response {
+            ok: false,
+            description: Some("Unauthorized".to_owned()),
+            result: None,
+        })
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+}
+
+#[tokio::test]
+async fn invalid_token_stops_start() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let c = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(UnauthorizedAPI {
+            requests: requests.clone(),
+        })))
+        .build();
+
+    let res = tokio::time::timeout(tokio::time::Duration::from_secs(1), c.start()).await;
+    match res {
+        Ok(Err(Error::Telegram(TelegramError::InvalidToken))) => {},
+        other => panic!("expected an invalid token error, got {:?}", other),
+    }
+    // only the getMe request was made, the client didn't start polling
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert_eq!(c.me(), None);
+}
+
+type Requests = Arc<parking_lot::Mutex<Vec<(String, Option<serde_json::Value>)>>>;
+
+struct WebhookSetAPI {
+    requests: Requests,
+}
+
+#[async_trait::async_trait]
+impl API for WebhookSetAPI {
+    async fn get(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.requests.lock().push((endpoint.to_string(), data));
+        Ok(match endpoint {
+            APIEndpoint::GetMe => Response {
+                ok: true,
+                description: None,
+                result: Some(serde_json::value::to_raw_value(&serde_json::json!({
+                    "id": 1,
+                    "is_bot": true,
+                    "first_name": "test",
+                    "username": "test_bot",
+                }))?),
+            },
+            APIEndpoint::DeleteWebhook => Response {
+                ok: true,
+                description: None,
+                result: Some(serde_json::value::to_raw_value(&serde_json::Value::Bool(true))?),
+            },
+            _ => Response {
+                ok: false,
+                description: Some(
+                    "Conflict: can't use getUpdates method while webhook is active; use \
+                     deleteWebhook to delete the webhook first"
+                        .to_owned(),
+                ),
+                result: None,
+            },
+        })
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+}
+
+async fn start_with_webhook_set(
+    builder: &mut ClientBuilder,
+) -> Vec<(String, Option<serde_json::Value>)> {
+    let requests = Requests::default();
+    let c = builder
+        .set_api_client(Arc::new(Box::new(WebhookSetAPI {
+            requests: requests.clone(),
+        })))
+        .build();
+
+    let res = tokio::time::timeout(tokio::time::Duration::from_secs(1), c.start()).await;
+    match res {
+        Ok(Err(Error::Telegram(TelegramError::WebhookConflict))) => {},
+        other => panic!("expected a webhook conflict error, got {:?}", other),
+    }
+    let requests = requests.lock().clone();
+    requests
+}
+
+#[tokio::test]
+async fn webhook_is_deleted_before_polling() {
+    let requests = start_with_webhook_set(&mut ClientBuilder::new()).await;
+    let endpoints: Vec<_> = requests.iter().map(|(e, _)| e.as_str()).collect();
+    assert_eq!(endpoints, vec!["getMe", "deleteWebhook", "getUpdates"]);
+    assert_eq!(requests[1].1, Some(serde_json::json!({"drop_pending_updates": null})));
+
+    let requests =
+        start_with_webhook_set(ClientBuilder::new().set_drop_pending_updates(true)).await;
+    assert_eq!(
+        requests[1].1,
+        Some(serde_json::json!({"drop_pending_updates": true}))
+    );
+
+    let requests = start_with_webhook_set(ClientBuilder::new().set_delete_webhook(false)).await;
+    let endpoints: Vec<_> = requests.iter().map(|(e, _)| e.as_str()).collect();
+    assert_eq!(endpoints, vec!["getMe", "getUpdates"]);
+}
+
+struct PendingUpdatesAPI {
+    requests: Requests,
+}
+
+#[async_trait::async_trait]
+impl API for PendingUpdatesAPI {
+    async fn get(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+    ) -> Result<Response> {
+        let offset = data.as_ref().map(|d| d["offset"].clone());
+        self.requests.lock().push((endpoint.to_string(), data));
+        let result = match endpoint {
+            APIEndpoint::GetMe => serde_json::json!({
+                "id": 1,
+                "is_bot": true,
+                "first_name": "test",
+                "username": "test_bot",
+            }),
+            APIEndpoint::GetUpdates if offset == Some(serde_json::json!(-1)) => {
+                serde_json::json!([{
+                    "update_id": 41,
+                    "message": {
+                        "message_id": 1,
+                        "date": 0,
+                        "chat": {"id": 1, "type": "private", "first_name": "a"},
+                        "text": "sent while the bot was down",
+                    },
+                }])
+            },
+            _ => {
+                return Ok(Response {
+                    ok: false,
+                    description: Some("Unauthorized".to_owned()),
+                    result: None,
+                })
+            },
+        };
+        Ok(Response {
+            ok: true,
+            description: None,
+            result: Some(serde_json::value::to_raw_value(&result)?),
+        })
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<serde_json::Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+}
+
+#[tokio::test]
+async fn pending_updates_are_skipped_without_deleting_webhook() {
+    let requests = Requests::default();
+    let c = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(PendingUpdatesAPI {
+            requests: requests.clone(),
+        })))
+        .set_delete_webhook(false)
+        .set_drop_pending_updates(true)
+        .build();
+
+    let res = tokio::time::timeout(tokio::time::Duration::from_secs(1), c.start()).await;
+    assert!(matches!(res, Ok(Err(_))), "expected polling to fail");
+
+    let requests = requests.lock();
+    let endpoints: Vec<_> = requests.iter().map(|(e, _)| e.as_str()).collect();
+    assert_eq!(endpoints, vec!["getMe", "getUpdates", "getUpdates"]);
+    let skip = requests[1].1.as_ref().unwrap();
+    assert_eq!(skip["offset"], -1);
+    assert_eq!(skip["limit"], 1);
+    assert_eq!(requests[2].1.as_ref().unwrap()["offset"], 42);
+}
+
+#[derive(Default)]
+struct RecordingObserver {
+    requests: parking_lot::Mutex<Vec<String>>,
+    responses: parking_lot::Mutex<Vec<(String, bool)>>,
+}
+
+impl ApiObserver for RecordingObserver {
+    fn on_request(&self, method_name: &str) {
+        self.requests.lock().push(method_name.to_owned());
+    }
+
+    fn on_response(&self, method_name: &str, _duration: Duration, result: &Result<Response>) {
+        let ok = matches!(result, Ok(res) if res.ok);
+        self.responses.lock().push((method_name.to_owned(), ok));
+    }
+}
+
+#[tokio::test]
+async fn api_observer_sees_every_request() {
+    let observer = Arc::new(RecordingObserver::default());
+    start_with_webhook_set(ClientBuilder::new().set_api_observer(observer.clone())).await;
+
+    assert_eq!(
+        *observer.requests.lock(),
+        vec!["getMe", "deleteWebhook", "getUpdates"]
+    );
+    assert_eq!(
+        *observer.responses.lock(),
+        vec![
+            ("getMe".to_owned(), true),
+            ("deleteWebhook".to_owned(), true),
+            ("getUpdates".to_owned(), false)
+        ]
+    );
+}
+
+static WAITED_B: parking_lot::Mutex<Vec<String>> = parking_lot::const_mutex(Vec::new());
+static SEEN_B: parking_lot::Mutex<Vec<String>> = parking_lot::const_mutex(Vec::new());
+
+#[tokio::test]
+async fn wait_for_next_message_and_callback_query() -> Result<()> {
+    let mut c = ClientBuilder::new().set_token("test").build();
+    c.subscribe_handler_func(|ctx, u| {
+        Box::pin(async move {
+            let text = match u.content {
+                UpdateContent::Message(m) => m.get_text().unwrap(),
+                UpdateContent::CallbackQuery(q) => q.data.unwrap(),
+                _ => return,
+            };
+            SEEN_B.lock().push(text.clone());
+
+            let timeout = tokio::time::Duration::from_millis(100);
+            let res = match text.as_str() {
+                "ask" => ctx.wait_for_message(40, 1, timeout).await.map(|m| m.get_text()),
+                "buttons" => ctx.wait_for_callback_query(40, 7, timeout).await.map(|q| q.data),
+                _ => return,
+            };
+            WAITED_B.lock().push(match res {
+                Ok(text) => text.unwrap(),
+                Err(Error::Telegram(TelegramError::Timeout)) => "timeout".to_owned(),
+                Err(err) => panic!("unexpected error: {}", err),
+            });
+        })
+    });
+
+    let sleep = || tokio::time::sleep(tokio::time::Duration::from_millis(20));
+    c.fire_handlers(text_update(1, "ask"));
+    sleep().await;
+    assert_eq!(c.waiting_handlers(), 1);
+    c.fire_handlers(text_update(2, "answer"));
+    sleep().await;
+
+    c.fire_handlers(text_update(7, "buttons"));
+    sleep().await;
+    let query = |chat_id: i64, data: &str| {
+        serde_json::from_value(serde_json::json!({
+            "update_id": 2,
+            "callback_query": {
+                "id": "query",
+                "from": {"id": 1, "is_bot": false, "first_name": "test"},
+                "message": chat_message(chat_id, 7, "buttons"),
+                "chat_instance": "instance",
+                "data": data,
+            },
+        }))
+    };
+    // a message with the same id in another chat isn't the one being waited on
+    c.fire_handlers(query(41, "elsewhere")?);
+    sleep().await;
+    c.fire_handlers(query(40, "pressed")?);
+    sleep().await;
+
+    // after timing out the waiter is removed, so the message isn't consumed
+    c.fire_handlers(text_update(3, "ask"));
+    tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
+    assert_eq!(c.waiting_handlers(), 0);
+    c.fire_handlers(text_update(4, "late"));
+    sleep().await;
+
+    assert_eq!(*WAITED_B.lock(), vec!["answer", "pressed", "timeout"]);
+    assert_eq!(*SEEN_B.lock(), vec!["ask", "buttons", "elsewhere", "ask", "late"]);
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! Updates shared by multiple integration tests, built from the json telegram
+//! would send
+
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use telexide::model::Update;
+
+/// The json of a text message sent by the user with id 1 in the private chat
+/// with the given id
+pub fn chat_message(chat_id: i64, message_id: i64, text: &str) -> Value {
+    json!({
+        "message_id": message_id,
+        "date": 0,
+        "chat": {"id": chat_id, "type": "private"},
+        "from": {"id": 1, "is_bot": false, "first_name": "test"},
+        "text": text,
+    })
+}
+
+fn message_update(message: Value) -> Update {
+    serde_json::from_value(json!({
+        "update_id": 10,
+        "message": message,
+    }))
+    .unwrap()
+}
+
+/// An update with a text message in the private chat with id 40
+pub fn text_update(message_id: i64, text: &str) -> Update {
+    message_update(chat_message(40, message_id, text))
+}
+
+/// An update with a text message in the private chat with id 40, of which the
+/// first word is a bot command
+pub fn command_update(message_id: i64, text: &str) -> Update {
+    let length = text.split_whitespace().next().unwrap_or_default().len();
+    let mut message = chat_message(40, message_id, text);
+    message["entities"] = json!([{"type": "bot_command", "offset": 0, "length": length}]);
+    message_update(message)
+}
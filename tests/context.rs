@@ -0,0 +1,507 @@
+use parking_lot::{Mutex, RwLock};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use telexide::{
+    api::{
+        types::{
+            AnswerCallbackQuery,
+            InlineQueryResult,
+            InlineQueryResultArticle,
+            InlineQueryResultContact,
+            InlineQueryResultPhoto,
+            InlineQueryResultVenue,
+            InputMessageContent,
+            InputTextMessageContent,
+            SendDice,
+            SendMessage,
+        },
+        APIEndpoint,
+        Response,
+        API,
+    },
+    client::{ClientBuilder, Context, TypeMapKey},
+    framework::Framework,
+    model::{
+        CallbackQuery,
+        DiceEmoji,
+        InlineQuery,
+        Message,
+        ParseMode,
+        PreCheckoutQuery,
+        ShippingOption,
+        ShippingQuery,
+        Update,
+        UpdateContent,
+    },
+    utils::{result::TelegramError, FormDataFile, FormattedTextBuilder},
+    Error,
+    Result,
+};
+use typemap::ShareMap;
+
+type Requests = Arc<Mutex<Vec<(String, Option<Value>)>>>;
+
+struct MockAPI {
+    requests: Requests,
+}
+
+impl MockAPI {
+    fn respond(&self, endpoint: APIEndpoint, data: Option<Value>) -> Response {
+        let result = match endpoint {
+            APIEndpoint::AnswerCallbackQuery
+            | APIEndpoint::AnswerInlineQuery
+            | APIEndpoint::AnswerShippingQuery
+            | APIEndpoint::AnswerPreCheckoutQuery => json!(true),
+            _ => message_json(2, "sent"),
+        };
+
+        self.requests.lock().push((endpoint.to_string(), data));
+        Response {
+            ok: true,
+            description: None,
+            result: serde_json::value::to_raw_value(&result).ok(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl API for MockAPI {
+    async fn get(&self, endpoint: APIEndpoint, data: Option<Value>) -> Result<Response> {
+        Ok(self.respond(endpoint, data))
+    }
+
+    async fn post(&self, endpoint: APIEndpoint, data: Option<Value>) -> Result<Response> {
+        Ok(self.respond(endpoint, data))
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> Result<Response> {
+        Ok(self.respond(endpoint, data))
+    }
+}
+
+fn message_json(message_id: i64, text: &str) -> Value {
+    json!({
+        "message_id": message_id,
+        "date": 0,
+        "chat": {"id": 5, "type": "private", "first_name": "test"},
+        "text": text,
+    })
+}
+
+fn setup() -> (Requests, Context, Message) {
+    let requests = Requests::default();
+    let ctx = Context::new(
+        Arc::new(Box::new(MockAPI {
+            requests: requests.clone(),
+        })),
+        Arc::new(RwLock::new(ShareMap::custom())),
+    );
+    let message = serde_json::from_value(message_json(1, "hi")).unwrap();
+    (requests, ctx, message)
+}
+
+#[tokio::test]
+async fn reply_fills_in_chat_and_reply() -> Result<()> {
+    let (requests, ctx, message) = setup();
+
+    let sent = ctx.reply_markdown(&message, "*hello*").await?;
+    assert_eq!(sent.message_id, 2);
+
+    let requests = requests.lock();
+    let (endpoint, data) = &requests[0];
+    let data = data.as_ref().unwrap();
+    assert_eq!(endpoint, "sendMessage");
+    assert_eq!(data["chat_id"], 5);
+    assert_eq!(data["text"], "*hello*");
+    assert_eq!(data["parse_mode"], "MarkdownV2");
+    assert_eq!(data["reply_to_message_id"], 1);
+    assert_eq!(data["allow_sending_without_reply"], true);
+    Ok(())
+}
+
+#[tokio::test]
+async fn send_to_and_edit_text() -> Result<()> {
+    let (requests, ctx, message) = setup();
+
+    ctx.send_to(7, "hello").await?;
+    ctx.edit_text(&message, "edited").await?;
+
+    let requests = requests.lock();
+    let (endpoint, data) = &requests[0];
+    let data = data.as_ref().unwrap();
+    assert_eq!(endpoint, "sendMessage");
+    assert_eq!(data["chat_id"], 7);
+    assert!(data.get("reply_to_message_id").is_none());
+
+    let (endpoint, data) = &requests[1];
+    let data = data.as_ref().unwrap();
+    assert_eq!(endpoint, "editMessageText");
+    assert_eq!(data["chat_id"], 5);
+    assert_eq!(data["message_id"], 1);
+    assert_eq!(data["text"], "edited");
+    Ok(())
+}
+
+#[tokio::test]
+async fn context_derefs_to_api() -> Result<()> {
+    let (requests, ctx, message) = setup();
+
+    ctx.answer(&message, "hello").await?;
+    ctx.send_dice(SendDice::new(message.chat.get_id(), DiceEmoji::Dice)).await?;
+
+    let requests = requests.lock();
+    let (endpoint, data) = &requests[0];
+    let data = data.as_ref().unwrap();
+    assert_eq!(endpoint, "sendMessage");
+    assert_eq!(data["chat_id"], 5);
+    assert_eq!(data["text"], "hello");
+    assert!(data.get("reply_to_message_id").is_none());
+
+    let (endpoint, data) = &requests[1];
+    assert_eq!(endpoint, "sendDice");
+    assert_eq!(data.as_ref().unwrap()["chat_id"], 5);
+    Ok(())
+}
+
+#[tokio::test]
+async fn send_message_split_sends_parts_in_order() -> Result<()> {
+    let (requests, ctx, _) = setup();
+
+    let text = format!("{}\n{}", "a".repeat(3000), "b ".repeat(1500));
+    let sent = ctx.send_message_split(7, &text).await?;
+    assert_eq!(sent.len(), 2);
+
+    let requests = requests.lock();
+    let texts: Vec<_> = requests
+        .iter()
+        .map(|(endpoint, data)| {
+            assert_eq!(endpoint, "sendMessage");
+            assert_eq!(data.as_ref().unwrap()["chat_id"], 7);
+            data.as_ref().unwrap()["text"].as_str().unwrap().to_owned()
+        })
+        .collect();
+    assert_eq!(texts, vec!["a".repeat(3000), "b ".repeat(1500).trim_end().to_owned()]);
+    Ok(())
+}
+
+fn callback_update(data: &str) -> Update {
+    let query: CallbackQuery = serde_json::from_value(json!({
+        "id": "query",
+        "from": {"id": 3, "is_bot": false, "first_name": "test"},
+        "message": message_json(1, "buttons"),
+        "chat_instance": "instance",
+        "data": data,
+    }))
+    .unwrap();
+
+    Update {
+        update_id: 1,
+        raw_json: None,
+        content: UpdateContent::CallbackQuery(query),
+    }
+}
+
+#[tokio::test]
+async fn callback_query_helpers() {
+    if let UpdateContent::CallbackQuery(query) = callback_update("[1, 2]").content {
+        assert_eq!(query.chat_id(), Some(5));
+        assert_eq!(query.message_id(), Some(1));
+        assert_eq!(query.data_as::<Vec<i64>>().unwrap().unwrap(), vec![1, 2]);
+        assert!(query.data_as::<String>().unwrap().is_err());
+    }
+}
+
+#[tokio::test]
+async fn auto_answer_only_unanswered_callbacks() {
+    let requests = Requests::default();
+    let mut client = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(MockAPI {
+            requests: requests.clone(),
+        })))
+        .set_auto_answer_callbacks(true)
+        .build();
+
+    client.subscribe_handler_func(|ctx, update| {
+        Box::pin(async move {
+            if let UpdateContent::CallbackQuery(query) = update.content {
+                match query.data.as_deref() {
+                    Some("answer") => {
+                        ctx.answer_callback_text(&query, "answered").await.unwrap();
+                    },
+                    Some("answer through api") => {
+                        let mut data = AnswerCallbackQuery::new(&query.id);
+                        data.set_text("answered");
+                        ctx.answer_callback_query(data).await.unwrap();
+                    },
+                    _ => {},
+                }
+            }
+        })
+    });
+
+    client.fire_handlers(callback_update("answer"));
+    client.fire_handlers(callback_update("answer through api"));
+    client.fire_handlers(callback_update("ignore"));
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    let requests = requests.lock();
+    assert_eq!(requests.len(), 3);
+    assert!(requests
+        .iter()
+        .all(|(endpoint, _)| endpoint == "answerCallbackQuery"));
+    assert_eq!(
+        requests
+            .iter()
+            .filter(|(_, data)| data.as_ref().unwrap().get("text").is_some())
+            .count(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn default_parse_mode_precedence() {
+    let requests = Requests::default();
+    let mut client = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(MockAPI {
+            requests: requests.clone(),
+        })))
+        .set_default_parse_mode(ParseMode::MarkdownV2)
+        .build();
+
+    client.subscribe_handler_func(|ctx, update| {
+        Box::pin(async move {
+            let message = match update.content {
+                UpdateContent::Message(message) => message,
+                _ => return,
+            };
+
+            ctx.reply(&message, "*default*").await.unwrap();
+            ctx.reply_html(&message, "<b>explicit</b>").await.unwrap();
+
+            let mut raw = SendMessage::new(5, "*raw*");
+            raw.set_parse_mode(&ParseMode::None);
+            ctx.send_message(raw).await.unwrap();
+
+            let mut with_entities = SendMessage::new(5, "entities");
+            with_entities.set_entities(Vec::new());
+            ctx.send_message(with_entities).await.unwrap();
+
+            let plain = FormattedTextBuilder::new().text("*plain*").clone();
+            ctx.reply_formatted(&message, &plain).await.unwrap();
+
+            ctx.api
+                .send_message(SendMessage::new(5, "*api*"))
+                .await
+                .unwrap();
+        })
+    });
+
+    client.fire_handlers(Update {
+        update_id: 1,
+        raw_json: None,
+        content: UpdateContent::Message(serde_json::from_value(message_json(1, "hi")).unwrap()),
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    let parse_modes: Vec<_> = requests
+        .lock()
+        .iter()
+        .map(|(_, data)| data.as_ref().unwrap().get("parse_mode").cloned())
+        .collect();
+    assert_eq!(
+        parse_modes,
+        vec![
+            Some(json!("MarkdownV2")),
+            Some(json!("HTML")),
+            None,
+            None,
+            None,
+            None,
+        ]
+    );
+}
+
+fn pre_checkout_update(payload: &str) -> Update {
+    let query: PreCheckoutQuery = serde_json::from_value(json!({
+        "id": payload,
+        "from": {"id": 3, "is_bot": false, "first_name": "test"},
+        "currency": "EUR",
+        "total_amount": 500,
+        "invoice_payload": payload,
+    }))
+    .unwrap();
+
+    Update {
+        update_id: 1,
+        raw_json: None,
+        content: UpdateContent::PreCheckoutQuery(query),
+    }
+}
+
+fn shipping_update(payload: &str) -> Update {
+    let query: ShippingQuery = serde_json::from_value(json!({
+        "id": payload,
+        "from": {"id": 3, "is_bot": false, "first_name": "test"},
+        "invoice_payload": payload,
+        "shipping_address": {
+            "country_code": "NL",
+            "state": "",
+            "city": "Amsterdam",
+            "street_line1": "Dam 1",
+            "street_line2": "",
+            "post_code": "1012",
+        },
+    }))
+    .unwrap();
+
+    Update {
+        update_id: 1,
+        raw_json: None,
+        content: UpdateContent::ShippingQuery(query),
+    }
+}
+
+#[tokio::test]
+async fn auto_decline_unanswered_pre_checkout() {
+    let mut framework = Framework::new("bot");
+    framework
+        .add_pre_checkout_query_handler("order:", |ctx, query| {
+            Box::pin(async move {
+                ctx.answer_pre_checkout(&query, None).await.unwrap();
+            })
+        })
+        .add_shipping_query_handler("", |ctx, query| {
+            Box::pin(async move {
+                let mut option = ShippingOption::new("post", "Post");
+                option.add_price("Shipping", 250).add_price("Packaging", 50);
+                let options = if query.shipping_address.country_code == "NL" {
+                    Ok(vec![option])
+                } else {
+                    Err("we don't ship there")
+                };
+                ctx.answer_shipping(&query, options).await.unwrap();
+            })
+        });
+
+    let requests = Requests::default();
+    let client = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(MockAPI {
+            requests: requests.clone(),
+        })))
+        .set_framework(Arc::new(framework))
+        .set_auto_decline_pre_checkout(Some("sold out"))
+        .build();
+
+    client.fire_handlers(pre_checkout_update("order:1"));
+    client.fire_handlers(pre_checkout_update("unknown"));
+    client.fire_handlers(shipping_update("order:2"));
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    let requests = requests.lock();
+    assert_eq!(requests.len(), 3);
+    let answer = |id: &str| {
+        requests
+            .iter()
+            .filter_map(|(_, data)| data.as_ref())
+            .find(|data| data["pre_checkout_query_id"] == id || data["shipping_query_id"] == id)
+            .cloned()
+            .unwrap()
+    };
+
+    assert_eq!(answer("order:1"), json!({"pre_checkout_query_id": "order:1", "ok": true}));
+    assert_eq!(
+        answer("unknown"),
+        json!({"pre_checkout_query_id": "unknown", "ok": false, "error_message": "sold out"})
+    );
+
+    let shipping = answer("order:2");
+    assert_eq!(shipping["ok"], true);
+    assert_eq!(shipping["shipping_options"][0]["prices"][1]["amount"], 50);
+}
+
+struct Greeting;
+
+impl TypeMapKey for Greeting {
+    type Value = String;
+}
+
+#[tokio::test]
+async fn builder_data_is_shared_with_handlers() {
+    let requests = Requests::default();
+    let mut client = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(MockAPI {
+            requests: requests.clone(),
+        })))
+        .insert_data::<Greeting>("hello".to_owned())
+        .build();
+
+    client.subscribe_handler_func(|ctx, update| {
+        Box::pin(async move {
+            if let UpdateContent::Message(message) = update.content {
+                let greeting = ctx.data.read().get::<Greeting>().cloned().unwrap();
+                ctx.reply(&message, &greeting).await.unwrap();
+            }
+        })
+    });
+
+    let message = serde_json::from_value(message_json(1, "hi")).unwrap();
+    client.fire_handlers(Update {
+        update_id: 1,
+        raw_json: None,
+        content: UpdateContent::Message(message),
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(requests.lock()[0].1.as_ref().unwrap()["text"], "hello");
+}
+
+#[tokio::test]
+async fn inline_result_ids() -> Result<()> {
+    let (requests, ctx, _) = setup();
+    let query: InlineQuery = serde_json::from_value(json!({
+        "id": "query",
+        "from": {"id": 3, "is_bot": false, "first_name": "test"},
+        "query": "",
+        "offset": "",
+    }))?;
+
+    let content = InputMessageContent::Text(InputTextMessageContent {
+        message_text: "hi".to_owned(),
+        parse_mode: None,
+        entities: None,
+        disable_web_page_preview: false,
+    });
+    let mut without_id: InlineQueryResult =
+        InlineQueryResultArticle::new("article", content.clone()).into();
+    without_id.set_id("");
+    let photo: InlineQueryResult = InlineQueryResultPhoto::new("https://a/b.jpg", "t").into();
+    let venue = InlineQueryResultVenue::new(52.37, 4.89, "venue", "Dam 1").into();
+    let contact = InlineQueryResultContact::new("+123456", "test").into();
+    ctx.answer_inline(&query, vec![without_id, photo.clone(), venue, contact]).await?;
+
+    let ids: Vec<String> = requests.lock()[0].1.as_ref().unwrap()["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap().to_owned())
+        .collect();
+    assert_eq!(ids[1], photo.id());
+    assert_eq!(ids.iter().collect::<std::collections::HashSet<_>>().len(), 4);
+    assert!(ids.iter().all(|id| id.len() == 36 && id.chars().nth(14) == Some('4')));
+
+    let mut duplicate: InlineQueryResult = InlineQueryResultArticle::new("a", content).into();
+    duplicate.set_id(photo.id());
+    match ctx.answer_inline(&query, vec![photo, duplicate]).await {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("multiple results have the id"))
+        },
+        res => panic!("expected the duplicate ids to be rejected, got {:?}", res),
+    }
+    assert_eq!(requests.lock().len(), 1);
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use parking_lot::Mutex;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use telexide::{
+    client::{ClientBuilder, Context},
+    framework::{
+        Clock,
+        CommandResult,
+        Cooldown,
+        CooldownKey,
+        CooldownScope,
+        CooldownStorage,
+        Framework,
+        InMemoryCooldownStorage,
+    },
+    macros::{command, prepare_listener},
+    model::{
+        Chat,
+        GroupChat,
+        Message,
+        MessageContent,
+        MessageEntity,
+        TextBlock,
+        Update,
+        UpdateContent,
+        User,
+    },
+};
+
+struct MockClock(Mutex<Instant>);
+
+impl MockClock {
+    fn new() -> Arc<Self> {
+        Arc::new(Self(Mutex::new(Instant::now())))
+    }
+
+    fn advance(&self, secs: u64) {
+        *self.0.lock() += Duration::from_secs(secs);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        *self.0.lock()
+    }
+}
+
+fn key(scope: CooldownScope, id: i64) -> CooldownKey {
+    CooldownKey {
+        command: "meme",
+        scope,
+        id,
+    }
+}
+
+fn cooldown(uses: u32, secs: u64, scope: CooldownScope) -> Cooldown {
+    Cooldown {
+        uses,
+        duration: Duration::from_secs(secs),
+        scope,
+    }
+}
+
+#[tokio::test]
+async fn cooldown_window() {
+    let clock = MockClock::new();
+    let storage = InMemoryCooldownStorage::with_clock(clock.clone());
+    let limit = [(key(CooldownScope::Chat, 1), cooldown(3, 60, CooldownScope::Chat))];
+
+    for _ in 0..3 {
+        assert_eq!(storage.check_cooldowns(&limit).await, None);
+        clock.advance(10);
+    }
+    // the oldest use was 30 seconds ago, so it leaves the window in 30 seconds
+    assert_eq!(
+        storage.check_cooldowns(&limit).await,
+        Some(Duration::from_secs(30))
+    );
+
+    clock.advance(30);
+    assert_eq!(storage.check_cooldowns(&limit).await, None);
+    assert_eq!(
+        storage.check_cooldowns(&limit).await,
+        Some(Duration::from_secs(10))
+    );
+}
+
+#[tokio::test]
+async fn cooldown_uses_longest_remaining_duration() {
+    let clock = MockClock::new();
+    let storage = InMemoryCooldownStorage::with_clock(clock.clone());
+    let user = (key(CooldownScope::User, 7), cooldown(1, 30, CooldownScope::User));
+    let chat = (key(CooldownScope::Chat, 1), cooldown(2, 60, CooldownScope::Chat));
+
+    assert_eq!(storage.check_cooldowns(&[user.clone(), chat.clone()]).await, None);
+    clock.advance(10);
+    assert_eq!(
+        storage.check_cooldowns(&[user.clone(), chat.clone()]).await,
+        Some(Duration::from_secs(20))
+    );
+
+    // being throttled by the user cooldown doesn't use up the chat cooldown
+    let other = (key(CooldownScope::User, 8), cooldown(1, 30, CooldownScope::User));
+    assert_eq!(storage.check_cooldowns(&[other.clone(), chat.clone()]).await, None);
+
+    clock.advance(25);
+    assert_eq!(
+        storage.check_cooldowns(&[user, chat]).await,
+        Some(Duration::from_secs(25))
+    );
+}
+
+#[tokio::test]
+async fn cooldown_buckets_are_cleaned_up() {
+    let clock = MockClock::new();
+    let storage = InMemoryCooldownStorage::with_clock(clock.clone());
+
+    for id in 0..10 {
+        let limit = [(key(CooldownScope::User, id), cooldown(1, 30, CooldownScope::User))];
+        assert_eq!(storage.check_cooldowns(&limit).await, None);
+    }
+    assert_eq!(storage.len(), 10);
+
+    clock.advance(45);
+    storage.cleanup();
+    assert!(storage.is_empty());
+
+    // expired buckets are also removed while the storage is being used
+    for id in 0..10 {
+        let limit = [(key(CooldownScope::User, id), cooldown(1, 30, CooldownScope::User))];
+        storage.check_cooldowns(&limit).await;
+    }
+    clock.advance(61);
+    let limit = [(key(CooldownScope::Global, 0), cooldown(1, 30, CooldownScope::Global))];
+    storage.check_cooldowns(&limit).await;
+    assert_eq!(storage.len(), 1);
+}
+
+static MEME_B: AtomicUsize = AtomicUsize::new(0);
+static THROTTLED_B: Mutex<Vec<(i64, Duration)>> = parking_lot::const_mutex(Vec::new());
+
+#[prepare_listener]
+async fn on_throttled(_c: Context, m: Message, remaining: Duration) {
+    THROTTLED_B.lock().push((m.message_id, remaining));
+}
+
+#[command(description = "sends a meme")]
+#[cooldown(user = "30s", chat = "2/1m")]
+async fn meme(_c: Context, _m: Message) -> CommandResult {
+    MEME_B.fetch_add(1, Ordering::Acquire);
+    Ok(())
+}
+
+fn meme_update(message_id: i64, user_id: i64) -> Update {
+    Update {
+        update_id: 10,
+        raw_json: None,
+        content: UpdateContent::Message(Message {
+            message_id,
+            from: Some(User {
+                id: user_id,
+                is_bot: false,
+                first_name: "test".to_owned(),
+                last_name: None,
+                username: None,
+                language_code: None,
+                can_join_groups: None,
+                can_read_all_group_messages: None,
+                supports_inline_queries: None,
+                is_premium: None,
+                added_to_attachment_menu: None,
+            }),
+            date: chrono::offset::Utc::now(),
+            chat: Chat::Group(GroupChat {
+                id: -40,
+                title: "test".to_owned(),
+                photo: None,
+                description: None,
+                invite_link: None,
+                pinned_message: None,
+                permissions: None,
+                has_protected_content: None,
+                message_auto_delete_time: None,
+            }),
+            sender_chat: None,
+            forward_data: None,
+            forward_origin: None,
+            reply_to_message: None,
+            via_bot: None,
+            edit_date: None,
+            author_signature: None,
+            message_thread_id: None,
+            is_topic_message: false,
+            connected_website: None,
+            passport_data: None,
+            reply_markup: None,
+            content: MessageContent::Text {
+                content: "/meme".to_owned(),
+                entities: vec![MessageEntity::BotCommand(TextBlock {
+                    offset: 0,
+                    length: 5,
+                })],
+            },
+        }),
+    }
+}
+
+#[tokio::test]
+async fn test_command_cooldowns() {
+    let clock = MockClock::new();
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&meme_COMMAND);
+    fr.set_cooldown_handler(on_throttled)
+        .set_cooldown_storage(Arc::new(InMemoryCooldownStorage::with_clock(clock.clone())));
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    let updates = [(1, 1, 0), (2, 1, 10), (3, 2, 0), (4, 3, 5), (5, 1, 25)];
+    for (message_id, user_id, advance) in updates {
+        clock.advance(advance);
+        c.fire_handlers(meme_update(message_id, user_id));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    // user 1 is throttled by the user cooldown, user 3 by the chat cooldown
+    // and user 1 again by the chat cooldown after the user cooldown expired
+    assert_eq!(MEME_B.load(Ordering::Relaxed), 2);
+    assert_eq!(*THROTTLED_B.lock(), vec![
+        (2, Duration::from_secs(20)),
+        (4, Duration::from_secs(45)),
+        (5, Duration::from_secs(20)),
+    ]);
+}
@@ -0,0 +1,76 @@
+use telexide::{
+    model::Message,
+    utils::deep_link::{
+        build_start_link,
+        decode_payload,
+        encode_payload,
+        is_valid_payload,
+        parse_start_payload,
+        MAX_START_PAYLOAD_LENGTH,
+    },
+};
+
+fn text_message(text: &str) -> Message {
+    serde_json::from_value(serde_json::json!({
+        "message_id": 1,
+        "date": 0,
+        "chat": {"id": 40, "type": "private"},
+        "text": text,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn build_start_links() -> telexide::Result<()> {
+    assert_eq!(
+        build_start_link("my_bot", "ref_42-a")?,
+        "https://t.me/my_bot?start=ref_42-a"
+    );
+    assert_eq!(
+        build_start_link("@my_bot", "x")?,
+        "https://t.me/my_bot?start=x"
+    );
+
+    assert!(build_start_link("my_bot", "").is_err());
+    assert!(build_start_link("my_bot", "ref=42").is_err());
+    assert!(build_start_link("my_bot", "with space").is_err());
+    assert!(build_start_link("my_bot", "café").is_err());
+    assert!(build_start_link("my_bot", &"a".repeat(MAX_START_PAYLOAD_LENGTH + 1)).is_err());
+    assert!(is_valid_payload(&"a".repeat(MAX_START_PAYLOAD_LENGTH)));
+    Ok(())
+}
+
+#[test]
+fn parse_start_payloads() {
+    assert_eq!(
+        parse_start_payload(&text_message("/start ref_42")).as_deref(),
+        Some("ref_42")
+    );
+    assert_eq!(
+        parse_start_payload(&text_message("/start@my_bot abc-1")).as_deref(),
+        Some("abc-1")
+    );
+
+    assert_eq!(parse_start_payload(&text_message("/start")), None);
+    assert_eq!(parse_start_payload(&text_message("/start ref=42")), None);
+    assert_eq!(parse_start_payload(&text_message("/help ref_42")), None);
+    assert_eq!(parse_start_payload(&text_message("/starter ref_42")), None);
+}
+
+#[test]
+fn encode_and_decode_payloads() -> telexide::Result<()> {
+    for data in [&b"f"[..], b"fo", b"foo", b"ref=42", &[0xfb, 0xff, 0xbf]] {
+        let payload = encode_payload(data)?;
+        assert!(is_valid_payload(&payload), "{} isn't valid", payload);
+        assert_eq!(decode_payload(&payload).as_deref(), Some(data));
+    }
+    assert_eq!(encode_payload(&[0xfb, 0xff, 0xbf])?, "-_-_");
+
+    assert!(encode_payload(&[0; 48]).is_ok());
+    assert!(encode_payload(&[0; 49]).is_err());
+    assert!(encode_payload(&[]).is_err());
+
+    assert_eq!(decode_payload("a"), None);
+    assert_eq!(decode_payload("ab+/"), None);
+    Ok(())
+}
@@ -0,0 +1,213 @@
+mod common;
+
+use common::{command_update, text_update};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use telexide::{
+    client::{ClientBuilder, Context},
+    framework::{
+        Clock,
+        CommandResult,
+        Dialogue,
+        DialogueKey,
+        DialogueStorage,
+        ExpiringDialogueStorage,
+        Framework,
+        InMemoryDialogueStorage,
+        JsonFileDialogueStorage,
+    },
+    macros::{command, prepare_listener},
+    model::{Message, MessageContent},
+    Result,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+enum Registration {
+    AskName,
+    AskAge { name: String },
+    Confirm { name: String, age: u8 },
+}
+
+fn message_text(message: &Message) -> String {
+    match &message.content {
+        MessageContent::Text {
+            content, ..
+        } => content.clone(),
+        _ => String::new(),
+    }
+}
+
+static REGISTERED: Mutex<Vec<(String, u8)>> = parking_lot::const_mutex(Vec::new());
+
+#[command(description = "starts the registration")]
+async fn register(c: Context, m: Message) -> CommandResult {
+    c.dialogue::<Registration>(&m)
+        .unwrap()
+        .enter(Registration::AskName)
+        .await?;
+    Ok(())
+}
+
+#[prepare_listener]
+async fn ask_name(_c: Context, m: Message, d: Dialogue<Registration>, _s: Registration) {
+    d.update(Registration::AskAge {
+        name: message_text(&m),
+    })
+    .await
+    .unwrap();
+}
+
+#[prepare_listener]
+async fn ask_age(_c: Context, m: Message, d: Dialogue<Registration>, s: Registration) {
+    if let (Registration::AskAge { name }, Ok(age)) = (s, message_text(&m).parse()) {
+        d.update(Registration::Confirm { name, age }).await.unwrap();
+    }
+}
+
+#[prepare_listener]
+async fn confirm(_c: Context, m: Message, d: Dialogue<Registration>, s: Registration) {
+    if let Registration::Confirm { name, age } = s {
+        if message_text(&m) == "yes" {
+            REGISTERED.lock().push((name, age));
+        }
+    }
+    d.exit().await.unwrap();
+}
+
+#[tokio::test]
+async fn registration_dialogue() -> Result<()> {
+    let storage = Arc::new(InMemoryDialogueStorage::new());
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&register_COMMAND);
+    fr.set_dialogue_storage(storage.clone())
+        .add_dialogue_handler::<Registration>("AskName", ask_name)
+        .add_dialogue_handler::<Registration>("AskAge", ask_age)
+        .add_dialogue_handler::<Registration>("Confirm", confirm);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    let key = DialogueKey {
+        chat_id: 40,
+        user_id: 1,
+    };
+    let updates = vec![
+        (text_update(1, "ignored"), None),
+        (command_update(2, "/register"), Some(Registration::AskName)),
+        (text_update(3, "alice"), Some(Registration::AskAge {
+            name: "alice".to_owned(),
+        })),
+        (text_update(4, "old"), Some(Registration::AskAge {
+            name: "alice".to_owned(),
+        })),
+        (text_update(5, "30"), Some(Registration::Confirm {
+            name: "alice".to_owned(),
+            age: 30,
+        })),
+        (text_update(6, "yes"), None),
+    ];
+
+    for (update, expected) in updates {
+        c.fire_handlers(update);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let state = storage.get_state(key).await?;
+        assert_eq!(state, expected.map(|s| serde_json::to_value(s).unwrap()));
+    }
+
+    assert_eq!(*REGISTERED.lock(), vec![("alice".to_owned(), 30)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_file_dialogue_storage() -> Result<()> {
+    let path =
+        std::env::temp_dir().join(format!("telexide_dialogues_{}.json", std::process::id()));
+    let key = DialogueKey {
+        chat_id: 1,
+        user_id: 2,
+    };
+
+    let storage = Arc::new(JsonFileDialogueStorage::new(&path)?);
+    let dialogue = Dialogue::<Registration>::new(key, storage);
+    assert!(dialogue.update(Registration::AskName).await.is_err());
+    dialogue
+        .enter(Registration::AskAge {
+            name: "bob".to_owned(),
+        })
+        .await?;
+    // the file is written to a temporary file first, which replaces it
+    let mut temp_path = path.clone().into_os_string();
+    temp_path.push(".tmp");
+    assert!(!std::path::Path::new(&temp_path).exists());
+
+    let storage = Arc::new(JsonFileDialogueStorage::new(&path)?);
+    let reloaded = Dialogue::<Registration>::new(key, storage);
+    assert_eq!(
+        reloaded.get().await?,
+        Some(Registration::AskAge {
+            name: "bob".to_owned(),
+        })
+    );
+
+    reloaded.exit().await?;
+    assert!(!reloaded.is_active().await?);
+    assert_eq!(
+        JsonFileDialogueStorage::new(&path)?.get_state(key).await?,
+        None
+    );
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+struct MockClock(parking_lot::Mutex<Instant>);
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        *self.0.lock()
+    }
+}
+
+#[tokio::test]
+async fn dialogues_time_out() -> Result<()> {
+    let clock = Arc::new(MockClock(parking_lot::Mutex::new(Instant::now())));
+    let advance = |secs| *clock.0.lock() += Duration::from_secs(secs);
+    let inner = Arc::new(InMemoryDialogueStorage::new());
+    let storage = Arc::new(ExpiringDialogueStorage::with_clock(
+        inner.clone(),
+        Duration::from_secs(60),
+        clock.clone(),
+    ));
+
+    let key = DialogueKey {
+        chat_id: 1,
+        user_id: 2,
+    };
+    let dialogue = Dialogue::<Registration>::new(key, storage.clone());
+    dialogue.enter(Registration::AskName).await?;
+
+    advance(59);
+    assert_eq!(dialogue.get().await?, Some(Registration::AskName));
+
+    // changing the state restarts the timeout
+    dialogue
+        .update(Registration::AskAge {
+            name: "bob".to_owned(),
+        })
+        .await?;
+    advance(59);
+    assert!(dialogue.is_active().await?);
+
+    advance(1);
+    assert_eq!(dialogue.get().await?, None);
+    assert_eq!(inner.get_state(key).await?, None);
+    assert!(dialogue.update(Registration::AskName).await.is_err());
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use parking_lot::Mutex;
+use std::{sync::Arc, time::Duration};
+use telexide::{
+    client::{ClientBuilder, Context},
+    framework::{Filter, Framework},
+    macros::prepare_listener,
+    model::{
+        Chat,
+        ChatType,
+        ContentKind,
+        Message,
+        MessageContent,
+        PrivateChat,
+        SuperGroupChat,
+        Update,
+        UpdateContent,
+        User,
+    },
+};
+
+fn message(chat: Chat, user_id: i64, text: &str) -> Message {
+    Message {
+        message_id: 1,
+        from: Some(User {
+            id: user_id,
+            is_bot: false,
+            first_name: "test".to_owned(),
+            last_name: None,
+            username: None,
+            language_code: None,
+            can_join_groups: None,
+            can_read_all_group_messages: None,
+            supports_inline_queries: None,
+            is_premium: None,
+            added_to_attachment_menu: None,
+        }),
+        date: chrono::offset::Utc::now(),
+        chat,
+        sender_chat: None,
+        forward_data: None,
+        forward_origin: None,
+        reply_to_message: None,
+        via_bot: None,
+        edit_date: None,
+        author_signature: None,
+        message_thread_id: None,
+        is_topic_message: false,
+        connected_website: None,
+        passport_data: None,
+        reply_markup: None,
+        content: MessageContent::Text {
+            content: text.to_owned(),
+            entities: Vec::new(),
+        },
+    }
+}
+
+fn supergroup() -> Chat {
+    Chat::SuperGroup(SuperGroupChat {
+        id: -100,
+        title: "test".to_owned(),
+        username: None,
+        is_forum: None,
+        photo: None,
+        description: None,
+        invite_link: None,
+        pinned_message: None,
+        permissions: None,
+        slow_mode_delay: None,
+        sticker_set_name: None,
+        can_set_sticker_set: None,
+        linked_chat_id: None,
+        location: None,
+        join_to_send_messages: None,
+        join_by_request: None,
+        has_protected_content: None,
+        message_auto_delete_time: None,
+        active_usernames: None,
+    })
+}
+
+fn private() -> Chat {
+    Chat::Private(PrivateChat {
+        id: 40,
+        username: None,
+        first_name: None,
+        bio: None,
+        last_name: None,
+        photo: None,
+        has_private_forwards: None,
+        has_restricted_voice_and_video_messages: None,
+        message_auto_delete_time: None,
+        active_usernames: None,
+    })
+}
+
+static CHECKED: Mutex<Vec<&str>> = parking_lot::const_mutex(Vec::new());
+
+fn in_supergroup(m: &Message) -> bool {
+    CHECKED.lock().push("chat");
+    m.chat.get_type() == ChatType::SuperGroup
+}
+
+fn is_text(m: &Message) -> bool {
+    CHECKED.lock().push("content");
+    m.content.kind() == ContentKind::Text
+}
+
+fn from_admin(m: &Message) -> bool {
+    CHECKED.lock().push("user");
+    m.from.as_ref().is_some_and(|u| u.id == 1)
+}
+
+#[test]
+fn combined_filters_short_circuit() {
+    let filter = Filter::custom(in_supergroup)
+        .and(Filter::custom(is_text))
+        .and(Filter::custom(from_admin).not());
+
+    let cases = [
+        (message(private(), 2, "hi"), false, vec!["chat"]),
+        (message(supergroup(), 1, "hi"), false, vec!["chat", "content", "user"]),
+        (message(supergroup(), 2, "hi"), true, vec!["chat", "content", "user"]),
+    ];
+    for (m, expected, checked) in cases {
+        CHECKED.lock().clear();
+        assert_eq!(filter.matches(&m), expected);
+        assert_eq!(*CHECKED.lock(), checked);
+    }
+
+    let filter = Filter::custom(in_supergroup)
+        .or(Filter::custom(is_text))
+        .or(Filter::custom(from_admin));
+    CHECKED.lock().clear();
+    assert!(filter.matches(&message(supergroup(), 2, "hi")));
+    assert_eq!(*CHECKED.lock(), vec!["chat"]);
+}
+
+#[test]
+fn builtin_filters() -> telexide::Result<()> {
+    let filter = Filter::chat_type(ChatType::SuperGroup)
+        .and(Filter::text_matches(r"^!(ban|kick)\b")?)
+        .and(Filter::from_users([1, 2]));
+
+    assert!(filter.matches(&message(supergroup(), 1, "!ban spammer")));
+    assert!(!filter.matches(&message(supergroup(), 3, "!ban spammer")));
+    assert!(!filter.matches(&message(supergroup(), 2, "!banana")));
+    assert!(!filter.matches(&message(private(), 1, "!kick")));
+    assert!(!Filter::has_content(ContentKind::Photo).matches(&message(private(), 1, "hi")));
+    assert!(Filter::text_matches("(unclosed").is_err());
+    Ok(())
+}
+
+static HANDLED: Mutex<Vec<&str>> = parking_lot::const_mutex(Vec::new());
+
+#[prepare_listener]
+async fn on_client_message(_c: Context, _m: Message) {
+    HANDLED.lock().push("client");
+}
+
+#[prepare_listener]
+async fn on_framework_message(_c: Context, _m: Message) {
+    HANDLED.lock().push("framework");
+}
+
+#[tokio::test]
+async fn filtered_message_handlers() -> telexide::Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_message_handler(Filter::text_matches("hello")?, on_framework_message);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .add_message_handler(
+            Filter::chat_type(ChatType::Private).not(),
+            on_client_message,
+        )
+        .build();
+
+    for (chat, text) in [(private(), "hello"), (supergroup(), "bye"), (private(), "bye")] {
+        c.fire_handlers(Update {
+            update_id: 1,
+            raw_json: None,
+            content: UpdateContent::Message(message(chat, 1, text)),
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    assert_eq!(*HANDLED.lock(), vec!["framework", "client"]);
+    Ok(())
+}
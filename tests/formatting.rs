@@ -0,0 +1,214 @@
+use telexide::{
+    api::types::SendMessage,
+    model::{MessageEntity, ParseMode, TextBlock, TextLink, TextMention, User},
+    utils::{escape_html, escape_markdown, escape_markdown_v2, FormattedTextBuilder},
+};
+
+const MARKDOWN_V2_RESERVED: &str = "_*[]()~`>#+-=|{}.!\\";
+const MARKDOWN_RESERVED: &str = "_*`[";
+const ALPHABET: &[char] = &[
+    'a', 'Z', '0', ' ', '\n', '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|',
+    '{', '}', '.', '!', '\\', '<', '&', '"', 'é', '你', '👋', '🏳',
+];
+
+/// a small xorshift generator, so the generated strings are reproducible
+fn random_strings(count: usize) -> Vec<String> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    (0..count)
+        .map(|_| {
+            let len = (next() % 40) as usize;
+            (0..len)
+                .map(|_| ALPHABET[(next() % ALPHABET.len() as u64) as usize])
+                .collect()
+        })
+        .collect()
+}
+
+/// parses escaped `MarkdownV2` text, returning `None` if a reserved character
+/// is not escaped
+fn unescape_markdown_v2(text: &str) -> Option<String> {
+    let mut res = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            res.push(chars.next()?);
+        } else if MARKDOWN_V2_RESERVED.contains(c) {
+            return None;
+        } else {
+            res.push(c);
+        }
+    }
+    Some(res)
+}
+
+/// parses escaped legacy `Markdown` text, returning `None` if a reserved
+/// character is not escaped. Backslashes before other characters are kept.
+fn unescape_markdown(text: &str) -> Option<String> {
+    let mut res = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(&next) if c == '\\' && MARKDOWN_RESERVED.contains(next) => {
+                res.push(next);
+                chars.next();
+            },
+            _ if MARKDOWN_RESERVED.contains(c) => return None,
+            _ => res.push(c),
+        }
+    }
+    Some(res)
+}
+
+fn unescape_html(text: &str) -> Option<String> {
+    if text.contains('<') || text.contains('>') || text.contains('"') {
+        return None;
+    }
+
+    Some(
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&amp;", "&"),
+    )
+}
+
+#[test]
+fn escaped_markdown_v2_round_trips() {
+    for s in random_strings(500) {
+        assert_eq!(unescape_markdown_v2(&escape_markdown_v2(&s)), Some(s));
+    }
+}
+
+#[test]
+fn escaped_markdown_round_trips() {
+    for s in random_strings(500) {
+        assert_eq!(unescape_markdown(&escape_markdown(&s)), Some(s));
+    }
+    assert_eq!(escape_markdown("a_b*c`d[e]f.g"), "a\\_b\\*c\\`d\\[e]f.g");
+}
+
+#[test]
+#[allow(deprecated)]
+fn parse_mode_default_and_legacy_markdown() {
+    assert_eq!(ParseMode::default(), ParseMode::HTML);
+    assert_eq!(serde_json::to_value(ParseMode::Markdown).unwrap(), "Markdown");
+    assert_eq!(
+        serde_json::from_value::<ParseMode>(serde_json::json!("Markdown")).unwrap(),
+        ParseMode::Markdown
+    );
+}
+
+#[test]
+fn escaped_html_round_trips() {
+    for s in random_strings(500) {
+        assert_eq!(unescape_html(&escape_html(&s)), Some(s));
+    }
+}
+
+#[test]
+fn builder_outputs_formatting() {
+    let mut builder = FormattedTextBuilder::new();
+    builder
+        .text("👋 hi ")
+        .bold("there!")
+        .text(" see ")
+        .link("the docs", "https://core.telegram.org/bots/api#(x)")
+        .code("a`b");
+
+    assert_eq!(
+        builder.to_markdown_v2(),
+        (
+            "👋 hi *there\\!* see [the docs](https://core.telegram.org/bots/api#(x\\))`a\\`b`"
+                .to_owned(),
+            ParseMode::MarkdownV2
+        )
+    );
+
+    assert_eq!(
+        builder.to_html(),
+        (
+            "👋 hi <b>there!</b> see <a href=\"https://core.telegram.org/bots/api#(x)\">the \
+             docs</a><code>a`b</code>"
+                .to_owned(),
+            ParseMode::HTML
+        )
+    );
+
+    let (text, entities) = builder.to_entities();
+    assert_eq!(text, "👋 hi there! see the docsa`b");
+    assert_eq!(entities, vec![
+        MessageEntity::Bold(TextBlock {
+            offset: 6,
+            length: 6,
+        }),
+        MessageEntity::TextLink(TextLink {
+            text_block: TextBlock {
+                offset: 17,
+                length: 8,
+            },
+            url: "https://core.telegram.org/bots/api#(x)".to_owned(),
+        }),
+        MessageEntity::Code(TextBlock {
+            offset: 25,
+            length: 3,
+        }),
+    ]);
+
+    for entity in &entities {
+        assert!(entity.get_text(&text).is_some());
+    }
+    assert_eq!(entities[0].get_text(&text), Some("there!".to_owned()));
+}
+
+#[test]
+fn send_message_with_entities() {
+    let user = User {
+        id: 7,
+        is_bot: false,
+        first_name: "Zoë".to_owned(),
+        last_name: Some("🦀".to_owned()),
+        username: None,
+        language_code: None,
+        can_join_groups: None,
+        can_read_all_group_messages: None,
+        supports_inline_queries: None,
+        is_premium: None,
+        added_to_attachment_menu: None,
+    };
+
+    let mut text = FormattedTextBuilder::new();
+    text.text("🏳 hey ").mention_user(&user).italic("!");
+
+    let message = SendMessage::new_formatted(10, &text);
+    assert_eq!(message.text, "🏳 hey Zoë 🦀!");
+    assert_eq!(message.parse_mode, None);
+    assert_eq!(
+        message.entities,
+        Some(vec![
+            MessageEntity::TextMention(TextMention {
+                text_block: TextBlock {
+                    offset: 7,
+                    length: 6,
+                },
+                user,
+            }),
+            MessageEntity::Italic(TextBlock {
+                offset: 13,
+                length: 1,
+            }),
+        ])
+    );
+
+    let built = SendMessage::builder(10, "").formatted(&text).build();
+    assert_eq!(built, message);
+
+    let plain = SendMessage::new_formatted(10, FormattedTextBuilder::new().text("plain"));
+    assert_eq!(plain.entities, None);
+}
@@ -0,0 +1,1077 @@
+mod common;
+
+use common::{command_update, text_update};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use telexide::{
+    api::{APIEndpoint, Response, API},
+    client::{ClientBuilder, Context, MessageKind},
+    framework::{CheckResult, CommandResult, CommandStatus, Framework},
+    macros::{command, prepare_check, prepare_listener, FromCommandArguments},
+    model::{
+        CallbackQuery,
+        ChannelChat,
+        Chat,
+        ChosenInlineResult,
+        GroupChat,
+        InlineQuery,
+        Message,
+        Update,
+        UpdateContent,
+        User,
+    },
+    utils::{CallbackData, FormDataFile},
+    Result,
+};
+
+static PREFIX_B: AtomicUsize = AtomicUsize::new(0);
+
+#[command(description = "testing prefixes")]
+async fn prefixed(_c: Context, m: Message) -> CommandResult {
+    PREFIX_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_command_prefixes_and_case() -> Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&prefixed_COMMAND);
+    fr.set_prefixes(vec!["/", "!"]).set_case_insensitive(true);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    c.fire_handlers(command_update(1, "/prefixed"));
+    c.fire_handlers(text_update(2, "!PreFixed some args"));
+    c.fire_handlers(command_update(4, "/PREFIXED@Test_Bot"));
+    c.fire_handlers(command_update(100, "/prefixed@other_bot"));
+    c.fire_handlers(text_update(100, "?prefixed"));
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(PREFIX_B.load(Ordering::Relaxed), 7);
+    Ok(())
+}
+
+static CASE_B: AtomicUsize = AtomicUsize::new(0);
+
+#[command(description = "testing case sensitivity")]
+async fn sensitive(_c: Context, m: Message) -> CommandResult {
+    CASE_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_command_case_sensitive_by_default() -> Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&sensitive_COMMAND);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    c.fire_handlers(command_update(1, "/Sensitive"));
+    c.fire_handlers(text_update(100, "!sensitive"));
+    c.fire_handlers(command_update(2, "/sensitive@test_bot"));
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(CASE_B.load(Ordering::Relaxed), 2);
+    Ok(())
+}
+
+static CHECKED_B: AtomicUsize = AtomicUsize::new(0);
+static CHECK_FAILED_B: AtomicUsize = AtomicUsize::new(0);
+
+#[prepare_check]
+async fn is_even(_c: Context, m: Message) -> bool {
+    m.message_id % 2 == 0
+}
+
+#[prepare_check]
+async fn is_small(_c: Context, m: Message) -> CheckResult {
+    if m.message_id < 100 {
+        CheckResult::Success
+    } else {
+        CheckResult::failure("too big")
+    }
+}
+
+#[prepare_listener]
+async fn check_failed(_c: Context, m: Message, reason: Option<String>) {
+    if reason.is_some() {
+        CHECK_FAILED_B.fetch_add(m.message_id as usize * 10, Ordering::Acquire);
+    } else {
+        CHECK_FAILED_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+    }
+}
+
+#[command(description = "testing checks")]
+#[check(is_even, is_small)]
+async fn checked(_c: Context, m: Message) -> CommandResult {
+    CHECKED_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_command_checks() -> Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&checked_COMMAND);
+    fr.set_check_failure_handler(check_failed);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    c.fire_handlers(command_update(2, "/checked"));
+    c.fire_handlers(command_update(3, "/checked"));
+    c.fire_handlers(command_update(200, "/checked"));
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(CHECKED_B.load(Ordering::Relaxed), 2);
+    assert_eq!(CHECK_FAILED_B.load(Ordering::Relaxed), 2003);
+    Ok(())
+}
+
+static COOLDOWN_B: AtomicUsize = AtomicUsize::new(0);
+static ON_COOLDOWN_B: AtomicUsize = AtomicUsize::new(0);
+
+#[prepare_listener]
+async fn on_cooldown(_c: Context, m: Message, remaining: Duration) {
+    if remaining > Duration::from_secs(50) && remaining <= Duration::from_secs(60) {
+        ON_COOLDOWN_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+    }
+}
+
+#[command(description = "testing cooldowns")]
+#[cooldown(60, chat)]
+async fn cooled(_c: Context, m: Message) -> CommandResult {
+    COOLDOWN_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_command_cooldown() -> Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&cooled_COMMAND);
+    fr.set_cooldown_handler(on_cooldown);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    c.fire_handlers(command_update(1, "/cooled"));
+    tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+    c.fire_handlers(command_update(2, "/cooled"));
+    c.fire_handlers(command_update(4, "/cooled"));
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(COOLDOWN_B.load(Ordering::Relaxed), 1);
+    assert_eq!(ON_COOLDOWN_B.load(Ordering::Relaxed), 6);
+    Ok(())
+}
+
+struct Page(usize);
+
+impl CallbackData for Page {
+    const PREFIX: &'static str = "page";
+
+    fn to_fields(&self) -> Vec<String> {
+        vec![self.0.to_string()]
+    }
+
+    fn from_fields(fields: &[&str]) -> Option<Self> {
+        match fields {
+            [page] => Some(Page(page.parse().ok()?)),
+            _ => None,
+        }
+    }
+}
+
+static PAGE_B: AtomicUsize = AtomicUsize::new(0);
+
+#[prepare_listener]
+async fn on_page(_c: Context, _q: CallbackQuery, page: Page) {
+    PAGE_B.fetch_add(page.0, Ordering::Acquire);
+}
+
+fn callback_update(data: &str) -> Update {
+    Update {
+        update_id: 10,
+        raw_json: None,
+        content: UpdateContent::CallbackQuery(
+            serde_json::from_value(serde_json::json!({
+                "id": "query",
+                "from": {"id": 3, "is_bot": false, "first_name": "test"},
+                "chat_instance": "instance",
+                "data": data,
+            }))
+            .unwrap(),
+        ),
+    }
+}
+
+#[tokio::test]
+async fn test_callback_handlers() -> Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_callback_handler(on_page);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    c.fire_handlers(callback_update(&Page(3).encode()?));
+    c.fire_handlers(callback_update("page:invalid"));
+    c.fire_handlers(callback_update("other:4"));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(PAGE_B.load(Ordering::Relaxed), 3);
+    Ok(())
+}
+
+struct MockAPI {
+    sent: Arc<Mutex<Vec<Value>>>,
+}
+
+#[async_trait::async_trait]
+impl API for MockAPI {
+    async fn get(&self, endpoint: APIEndpoint, data: Option<Value>) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(&self, _endpoint: APIEndpoint, data: Option<Value>) -> Result<Response> {
+        self.sent.lock().push(data.unwrap_or_default());
+        Ok(Response {
+            ok: true,
+            description: None,
+            result: Some(serde_json::value::to_raw_value(&json!({
+                "message_id": 1,
+                "date": 0,
+                "chat": {"id": 40, "type": "private"},
+                "text": "reply",
+            }))?),
+        })
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+}
+
+#[derive(FromCommandArguments)]
+struct AddArgs {
+    amount: usize,
+    note: Option<String>,
+}
+
+static ADD_B: AtomicUsize = AtomicUsize::new(0);
+
+#[command(description = "testing arguments")]
+async fn add(_c: Context, _m: Message, args: AddArgs) -> CommandResult {
+    ADD_B.fetch_add(args.amount, Ordering::Acquire);
+    if args.note.as_deref() == Some("two words") {
+        ADD_B.fetch_add(100, Ordering::Acquire);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_command_arguments() -> Result<()> {
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&add_COMMAND);
+    fr.add_prefix("!");
+
+    let c = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(MockAPI {
+            sent: sent.clone(),
+        })))
+        .set_framework(Arc::new(fr))
+        .build();
+
+    c.fire_handlers(command_update(1, "/add 2"));
+    c.fire_handlers(command_update(2, "/add@test_bot 3 \"two words\""));
+    c.fire_handlers(text_update(3, "!add 5"));
+    c.fire_handlers(command_update(4, "/add five"));
+    c.fire_handlers(command_update(5, "/add"));
+    c.fire_handlers(text_update(6, "!add six"));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(ADD_B.load(Ordering::Relaxed), 110);
+
+    // the usage is shown with the prefix the command was called with
+    let mut usages: Vec<_> = sent
+        .lock()
+        .iter()
+        .map(|reply| reply["text"].as_str().unwrap().lines().last().unwrap().to_owned())
+        .collect();
+    usages.sort();
+    assert_eq!(usages, vec![
+        "usage: !add <amount> [note]",
+        "usage: /add <amount> [note]",
+        "usage: /add <amount> [note]",
+    ]);
+    Ok(())
+}
+
+static SUB_B: Mutex<Vec<String>> = parking_lot::const_mutex(Vec::new());
+
+#[command(description = "lists the entries")]
+async fn list(c: Context, _m: Message) -> CommandResult {
+    SUB_B.lock().push(format!("list {}", c.args().raw()));
+    Ok(())
+}
+
+#[command(description = "bans a user")]
+async fn ban(c: Context, _m: Message) -> CommandResult {
+    SUB_B.lock().push(format!("ban {}", c.args().raw()));
+    Ok(())
+}
+
+#[command(description = "manages bans")]
+#[subcommands(list)]
+async fn bans(c: Context, _m: Message) -> CommandResult {
+    SUB_B.lock().push(format!("bans {}", c.args().raw()));
+    Ok(())
+}
+
+#[command(description = "admin commands")]
+#[subcommands(ban, bans)]
+async fn admin(c: Context, _m: Message) -> CommandResult {
+    SUB_B.lock().push(format!("admin {}", c.args().raw()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subcommands() -> Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&admin_COMMAND);
+    assert_eq!(fr.get_commands().len(), 1);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    let calls = vec![
+        ("/admin ban 123", "ban 123"),
+        ("/admin bans list all", "list all"),
+        ("/admin bans unknown", "bans unknown"),
+        ("/admin unknown 1", "admin unknown 1"),
+    ];
+
+    for (text, _) in &calls {
+        c.fire_handlers(command_update(1, text));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    let expected: Vec<&str> = calls.iter().map(|(_, e)| *e).collect();
+    assert_eq!(*SUB_B.lock(), expected);
+    Ok(())
+}
+
+struct MemberAPI {
+    member_requests: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl API for MemberAPI {
+    async fn get(&self, endpoint: APIEndpoint, data: Option<Value>) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(&self, _endpoint: APIEndpoint, data: Option<Value>) -> Result<Response> {
+        self.member_requests.fetch_add(1, Ordering::Acquire);
+        let user_id = data.unwrap_or_default()["user_id"].as_i64().unwrap_or_default();
+        let status = if user_id % 2 == 0 { "administrator" } else { "member" };
+        Ok(Response {
+            ok: true,
+            description: None,
+            result: Some(serde_json::value::to_raw_value(&json!({
+                "status": status,
+                "user": {"id": user_id, "is_bot": false, "first_name": "test"},
+            }))?),
+        })
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+}
+
+fn guarded_update(message_id: i64, text: &str, user_id: Option<i64>, in_group: bool) -> Update {
+    let mut update = command_update(message_id, text);
+    if let UpdateContent::Message(ref mut message) = update.content {
+        message.from = user_id.map(|id| User {
+            id,
+            is_bot: false,
+            first_name: "test".to_owned(),
+            last_name: None,
+            username: None,
+            language_code: None,
+            can_join_groups: None,
+            can_read_all_group_messages: None,
+            supports_inline_queries: None,
+            is_premium: None,
+            added_to_attachment_menu: None,
+        });
+        if in_group {
+            message.chat = Chat::Group(GroupChat {
+                id: -50,
+                title: "group".to_owned(),
+                photo: None,
+                description: None,
+                invite_link: None,
+                pinned_message: None,
+                permissions: None,
+                has_protected_content: None,
+                message_auto_delete_time: None,
+            });
+            if user_id.is_none() {
+                message.sender_chat = Some(message.chat.clone());
+            }
+        }
+    }
+    update
+}
+
+static GUARDED_B: Mutex<Vec<i64>> = parking_lot::const_mutex(Vec::new());
+static GUARD_FAILED_B: Mutex<Vec<(i64, String)>> = parking_lot::const_mutex(Vec::new());
+
+#[prepare_listener]
+async fn guard_failed(_c: Context, m: Message, reason: Option<String>) {
+    GUARD_FAILED_B
+        .lock()
+        .push((m.message_id, reason.unwrap_or_default()));
+}
+
+#[command(description = "testing owner only")]
+#[owner_only]
+async fn owned(_c: Context, m: Message) -> CommandResult {
+    GUARDED_B.lock().push(m.message_id);
+    Ok(())
+}
+
+#[command(description = "testing group admin guards")]
+#[only_in(groups)]
+#[required_permissions(admin)]
+async fn moderate(_c: Context, m: Message) -> CommandResult {
+    GUARDED_B.lock().push(m.message_id);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_command_guards() -> Result<()> {
+    let member_requests = Arc::new(AtomicUsize::new(0));
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&owned_COMMAND);
+    fr.add_command(&moderate_COMMAND);
+    fr.set_owners(vec![7])
+        .set_chat_member_cache_duration(Duration::from_secs(60))
+        .set_check_failure_handler(guard_failed);
+
+    let c = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(MemberAPI {
+            member_requests: member_requests.clone(),
+        })))
+        .set_framework(Arc::new(fr))
+        .build();
+
+    let updates = vec![
+        guarded_update(1, "/owned", Some(7), false),
+        guarded_update(2, "/owned", Some(8), false),
+        guarded_update(3, "/moderate", Some(4), false),
+        guarded_update(4, "/moderate", Some(4), true),
+        guarded_update(5, "/moderate", Some(4), true),
+        guarded_update(6, "/moderate", Some(5), true),
+        guarded_update(7, "/moderate", None, true),
+    ];
+    for update in updates {
+        c.fire_handlers(update);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    assert_eq!(*GUARDED_B.lock(), vec![1, 4, 5, 7]);
+    assert_eq!(*GUARD_FAILED_B.lock(), vec![
+        (2, "this command can only be used by the owners of the bot".to_owned()),
+        (3, "this command can only be used in groups".to_owned()),
+        (6, "this command can only be used by admins of the chat".to_owned()),
+    ]);
+    assert_eq!(member_requests.load(Ordering::Relaxed), 2);
+    Ok(())
+}
+
+#[command(description = "starts the bot")]
+async fn start(_c: Context, _m: Message) -> CommandResult {
+    Ok(())
+}
+
+#[derive(FromCommandArguments)]
+struct KickArgs {
+    user: i64,
+    reason: Option<String>,
+}
+
+static KICK_B: Mutex<Vec<(i64, Option<String>)>> = parking_lot::const_mutex(Vec::new());
+
+#[command(description = "kicks the user", category = "Moderation")]
+async fn kick(_c: Context, _m: Message, args: KickArgs) -> CommandResult {
+    KICK_B.lock().push((args.user, args.reason));
+    Ok(())
+}
+
+#[command(description = "a secret command", category = "Moderation")]
+#[owner_only]
+async fn secret(_c: Context, _m: Message) -> CommandResult {
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_help_command() -> Result<()> {
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&kick_COMMAND);
+    fr.add_command(&start_COMMAND);
+    fr.add_command(&secret_COMMAND);
+    fr.with_help();
+
+    let c = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(MockAPI {
+            sent: sent.clone(),
+        })))
+        .set_framework(Arc::new(fr))
+        .build();
+
+    for text in &["/help", "/help kick", "/help secret", "/kick 42 spamming", "/kick 7"] {
+        c.fire_handlers(command_update(1, text));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    let texts: Vec<_> = sent.lock().iter().map(|m| m["text"].clone()).collect();
+    assert_eq!(texts, vec![
+        json!(
+            "Commands\n/start - starts the bot\n\nModeration\n/kick - kicks the user\n\nUse \
+             /help <command> for more information about a command"
+        ),
+        json!("/kick\nkicks the user\n\nUsage: /kick <user> [reason]\nCategory: Moderation"),
+        json!("There is no command named secret"),
+    ]);
+    assert_eq!(sent.lock()[1]["entities"][0], json!({"type": "bold", "offset": 0, "length": 5}));
+    assert_eq!(*KICK_B.lock(), vec![(42, Some("spamming".to_owned())), (7, None)]);
+    Ok(())
+}
+
+static STATS_B: AtomicUsize = AtomicUsize::new(0);
+
+#[command(description = "testing aliases")]
+#[aliases("stat", "s")]
+async fn stats(_c: Context, m: Message) -> CommandResult {
+    STATS_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+    Ok(())
+}
+
+#[command(description = "testing alias collisions")]
+#[aliases("s")]
+async fn status(_c: Context, _m: Message) -> CommandResult {
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_command_aliases() -> Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&stats_COMMAND);
+    fr.add_prefix("!").set_no_prefix_in_private(true);
+    assert_eq!(fr.get_commands()[0].get_bot_command().command, "stats");
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    c.fire_handlers(command_update(1, "/stat"));
+    c.fire_handlers(text_update(2, "!s@test_bot"));
+    c.fire_handlers(text_update(4, "stats"));
+    c.fire_handlers(command_update(100, "/s@other_bot"));
+    c.fire_handlers(text_update(100, "statistics"));
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(STATS_B.load(Ordering::Relaxed), 7);
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "the name or alias \"s\" of the status command collides with the stats command")]
+fn test_command_alias_collision() {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&stats_COMMAND);
+    fr.add_command(&status_COMMAND);
+}
+
+#[command(description = "testing invalid aliases")]
+#[aliases("Stat")]
+async fn uppercase(_c: Context, _m: Message) -> CommandResult {
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "the alias \"Stat\" of the uppercase command has to be 1-32 lowercase")]
+fn test_invalid_command_alias() {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&uppercase_COMMAND);
+}
+
+static UNKNOWN_B: AtomicUsize = AtomicUsize::new(0);
+static DEFAULT_B: AtomicUsize = AtomicUsize::new(0);
+static KNOWN_B: AtomicUsize = AtomicUsize::new(0);
+
+#[prepare_listener]
+async fn on_unknown(_c: Context, m: Message) {
+    UNKNOWN_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+}
+
+#[prepare_listener]
+async fn on_default(_c: Context, m: Message) {
+    DEFAULT_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+}
+
+#[command(description = "testing fallback handlers")]
+async fn known(_c: Context, m: Message) -> CommandResult {
+    KNOWN_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fallback_handlers() -> Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&known_COMMAND);
+    fr.add_prefix("!")
+        .set_no_prefix_in_private(true)
+        .set_unknown_command_handler(on_unknown)
+        .set_default_handler(on_default)
+        .set_handle_edits(true);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    c.fire_handlers(command_update(1, "/known"));
+    c.fire_handlers(command_update(2, "/typo"));
+    c.fire_handlers(command_update(4, "/typo@test_bot"));
+    c.fire_handlers(text_update(8, "!typo"));
+    c.fire_handlers(command_update(100, "/typo@other_bot"));
+    c.fire_handlers(command_update(100, "/known@other_bot"));
+    c.fire_handlers(text_update(16, "hello there"));
+    c.fire_handlers(text_update(32, "hi@other_bot"));
+    c.fire_handlers(message_kind_update(64, MessageKind::EditedMessage));
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(KNOWN_B.load(Ordering::Relaxed), 1);
+    assert_eq!(UNKNOWN_B.load(Ordering::Relaxed), 14);
+    assert_eq!(DEFAULT_B.load(Ordering::Relaxed), 48);
+    Ok(())
+}
+
+static EDIT_B: AtomicUsize = AtomicUsize::new(0);
+static POST_B: AtomicUsize = AtomicUsize::new(0);
+
+#[command(description = "testing edits and channel posts")]
+async fn post(c: Context, m: Message) -> CommandResult {
+    let id = m.message_id as usize;
+    match c.message_kind() {
+        Some(MessageKind::ChannelPost) => POST_B.fetch_add(id, Ordering::Acquire),
+        _ if c.is_edit() => EDIT_B.fetch_add(id, Ordering::Acquire),
+        _ => 0,
+    };
+    Ok(())
+}
+
+/// rewraps the message of the update into the given kind of update, posting
+/// it in a channel for channel posts
+fn message_kind_update(message_id: i64, kind: MessageKind) -> Update {
+    let mut update = command_update(message_id, "/post");
+    let UpdateContent::Message(mut message) = update.content else {
+        unreachable!();
+    };
+
+    if let MessageKind::ChannelPost | MessageKind::EditedChannelPost = kind {
+        message.chat = Chat::Channel(ChannelChat {
+            id: -100,
+            title: "test".to_owned(),
+            username: None,
+            photo: None,
+            description: None,
+            invite_link: None,
+            pinned_message: None,
+            linked_chat_id: None,
+            has_protected_content: None,
+            message_auto_delete_time: None,
+            active_usernames: None,
+        });
+    }
+
+    update.content = match kind {
+        MessageKind::Message => UpdateContent::Message(message),
+        MessageKind::EditedMessage => UpdateContent::EditedMessage(message),
+        MessageKind::ChannelPost => UpdateContent::ChannelPost(message),
+        MessageKind::EditedChannelPost => UpdateContent::EditedChannelPost(message),
+    };
+    update
+}
+
+#[tokio::test]
+async fn test_edits_and_channel_posts() -> Result<()> {
+    let updates = |c: &telexide::Client| {
+        c.fire_handlers(message_kind_update(1, MessageKind::EditedMessage));
+        c.fire_handlers(message_kind_update(2, MessageKind::ChannelPost));
+        c.fire_handlers(message_kind_update(4, MessageKind::EditedChannelPost));
+    };
+
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&post_COMMAND);
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+    updates(&c);
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    assert_eq!(EDIT_B.load(Ordering::Relaxed), 0);
+    assert_eq!(POST_B.load(Ordering::Relaxed), 0);
+
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&post_COMMAND);
+    fr.set_handle_channel_posts(true);
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+    updates(&c);
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    assert_eq!(EDIT_B.load(Ordering::Relaxed), 0);
+    assert_eq!(POST_B.load(Ordering::Relaxed), 2);
+
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&post_COMMAND);
+    fr.set_handle_channel_posts(true).set_handle_edits(true);
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+    updates(&c);
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    assert_eq!(EDIT_B.load(Ordering::Relaxed), 5);
+    assert_eq!(POST_B.load(Ordering::Relaxed), 4);
+    Ok(())
+}
+
+static INLINE_B: Mutex<Vec<(&str, String)>> = parking_lot::const_mutex(Vec::new());
+
+#[prepare_listener]
+async fn gif_query(c: Context, q: InlineQuery) {
+    INLINE_B.lock().push(("gif", q.query.clone()));
+    c.answer_inline(&q, Vec::new()).await.ok();
+}
+
+#[prepare_listener]
+async fn gif_sticker_query(_c: Context, q: InlineQuery) {
+    INLINE_B.lock().push(("gif sticker", q.query));
+}
+
+#[prepare_listener]
+async fn any_query(_c: Context, q: InlineQuery) {
+    INLINE_B.lock().push(("", q.query));
+}
+
+#[prepare_listener]
+async fn gif_chosen(_c: Context, r: ChosenInlineResult) {
+    INLINE_B.lock().push(("chosen", r.result_id));
+}
+
+fn test_user() -> User {
+    User {
+        id: 7,
+        is_bot: false,
+        first_name: "test".to_owned(),
+        last_name: None,
+        username: None,
+        language_code: None,
+        can_join_groups: None,
+        can_read_all_group_messages: None,
+        supports_inline_queries: None,
+        is_premium: None,
+        added_to_attachment_menu: None,
+    }
+}
+
+fn inline_query_update(query: &str) -> Update {
+    Update {
+        update_id: 10,
+        raw_json: None,
+        content: UpdateContent::InlineQuery(InlineQuery {
+            id: "query".to_owned(),
+            from: test_user(),
+            location: None,
+            query: query.to_owned(),
+            offset: String::new(),
+            chat_type: None,
+        }),
+    }
+}
+
+fn chosen_inline_result_update(result_id: &str) -> Update {
+    Update {
+        update_id: 10,
+        raw_json: None,
+        content: UpdateContent::ChosenInlineResult(ChosenInlineResult {
+            result_id: result_id.to_owned(),
+            from: test_user(),
+            location: None,
+            query: String::new(),
+            inline_message_id: None,
+        }),
+    }
+}
+
+#[tokio::test]
+async fn test_inline_query_handlers() -> Result<()> {
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let mut fr = Framework::new("test_bot");
+    fr.add_inline_query_handler("gif", gif_query)
+        .add_inline_query_handler("gif sticker", gif_sticker_query)
+        .add_chosen_inline_result_handler("gif:", gif_chosen);
+
+    let c = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(MockAPI {
+            sent: sent.clone(),
+        })))
+        .set_framework(Arc::new(fr))
+        .build();
+
+    c.fire_handlers(inline_query_update("gif  cats"));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    c.fire_handlers(inline_query_update("gif sticker dogs"));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    c.fire_handlers(inline_query_update("memes"));
+    c.fire_handlers(inline_query_update("gifts"));
+    c.fire_handlers(chosen_inline_result_update("gif:12"));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    c.fire_handlers(chosen_inline_result_update("sticker:12"));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    assert_eq!(*INLINE_B.lock(), vec![
+        ("gif", "cats".to_owned()),
+        ("gif sticker", "dogs".to_owned()),
+        ("chosen", "gif:12".to_owned()),
+    ]);
+    assert_eq!(*sent.lock(), vec![json!({
+        "inline_query_id": "query",
+        "results": [],
+        "cache_time": 10,
+        "is_personal": true,
+    })]);
+    INLINE_B.lock().clear();
+
+    let mut fr = Framework::new("test_bot");
+    fr.add_inline_query_handler("gif sticker", gif_sticker_query)
+        .add_inline_query_handler("", any_query);
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    c.fire_handlers(inline_query_update("gif sticker dogs"));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    c.fire_handlers(inline_query_update("memes"));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    c.fire_handlers(inline_query_update("gif stickers"));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    assert_eq!(*INLINE_B.lock(), vec![
+        ("gif sticker", "dogs".to_owned()),
+        ("", "memes".to_owned()),
+        ("", "gif stickers".to_owned()),
+    ]);
+    Ok(())
+}
+
+static HOOKED_B: AtomicUsize = AtomicUsize::new(0);
+static BEFORE_B: Mutex<Vec<(i64, &str)>> = parking_lot::const_mutex(Vec::new());
+static AFTER_B: Mutex<Vec<(i64, String)>> = parking_lot::const_mutex(Vec::new());
+
+#[prepare_listener]
+async fn before_hook(_c: Context, m: Message, command: &'static str) -> bool {
+    BEFORE_B.lock().push((m.message_id, command));
+    m.message_id != 3
+}
+
+#[prepare_listener]
+async fn after_hook(_c: Context, m: Message, command: &'static str, status: CommandStatus) {
+    let status = match status {
+        CommandStatus::Completed(Ok(())) => "ok".to_owned(),
+        CommandStatus::Completed(Err(err)) => format!("error: {}", err.0),
+        CommandStatus::Vetoed => "vetoed".to_owned(),
+        CommandStatus::CheckFailed(reason) => format!("check failed: {reason:?}"),
+        CommandStatus::OnCooldown(_) => "cooldown".to_owned(),
+    };
+    AFTER_B.lock().push((m.message_id, format!("{command} {status}")));
+}
+
+#[command(description = "testing hooks")]
+#[check(is_small)]
+async fn hooked(_c: Context, m: Message) -> CommandResult {
+    HOOKED_B.fetch_add(m.message_id as usize, Ordering::Acquire);
+    if m.message_id == 5 {
+        return Err("five".into());
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_command_hooks() -> Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&hooked_COMMAND);
+    fr.set_before_hook(before_hook).set_after_hook(after_hook);
+
+    let c = ClientBuilder::new()
+        .set_token("test")
+        .set_framework(Arc::new(fr))
+        .build();
+
+    for id in [1, 3, 5, 200] {
+        c.fire_handlers(command_update(id, "/hooked"));
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+    }
+    c.fire_handlers(command_update(7, "/unknown"));
+    tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+
+    // the vetoed and failed commands don't run, but are still passed to the
+    // after hook
+    assert_eq!(HOOKED_B.load(Ordering::Relaxed), 6);
+    assert_eq!(*BEFORE_B.lock(), vec![
+        (1, "hooked"),
+        (3, "hooked"),
+        (5, "hooked"),
+        (200, "hooked"),
+    ]);
+    assert_eq!(*AFTER_B.lock(), vec![
+        (1, "hooked ok".to_owned()),
+        (3, "hooked vetoed".to_owned()),
+        (5, "hooked error: five".to_owned()),
+        (200, "hooked check failed: Some(\"too big\")".to_owned()),
+    ]);
+    Ok(())
+}
+
+#[command(description = "a listed command")]
+async fn listed(_c: Context, _m: Message) -> CommandResult {
+    Ok(())
+}
+
+#[command(description = "a hidden command")]
+#[hidden]
+async fn unlisted(_c: Context, _m: Message) -> CommandResult {
+    Ok(())
+}
+
+#[command(name = "Shout", description = "a command with an invalid name")]
+async fn shout(_c: Context, _m: Message) -> CommandResult {
+    Ok(())
+}
+
+struct SetCommandsAPI {
+    sent: Arc<Mutex<Vec<(String, Value)>>>,
+}
+
+#[async_trait::async_trait]
+impl API for SetCommandsAPI {
+    async fn get(&self, endpoint: APIEndpoint, data: Option<Value>) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+
+    async fn post(&self, endpoint: APIEndpoint, data: Option<Value>) -> Result<Response> {
+        self.sent.lock().push((endpoint.to_string(), data.unwrap_or_default()));
+        Ok(Response {
+            ok: true,
+            description: None,
+            result: Some(serde_json::value::to_raw_value(&json!(true))?),
+        })
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> Result<Response> {
+        self.post(endpoint, data).await
+    }
+}
+
+#[tokio::test]
+async fn test_register_commands() -> Result<()> {
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&listed_COMMAND);
+    fr.add_command(&unlisted_COMMAND);
+    fr.with_help();
+
+    let expected = json!([
+        {"command": "listed", "description": "a listed command"},
+        {"command": "help", "description": "Lists the available commands"},
+    ]);
+    assert_eq!(serde_json::to_value(fr.get_bot_commands()?)?, expected);
+
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let api = SetCommandsAPI {
+        sent: sent.clone(),
+    };
+    fr.register_commands_with_telegram(&api).await?;
+    assert_eq!(*sent.lock(), vec![(
+        "setMyCommands".to_owned(),
+        json!({ "commands": expected })
+    )]);
+
+    // hidden commands can still be called, but aren't listed by the help command
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let c = ClientBuilder::new()
+        .set_api_client(Arc::new(Box::new(MockAPI {
+            sent: sent.clone(),
+        })))
+        .set_framework(Arc::new(fr))
+        .build();
+    c.fire_handlers(command_update(1, "/help"));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let text = sent.lock()[0]["text"].as_str().unwrap().to_owned();
+    assert!(text.contains("/listed") && !text.contains("/unlisted"));
+
+    let mut fr = Framework::new("test_bot");
+    fr.add_command(&shout_COMMAND);
+    assert!(fr.get_bot_commands().is_err());
+    Ok(())
+}
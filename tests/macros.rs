@@ -31,7 +31,8 @@ async fn test_using_macro_to_prepare() -> Result<()> {
 
     c.fire_handlers(Update {
         update_id: 10,
-        content: UpdateContent::Unknown,
+        raw_json: None,
+        content: UpdateContent::Unknown(Box::default()),
     });
 
     tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
@@ -58,6 +59,7 @@ async fn test_using_command() -> Result<()> {
 
     c.fire_handlers(Update {
         update_id: 10,
+        raw_json: None,
         content: UpdateContent::Message(Message {
             message_id: 30,
             from: None,
@@ -69,13 +71,20 @@ async fn test_using_command() -> Result<()> {
                 bio: None,
                 last_name: None,
                 photo: None,
+                has_private_forwards: None,
+                has_restricted_voice_and_video_messages: None,
+                message_auto_delete_time: None,
+                active_usernames: None,
             }),
             sender_chat: None,
             forward_data: None,
+            forward_origin: None,
             reply_to_message: None,
             via_bot: None,
             edit_date: None,
             author_signature: None,
+            message_thread_id: None,
+            is_topic_message: false,
             connected_website: None,
             passport_data: None,
             reply_markup: None,
@@ -89,6 +98,7 @@ async fn test_using_command() -> Result<()> {
 
     c.fire_handlers(Update {
         update_id: 10,
+        raw_json: None,
         content: UpdateContent::Message(Message {
             message_id: 30,
             from: None,
@@ -100,13 +110,20 @@ async fn test_using_command() -> Result<()> {
                 bio: None,
                 last_name: None,
                 photo: None,
+                has_private_forwards: None,
+                has_restricted_voice_and_video_messages: None,
+                message_auto_delete_time: None,
+                active_usernames: None,
             }),
             sender_chat: None,
             forward_data: None,
+            forward_origin: None,
             reply_to_message: None,
             via_bot: None,
             edit_date: None,
             author_signature: None,
+            message_thread_id: None,
+            is_topic_message: false,
             connected_website: None,
             passport_data: None,
             reply_markup: None,
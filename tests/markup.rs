@@ -0,0 +1,282 @@
+use telexide::model::{
+    ForceReply,
+    InlineKeyboardButton,
+    InlineKeyboardMarkup,
+    KeyboardButtonRequestChat,
+    KeyboardButtonRequestUsers,
+    Message,
+    MessageContent,
+    PollType,
+    ReplyKeyboardMarkup,
+    ReplyKeyboardRemove,
+    ReplyMarkup,
+    SwitchInlineQueryChosenChat,
+};
+
+#[test]
+fn build_inline_keyboard() -> telexide::Result<()> {
+    let markup = InlineKeyboardMarkup::builder()
+        .callback("Yes", "yes")
+        .callback("No", "no")
+        .row()
+        .url("Docs", "https://docs.rs/telexide")
+        .row()
+        .row()
+        .switch_inline("Share", "")
+        .build()?;
+
+    assert_eq!(
+        serde_json::to_value(&markup)?,
+        serde_json::json!({
+            "inline_keyboard": [
+                [
+                    {"text": "Yes", "callback_data": "yes"},
+                    {"text": "No", "callback_data": "no"}
+                ],
+                [
+                    {"text": "Docs", "url": "https://docs.rs/telexide"}
+                ],
+                [
+                    {"text": "Share", "switch_inline_query": ""}
+                ]
+            ]
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn inline_keyboard_rejects_invalid_callback_data() {
+    let res = InlineKeyboardMarkup::builder()
+        .callback("Too long", &"x".repeat(65))
+        .build();
+    assert!(res.is_err());
+
+    let res = InlineKeyboardMarkup::builder().callback("Empty", "").build();
+    assert!(res.is_err());
+
+    let res = InlineKeyboardMarkup::builder()
+        .callback("Longest", &"x".repeat(64))
+        .build();
+    assert!(res.is_ok());
+}
+
+#[test]
+fn switch_inline_query_chosen_chat() -> telexide::Result<()> {
+    let markup = InlineKeyboardMarkup::builder()
+        .switch_inline_chosen_chat(
+            "Share",
+            SwitchInlineQueryChosenChat::new("cats")
+                .set_allow_user_chats(true)
+                .set_allow_group_chats(true)
+                .clone(),
+        )
+        .build()?;
+
+    assert_eq!(
+        serde_json::to_value(&markup)?,
+        serde_json::json!({
+            "inline_keyboard": [[{
+                "text": "Share",
+                "switch_inline_query_chosen_chat": {
+                    "query": "cats",
+                    "allow_user_chats": true,
+                    "allow_group_chats": true
+                }
+            }]]
+        })
+    );
+
+    let button: InlineKeyboardButton = serde_json::from_value(serde_json::json!({
+        "text": "Share",
+        "switch_inline_query_chosen_chat": {"allow_channel_chats": true}
+    }))?;
+    let chosen_chat = button.switch_inline_query_chosen_chat.unwrap();
+    assert_eq!(chosen_chat.query, None);
+    assert!(chosen_chat.allow_channel_chats && !chosen_chat.allow_bot_chats);
+    assert_eq!(button.callback_data, None);
+    Ok(())
+}
+
+#[test]
+fn inline_buttons_need_exactly_one_action() {
+    let mut button = InlineKeyboardButton::callback("Both", "data");
+    button.switch_inline_query_chosen_chat = Some(SwitchInlineQueryChosenChat::default());
+    assert!(InlineKeyboardMarkup::builder().button(button).build().is_err());
+
+    let mut button = InlineKeyboardButton::callback("None", "data");
+    button.callback_data = None;
+    assert!(InlineKeyboardMarkup::builder().button(button).build().is_err());
+}
+
+#[test]
+fn build_reply_keyboard() -> serde_json::Result<()> {
+    let markup = ReplyKeyboardMarkup::builder()
+        .text("hello")
+        .request_contact("Contact")
+        .row()
+        .request_location("Location")
+        .request_poll("Quiz", Some(PollType::Quiz))
+        .set_resize_keyboard(true)
+        .set_one_time_keyboard(true)
+        .build();
+
+    assert_eq!(
+        serde_json::to_value(&markup)?,
+        serde_json::json!({
+            "keyboard": [
+                [
+                    {"text": "hello", "request_contact": false, "request_location": false},
+                    {"text": "Contact", "request_contact": true, "request_location": false}
+                ],
+                [
+                    {"text": "Location", "request_contact": false, "request_location": true},
+                    {
+                        "text": "Quiz",
+                        "request_contact": false,
+                        "request_location": false,
+                        "request_poll": {"type": "quiz"}
+                    }
+                ]
+            ],
+            "resize_keyboard": true,
+            "one_time_keyboard": true,
+            "selective": false
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn request_users_and_chats() -> serde_json::Result<()> {
+    let mut users = KeyboardButtonRequestUsers::new(1);
+    users.max_quantity = Some(3);
+    users.user_is_bot = Some(false);
+    users.request_name = true;
+    let mut chat = KeyboardButtonRequestChat::new(2, true);
+    chat.request_title = true;
+
+    let markup = ReplyKeyboardMarkup::builder()
+        .request_users("Users", users.clone())
+        .request_chat("Channel", chat.clone())
+        .build();
+    let keyboard = serde_json::to_value(&markup)?;
+    assert_eq!(
+        keyboard["keyboard"][0][0]["request_users"],
+        serde_json::json!({
+            "request_id": 1,
+            "user_is_bot": false,
+            "max_quantity": 3,
+            "request_name": true,
+        })
+    );
+    assert_eq!(
+        keyboard["keyboard"][0][1]["request_chat"],
+        serde_json::json!({"request_id": 2, "chat_is_channel": true, "request_title": true})
+    );
+
+    let shared: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 5,
+        "date": 1700000000,
+        "chat": {"id": 1, "type": "private", "first_name": "a"},
+        "users_shared": {
+            "request_id": 1,
+            "users": [
+                {"user_id": 10, "first_name": "ten"},
+                {"user_id": 11, "username": "eleven"},
+                {"user_id": 12, "photo": [
+                    {"file_id": "p", "file_unique_id": "u", "width": 160, "height": 160},
+                ]},
+            ],
+        },
+    }))?;
+    match shared.content {
+        MessageContent::UsersShared {
+            content,
+        } => {
+            assert!(users.is_answered_by(&content));
+            assert_eq!(content.user_ids(), vec![10, 11, 12]);
+            assert_eq!(content.users[0].first_name.as_deref(), Some("ten"));
+            assert_eq!(content.users[1].username.as_deref(), Some("eleven"));
+            assert_eq!(content.users[2].photo.as_ref().map(Vec::len), Some(1));
+        },
+        content => panic!("expected shared users, got {:?}", content),
+    }
+
+    let shared: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 6,
+        "date": 1700000000,
+        "chat": {"id": 1, "type": "private", "first_name": "a"},
+        "chat_shared": {"request_id": 3, "chat_id": -100123, "title": "news"},
+    }))?;
+    match shared.content {
+        MessageContent::ChatShared {
+            content,
+        } => {
+            assert!(!chat.is_answered_by(&content));
+            assert_eq!(content.chat_id, -100123);
+        },
+        content => panic!("expected a shared chat, got {:?}", content),
+    }
+    Ok(())
+}
+
+#[test]
+fn persistent_keyboards_and_placeholders() -> serde_json::Result<()> {
+    let keyboard = ReplyKeyboardMarkup::builder()
+        .text("hello")
+        .set_is_persistent(true)
+        .set_input_field_placeholder("pick one")
+        .build();
+    let json = serde_json::to_value(&keyboard)?;
+    assert_eq!(json["is_persistent"], true);
+    assert_eq!(json["input_field_placeholder"], "pick one");
+
+    let mut force = ForceReply::new(false);
+    force.set_input_field_placeholder("your name");
+    assert_eq!(
+        serde_json::to_value(&force)?,
+        serde_json::json!({
+            "force_reply": true,
+            "selective": false,
+            "input_field_placeholder": "your name"
+        })
+    );
+
+    // the untagged markup still picks the right variant with the new fields
+    let markup: ReplyMarkup = serde_json::from_value(json)?;
+    assert_eq!(markup, ReplyMarkup::ReplyKeyboardMarkup(keyboard));
+    let markup: ReplyMarkup = serde_json::from_value(serde_json::to_value(&force)?)?;
+    assert_eq!(markup, ReplyMarkup::ForceReply(force));
+    Ok(())
+}
+
+#[test]
+fn convert_into_reply_markup() -> telexide::Result<()> {
+    let inline = InlineKeyboardMarkup::builder().callback("Yes", "yes").build()?;
+    let keyboard = ReplyKeyboardMarkup::builder().text("hi").build();
+    let remove = ReplyKeyboardRemove {
+        remove_keyboard: true,
+        selective: false,
+    };
+    let force = ForceReply {
+        force_reply: true,
+        selective: true,
+        input_field_placeholder: None,
+    };
+
+    assert_eq!(
+        ReplyMarkup::from(inline.clone()),
+        ReplyMarkup::InlineKeyboardMarkup(inline)
+    );
+    assert_eq!(
+        ReplyMarkup::from(keyboard.clone()),
+        ReplyMarkup::ReplyKeyboardMarkup(keyboard)
+    );
+
+    let markup: ReplyMarkup = remove.clone().into();
+    assert_eq!(markup, ReplyMarkup::ReplyKeyboardRemove(remove));
+    let markup: ReplyMarkup = force.clone().into();
+    assert_eq!(markup, ReplyMarkup::ForceReply(force));
+    Ok(())
+}
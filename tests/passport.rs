@@ -0,0 +1,121 @@
+#![cfg(feature = "passport-decrypt")]
+
+use std::{fs, path::Path};
+use telexide::model::{
+    FileCredentials,
+    IdDocumentData,
+    PassportData,
+    PassportDecryptionError,
+    PassportElementData,
+    PersonalDetails,
+    TelegramPassportElement,
+};
+
+/// The files in `tests/fixtures/passport` are encrypted following telegram's
+/// documentation independently of this crate: `passport_data.json` is
+/// encrypted for `private_key.pem`, and `front_side.jpg.enc` is the
+/// downloaded front side of its passport element.
+fn fixture(name: &str) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/passport").join(name);
+    fs::read(path).expect("fixture exists")
+}
+
+fn passport_data() -> PassportData {
+    serde_json::from_slice(&fixture("passport_data.json")).unwrap()
+}
+
+#[test]
+fn decrypts_passport_data() {
+    let decrypted = passport_data().decrypt(&fixture("private_key.pem")).unwrap();
+
+    assert_eq!(decrypted.credentials.nonce, "some nonce");
+    assert_eq!(decrypted.elements.len(), 3);
+
+    let details = decrypted
+        .element(TelegramPassportElement::PersonalDetails)
+        .unwrap();
+    match &details.data {
+        Some(PassportElementData::PersonalDetails(PersonalDetails {
+            first_name,
+            middle_name,
+            country_code,
+            ..
+        })) => {
+            assert_eq!(first_name, "Jane");
+            assert_eq!(middle_name, &None);
+            assert_eq!(country_code, "NL");
+        },
+        d => panic!("unexpected personal details: {:?}", d),
+    }
+
+    let passport = decrypted.element(TelegramPassportElement::Passport).unwrap();
+    assert_eq!(
+        passport.data,
+        Some(PassportElementData::IdDocument(IdDocumentData {
+            document_no: "X1234".to_owned(),
+            expiry_date: Some("01.01.2030".to_owned()),
+        }))
+    );
+    let front_side = passport.front_side.as_ref().unwrap();
+    assert_eq!(front_side.file.file_id, "front");
+    assert_eq!(
+        front_side.decrypt(&fixture("front_side.jpg.enc")).unwrap(),
+        b"\xff\xd8 jpeg bytes"
+    );
+
+    let email = decrypted.element(TelegramPassportElement::Email).unwrap();
+    assert_eq!(email.email.as_deref(), Some("jane@example.com"));
+    assert_eq!(email.data, None);
+}
+
+#[test]
+fn passport_decryption_errors() {
+    let data = passport_data();
+    let pem = fixture("private_key.pem");
+    let encrypted_front_side = fixture("front_side.jpg.enc");
+
+    assert_eq!(
+        data.decrypt(&fixture("other_key.pem")),
+        Err(PassportDecryptionError::WrongKey)
+    );
+
+    let mut tampered = data.clone();
+    tampered.credentials.hash = base64::encode([0; 32]);
+    assert_eq!(
+        tampered.decrypt(&pem),
+        Err(PassportDecryptionError::HashMismatch)
+    );
+
+    let mut invalid = data.clone();
+    invalid.data[0].data = Some("not base64!".to_owned());
+    assert!(matches!(
+        invalid.decrypt(&pem),
+        Err(PassportDecryptionError::Malformed(_))
+    ));
+    assert!(matches!(
+        data.decrypt(b"not a key"),
+        Err(PassportDecryptionError::Malformed(_))
+    ));
+
+    let decrypted = data.decrypt(&pem).unwrap();
+    let front_side = decrypted.elements[1].front_side.as_ref().unwrap();
+    let mut corrupted = encrypted_front_side.clone();
+    corrupted[0] ^= 1;
+    assert_eq!(
+        front_side.decrypt(&corrupted),
+        Err(PassportDecryptionError::HashMismatch)
+    );
+    assert!(matches!(
+        front_side.decrypt(&corrupted[1..]),
+        Err(PassportDecryptionError::Malformed(_))
+    ));
+
+    let wrong_credentials = FileCredentials {
+        file_hash: front_side.credentials.file_hash.clone(),
+        secret: base64::encode([1; 32]),
+    };
+    assert_eq!(
+        telexide::model::decrypt_passport_file(&encrypted_front_side, &wrong_credentials),
+        Err(PassportDecryptionError::HashMismatch)
+    );
+}
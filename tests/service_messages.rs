@@ -0,0 +1,183 @@
+use std::{fs, path::Path};
+use telexide::model::{
+    BackgroundFill,
+    BackgroundType,
+    ContentKind,
+    Message,
+    MessageContent,
+    WriteAccessAllowed,
+};
+
+/// Every service message in `tests/fixtures/service_messages` is named after
+/// the field telegram sends its content in, optionally followed by a
+/// description of the variant, like `chat_background_set.gradient.json`.
+/// When adding support for a new service message, add a fixture for it here.
+fn fixtures() -> Vec<(String, serde_json::Value)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/service_messages");
+    let mut fixtures: Vec<_> = fs::read_dir(dir)
+        .expect("fixtures directory exists")
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            let json = fs::read_to_string(&path).unwrap();
+            (name, serde_json::from_str(&json).unwrap())
+        })
+        .collect();
+    fixtures.sort_by(|(a, _), (b, _)| a.cmp(b));
+    fixtures
+}
+
+fn fixture(name: &str) -> Message {
+    let (_, value) = fixtures()
+        .into_iter()
+        .find(|(n, _)| n == name)
+        .expect("fixture exists");
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn all_service_message_fixtures_parse() {
+    let fixtures = fixtures();
+    assert!(fixtures.len() >= 10);
+
+    for (name, value) in fixtures {
+        let field = name.split('.').next().unwrap();
+        let message: Message = serde_json::from_value(value.clone())
+            .unwrap_or_else(|e| panic!("{} failed to parse: {}", name, e));
+        assert_ne!(message.content.kind(), ContentKind::Unknown, "{}", name);
+        assert!(message.is_service_message(), "{} isn't a service message", name);
+
+        let serialized = serde_json::to_value(&message).unwrap();
+        assert!(!serialized[field].is_null(), "{} lost its content", name);
+        let reparsed: Message = serde_json::from_value(serialized).unwrap();
+        assert_eq!(reparsed, message, "{} changed when serializing", name);
+    }
+}
+
+#[test]
+fn write_access_allowed() {
+    let content = |name| match fixture(name).content {
+        MessageContent::WriteAccessAllowed {
+            content,
+        } => content,
+        other => panic!("expected write access to be allowed, got {:?}", other),
+    };
+
+    assert!(content("write_access_allowed.attachment_menu.json").from_attachment_menu);
+    let web_app = content("write_access_allowed.web_app.json");
+    assert_eq!(web_app.web_app_name.as_deref(), Some("shop"));
+    assert!(!web_app.from_request);
+    assert_eq!(content("write_access_allowed.empty.json"), WriteAccessAllowed::default());
+}
+
+#[test]
+fn boost_added() {
+    match fixture("boost_added.json").content {
+        MessageContent::BoostAdded {
+            content,
+        } => assert_eq!(content.boost_count, 4),
+        other => panic!("expected a boost, got {:?}", other),
+    }
+}
+
+#[test]
+fn chat_background_set() {
+    let background = |name| match fixture(name).content {
+        MessageContent::ChatBackgroundSet {
+            content,
+        } => content.background_type,
+        other => panic!("expected a chat background, got {:?}", other),
+    };
+
+    assert_eq!(
+        background("chat_background_set.gradient.json"),
+        BackgroundType::Fill {
+            fill: BackgroundFill::Gradient {
+                top_color: 0xFF_FF_FF,
+                bottom_color: 0xFF,
+                rotation_angle: 45,
+            },
+            dark_theme_dimming: 50,
+        }
+    );
+    match background("chat_background_set.wallpaper.json") {
+        BackgroundType::Wallpaper {
+            document,
+            is_blurred,
+            is_moving,
+            ..
+        } => {
+            assert_eq!(document.file_name.as_deref(), Some("wallpaper.jpg"));
+            assert!(is_blurred && !is_moving);
+        },
+        other => panic!("expected a wallpaper, got {:?}", other),
+    }
+    match background("chat_background_set.pattern.json") {
+        BackgroundType::Pattern {
+            fill: BackgroundFill::FreeformGradient {
+                colors,
+            },
+            intensity,
+            ..
+        } => {
+            assert_eq!(colors.len(), 3);
+            assert_eq!(intensity, 50);
+        },
+        other => panic!("expected a pattern, got {:?}", other),
+    }
+    assert_eq!(
+        background("chat_background_set.chat_theme.json"),
+        BackgroundType::ChatTheme {
+            theme_name: "🏠".to_owned(),
+        }
+    );
+
+    let unknown: BackgroundType =
+        serde_json::from_value(serde_json::json!({"type": "hologram", "depth": 3})).unwrap();
+    assert!(matches!(unknown, BackgroundType::Unknown(ref other) if other["depth"] == 3));
+}
+
+#[test]
+fn forum_topics() {
+    let created = fixture("forum_topic_created.json");
+    assert_eq!(created.topic_id(), Some(150));
+    match created.content {
+        MessageContent::ForumTopicCreated {
+            content,
+        } => {
+            assert_eq!(content.name, "Help");
+            assert_eq!(content.icon_color, 0x6F_B9_F0);
+            assert_eq!(content.icon_custom_emoji_id.as_deref(), Some("5377498341074542641"));
+        },
+        other => panic!("expected a created topic, got {:?}", other),
+    }
+
+    match fixture("forum_topic_edited.json").content {
+        MessageContent::ForumTopicEdited {
+            content,
+        } => {
+            assert_eq!(content.name.as_deref(), Some("Questions"));
+            assert_eq!(content.icon_custom_emoji_id, None);
+        },
+        other => panic!("expected an edited topic, got {:?}", other),
+    }
+
+    let kinds: Vec<_> = [
+        "forum_topic_closed.json",
+        "forum_topic_reopened.json",
+        "general_forum_topic_hidden.json",
+        "general_forum_topic_unhidden.json",
+    ]
+    .iter()
+    .map(|name| fixture(name).content.kind())
+    .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ContentKind::ForumTopicClosed,
+            ContentKind::ForumTopicReopened,
+            ContentKind::GeneralForumTopicHidden,
+            ContentKind::GeneralForumTopicUnhidden,
+        ]
+    );
+}
@@ -0,0 +1,89 @@
+use telexide::{
+    model::ParseMode,
+    utils::{result::TelegramError, split_text, MAX_MESSAGE_LENGTH},
+    Error,
+};
+
+fn split<'a>(text: &'a str, parse_mode: Option<ParseMode>, max_length: usize) -> Vec<&'a str> {
+    split_text(text, parse_mode.as_ref(), max_length).unwrap()
+}
+
+fn assert_unsplittable(text: &str, parse_mode: ParseMode, max_length: usize) {
+    match split_text(text, Some(&parse_mode), max_length) {
+        Err(Error::Telegram(TelegramError::InvalidArgument(e))) => {
+            assert!(e.contains("can't be split"), "{}", e)
+        },
+        res => panic!("expected {:?} not to be splittable, got {:?}", text, res),
+    }
+}
+
+#[test]
+fn split_plain_text() {
+    assert_eq!(split(" short \n", None, 10), vec![" short \n"]);
+    assert_eq!(split("aaaa\nbbbb cccc", None, 10), vec!["aaaa", "bbbb cccc"]);
+    assert_eq!(split("aaa bbb ccc", None, 8), vec!["aaa bbb", "ccc"]);
+    assert_eq!(split("aaa   \n\n  bbb", None, 5), vec!["aaa", "bbb"]);
+    assert_eq!(split("abcdefghij", None, 4), vec!["abcd", "efgh", "ij"]);
+    assert_eq!(split("abcdefghij", Some(ParseMode::None), 4), vec!["abcd", "efgh", "ij"]);
+
+    // emoji take up two UTF-16 code units and are never split
+    assert_eq!(split("😀😀😀", None, 4), vec!["😀😀", "😀"]);
+    assert_eq!(split("😀😀😀", None, 3), vec!["😀", "😀", "😀"]);
+}
+
+#[test]
+fn split_long_log() {
+    let log = "a log line\n".repeat(1000);
+    let parts = split(&log, None, MAX_MESSAGE_LENGTH);
+
+    assert_eq!(parts.len(), 3);
+    assert!(parts.iter().all(|p| p.encode_utf16().count() <= MAX_MESSAGE_LENGTH));
+    assert!(parts.iter().all(|p| p.ends_with("a log line")));
+    assert_eq!(parts.join("\n"), log.trim_end());
+}
+
+#[test]
+fn split_html() {
+    let html = Some(ParseMode::HTML);
+    assert_eq!(split("<b>aa bb</b> cc", html.clone(), 12), vec!["<b>aa bb</b>", "cc"]);
+    assert_eq!(
+        split("<a href=\"https://example.com\">a</a> b", html.clone(), 35),
+        vec!["<a href=\"https://example.com\">a</a>", "b"]
+    );
+    assert_eq!(split("a&amp;b&amp;c", html.clone(), 8), vec!["a&amp;b", "&amp;c"]);
+    assert_eq!(split("<b>a</b><br/>b c", html, 14), vec!["<b>a</b><br/>b", "c"]);
+    assert_unsplittable("<b>aaaaaa aaaa</b>", ParseMode::HTML, 8);
+}
+
+#[test]
+fn split_markdown_v2() {
+    let markdown = Some(ParseMode::MarkdownV2);
+    assert_eq!(split("*aa bb* cc", markdown.clone(), 8), vec!["*aa bb*", "cc"]);
+    assert_eq!(
+        split("[aa bb](https://e.com) cc", markdown.clone(), 23),
+        vec!["[aa bb](https://e.com)", "cc"]
+    );
+    assert_eq!(split("\\*aa bb cc", markdown.clone(), 7), vec!["\\*aa bb", "cc"]);
+    assert_eq!(split("```\na b\n```\nc", markdown.clone(), 12), vec!["```\na b\n```", "c"]);
+    assert_eq!(
+        split("__aa bb__ ||cc dd||", markdown.clone(), 10),
+        vec!["__aa bb__", "||cc dd||"]
+    );
+    assert_eq!(split("**>aa bb||\ncc dd", markdown, 11), vec!["**>aa bb||", "cc dd"]);
+
+    assert_unsplittable("~aa bb~ cc", ParseMode::MarkdownV2, 6);
+    assert_unsplittable(">aa bb\ncc", ParseMode::MarkdownV2, 5);
+    assert_unsplittable("`aa bb` cc", ParseMode::MarkdownV2, 6);
+}
+
+#[test]
+#[allow(deprecated)]
+fn split_legacy_markdown() {
+    let markdown = Some(ParseMode::Markdown);
+    assert_eq!(split("*aa bb* cc", markdown.clone(), 8), vec!["*aa bb*", "cc"]);
+
+    // strikethrough and quotes don't exist in legacy markdown
+    assert_eq!(split("~aa bb~ cc", markdown.clone(), 6), vec!["~aa", "bb~ cc"]);
+    assert_eq!(split(">aa bb\ncc", markdown, 5), vec![">aa", "bb\ncc"]);
+    assert_unsplittable("_aa bb_ cc", ParseMode::Markdown, 6);
+}
@@ -0,0 +1,183 @@
+use futures::StreamExt;
+use parking_lot::Mutex;
+use serde_json::{json, value::RawValue, Value};
+use std::sync::Arc;
+use telexide::{
+    api::{APIEndpoint, Response, API},
+    client::UpdatesStream,
+    model::{ChatMember, MessageContent, UpdateContent},
+    utils::FormDataFile,
+    Result,
+};
+
+struct MockAPI {
+    requested_offsets: Arc<Mutex<Vec<i64>>>,
+    updates: fn(i64) -> String,
+}
+
+#[async_trait::async_trait]
+impl API for MockAPI {
+    async fn get(&self, _endpoint: APIEndpoint, data: Option<Value>) -> Result<Response> {
+        let offset = data.unwrap()["offset"].as_i64().unwrap();
+        self.requested_offsets.lock().push(offset);
+
+        Ok(Response {
+            ok: true,
+            description: None,
+            result: Some(RawValue::from_string((self.updates)(offset))?),
+        })
+    }
+
+    async fn post(&self, endpoint: APIEndpoint, data: Option<Value>) -> Result<Response> {
+        self.get(endpoint, data).await
+    }
+
+    async fn post_file(
+        &self,
+        endpoint: APIEndpoint,
+        data: Option<Value>,
+        _files: Option<Vec<FormDataFile>>,
+    ) -> Result<Response> {
+        self.get(endpoint, data).await
+    }
+}
+
+#[tokio::test]
+async fn stream_starts_at_and_advances_offset() -> Result<()> {
+    let requested_offsets = Arc::new(Mutex::new(Vec::new()));
+    let confirmed_offsets = Arc::new(Mutex::new(Vec::new()));
+
+    let mut stream = UpdatesStream::new(Arc::new(Box::new(MockAPI {
+        requested_offsets: requested_offsets.clone(),
+        updates: |offset| {
+            let updates: Vec<Value> = (offset..offset + 2)
+                .map(|id| {
+                    json!({
+                        "update_id": id,
+                    })
+                })
+                .collect();
+            json!(updates).to_string()
+        },
+    })));
+
+    let confirmed = confirmed_offsets.clone();
+    stream
+        .set_offset(40)
+        .set_offset_handler(move |offset| confirmed.lock().push(offset));
+    assert_eq!(stream.current_offset(), 40);
+
+    for expected in 40..43 {
+        let update = stream.next().await.unwrap()?;
+        assert_eq!(update.update_id, expected);
+        assert_eq!(stream.current_offset(), expected + 1);
+    }
+
+    assert_eq!(*requested_offsets.lock(), vec![40, 42]);
+    assert_eq!(*confirmed_offsets.lock(), vec![41, 42, 43]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stream_skips_invalid_updates() -> Result<()> {
+    let invalid_updates = Arc::new(Mutex::new(Vec::new()));
+
+    let mut stream = UpdatesStream::new(Arc::new(Box::new(MockAPI {
+        requested_offsets: Arc::default(),
+        updates: |_| {
+            json!([
+                {"update_id": 1},
+                {"update_id": 2, "message": {"message_id": "invalid"}},
+                {"update_id": 3},
+            ])
+            .to_string()
+        },
+    })));
+
+    let invalid = invalid_updates.clone();
+    stream.set_invalid_update_handler(move |raw| invalid.lock().push(raw));
+
+    assert_eq!(stream.next().await.unwrap()?.update_id, 1);
+    assert_eq!(stream.next().await.unwrap()?.update_id, 3);
+    assert_eq!(stream.current_offset(), 4);
+
+    let invalid_updates = invalid_updates.lock();
+    assert_eq!(invalid_updates.len(), 1);
+    assert_eq!(invalid_updates[0]["update_id"], 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stream_keeps_unknown_updates() -> Result<()> {
+    let mut stream = UpdatesStream::new(Arc::new(Box::new(MockAPI {
+        requested_offsets: Arc::default(),
+        updates: |_| {
+            json!([
+                {"update_id": 1, "some_future_update": {"id": 5}},
+                {
+                    "update_id": 2,
+                    "message": {
+                        "message_id": 1,
+                        "date": 0,
+                        "chat": {"id": 40, "type": "private"},
+                        "some_future_content": {"id": 6}
+                    }
+                },
+                {"update_id": 3, "my_chat_member": {
+                    "chat": {"id": 40, "type": "private"},
+                    "from": {"id": 1, "is_bot": false, "first_name": "test"},
+                    "date": 0,
+                    "old_chat_member": {
+                        "status": "member",
+                        "user": {"id": 2, "is_bot": true, "first_name": "bot"}
+                    },
+                    "new_chat_member": {
+                        "status": "some_future_status",
+                        "user": {"id": 2, "is_bot": true, "first_name": "bot"}
+                    }
+                }},
+                {"update_id": 4},
+            ])
+            .to_string()
+        },
+    })));
+
+    let update = stream.next().await.unwrap()?;
+    assert!(matches!(update.content, UpdateContent::Unknown(_)));
+
+    let update = stream.next().await.unwrap()?;
+    match update.content {
+        UpdateContent::Message(m) => assert_eq!(m.content, MessageContent::Unknown),
+        _ => panic!("expected a message"),
+    }
+
+    let update = stream.next().await.unwrap()?;
+    match update.content {
+        UpdateContent::MyChatMember(c) => {
+            assert!(matches!(c.new_chat_member, ChatMember::Unknown(_)));
+        },
+        _ => panic!("expected a chat member update"),
+    }
+
+    assert_eq!(stream.next().await.unwrap()?.update_id, 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stream_keeps_raw_json() -> Result<()> {
+    let mut stream = UpdatesStream::new(Arc::new(Box::new(MockAPI {
+        requested_offsets: Arc::default(),
+        updates: |_| r#"[{"update_id": 1, "some_new_update": {"id": 5}}]"#.to_owned(),
+    })));
+
+    let update = stream.next().await.unwrap()?;
+    assert_eq!(update.raw_json(), None);
+
+    stream.set_keep_raw_json(true);
+    let update = stream.next().await.unwrap()?;
+    assert_eq!(
+        update.raw_json(),
+        Some(r#"{"update_id": 1, "some_new_update": {"id": 5}}"#)
+    );
+    Ok(())
+}
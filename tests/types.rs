@@ -1,4 +1,60 @@
-use telexide::model::{Chat, Message, MessageContent, User};
+use chrono::{TimeZone, Utc};
+use std::time::Duration;
+use telexide::{
+    api::{
+        types::{
+            AnswerInlineQuery,
+            BanChatMember,
+            CreateInvoiceLink,
+            EditMessageLiveLocation,
+            GetChatMemberCount,
+            InlineQueryResult,
+            InlineQueryResultArticle,
+            InlineQueryResultsButton,
+            InputMessageContent,
+            InputTextMessageContent,
+            PassportAddressDocument,
+            PassportDataElement,
+            PassportElementError,
+            PassportIdentityDocument,
+            PassportTwoSidedDocument,
+            RestrictChatMember,
+            SendDice,
+            SetChatPermissions,
+            SetPassportDataErrors,
+        },
+        APIEndpoint,
+    },
+    model::{
+        Birthdate,
+        CallbackQuery,
+        Chat,
+        ChatAdministratorRights,
+        ChatFullInfo,
+        ChatMember,
+        ChatPermissions,
+        ChatRight,
+        ChatType,
+        Dice,
+        DiceEmoji,
+        InlineQuery,
+        LabeledPrice,
+        MaybeInaccessibleMessage,
+        Message,
+        MessageContent,
+        MessageEntity,
+        MessageEntityType,
+        MessageOrigin,
+        Poll,
+        ReactionType,
+        TelegramPassportElement,
+        Update,
+        UpdateContent,
+        User,
+        Venue,
+        WebhookInfo,
+    },
+};
 
 #[test]
 fn decode_user() -> serde_json::Result<()> {
@@ -14,6 +70,86 @@ fn decode_user() -> serde_json::Result<()> {
     assert_eq!(u.id, 456);
     assert_eq!(u.last_name, None);
     assert_eq!(u.username, None);
+    assert_eq!(u.is_premium, None);
+
+    let premium: User = serde_json::from_value(serde_json::json!({
+        "id": 457,
+        "is_bot": false,
+        "first_name": "y",
+        "is_premium": true,
+        "added_to_attachment_menu": true
+    }))?;
+    assert_eq!(premium.is_premium, Some(true));
+    assert_eq!(premium.added_to_attachment_menu, Some(true));
+    Ok(())
+}
+
+#[test]
+fn decode_chat_privacy_fields() -> serde_json::Result<()> {
+    let chat: Chat = serde_json::from_value(serde_json::json!({
+        "id": -1001234,
+        "type": "supergroup",
+        "title": "group",
+        "username": "group",
+        "active_usernames": ["group", "group_alias"],
+        "join_to_send_messages": true,
+        "join_by_request": false,
+        "has_protected_content": true,
+        "message_auto_delete_time": 86400,
+        "slow_mode_delay": 30,
+        "linked_chat_id": -1005678,
+        "location": {
+            "location": {"longitude": 4.89, "latitude": 52.37},
+            "address": "Amsterdam"
+        }
+    }))?;
+    match &chat {
+        Chat::SuperGroup(c) => {
+            assert_eq!(
+                c.active_usernames,
+                Some(vec!["group".to_owned(), "group_alias".to_owned()])
+            );
+            assert_eq!(c.join_to_send_messages, Some(true));
+            assert_eq!(c.join_by_request, Some(false));
+            assert_eq!(c.has_protected_content, Some(true));
+            assert_eq!(c.message_auto_delete_time, Some(86400));
+            assert_eq!(c.slow_mode_delay, Some(30));
+            assert_eq!(c.linked_chat_id, Some(-1005678));
+            assert_eq!(c.location.as_ref().map(|l| l.address.as_str()), Some("Amsterdam"));
+        },
+        c => panic!("expected a supergroup, got {:?}", c),
+    }
+    let round_trip: Chat = serde_json::from_value(serde_json::to_value(&chat)?)?;
+    assert_eq!(round_trip, chat);
+
+    let private: Chat = serde_json::from_value(serde_json::json!({
+        "id": 5,
+        "type": "private",
+        "first_name": "a",
+        "has_private_forwards": true,
+        "has_restricted_voice_and_video_messages": true
+    }))?;
+    match private {
+        Chat::Private(c) => {
+            assert_eq!(c.has_private_forwards, Some(true));
+            assert_eq!(c.has_restricted_voice_and_video_messages, Some(true));
+            assert_eq!(c.message_auto_delete_time, None);
+        },
+        c => panic!("expected a private chat, got {:?}", c),
+    }
+
+    let channel: Chat = serde_json::from_value(serde_json::json!({
+        "id": -1009,
+        "type": "channel",
+        "title": "channel"
+    }))?;
+    match channel {
+        Chat::Channel(c) => {
+            assert_eq!(c.has_protected_content, None);
+            assert_eq!(c.active_usernames, None);
+        },
+        c => panic!("expected a channel, got {:?}", c),
+    }
     Ok(())
 }
 
@@ -53,3 +189,1523 @@ fn decode_message() -> serde_json::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn message_urls() -> serde_json::Result<()> {
+    let message = |chat: serde_json::Value| -> serde_json::Result<Message> {
+        serde_json::from_value(serde_json::json!({
+            "message_id": 42,
+            "date": 0,
+            "chat": chat,
+            "text": "hi",
+        }))
+    };
+
+    let public = message(serde_json::json!({
+        "id": -1001234567890i64,
+        "type": "supergroup",
+        "title": "public",
+        "username": "public_group",
+    }))?;
+    assert_eq!(public.url().as_deref(), Some("https://t.me/public_group/42"));
+
+    let private = message(serde_json::json!({
+        "id": -1001234567890i64,
+        "type": "supergroup",
+        "title": "private",
+    }))?;
+    assert_eq!(private.url().as_deref(), Some("https://t.me/c/1234567890/42"));
+
+    let channel = message(serde_json::json!({
+        "id": -1009876543210i64,
+        "type": "channel",
+        "title": "channel",
+    }))?;
+    assert_eq!(channel.url().as_deref(), Some("https://t.me/c/9876543210/42"));
+
+    let group = message(serde_json::json!({"id": -12345, "type": "group", "title": "group"}))?;
+    assert_eq!(group.url(), None);
+    let dm = message(serde_json::json!({"id": 12345, "type": "private", "first_name": "a"}))?;
+    assert_eq!(dm.url(), None);
+
+    // the full chat of a public channel, as returned by get_chat
+    let full_channel: Chat = serde_json::from_value(serde_json::json!({
+        "id": -1009876543210i64,
+        "type": "channel",
+        "title": "channel",
+        "username": "public_channel",
+    }))?;
+    assert_eq!(
+        channel.link(&full_channel).as_deref(),
+        Some("https://t.me/public_channel/42")
+    );
+    assert_eq!(
+        full_channel.invite_url().as_deref(),
+        Some("https://t.me/public_channel")
+    );
+
+    assert_eq!(private.chat.invite_url(), None);
+    let invited: Chat = serde_json::from_value(serde_json::json!({
+        "id": -1001234567890i64,
+        "type": "supergroup",
+        "title": "private",
+        "invite_link": "https://t.me/+AbCdEf",
+    }))?;
+    assert_eq!(invited.invite_url().as_deref(), Some("https://t.me/+AbCdEf"));
+    assert_eq!(dm.chat.invite_url(), None);
+    Ok(())
+}
+
+#[test]
+fn chat_accessors() -> serde_json::Result<()> {
+    let private: Chat = serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "type": "private",
+        "first_name": "a",
+        "username": "user",
+        "photo": {
+            "small_file_id": "s",
+            "small_file_unique_id": "su",
+            "big_file_id": "b",
+            "big_file_unique_id": "bu"
+        }
+    }))?;
+    assert!(private.is_private() && !private.is_group());
+    assert_eq!(private.title(), None);
+    assert_eq!(private.username(), Some("user"));
+    assert_eq!(private.description(), None);
+    assert_eq!(private.photo().map(|p| p.big_file_id.as_str()), Some("b"));
+    assert_eq!(private.permissions(), None);
+
+    let group: Chat = serde_json::from_value(serde_json::json!({
+        "id": -2,
+        "type": "group",
+        "title": "group",
+        "description": "a group",
+        "permissions": {"can_send_messages": true}
+    }))?;
+    assert!(group.is_group() && !group.is_supergroup());
+    assert_eq!(group.title(), Some("group"));
+    assert_eq!(group.username(), None);
+    assert_eq!(group.description(), Some("a group"));
+    assert_eq!(group.permissions().map(|p| p.can_send_messages), Some(true));
+    assert_eq!(group.photo(), None);
+
+    let supergroup: Chat = serde_json::from_value(serde_json::json!({
+        "id": -1003,
+        "type": "supergroup",
+        "title": "supergroup",
+        "username": "super",
+        "pinned_message": {
+            "message_id": 7,
+            "date": 0,
+            "chat": {"id": -1003, "type": "supergroup", "title": "supergroup"},
+            "text": "pinned"
+        }
+    }))?;
+    assert!(supergroup.is_supergroup() && !supergroup.is_channel());
+    assert_eq!(supergroup.title(), Some("supergroup"));
+    assert_eq!(supergroup.username(), Some("super"));
+    assert_eq!(supergroup.pinned_message().map(|m| m.message_id), Some(7));
+    assert_eq!(supergroup.permissions(), None);
+
+    let channel: Chat = serde_json::from_value(serde_json::json!({
+        "id": -1004,
+        "type": "channel",
+        "title": "channel",
+        "description": "news"
+    }))?;
+    assert!(channel.is_channel() && !channel.is_private());
+    assert_eq!(channel.title(), Some("channel"));
+    assert_eq!(channel.username(), None);
+    assert_eq!(channel.description(), Some("news"));
+    assert_eq!(channel.pinned_message(), None);
+    assert_eq!(channel.permissions(), None);
+    Ok(())
+}
+
+#[test]
+fn message_accessors() -> serde_json::Result<()> {
+    let message = |content: serde_json::Value| -> serde_json::Result<Message> {
+        let mut message = serde_json::json!({
+            "message_id": 1,
+            "date": 0,
+            "chat": {"id": 40, "type": "private"},
+            "from": {"id": 2, "is_bot": false, "first_name": "a"},
+        });
+        message
+            .as_object_mut()
+            .unwrap()
+            .extend(content.as_object().unwrap().clone());
+        serde_json::from_value(message)
+    };
+    let file = |id: &str| serde_json::json!({"file_id": id, "file_unique_id": "u"});
+
+    let text = message(serde_json::json!({"text": "hello"}))?;
+    assert_eq!(text.text_or_caption(), Some("hello"));
+    assert_eq!(text.from_user().map(|u| u.id), Some(2));
+    assert_eq!(text.media_file_id(), None);
+    assert!(!text.is_service_message());
+
+    let size = |id: &str, side: u32| {
+        serde_json::json!({"file_id": id, "file_unique_id": id, "width": side, "height": side})
+    };
+    let (small, large) = (size("small", 90), size("large", 800));
+    let photo = message(serde_json::json!({"photo": [small, large], "caption": "look"}))?;
+    assert_eq!(photo.text_or_caption(), Some("look"));
+    assert_eq!(photo.media_file_id(), Some("large"));
+
+    let mut video = file("video");
+    video.as_object_mut().unwrap().extend(
+        serde_json::json!({"width": 1, "height": 1, "duration": 1})
+            .as_object()
+            .unwrap()
+            .clone(),
+    );
+    assert_eq!(message(serde_json::json!({ "video": video }))?.media_file_id(), Some("video"));
+    assert_eq!(
+        message(serde_json::json!({"document": file("document")}))?.media_file_id(),
+        Some("document")
+    );
+    let mut audio = file("audio");
+    audio["duration"] = 1.into();
+    assert_eq!(message(serde_json::json!({ "audio": audio }))?.media_file_id(), Some("audio"));
+    let mut voice = file("voice");
+    voice["duration"] = 1.into();
+    let voice = message(serde_json::json!({ "voice": voice }))?;
+    assert_eq!(voice.media_file_id(), Some("voice"));
+    assert_eq!(voice.text_or_caption(), None);
+
+    let mut sticker = file("sticker");
+    sticker.as_object_mut().unwrap().extend(
+        serde_json::json!({
+            "type": "regular",
+            "width": 512,
+            "height": 512,
+            "is_animated": false,
+            "is_video": false
+        })
+        .as_object()
+        .unwrap()
+        .clone(),
+    );
+    let sticker = message(serde_json::json!({ "sticker": sticker }))?;
+    assert_eq!(sticker.media_file_id(), Some("sticker"));
+
+    let title = message(serde_json::json!({"new_chat_title": "new"}))?;
+    assert!(title.is_service_message());
+    assert_eq!(title.media_file_id(), None);
+    let created = message(serde_json::json!({"group_chat_created": true}))?;
+    assert!(created.is_service_message());
+    Ok(())
+}
+
+#[test]
+fn decode_chat_full_info() -> serde_json::Result<()> {
+    let supergroup: ChatFullInfo = serde_json::from_str(
+        r#"{
+            "id": -1001234567890,
+            "title": "Rust group",
+            "username": "rust_group",
+            "type": "supergroup",
+            "is_forum": true,
+            "active_usernames": ["rust_group"],
+            "accent_color_id": 3,
+            "max_reaction_count": 11,
+            "available_reactions": [
+                {"type": "emoji", "emoji": "👍"},
+                {"type": "custom_emoji", "custom_emoji_id": "5368324170671202286"}
+            ],
+            "description": "About rust",
+            "invite_link": "https://t.me/+AbCdEf",
+            "pinned_message": {
+                "message_id": 12,
+                "date": 1700000000,
+                "chat": {"id": -1001234567890, "title": "Rust group", "type": "supergroup"},
+                "text": "read the rules"
+            },
+            "permissions": {
+                "can_send_messages": true,
+                "can_send_audios": true,
+                "can_send_documents": true,
+                "can_send_photos": true,
+                "can_send_videos": true,
+                "can_send_video_notes": true,
+                "can_send_voice_notes": true,
+                "can_send_polls": true,
+                "can_send_other_messages": true,
+                "can_add_web_page_previews": true,
+                "can_change_info": false,
+                "can_invite_users": true,
+                "can_pin_messages": false,
+                "can_manage_topics": false
+            },
+            "slow_mode_delay": 10,
+            "unrestrict_boost_count": 2,
+            "has_hidden_members": true,
+            "has_visible_history": true,
+            "sticker_set_name": "rust_stickers",
+            "can_set_sticker_set": true,
+            "custom_emoji_sticker_set_name": "rust_emoji",
+            "linked_chat_id": -1009876543210
+        }"#,
+    )?;
+    assert_eq!(supergroup.chat_type, ChatType::SuperGroup);
+    assert_eq!(supergroup.is_forum, Some(true));
+    assert_eq!(supergroup.max_reaction_count, Some(11));
+    assert_eq!(
+        supergroup.available_reactions.as_deref().map(|r| &r[0]),
+        Some(&ReactionType::Emoji {
+            emoji: "👍".to_owned()
+        })
+    );
+    assert_eq!(supergroup.unrestrict_boost_count, Some(2));
+    assert_eq!(supergroup.custom_emoji_sticker_set_name.as_deref(), Some("rust_emoji"));
+
+    let chat = Chat::from(supergroup);
+    assert!(chat.is_supergroup());
+    assert_eq!(chat.title(), Some("Rust group"));
+    assert_eq!(chat.pinned_message().map(|m| m.message_id), Some(12));
+    assert_eq!(chat.permissions().map(|p| p.can_pin_messages), Some(false));
+    match &chat {
+        Chat::SuperGroup(c) => {
+            assert_eq!(c.slow_mode_delay, Some(10));
+            assert_eq!(c.linked_chat_id, Some(-1_009_876_543_210));
+        },
+        _ => panic!("expected a supergroup"),
+    }
+
+    let private: ChatFullInfo = serde_json::from_str(
+        r#"{
+            "id": 123456789,
+            "first_name": "Ferris",
+            "last_name": "Crab",
+            "username": "ferris",
+            "type": "private",
+            "active_usernames": ["ferris"],
+            "birthdate": {"day": 15, "month": 5},
+            "business_opening_hours": {
+                "time_zone_name": "Europe/Amsterdam",
+                "opening_hours": [{"opening_minute": 540, "closing_minute": 1020}]
+            },
+            "personal_chat": {"id": -1001111111111, "title": "Ferris' blog", "type": "channel"},
+            "bio": "Unofficial mascot",
+            "has_private_forwards": true,
+            "emoji_status_custom_emoji_id": "5368324170671202286",
+            "emoji_status_expiration_date": 1700000000,
+            "accent_color_id": 0,
+            "max_reaction_count": 11
+        }"#,
+    )?;
+    assert_eq!(
+        private.birthdate,
+        Some(Birthdate {
+            day: 15,
+            month: 5,
+            year: None,
+        })
+    );
+    let hours = private.business_opening_hours.as_ref().unwrap();
+    assert_eq!(hours.opening_hours[0].closing_minute, 1020);
+    assert!(private.personal_chat.as_ref().is_some_and(|c| c.is_channel()));
+    assert_eq!(
+        private.emoji_status_expiration_date,
+        Utc.timestamp_opt(1_700_000_000, 0).single()
+    );
+    assert_eq!(private.description, None);
+
+    let chat = Chat::from(private);
+    assert!(chat.is_private());
+    assert_eq!(chat.username(), Some("ferris"));
+    match &chat {
+        Chat::Private(c) => {
+            assert_eq!(c.bio.as_deref(), Some("Unofficial mascot"));
+            assert_eq!(c.has_private_forwards, Some(true));
+        },
+        _ => panic!("expected a private chat"),
+    }
+
+    // only the id and type are required
+    let minimal: ChatFullInfo =
+        serde_json::from_value(serde_json::json!({"id": -5, "type": "group"}))?;
+    assert_eq!(Chat::from(minimal).title(), Some(""));
+    Ok(())
+}
+
+#[test]
+fn decode_auto_delete_timer_changed() -> serde_json::Result<()> {
+    // captured when enabling auto-delete in a group
+    let message: Message = serde_json::from_str(
+        r#"{
+            "message_id": 31,
+            "from": {"id": 123456789, "is_bot": false, "first_name": "Ferris"},
+            "chat": {"id": -987654321, "title": "Rust group", "type": "group"},
+            "date": 1700000000,
+            "message_auto_delete_timer_changed": {"message_auto_delete_time": 86400}
+        }"#,
+    )?;
+    assert!(message.is_service_message());
+    match &message.content {
+        MessageContent::MessageAutoDeleteTimerChanged { content } => {
+            assert_eq!(content.message_auto_delete_time, 86400);
+            assert!(!content.is_disabled());
+            assert_eq!(content.duration(), Some(Duration::from_secs(86400)));
+        },
+        other => panic!("expected a timer change, got {:?}", other),
+    }
+    let value = serde_json::to_value(&message)?;
+    assert_eq!(
+        value["message_auto_delete_timer_changed"]["message_auto_delete_time"],
+        86400
+    );
+
+    let disabled: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 32,
+        "chat": {"id": -987654321, "title": "Rust group", "type": "group"},
+        "date": 1700000100,
+        "message_auto_delete_timer_changed": {"message_auto_delete_time": 0},
+    }))?;
+    match &disabled.content {
+        MessageContent::MessageAutoDeleteTimerChanged { content } => {
+            assert!(content.is_disabled());
+            assert_eq!(content.duration(), None);
+        },
+        other => panic!("expected a timer change, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn decode_forum_topic_message() -> serde_json::Result<()> {
+    // captured in a topic of a forum supergroup
+    let message: Message = serde_json::from_str(
+        r#"{
+            "message_id": 120,
+            "message_thread_id": 101,
+            "from": {"id": 123456789, "is_bot": false, "first_name": "Ferris"},
+            "chat": {
+                "id": -1001234567890,
+                "title": "Rust forum",
+                "is_forum": true,
+                "type": "supergroup"
+            },
+            "date": 1700000000,
+            "is_topic_message": true,
+            "text": "hello topic"
+        }"#,
+    )?;
+    assert!(message.chat.is_forum());
+    assert_eq!(message.message_thread_id, Some(101));
+    assert!(message.is_topic_message);
+    assert_eq!(message.topic_id(), Some(101));
+
+    let value = serde_json::to_value(&message)?;
+    assert_eq!(value["message_thread_id"], 101);
+    assert_eq!(value["is_topic_message"], true);
+    assert_eq!(value["chat"]["is_forum"], true);
+
+    // a reply thread in a supergroup without topics
+    let reply: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 121,
+        "message_thread_id": 100,
+        "chat": {"id": -1_001_234_567_891_i64, "title": "Rust group", "type": "supergroup"},
+        "date": 1700000000,
+        "text": "in a thread",
+    }))?;
+    assert!(!reply.chat.is_forum() && !reply.is_topic_message);
+    assert_eq!(reply.topic_id(), None);
+
+    let private: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 1,
+        "chat": {"id": 5, "type": "private"},
+        "date": 1700000000,
+        "text": "hi",
+    }))?;
+    assert!(!private.chat.is_forum());
+    assert_eq!(private.message_thread_id, None);
+    Ok(())
+}
+
+#[test]
+fn decode_forward_origins() -> serde_json::Result<()> {
+    let forwarded = |origin: serde_json::Value| -> serde_json::Result<Message> {
+        serde_json::from_value(serde_json::json!({
+            "message_id": 7,
+            "date": 1700000100,
+            "chat": {"id": 1, "type": "private", "first_name": "a"},
+            "forward_origin": origin,
+            "text": "fwd",
+        }))
+    };
+    let channel_chat = serde_json::json!({"id": -1001, "type": "channel", "title": "news"});
+
+    let user = forwarded(serde_json::json!({
+        "type": "user",
+        "date": 1700000000,
+        "sender_user": {"id": 5, "is_bot": false, "first_name": "bob"},
+    }))?;
+    assert!(user.is_forwarded());
+    assert!(matches!(user.forward_origin, Some(MessageOrigin::User(_))));
+    assert_eq!(user.forward_origin_chat(), None);
+    let data = user.forward_data.unwrap();
+    assert_eq!(data.from.unwrap().id, 5);
+    assert_eq!(data.date, Utc.timestamp_opt(1_700_000_000, 0).unwrap());
+
+    let hidden = forwarded(serde_json::json!({
+        "type": "hidden_user",
+        "date": 1700000000,
+        "sender_user_name": "someone",
+    }))?;
+    assert!(matches!(hidden.forward_origin, Some(MessageOrigin::HiddenUser(_))));
+    let data = hidden.forward_data.unwrap();
+    assert_eq!(data.sender_name.as_deref(), Some("someone"));
+    assert!(data.from.is_none());
+
+    let chat = forwarded(serde_json::json!({
+        "type": "chat",
+        "date": 1700000000,
+        "sender_chat": {"id": -1002, "type": "supergroup", "title": "group"},
+        "author_signature": "admin",
+    }))?;
+    assert!(matches!(chat.forward_origin_chat(), Some(Chat::SuperGroup(_))));
+    let data = chat.forward_data.unwrap();
+    assert_eq!(data.signature.as_deref(), Some("admin"));
+    assert!(data.from_message_id.is_none());
+
+    let channel = forwarded(serde_json::json!({
+        "type": "channel",
+        "date": 1700000000,
+        "chat": channel_chat,
+        "message_id": 99,
+    }))?;
+    assert!(matches!(channel.forward_origin_chat(), Some(Chat::Channel(_))));
+    let data = channel.forward_data.unwrap();
+    assert_eq!(data.from_message_id, Some(99));
+    assert!(data.from_chat.is_some());
+
+    let not_forwarded = forwarded(serde_json::Value::Null)?;
+    assert!(!not_forwarded.is_forwarded());
+    Ok(())
+}
+
+#[test]
+fn legacy_forward_fields_fill_origin() -> serde_json::Result<()> {
+    let m: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 7,
+        "date": 1700000100,
+        "chat": {"id": 1, "type": "private", "first_name": "a"},
+        "forward_date": 1700000000,
+        "forward_from_chat": {"id": -1001, "type": "channel", "title": "news"},
+        "forward_from_message_id": 12,
+        "text": "fwd",
+    }))?;
+
+    match m.forward_origin {
+        Some(MessageOrigin::Channel(ref origin)) => assert_eq!(origin.message_id, 12),
+        ref other => panic!("unexpected origin {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn callback_query_on_accessible_and_inaccessible_messages() -> serde_json::Result<()> {
+    let query = |message: serde_json::Value| -> serde_json::Result<CallbackQuery> {
+        serde_json::from_value(serde_json::json!({
+            "id": "query",
+            "from": {"id": 3, "is_bot": false, "first_name": "test"},
+            "message": message,
+            "chat_instance": "instance",
+            "data": "pressed",
+        }))
+    };
+    let chat = serde_json::json!({"id": 5, "type": "private", "first_name": "a"});
+
+    let accessible = query(serde_json::json!({
+        "message_id": 1,
+        "date": 1700000000,
+        "chat": chat,
+        "text": "buttons",
+    }))?;
+    let message = accessible.message.as_ref().unwrap();
+    assert!(message.is_accessible());
+    assert_eq!(message.as_message().unwrap().get_text().as_deref(), Some("buttons"));
+    assert_eq!(accessible.chat_id(), Some(5));
+    assert_eq!(accessible.message_id(), Some(1));
+
+    let inaccessible = query(serde_json::json!({"message_id": 2, "date": 0, "chat": chat}))?;
+    match inaccessible.message {
+        Some(MaybeInaccessibleMessage::Inaccessible(ref m)) => assert_eq!(m.message_id, 2),
+        ref other => panic!("expected an inaccessible message, got {:?}", other),
+    }
+    assert!(inaccessible.message.as_ref().unwrap().as_message().is_none());
+    assert_eq!(inaccessible.chat_id(), Some(5));
+    assert_eq!(inaccessible.message_id(), Some(2));
+
+    let update: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "callback_query": serde_json::to_value(&inaccessible)?,
+    }))?;
+    assert!(update.effective_message().is_none());
+    assert_eq!(update.chat().map(Chat::get_id), Some(5));
+    Ok(())
+}
+
+#[test]
+fn entity_text_uses_utf16_offsets() -> serde_json::Result<()> {
+    let t = r#"{
+            "message_id": 16373893,
+            "date": 1585772722,
+            "chat": {
+                "id": 538733,
+                "type": "private",
+                "first_name": "test"
+            },
+            "text": "\ud83d\udc4b\u4f60\u597d /start@test_bot see https://telegram.org or this",
+            "entities": [
+                {"type": "bot_command", "offset": 5, "length": 15},
+                {"type": "url", "offset": 25, "length": 20},
+                {"type": "text_link", "offset": 49, "length": 4, "url": "https://core.telegram.org"},
+                {"type": "bold", "offset": 0, "length": 2}
+            ]
+        }"#;
+
+    let m: Message = serde_json::from_str(t)?;
+
+    assert_eq!(m.extract_command(), Some("/start@test_bot".to_owned()));
+    assert_eq!(m.extract_urls(), vec![
+        "https://telegram.org".to_owned(),
+        "https://core.telegram.org".to_owned()
+    ]);
+
+    let bold = m.entities_of_type(MessageEntityType::Bold).next();
+    assert_eq!(
+        bold.and_then(|e| m.get_entity_text(e)),
+        Some("\u{1f44b}".to_owned())
+    );
+    assert_eq!(m.entities_of_type(MessageEntityType::Code).count(), 0);
+    Ok(())
+}
+
+#[test]
+fn entity_text_in_caption() -> serde_json::Result<()> {
+    let t = r#"{
+            "message_id": 16373894,
+            "date": 1585772722,
+            "chat": {
+                "id": 538733,
+                "type": "private",
+                "first_name": "test"
+            },
+            "document": {
+                "file_id": "test-file",
+                "file_unique_id": "testing1"
+            },
+            "caption": "\ud83d\ude00\ud83d\ude00 /ping",
+            "caption_entities": [
+                {"type": "bot_command", "offset": 5, "length": 5},
+                {"type": "italic", "offset": 8, "length": 5}
+            ]
+        }"#;
+
+    let m: Message = serde_json::from_str(t)?;
+
+    assert_eq!(m.extract_command(), Some("/ping".to_owned()));
+
+    let italic = m.entities_of_type(MessageEntityType::Italic).next();
+    assert_eq!(italic.and_then(|e| m.get_entity_text(e)), None);
+    Ok(())
+}
+
+#[test]
+fn typed_message_entities() -> serde_json::Result<()> {
+    let m: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 3,
+        "date": 0,
+        "chat": {"id": 5, "type": "private"},
+        "text": "#tag $USD gone code link jane x",
+        "entities": [
+            {"type": "hashtag", "offset": 0, "length": 4},
+            {"type": "cashtag", "offset": 5, "length": 4},
+            {"type": "strikethrough", "offset": 10, "length": 4},
+            {"type": "pre", "offset": 15, "length": 4, "language": "rust"},
+            {"type": "text_link", "offset": 20, "length": 4, "url": "https://example.com"},
+            {
+                "type": "text_mention",
+                "offset": 25,
+                "length": 4,
+                "user": {"id": 8, "is_bot": false, "first_name": "Jane"}
+            },
+            {"type": "custom_emoji", "offset": 30, "length": 1, "custom_emoji_id": "5368324170671202286"}
+        ]
+    }))?;
+
+    let entities = m.get_entities().unwrap();
+    let kinds: Vec<String> = entities
+        .iter()
+        .map(|e| match e {
+            MessageEntity::HashTag(t) | MessageEntity::CashTag(t) | MessageEntity::StrikeThrough(t) => {
+                t.get_text(m.get_text().as_deref().unwrap())
+            },
+            MessageEntity::Pre(p) => p.language.clone().unwrap(),
+            MessageEntity::TextLink(l) => l.url.clone(),
+            MessageEntity::TextMention(mention) => mention.user.first_name.clone(),
+            MessageEntity::CustomEmoji(emoji) => emoji.custom_emoji_id.clone(),
+            MessageEntity::Mention(_)
+            | MessageEntity::BotCommand(_)
+            | MessageEntity::Url(_)
+            | MessageEntity::Email(_)
+            | MessageEntity::PhoneNumber(_)
+            | MessageEntity::Bold(_)
+            | MessageEntity::Italic(_)
+            | MessageEntity::Underline(_)
+            | MessageEntity::Spoiler(_)
+            | MessageEntity::Code(_)
+            | MessageEntity::Unknown(_) => panic!("unexpected entity {:?}", e),
+        })
+        .collect();
+    assert_eq!(kinds, vec![
+        "#tag",
+        "$USD",
+        "gone",
+        "rust",
+        "https://example.com",
+        "Jane",
+        "5368324170671202286"
+    ]);
+    assert_eq!(entities[6].get_type(), MessageEntityType::CustomEmoji);
+    assert_eq!(entities[6].get_text_block().length, 1);
+
+    assert_eq!(serde_json::to_value(&entities[0])?["type"], "hashtag");
+    assert_eq!(serde_json::to_value(&entities[1])?["type"], "cashtag");
+    assert_eq!(serde_json::to_value(&entities[2])?["type"], "strikethrough");
+
+    // entities serialized by older versions still deserialize
+    let old: MessageEntity = serde_json::from_value(serde_json::json!({
+        "type": "strike_through",
+        "offset": 0,
+        "length": 1
+    }))?;
+    assert_eq!(old.get_type(), MessageEntityType::StrikeThrough);
+    Ok(())
+}
+
+#[test]
+fn unknown_entity_types_are_kept() -> serde_json::Result<()> {
+    let message: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 1,
+        "date": 0,
+        "chat": {"id": 40, "type": "private"},
+        "text": "hello world",
+        "entities": [
+            {"type": "bold", "offset": 0, "length": 5},
+            {"type": "sparkles", "offset": 6, "length": 5, "intensity": 3}
+        ]
+    }))?;
+
+    let entities = match &message.content {
+        MessageContent::Text { entities, .. } => entities,
+        _ => panic!("expected a text message"),
+    };
+    assert_eq!(entities[0].get_type(), MessageEntityType::Bold);
+    assert_eq!(entities[1].get_type(), MessageEntityType::Unknown);
+    assert_eq!(entities[1].get_text("hello world").as_deref(), Some("world"));
+    match &entities[1] {
+        MessageEntity::Unknown(e) => {
+            assert_eq!(e.kind, "sparkles");
+            assert_eq!(e.other["intensity"], 3);
+        },
+        _ => panic!("expected an unknown entity"),
+    }
+
+    assert_eq!(
+        serde_json::to_value(&entities[1])?,
+        serde_json::json!({"type": "sparkles", "offset": 6, "length": 5, "intensity": 3})
+    );
+
+    // known types with invalid fields aren't turned into unknown entities
+    assert!(serde_json::from_value::<MessageEntity>(serde_json::json!({
+        "type": "text_link",
+        "offset": 0,
+        "length": 5,
+    }))
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn decode_webhook_info() -> serde_json::Result<()> {
+    let info: WebhookInfo = serde_json::from_value(serde_json::json!({
+        "url": "https://example.com/bot",
+        "has_custom_certificate": false,
+        "pending_update_count": 3,
+        "ip_address": "203.0.113.5",
+        "last_error_date": 1700000000,
+        "last_error_message": "Connection refused",
+        "max_connections": 40,
+        "allowed_updates": ["message", "callback_query"]
+    }))?;
+    assert!(info.is_set());
+    assert_eq!(
+        info.last_error_date,
+        Utc.timestamp_opt(1_700_000_000, 0).single()
+    );
+    assert_eq!(info.last_synchronization_error_date, None);
+    assert_eq!(info.allowed_updates.map(|u| u.len()), Some(2));
+
+    let info: WebhookInfo = serde_json::from_value(serde_json::json!({
+        "url": "",
+        "has_custom_certificate": false,
+        "pending_update_count": 0
+    }))?;
+    assert!(!info.is_set());
+    assert_eq!(info.last_error_date, None);
+    Ok(())
+}
+
+#[test]
+fn unknown_update_keeps_raw_fields() {
+    let update: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 5,
+        "some_new_update": {"id": 3},
+    }))
+    .unwrap();
+
+    match &update.content {
+        UpdateContent::Unknown(raw) => {
+            assert_eq!(raw.update_id, 5);
+            assert_eq!(raw.other["some_new_update"]["id"], 3);
+        },
+        _ => panic!("expected an unknown update"),
+    }
+
+    let json = serde_json::to_value(&update).unwrap();
+    assert_eq!(json["update_id"], 5);
+    assert_eq!(json["some_new_update"]["id"], 3);
+}
+
+#[test]
+fn live_location_edit_payload() -> serde_json::Result<()> {
+    let edit = EditMessageLiveLocation {
+        chat_id: Some(5),
+        message_id: Some(10),
+        inline_message_id: None,
+        reply_markup: None,
+        latitude: 52.37,
+        longitude: 4.89,
+        horizontal_accuracy: Some(25.5),
+        live_period: Some(7200),
+        heading: Some(90),
+        proximity_alert_radius: Some(500),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&edit)?,
+        serde_json::json!({
+            "chat_id": 5,
+            "message_id": 10,
+            "latitude": 52.37,
+            "longitude": 4.89,
+            "horizontal_accuracy": 25.5,
+            "live_period": 7200,
+            "heading": 90,
+            "proximity_alert_radius": 500,
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn decode_proximity_alert() -> serde_json::Result<()> {
+    let t = r#"{
+            "message_id": 3,
+            "date": 0,
+            "chat": {"id": 5, "type": "private", "first_name": "x"},
+            "proximity_alert_triggered": {
+                "traveler": {"id": 1, "is_bot": false, "first_name": "a"},
+                "watcher": {"id": 2, "is_bot": false, "first_name": "b"},
+                "distance": 42
+            }
+        }"#;
+
+    let m: Message = serde_json::from_str(t)?;
+    match m.content {
+        MessageContent::ProximityAlertTriggered {
+            content,
+        } => {
+            assert_eq!(content.traveler.id, 1);
+            assert_eq!(content.watcher.id, 2);
+            assert_eq!(content.distance, 42);
+        },
+        other => panic!("expected a proximity alert, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn chat_permission_presets() -> serde_json::Result<()> {
+    let all = serde_json::to_value(ChatPermissions::all())?;
+    assert!(all.as_object().unwrap().values().all(|v| v == true));
+
+    let built = ChatPermissions::builder()
+        .can_send_messages(true)
+        .can_invite_users(true)
+        .build();
+    assert!(built.can_send_messages && built.can_invite_users);
+    assert!(!built.can_send_media_messages && !built.can_pin_messages);
+
+    let until = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+    let mute = RestrictChatMember::mute(-100, 5, until);
+    assert_eq!(mute.permissions, ChatPermissions::none());
+    assert_eq!(mute.until_date, Some(until));
+    let mute = serde_json::to_value(&mute)?;
+    assert!(mute["permissions"]
+        .as_object()
+        .unwrap()
+        .values()
+        .all(|v| v == false));
+    Ok(())
+}
+
+#[test]
+fn independent_chat_permissions() -> serde_json::Result<()> {
+    let legacy: ChatPermissions = serde_json::from_value(serde_json::json!({
+        "can_send_messages": true,
+        "can_send_media_messages": true
+    }))?;
+    assert!(legacy.can_send_media_messages);
+    assert_eq!(legacy.can_send_photos, None);
+
+    let allowed = ChatPermissions::allow_all();
+    assert_eq!(allowed.can_send_voice_notes, Some(true));
+    assert!(serde_json::to_value(&allowed)?.as_object().unwrap().values().all(|v| v == true));
+
+    let permissions = ChatPermissions::builder()
+        .can_send_messages(true)
+        .can_send_photos(true)
+        .can_send_videos(false)
+        .build();
+    let mut restrict = RestrictChatMember::new(-100, 5, permissions);
+    assert!(serde_json::to_value(&restrict)?.get("use_independent_chat_permissions").is_none());
+
+    restrict.set_use_independent_chat_permissions(true);
+    assert_eq!(
+        serde_json::to_value(&restrict)?,
+        serde_json::json!({
+            "chat_id": -100,
+            "user_id": 5,
+            "permissions": {
+                "can_send_messages": true,
+                "can_send_media_messages": false,
+                "can_send_photos": true,
+                "can_send_videos": false,
+                "can_send_polls": false,
+                "can_send_other_messages": false,
+                "can_add_web_page_previews": false,
+                "can_change_info": false,
+                "can_invite_users": false,
+                "can_pin_messages": false
+            },
+            "use_independent_chat_permissions": true
+        })
+    );
+
+    let mut defaults = SetChatPermissions::new(-100, ChatPermissions::restrict_all());
+    defaults.set_use_independent_chat_permissions(true);
+    let json = serde_json::to_value(&defaults)?;
+    assert_eq!(json["use_independent_chat_permissions"], true);
+    assert_eq!(json["permissions"]["can_send_documents"], false);
+    assert_eq!(json["permissions"]["can_send_audios"], false);
+    assert!(json["permissions"].as_object().unwrap().values().all(|v| v == false));
+    Ok(())
+}
+
+#[test]
+fn restrictions_for_a_duration() -> serde_json::Result<()> {
+    let now = Utc::now().timestamp();
+
+    let ban = BanChatMember::for_duration(-100, 5, Duration::from_secs(2 * 60 * 60));
+    let until = ban.until_date.unwrap();
+    assert!((now + 7200..=now + 7201).contains(&until));
+
+    // too short restrictions would be forever, so they are rounded up
+    let mute = RestrictChatMember::mute_for(-100, 5, Duration::from_secs(5));
+    let until = serde_json::to_value(&mute)?["until_date"].as_i64().unwrap();
+    assert!((now + 30..now + 60).contains(&until));
+
+    let forever = Duration::from_secs(400 * 24 * 60 * 60);
+    let mut mute = RestrictChatMember::mute_for(-100, 5, forever);
+    assert_eq!(mute.until_date, None);
+    mute.set_until_after(Duration::from_secs(60));
+    assert!(mute.until_date.is_some());
+    Ok(())
+}
+
+#[test]
+fn dates_are_in_seconds() -> serde_json::Result<()> {
+    let m: Message = serde_json::from_str(
+        r#"{"message_id": 3, "date": 1585772722, "chat": {"id": 5, "type": "private"}}"#,
+    )?;
+    assert_eq!(m.date, Utc.timestamp_opt(1_585_772_722, 0).unwrap());
+    Ok(())
+}
+
+#[test]
+fn venue_identifiers_round_trip() -> serde_json::Result<()> {
+    let google = serde_json::json!({
+        "location": {"latitude": 52.37, "longitude": 4.89},
+        "title": "Rijksmuseum",
+        "address": "Museumstraat 1",
+        "google_place_id": "ChIJ5Ra7we4JxkcRhYVAaq5zQ9U",
+        "google_place_type": "museum",
+    });
+    let m: Message = serde_json::from_value(serde_json::json!({
+        "message_id": 3,
+        "date": 0,
+        "chat": {"id": 5, "type": "private"},
+        "location": {"latitude": 52.37, "longitude": 4.89},
+        "venue": google,
+    }))?;
+
+    let venue = m.get_venue().expect("expected a venue message");
+    assert_eq!(venue.google_place_type.as_deref(), Some("museum"));
+    assert_eq!(venue.foursquare_id, None);
+    let serialized = serde_json::to_value(venue)?;
+    assert!(serialized.get("foursquare_id").is_none());
+    assert_eq!(serialized["google_place_id"], google["google_place_id"]);
+    assert_eq!(&serde_json::from_value::<Venue>(serialized)?, venue);
+
+    let result = serde_json::json!({
+        "type": "venue",
+        "id": "1",
+        "latitude": 52.37,
+        "longitude": 4.89,
+        "title": "Rijksmuseum",
+        "address": "Museumstraat 1",
+        "foursquare_id": "4a27082ef964a520db921fe3",
+        "foursquare_type": "arts_entertainment/museum_art",
+        "input_message_content": {
+            "latitude": 52.37,
+            "longitude": 4.89,
+            "title": "Rijksmuseum",
+            "address": "Museumstraat 1",
+            "google_place_id": "ChIJ5Ra7we4JxkcRhYVAaq5zQ9U",
+        },
+    });
+    let parsed: InlineQueryResult = serde_json::from_value(result.clone())?;
+    match &parsed {
+        InlineQueryResult::Venue(venue) => {
+            assert_eq!(venue.google_place_id, None);
+            assert!(matches!(
+                &venue.input_message_content,
+                Some(InputMessageContent::Venue(content)) if content.google_place_id.is_some()
+            ));
+        },
+        other => panic!("expected a venue result, got {:?}", other),
+    }
+    assert_eq!(serde_json::to_value(&parsed)?, result);
+    Ok(())
+}
+
+#[test]
+fn poll_results() -> serde_json::Result<()> {
+    let mut poll: Poll = serde_json::from_value(serde_json::json!({
+        "id": "1",
+        "question": "2 + 2?",
+        "options": [
+            {"text": "3", "voter_count": 1},
+            {"text": "4", "voter_count": 5},
+            {"text": "5", "voter_count": 0},
+        ],
+        "total_voter_count": 6,
+        "is_closed": true,
+        "type": "quiz",
+        "correct_option_id": 1,
+    }))?;
+
+    assert_eq!(poll.total_votes(), 6);
+    assert_eq!(poll.winning_option().map(|o| o.text.as_str()), Some("4"));
+    assert_eq!(poll.correct_option().map(|o| o.text.as_str()), Some("4"));
+
+    poll.options[0].voter_count = 5;
+    assert_eq!(poll.winning_option(), None);
+    Ok(())
+}
+
+#[test]
+fn chat_member_status_helpers() -> serde_json::Result<()> {
+    let user = serde_json::json!({"id": 1, "is_bot": false, "first_name": "x"});
+    let member = |status: serde_json::Value| -> serde_json::Result<ChatMember> {
+        let mut status = status;
+        status["user"] = user.clone();
+        serde_json::from_value(status)
+    };
+
+    let admin = member(serde_json::json!({
+        "status": "administrator",
+        "custom_title": "mod",
+        "can_be_edited": true,
+    }))?;
+    assert!(admin.is_admin() && admin.is_member() && admin.can_be_edited());
+    assert_eq!(admin.custom_title(), Some("mod"));
+
+    let restricted = member(serde_json::json!({
+        "status": "restricted",
+        "until_date": 0,
+        "is_member": true,
+    }))?;
+    assert!(restricted.is_restricted() && restricted.is_member() && !restricted.is_admin());
+    assert_eq!(restricted.until_date(), None);
+
+    let banned = member(serde_json::json!({"status": "kicked", "until_date": 1700000000}))?;
+    assert!(banned.is_banned() && !banned.is_member());
+    assert_eq!(banned.until_date(), Utc.timestamp_opt(1_700_000_000, 0).single());
+    assert_eq!(banned.custom_title(), None);
+
+    let left = member(serde_json::json!({"status": "left"}))?;
+    assert!(!left.is_member() && !left.is_banned() && !left.can_be_edited());
+
+    let unknown = member(serde_json::json!({"status": "lurking", "since": 5}))?;
+    match &unknown {
+        ChatMember::Unknown(m) => {
+            assert_eq!(m.status, "lurking");
+            assert_eq!(m.other["since"], 5);
+        },
+        _ => panic!("expected an unknown member status"),
+    }
+    assert!(!unknown.is_member() && !unknown.is_admin());
+    assert_eq!(unknown.get_user().id, 1);
+    assert_eq!(serde_json::to_value(&unknown)?["status"], "lurking");
+
+    // known statuses with invalid fields aren't turned into unknown ones
+    assert!(member(serde_json::json!({"status": "creator", "is_anonymous": "yes"})).is_err());
+    Ok(())
+}
+
+#[test]
+fn decode_chat_member_updates() -> serde_json::Result<()> {
+    // captured when adding a bot to a group as administrator
+    let added: Update = serde_json::from_str(
+        r#"{
+            "update_id": 840394455,
+            "my_chat_member": {
+                "chat": {"id": -1001587432195, "title": "bot test", "type": "supergroup"},
+                "from": {"id": 63219374, "is_bot": false, "first_name": "Jane", "language_code": "en"},
+                "date": 1688720562,
+                "old_chat_member": {
+                    "user": {"id": 5931221743, "is_bot": true, "first_name": "Test", "username": "test_bot"},
+                    "status": "left"
+                },
+                "new_chat_member": {
+                    "user": {"id": 5931221743, "is_bot": true, "first_name": "Test", "username": "test_bot"},
+                    "status": "administrator",
+                    "can_be_edited": false,
+                    "can_manage_chat": true,
+                    "can_change_info": true,
+                    "can_delete_messages": true,
+                    "can_invite_users": true,
+                    "can_restrict_members": true,
+                    "can_pin_messages": true,
+                    "can_promote_members": false,
+                    "can_manage_voice_chats": true,
+                    "is_anonymous": false
+                }
+            }
+        }"#,
+    )?;
+    match &added.content {
+        UpdateContent::MyChatMember(c) => {
+            assert!(c.bot_was_added());
+            assert!(!c.bot_was_removed());
+            assert!(c.new_chat_member.is_admin());
+            assert!(!c.via_chat_folder_invite_link);
+            assert_eq!(c.date, Utc.timestamp_opt(1_688_720_562, 0).unwrap());
+        },
+        c => panic!("expected a my_chat_member update, got {:?}", c),
+    }
+    assert_eq!(added.get_chat_id(), Some(-1_001_587_432_195));
+
+    // captured when a user joined using a chat folder link and later left
+    let joined: Update = serde_json::from_str(
+        r#"{
+            "update_id": 840394456,
+            "chat_member": {
+                "chat": {"id": -1001587432195, "title": "bot test", "type": "supergroup"},
+                "from": {"id": 81273645, "is_bot": false, "first_name": "John"},
+                "date": 1688720600,
+                "old_chat_member": {
+                    "user": {"id": 81273645, "is_bot": false, "first_name": "John"},
+                    "status": "left"
+                },
+                "new_chat_member": {
+                    "user": {"id": 81273645, "is_bot": false, "first_name": "John"},
+                    "status": "member"
+                },
+                "via_chat_folder_invite_link": true
+            }
+        }"#,
+    )?;
+    match &joined.content {
+        UpdateContent::ChatMember(c) => {
+            assert!(c.user_joined());
+            assert!(!c.user_left());
+            assert!(!c.bot_was_added());
+            assert!(c.via_chat_folder_invite_link);
+
+            let mut left = c.clone();
+            std::mem::swap(&mut left.old_chat_member, &mut left.new_chat_member);
+            assert!(left.user_left());
+        },
+        c => panic!("expected a chat_member update, got {:?}", c),
+    }
+    Ok(())
+}
+
+#[test]
+fn chat_member_admin_rights() -> serde_json::Result<()> {
+    let admin: ChatMember = serde_json::from_value(serde_json::json!({
+        "status": "administrator",
+        "user": {"id": 1, "is_bot": false, "first_name": "x"},
+        "is_anonymous": true,
+        "can_manage_chat": true,
+        "can_delete_messages": true,
+        "can_restrict_members": true,
+        "can_pin_messages": true,
+    }))?;
+    let rights = admin.admin_rights().expect("administrators have rights");
+    assert!(rights.is_anonymous && rights.can_delete_messages && rights.can_restrict_members);
+    assert!(rights.can_pin_messages && !rights.can_promote_members && !rights.can_post_messages);
+
+    let creator: ChatMember = serde_json::from_value(serde_json::json!({
+        "status": "creator",
+        "user": {"id": 2, "is_bot": false, "first_name": "y"},
+    }))?;
+    assert_eq!(creator.admin_rights(), Some(ChatAdministratorRights::all(false)));
+
+    let member: ChatMember = serde_json::from_value(serde_json::json!({
+        "status": "member",
+        "user": {"id": 3, "is_bot": false, "first_name": "z"},
+    }))?;
+    assert_eq!(member.admin_rights(), None);
+    Ok(())
+}
+
+#[test]
+fn chat_member_rights() -> serde_json::Result<()> {
+    let member = |value: serde_json::Value| -> serde_json::Result<ChatMember> {
+        let mut value = value;
+        value["user"] = serde_json::json!({"id": 1, "is_bot": false, "first_name": "x"});
+        serde_json::from_value(value)
+    };
+
+    let creator = member(serde_json::json!({"status": "creator"}))?;
+    assert!(creator.can(ChatRight::PromoteMembers) && creator.can(ChatRight::SendPhotos));
+
+    let admin = member(serde_json::json!({
+        "status": "administrator",
+        "can_delete_messages": true,
+        "can_invite_users": true,
+    }))?;
+    assert!(admin.can(ChatRight::DeleteMessages) && admin.can(ChatRight::InviteUsers));
+    assert!(admin.can(ChatRight::SendMessages) && !admin.can(ChatRight::RestrictMembers));
+
+    let plain = member(serde_json::json!({"status": "member"}))?;
+    assert!(plain.can(ChatRight::SendVoiceNotes) && !plain.can(ChatRight::PinMessages));
+
+    let restricted = member(serde_json::json!({
+        "status": "restricted",
+        "until_date": 1700000000,
+        "is_member": true,
+        "can_send_messages": true,
+        "can_send_media_messages": true,
+        "can_send_photos": false,
+        "can_invite_users": true,
+    }))?;
+    match &restricted {
+        ChatMember::Restricted(m) => {
+            assert_eq!(m.can_send_photos, Some(false));
+            assert_eq!(m.can_send_videos, None);
+        },
+        _ => panic!("expected a restricted member"),
+    }
+    assert!(restricted.can(ChatRight::SendMessages) && restricted.can(ChatRight::InviteUsers));
+    assert!(!restricted.can(ChatRight::SendPhotos) && restricted.can(ChatRight::SendVideos));
+    assert!(!restricted.can(ChatRight::SendPolls) && !restricted.can(ChatRight::DeleteMessages));
+    assert_eq!(restricted.until_date(), Utc.timestamp_opt(1_700_000_000, 0).single());
+
+    let left = member(serde_json::json!({"status": "left"}))?;
+    let banned = member(serde_json::json!({"status": "kicked", "until_date": 0}))?;
+    assert!(!left.can(ChatRight::SendMessages) && !banned.can(ChatRight::SendMessages));
+    Ok(())
+}
+
+#[test]
+#[allow(deprecated)]
+fn chat_member_count_endpoint() {
+    assert_eq!(APIEndpoint::GetChatMemberCount.as_str(), "getChatMemberCount");
+    assert_eq!(APIEndpoint::GetChatMembersCount.as_str(), "getChatMembersCount");
+    assert_eq!(APIEndpoint::BanChatMember.as_str(), "banChatMember");
+    assert_eq!(APIEndpoint::KickChatMember.as_str(), "kickChatMember");
+
+    let old: telexide::api::types::GetChatMembersCount = GetChatMemberCount {
+        chat_id: 5,
+    };
+    assert_eq!(old.chat_id, 5);
+}
+
+#[test]
+fn dice_emoji() -> serde_json::Result<()> {
+    // emoji, value, max value, win, jackpot
+    let fixtures = [
+        ("🎲", 6, Some(6), true, false),
+        ("🎯", 5, Some(6), false, false),
+        ("🏀", 4, Some(5), true, false),
+        ("⚽", 2, Some(5), false, false),
+        ("🎳", 6, Some(6), true, false),
+        ("🎰", 64, Some(64), true, true),
+        ("🎰", 22, Some(64), true, false),
+        ("🎰", 23, Some(64), false, false),
+        ("🪀", 3, None, false, false),
+    ];
+
+    for (emoji, value, max_value, win, jackpot) in fixtures {
+        let json = serde_json::json!({"emoji": emoji, "value": value});
+        let dice: Dice = serde_json::from_value(json.clone())?;
+        assert_eq!(dice.max_value(), max_value, "{}", emoji);
+        assert_eq!(dice.is_win(), win, "{} {}", emoji, value);
+        assert_eq!(dice.is_jackpot(), jackpot, "{} {}", emoji, value);
+        assert_eq!(dice.emoji.as_str(), emoji);
+        assert_eq!(serde_json::to_value(&dice)?, json);
+    }
+
+    let dice: Dice = serde_json::from_str(r#"{"emoji": "🪀", "value": 1}"#)?;
+    assert_eq!(dice.emoji, DiceEmoji::Other("🪀".to_owned()));
+
+    let send = SendDice::new(5, DiceEmoji::Basketball);
+    assert_eq!(serde_json::to_value(&send)?["emoji"], "🏀");
+    Ok(())
+}
+
+#[test]
+fn update_chat_user_and_message() -> serde_json::Result<()> {
+    let user = serde_json::json!({"id": 7, "is_bot": false, "first_name": "x"});
+    let chat = serde_json::json!({"id": -100, "type": "supergroup", "title": "group"});
+    let message = serde_json::json!({
+        "message_id": 1,
+        "date": 1700000000,
+        "chat": chat,
+        "from": user,
+        "text": "hi",
+    });
+
+    let edited: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "edited_message": message,
+    }))?;
+    assert_eq!(edited.chat().map(Chat::get_id), Some(-100));
+    assert_eq!(edited.effective_message().map(|m| m.message_id), Some(1));
+    assert_eq!(edited.from_user().map(|u| u.id), Some(7));
+
+    let no_message: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 2,
+        "callback_query": {"id": "q", "from": user, "chat_instance": "i", "inline_message_id": "m"},
+    }))?;
+    assert_eq!(no_message.effective_message(), None);
+    assert_eq!(no_message.chat(), None);
+
+    let callback: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 2,
+        "callback_query": {"id": "q", "from": user, "chat_instance": "i", "message": message},
+    }))?;
+    assert_eq!(callback.chat().map(Chat::get_id), Some(-100));
+    assert_eq!(callback.effective_message().map(|m| m.message_id), Some(1));
+    assert_eq!(callback.from_user().map(|u| u.id), Some(7));
+
+    let inline: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 3,
+        "inline_query": {"id": "q", "from": user, "query": "", "offset": ""},
+    }))?;
+    assert_eq!(inline.chat(), None);
+    assert_eq!(inline.effective_message(), None);
+    assert_eq!(inline.from_user().map(|u| u.id), Some(7));
+
+    let unknown: Update = serde_json::from_value(serde_json::json!({"update_id": 4}))?;
+    assert_eq!(unknown.chat(), None);
+    assert_eq!(unknown.from_user(), None);
+    Ok(())
+}
+
+#[test]
+fn inline_query_results_button() -> serde_json::Result<()> {
+    let mut answer = AnswerInlineQuery::new("q", Vec::new());
+    assert!(serde_json::to_value(&answer)?.get("button").is_none());
+
+    answer.set_button(InlineQueryResultsButton::web_app("Open", "https://example.com"));
+    assert_eq!(
+        serde_json::to_value(&answer)?["button"],
+        serde_json::json!({"text": "Open", "web_app": {"url": "https://example.com"}})
+    );
+
+    answer.set_button(InlineQueryResultsButton::start_parameter("Connect", "connect"));
+    assert_eq!(
+        serde_json::to_value(&answer)?["button"],
+        serde_json::json!({"text": "Connect", "start_parameter": "connect"})
+    );
+
+    #[allow(deprecated)]
+    let old = AnswerInlineQuery {
+        button: None,
+        switch_pm_text: Some("Connect".to_owned()),
+        switch_pm_parameter: Some("connect".to_owned()),
+        ..AnswerInlineQuery::new("q", Vec::new())
+    };
+    let json = serde_json::to_value(&old)?;
+    assert_eq!(json, serde_json::to_value(&answer)?);
+    assert!(json.get("switch_pm_text").is_none());
+    Ok(())
+}
+
+#[test]
+fn paginated_inline_query_results() -> serde_json::Result<()> {
+    let content = InputMessageContent::Text(InputTextMessageContent {
+        message_text: "hi".to_owned(),
+        parse_mode: None,
+        entities: None,
+        disable_web_page_preview: false,
+    });
+    let results: Vec<InlineQueryResult> = (0..120)
+        .map(|i| InlineQueryResultArticle::new(&i.to_string(), content.clone()).into())
+        .collect();
+
+    let mut query: InlineQuery = serde_json::from_value(serde_json::json!({
+        "id": "q",
+        "from": {"id": 3, "is_bot": false, "first_name": "test"},
+        "query": "",
+        "offset": "",
+    }))?;
+    assert_eq!(query.page(), 0);
+    assert_eq!(query.cursor::<u64>(), None);
+
+    let first = AnswerInlineQuery::new_paginated("q", results.clone(), query.page());
+    assert_eq!(first.results.len(), 50);
+    assert_eq!(first.results[0].id(), results[0].id());
+    assert_eq!(first.next_offset.as_deref(), Some("1"));
+
+    query.offset = first.next_offset.unwrap();
+    assert_eq!(query.page(), 1);
+    query.offset = "2".to_owned();
+    let last = AnswerInlineQuery::new_paginated("q", results.clone(), query.page());
+    assert_eq!(last.results.len(), 20);
+    assert_eq!(last.results[0].id(), results[100].id());
+    assert_eq!(last.next_offset.as_deref(), Some(""));
+
+    let beyond = AnswerInlineQuery::new_paginated("q", results, 7);
+    assert!(beyond.results.is_empty());
+    assert_eq!(beyond.next_offset.as_deref(), Some(""));
+
+    let mut answer = AnswerInlineQuery::new("q", Vec::new());
+    answer.set_next_cursor(Some(1_650_000_000u64));
+    query.offset = answer.next_offset.clone().unwrap();
+    assert_eq!(query.cursor::<u64>(), Some(1_650_000_000));
+    query.offset = "not a page".to_owned();
+    assert_eq!(query.page(), 0);
+    answer.set_next_cursor(None::<u64>);
+    assert_eq!(serde_json::to_value(&answer)?["next_offset"], "");
+    Ok(())
+}
+
+#[test]
+fn invoice_link_payloads() -> serde_json::Result<()> {
+    let mut fiat = CreateInvoiceLink::new(
+        "Shirt",
+        "A black shirt",
+        "order:1",
+        "provider",
+        "EUR",
+        vec![LabeledPrice::new("Shirt", 2000), LabeledPrice::new("Tax", 420)],
+    );
+    fiat.max_tip_amount = Some(500);
+    fiat.need_shipping_address = true;
+    let json = serde_json::to_value(&fiat)?;
+    assert_eq!(json["provider_token"], "provider");
+    assert_eq!(json["currency"], "EUR");
+    assert_eq!(json["prices"][1], serde_json::json!({"label": "Tax", "amount": 420}));
+    assert_eq!(json["max_tip_amount"], 500);
+    assert_eq!(json["need_shipping_address"], true);
+
+    let stars = CreateInvoiceLink::stars("Boost", "A profile boost", "order:2", 50);
+    let json = serde_json::to_value(&stars)?;
+    assert!(json.get("provider_token").is_none());
+    assert!(json.get("max_tip_amount").is_none());
+    assert_eq!(json["currency"], "XTR");
+    assert_eq!(json["prices"], serde_json::json!([{"label": "Boost", "amount": 50}]));
+    Ok(())
+}
+
+#[test]
+fn passport_element_errors() -> serde_json::Result<()> {
+    let data = SetPassportDataErrors::new(5, vec![
+        PassportElementError::data_field(
+            PassportDataElement::PersonalDetails,
+            "first_name",
+            "hash",
+            "wrong name",
+        ),
+        PassportElementError::reverse_side(
+            PassportTwoSidedDocument::DriverLicense,
+            "hash",
+            "blurry",
+        ),
+        PassportElementError::files(
+            PassportAddressDocument::UtilityBill,
+            &["a", "b"],
+            "expired",
+        ),
+        PassportElementError::translation_file(
+            PassportIdentityDocument::Passport,
+            "hash",
+            "unreadable",
+        ),
+        PassportElementError::unspecified(TelegramPassportElement::Email, "hash", "invalid"),
+    ]);
+
+    assert_eq!(
+        serde_json::to_value(&data)?,
+        serde_json::json!({
+            "user_id": 5,
+            "errors": [
+                {
+                    "source": "data",
+                    "type": "personal_details",
+                    "field_name": "first_name",
+                    "data_hash": "hash",
+                    "message": "wrong name",
+                },
+                {
+                    "source": "reverse_side",
+                    "type": "driver_license",
+                    "file_hash": "hash",
+                    "message": "blurry",
+                },
+                {
+                    "source": "files",
+                    "type": "utility_bill",
+                    "file_hashes": ["a", "b"],
+                    "message": "expired",
+                },
+                {
+                    "source": "translation_file",
+                    "type": "passport",
+                    "file_hash": "hash",
+                    "message": "unreadable",
+                },
+                {
+                    "source": "unspecified",
+                    "type": "email",
+                    "element_hash": "hash",
+                    "message": "invalid",
+                },
+            ],
+        })
+    );
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use telexide::{
+    model::{MessageEntity, TextBlock},
+    utils::{entity_text, utf16_offset_to_byte},
+};
+
+fn bold(offset: usize, length: usize) -> MessageEntity {
+    MessageEntity::Bold(TextBlock {
+        offset,
+        length,
+    })
+}
+
+#[test]
+fn utf16_offsets_to_bytes() {
+    assert_eq!(utf16_offset_to_byte("hello", 0), Some(0));
+    assert_eq!(utf16_offset_to_byte("hello", 3), Some(3));
+    assert_eq!(utf16_offset_to_byte("hello", 5), Some(5));
+    assert_eq!(utf16_offset_to_byte("hello", 6), None);
+    assert_eq!(utf16_offset_to_byte("", 0), Some(0));
+
+    // "é" is 2 bytes but a single UTF-16 code unit
+    assert_eq!(utf16_offset_to_byte("héllo", 2), Some(3));
+
+    // "😀" is 4 bytes and 2 UTF-16 code units
+    let text = "a😀b";
+    assert_eq!(utf16_offset_to_byte(text, 1), Some(1));
+    assert_eq!(utf16_offset_to_byte(text, 2), None);
+    assert_eq!(utf16_offset_to_byte(text, 3), Some(5));
+    assert_eq!(utf16_offset_to_byte(text, 4), Some(6));
+    assert_eq!(utf16_offset_to_byte("😀", 1), None);
+}
+
+#[test]
+fn entity_text_with_multi_byte_characters() {
+    let text = "héllo 😀 wörld 👍🏽!";
+    assert_eq!(entity_text(text, &bold(0, 5)), Some("héllo"));
+    assert_eq!(entity_text(text, &bold(6, 2)), Some("😀"));
+    assert_eq!(entity_text(text, &bold(9, 5)), Some("wörld"));
+    // a thumbs up with a skin tone modifier, both outside the BMP
+    assert_eq!(entity_text(text, &bold(15, 4)), Some("👍🏽"));
+    assert_eq!(entity_text(text, &bold(15, 5)), Some("👍🏽!"));
+
+    // halves of surrogate pairs and ranges past the end
+    assert_eq!(entity_text(text, &bold(7, 1)), None);
+    assert_eq!(entity_text(text, &bold(6, 1)), None);
+    assert_eq!(entity_text(text, &bold(15, 6)), None);
+    assert_eq!(entity_text(text, &bold(40, 1)), None);
+
+    // agrees with the copying version for valid entities
+    let entity = bold(9, 5);
+    assert_eq!(entity.get_text(text).as_deref(), entity_text(text, &entity));
+    assert_eq!(entity.get_text_block().get_str(text), Some("wörld"));
+    assert_eq!(entity.get_text_block().get_text_after(text), " 👍🏽!");
+    assert_eq!(bold(7, 1).get_text(text), None);
+    assert_eq!(bold(7, 1).get_text_block().get_text(text), "");
+}
@@ -35,7 +35,8 @@ async fn webhook_gets_called() -> Result<()> {
         .header("accept", "application/json")
         .body(hyper::Body::from(serde_json::to_string(&Update {
             update_id: 10,
-            content: UpdateContent::Unknown,
+            raw_json: None,
+            content: UpdateContent::Unknown(Box::default()),
         })?))?;
     client.request(req).await?;
 
@@ -43,3 +44,28 @@ async fn webhook_gets_called() -> Result<()> {
     assert_eq!(ATOMIC.load(Ordering::Relaxed), 10);
     Ok(())
 }
+
+#[tokio::test]
+async fn webhook_keeps_raw_json() -> Result<()> {
+    let client = hyper::Client::new();
+
+    let mut webhook_opts = WebhookOptions::new();
+    webhook_opts.set_path("/testing/raw").set_port(8007);
+
+    let mut webhook = Webhook::new(&webhook_opts);
+    webhook.set_keep_raw_json(true);
+    let mut update_receiver = webhook.start();
+    tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
+
+    // formatted unlike serde_json would, to make sure the body is kept as is
+    let body = "{ \"update_id\" : 12,\n  \"some_new_update\": {\"text\": \"caf\\u00e9\", \"n\": 1.50} }";
+    let req = hyper::Request::post("http://localhost:8007/testing/raw")
+        .header("content-type", "application/json")
+        .body(hyper::Body::from(body))?;
+    client.request(req).await?;
+
+    let update = update_receiver.recv().await.unwrap()?;
+    assert_eq!(update.update_id, 12);
+    assert_eq!(update.raw_json(), Some(body));
+    Ok(())
+}